@@ -0,0 +1,60 @@
+fn main() {
+    #[cfg(feature = "grpc")]
+    grpc::compile();
+}
+
+/// Generates the `ScheduleService` server from Rust definitions mirroring `proto/cron_exp.proto`
+/// so building does not require `protoc`.
+#[cfg(feature = "grpc")]
+mod grpc {
+    use tonic_build::manual::{Builder, Method, Service};
+
+    fn method(name: &str, route: &str, input: &str, output: &str) -> Method {
+        Method::builder()
+            .name(name)
+            .route_name(route)
+            .input_type(format!("crate::grpc::{}", input))
+            .output_type(format!("crate::grpc::{}", output))
+            .codec_path("tonic_prost::ProstCodec")
+            .build()
+    }
+
+    pub fn compile() {
+        println!("cargo:rerun-if-changed=build.rs");
+
+        let service = Service::builder()
+            .name("ScheduleService")
+            .package("cron_exp.v1")
+            .method(method("parse", "Parse", "ParseRequest", "ParseResponse"))
+            .method(method(
+                "validate",
+                "Validate",
+                "ValidateRequest",
+                "ValidateResponse",
+            ))
+            .method(method(
+                "next",
+                "Next",
+                "OccurrencesRequest",
+                "OccurrencesResponse",
+            ))
+            .method(method(
+                "prev",
+                "Prev",
+                "OccurrencesRequest",
+                "OccurrencesResponse",
+            ))
+            .method(method(
+                "describe",
+                "Describe",
+                "DescribeRequest",
+                "DescribeResponse",
+            ))
+            .build();
+
+        Builder::new()
+            .build_client(false)
+            .build_transport(false)
+            .compile(&[service]);
+    }
+}
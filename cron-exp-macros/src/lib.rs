@@ -0,0 +1,73 @@
+//! # cron-exp-macros
+//!
+//! The `cron!` macro validating hard coded CRON expressions at compile time, catching typos
+//! before the schedule is first parsed at runtime.
+//!
+//! Expressions are validated by `cron_exp::Schedule::from_str`, enable the `tz` feature to
+//! validate `CRON_TZ=` and `TZ=` prefixes.
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::str::FromStr;
+use syn::LitStr;
+
+/// Validates the CRON expression at compile time and expands to the parsed `cron_exp::Schedule`.
+/// ```rust
+/// use cron_exp::Schedule;
+/// use cron_exp_macros::cron;
+/// use std::str::FromStr;
+///
+/// let schedule = cron!("0 30 9 * * Mon-Fri");
+/// assert_eq!(Schedule::from_str("0 30 9 * * Mon-Fri").unwrap(), schedule);
+/// ```
+///
+/// Invalid expressions fail to compile with the parse error.
+/// ```compile_fail
+/// use cron_exp_macros::cron;
+///
+/// let schedule = cron!("0 30 25 * * Mon-Fri");
+/// ```
+#[proc_macro]
+pub fn cron(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand(input.into()).into()
+}
+
+fn expand(input: TokenStream) -> TokenStream {
+    let expression: LitStr = match syn::parse2(input) {
+        Ok(expression) => expression,
+        Err(e) => return e.to_compile_error(),
+    };
+    if let Err(e) = cron_exp::Schedule::from_str(&expression.value()) {
+        return syn::Error::new(expression.span(), format!("invalid CRON expression: {}", e))
+            .to_compile_error();
+    }
+    quote! {
+        <::cron_exp::Schedule as ::std::str::FromStr>::from_str(#expression)
+            .expect("the expression was validated at compile time")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_valid() {
+        let expanded = expand(quote!("0 30 9 * * Mon-Fri")).to_string();
+        assert!(expanded.contains("from_str"), "{}", expanded);
+        assert!(expanded.contains("\"0 30 9 * * Mon-Fri\""), "{}", expanded);
+    }
+
+    #[test]
+    fn expand_invalid() {
+        let expanded = expand(quote!("0 30 25 * * *")).to_string();
+        assert!(expanded.contains("compile_error"), "{}", expanded);
+        assert!(
+            expanded.contains("Invalid Range '25' in the hours field"),
+            "{}",
+            expanded
+        );
+
+        let expanded = expand(quote!(0 30 9)).to_string();
+        assert!(expanded.contains("compile_error"), "{}", expanded);
+    }
+}
@@ -0,0 +1,64 @@
+//! Datetime types other than `chrono`'s sharing the search of `chrono` datetimes.
+//!
+//! The search itself isn't generic over the calendar: fields are matched against `chrono`'s
+//! `NaiveDateTime` and days of week come from `chrono::Weekday` whatever the backend. A backend
+//! only converts instants and supplies the offsets of its zone, e.g. from jiff's own time zone
+//! database, so there is a single search rather than one per datetime library.
+use crate::schedule::Schedule;
+use chrono::{DateTime, TimeZone};
+
+/// A datetime type occurrences are searched from by converting it to and from the `chrono`
+/// datetime the search runs on, so every backend shares the one search algorithm.
+///
+/// It doesn't construct dates from their year, month, day and time of day nor query their day of
+/// week, the search does so on the local time of the converted datetime.
+pub(crate) trait CalendarBackend: Clone + Sized {
+    /// The zone occurrences are computed in, unless the schedule has its own.
+    type Zone: TimeZone;
+
+    /// Converts to the `chrono` datetime of the same instant.
+    fn to_chrono(&self) -> Option<DateTime<Self::Zone>>;
+
+    /// Converts an occurrence back, in the zone or offset of the datetime searched from.
+    fn from_chrono(dt: &DateTime<Self::Zone>, from: &Self) -> Option<Self>;
+}
+
+impl Schedule {
+    pub(crate) fn next_after_backend<B>(&self, dt: &B) -> Option<B>
+    where
+        B: CalendarBackend,
+    {
+        self.after(&dt.to_chrono()?)
+            .and_then(|next| B::from_chrono(&next, dt))
+    }
+
+    pub(crate) fn prev_before_backend<B>(&self, dt: &B) -> Option<B>
+    where
+        B: CalendarBackend,
+    {
+        self.before(&dt.to_chrono()?)
+            .and_then(|prev| B::from_chrono(&prev, dt))
+    }
+
+    pub(crate) fn iter_after_backend<'a, B>(&'a self, dt: &B) -> impl Iterator<Item = B> + 'a
+    where
+        B: CalendarBackend + 'a,
+    {
+        let from = dt.clone();
+        dt.to_chrono()
+            .into_iter()
+            .flat_map(move |start| self.iter_after(&start))
+            .map_while(move |next| B::from_chrono(&next, &from))
+    }
+
+    pub(crate) fn iter_before_backend<'a, B>(&'a self, dt: &B) -> impl Iterator<Item = B> + 'a
+    where
+        B: CalendarBackend + 'a,
+    {
+        let from = dt.clone();
+        dt.to_chrono()
+            .into_iter()
+            .flat_map(move |start| self.iter_before(&start))
+            .map_while(move |prev| B::from_chrono(&prev, &from))
+    }
+}
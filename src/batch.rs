@@ -0,0 +1,149 @@
+use crate::options::DstGapPolicy;
+use crate::schedule::Schedule;
+use chrono::{DateTime, NaiveDate, TimeZone};
+
+impl Schedule {
+    /// Returns the next occurrence after each of the supplied datetimes, in the same order,
+    /// e.g. to backfill the next run of a job for every line of a log.
+    ///
+    /// The datetimes are visited in chronological order so the occurrence found for one is
+    /// reused for the later ones before it in the same zone, searching once per occurrence
+    /// rather than once per datetime.
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 9 * * *").unwrap();
+    /// let at = |d, h| Utc.with_ymd_and_hms(2022, 6, d, h, 0, 0).unwrap();
+    /// assert_eq!(
+    ///     vec![Some(at(2, 9)), Some(at(1, 9)), Some(at(2, 9))],
+    ///     schedule.next_after_many(&[at(1, 12), at(1, 3), at(1, 18)])
+    /// );
+    /// ```
+    pub fn next_after_many<Z>(&self, datetimes: &[DateTime<Z>]) -> Vec<Option<DateTime<Z>>>
+    where
+        Z: TimeZone + PartialEq,
+    {
+        let mut order: Vec<usize> = (0..datetimes.len()).collect();
+        order.sort_by(|a, b| datetimes[*a].cmp(&datetimes[*b]));
+
+        let mut found = vec![None; datetimes.len()];
+        // the last datetime searched from and its next occurrence
+        let mut last: Option<(&DateTime<Z>, Option<DateTime<Z>>)> = None;
+        for index in order {
+            let dt = &datetimes[index];
+            let next = match &last {
+                // nothing occurs after an earlier datetime that hasn't been passed yet
+                Some((from, next))
+                    if from.timezone() == dt.timezone()
+                        && next.as_ref().is_none_or(|next| next > dt) =>
+                {
+                    next.clone()
+                }
+                _ => self.after(dt),
+            };
+            found[index] = next.clone();
+            last = Some((dt, next));
+        }
+        found
+    }
+
+    /// Returns the supplied datetimes that are occurrences of the schedule in their order, as
+    /// `Schedule::includes` checks them.
+    ///
+    /// Whether the date matches is computed once for consecutive datetimes on the same day,
+    /// leaving only the time of day to check for each.
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("*/15 9-17 * * Mon-Fri").unwrap();
+    /// let at = |d, h, m| Utc.with_ymd_and_hms(2022, 6, d, h, m, 0).unwrap();
+    /// assert_eq!(
+    ///     vec![at(3, 9, 15)],
+    ///     schedule.filter_matching(&[at(3, 9, 15), at(3, 9, 20), at(4, 9, 15)])
+    /// );
+    /// ```
+    pub fn filter_matching<Z>(&self, datetimes: &[DateTime<Z>]) -> Vec<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        let mut day: Option<(NaiveDate, bool)> = None;
+        datetimes
+            .iter()
+            .filter(|dt| {
+                // times moved out of DST gaps don't match the fields
+                if self.dst_gap_policy() != DstGapPolicy::Skip {
+                    return self.includes(*dt);
+                }
+                let local = match self.truncated_local(dt) {
+                    Some(local) => local,
+                    None => return false,
+                };
+                let date_matches = match day {
+                    Some((date, matches)) if date == local.date() => matches,
+                    _ => {
+                        let matches = self.matches_date(&local.date());
+                        day = Some((local.date(), matches));
+                        matches
+                    }
+                };
+                // matching times repeated when clocks fall back may be skipped
+                date_matches && self.matches_time(&local.time()) && self.includes(*dt)
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, FixedOffset, Utc};
+    use std::str::FromStr;
+
+    #[test]
+    fn next_after_many_matches_next_after() {
+        let schedule = Schedule::from_str("0 9,17 * * Mon-Fri").unwrap();
+        let start = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap();
+        // unsorted and repeated datetimes every 7 hours and 13 minutes
+        let mut datetimes: Vec<_> = (0..40)
+            .map(|i| start + Duration::minutes(i * 433))
+            .collect();
+        datetimes.reverse();
+        datetimes.push(start);
+        let expected: Vec<_> = datetimes.iter().map(|dt| schedule.next_after(dt)).collect();
+        assert_eq!(expected, schedule.next_after_many(&datetimes));
+
+        // occurrences are searched for in the zone of each datetime
+        let east = FixedOffset::east_opt(3600).unwrap();
+        let west = FixedOffset::west_opt(3600).unwrap();
+        let datetimes = [
+            start.with_timezone(&east),
+            (start + Duration::hours(1)).with_timezone(&west),
+        ];
+        let expected: Vec<_> = datetimes.iter().map(|dt| schedule.next_after(dt)).collect();
+        assert_eq!(expected, schedule.next_after_many(&datetimes));
+
+        let finished = Schedule::from_str("0 0 0 1 1 * 2020").unwrap();
+        assert_eq!(vec![None, None], finished.next_after_many(&[start, start]));
+    }
+
+    #[test]
+    fn filter_matching_matches_includes() {
+        let schedule = Schedule::from_str("0 */30 9 * * Mon").unwrap();
+        let start = Utc.with_ymd_and_hms(2022, 6, 5, 0, 0, 0).unwrap();
+        let datetimes: Vec<_> = (0..2000)
+            .map(|i| start + Duration::minutes(i * 5))
+            .collect();
+        let expected: Vec<_> = datetimes
+            .iter()
+            .filter(|dt| schedule.includes(*dt))
+            .cloned()
+            .collect();
+        assert_eq!(2, expected.len());
+        assert_eq!(expected, schedule.filter_matching(&datetimes));
+    }
+}
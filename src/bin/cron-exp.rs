@@ -0,0 +1,231 @@
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use clap::{Parser, Subcommand, ValueEnum};
+use cron_exp::Schedule;
+use std::fmt::Display;
+use std::io::{self, Write};
+use std::process;
+
+#[derive(Parser)]
+#[command(version, about = "CRON expression explorer")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Prints every fire time of the expression from --from up to --to, both included.
+    Simulate {
+        /// The CRON expression to simulate.
+        expression: String,
+
+        /// Start of the timeline, RFC 3339 or `YYYY-MM-DD HH:MM:SS` in the --tz timezone.
+        #[arg(long)]
+        from: String,
+
+        /// End of the timeline, RFC 3339 or `YYYY-MM-DD HH:MM:SS` in the --tz timezone.
+        #[arg(long)]
+        to: String,
+
+        /// IANA timezone the schedule is evaluated in, defaults to UTC.
+        #[arg(long)]
+        tz: Option<Tz>,
+
+        /// Output format of the timeline.
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Text,
+    Csv,
+    Json,
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::Simulate {
+            expression,
+            from,
+            to,
+            tz,
+            format,
+        } => {
+            let mut out = io::stdout().lock();
+            match tz {
+                Some(tz) => simulate(&mut out, &expression, &from, &to, tz, format),
+                None => simulate(&mut out, &expression, &from, &to, Utc, format),
+            }
+        }
+    };
+
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
+
+fn simulate<W, Z>(
+    out: &mut W,
+    expression: &str,
+    from: &str,
+    to: &str,
+    tz: Z,
+    format: Format,
+) -> Result<(), String>
+where
+    W: Write,
+    Z: TimeZone,
+    Z::Offset: Display,
+{
+    let schedule = Schedule::parse_spanned(expression).map_err(|e| {
+        // underline the offending token beneath the expression
+        let span = e.span();
+        format!(
+            "{}\n  {}\n  {}{}",
+            e.error(),
+            expression,
+            " ".repeat(expression[..span.start].chars().count()),
+            "^".repeat(expression[span].chars().count().max(1))
+        )
+    })?;
+    let from = parse_datetime(from, &tz)?;
+    let to = parse_datetime(to, &tz)?;
+
+    let mut timeline = schedule
+        .iter_between_inclusive(&from, &to)
+        .map(|dt| dt.to_rfc3339());
+
+    let written = match format {
+        Format::Text => timeline.try_for_each(|dt| writeln!(out, "{}", dt)),
+        Format::Csv => writeln!(out, "occurrence,datetime").and_then(|_| {
+            timeline
+                .enumerate()
+                .try_for_each(|(i, dt)| writeln!(out, "{},{}", i + 1, dt))
+        }),
+        Format::Json => {
+            let timeline: Vec<String> = timeline.collect();
+            writeln!(out, "{}", serde_json::to_string_pretty(&timeline).unwrap())
+        }
+    };
+    written.map_err(|e| e.to_string())
+}
+
+fn parse_datetime<Z>(s: &str, tz: &Z) -> Result<DateTime<Z>, String>
+where
+    Z: TimeZone,
+{
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Ok(dt.with_timezone(tz));
+    }
+    let naive = NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S"))
+        .map_err(|e| format!("invalid datetime '{}': {}", s, e))?;
+    tz.from_local_datetime(&naive)
+        .earliest()
+        .ok_or_else(|| format!("datetime '{}' does not exist in the timezone", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run<Z>(expression: &str, from: &str, to: &str, tz: Z, format: Format) -> String
+    where
+        Z: TimeZone,
+        Z::Offset: Display,
+    {
+        let mut out = Vec::new();
+        simulate(&mut out, expression, from, to, tz, format).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn both_bounds_are_included() {
+        assert_eq!(
+            "2022-06-01T09:00:00+00:00\n2022-06-01T10:00:00+00:00\n2022-06-01T11:00:00+00:00\n",
+            run(
+                "0 * * * *",
+                "2022-06-01 09:00:00",
+                "2022-06-01T11:00:00Z",
+                Utc,
+                Format::Text
+            )
+        );
+    }
+
+    #[test]
+    fn formats() {
+        let csv = run(
+            "30 9 * * Mon-Fri",
+            "2022-06-03 00:00:00",
+            "2022-06-07 00:00:00",
+            chrono_tz::Europe::Paris,
+            Format::Csv,
+        );
+        assert_eq!(
+            "occurrence,datetime\n1,2022-06-03T09:30:00+02:00\n2,2022-06-06T09:30:00+02:00\n",
+            csv
+        );
+        let json = run(
+            "30 9 * * Mon-Fri",
+            "2022-06-03 00:00:00",
+            "2022-06-07 00:00:00",
+            Utc,
+            Format::Json,
+        );
+        assert_eq!(
+            vec!["2022-06-03T09:30:00+00:00", "2022-06-06T09:30:00+00:00"],
+            serde_json::from_str::<Vec<String>>(&json).unwrap()
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let error = |expression: &str, from: &str| {
+            simulate(
+                &mut Vec::new(),
+                expression,
+                from,
+                "2022-06-02 00:00:00",
+                Utc,
+                Format::Text,
+            )
+            .unwrap_err()
+        };
+        // the offending token is underlined beneath the expression
+        let invalid = error("0 25 * * *", "2022-06-01 00:00:00");
+        assert!(invalid.ends_with("\n  0 25 * * *\n    ^^"), "{}", invalid);
+        assert!(error("0 * * * *", "yesterday").starts_with("invalid datetime 'yesterday'"));
+    }
+
+    #[test]
+    fn arguments() {
+        use clap::CommandFactory;
+
+        Cli::command().debug_assert();
+        let cli = Cli::try_parse_from([
+            "cron-exp",
+            "simulate",
+            "0 9 * * *",
+            "--from",
+            "2022-06-01 00:00:00",
+            "--to",
+            "2022-06-02 00:00:00",
+            "--tz",
+            "Europe/Paris",
+            "--format",
+            "csv",
+        ])
+        .unwrap();
+        let Command::Simulate { tz, format, .. } = cli.command;
+        assert_eq!(Some(chrono_tz::Europe::Paris), tz);
+        assert!(matches!(format, Format::Csv));
+        assert!(Cli::try_parse_from(["cron-exp", "simulate", "0 9 * * *"]).is_err());
+    }
+}
@@ -0,0 +1,241 @@
+//! The values of a field as a 64 bit mask, small enough for every field but the years.
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::{FromIterator, FusedIterator};
+use std::ops::{Bound, RangeBounds};
+
+/// A set of values below 64 stored as the bits of a `u64`, iterated in ascending order with
+/// trailing zero scans.
+///
+/// Replaces a `BTreeSet<u32>` without allocating, and orders like one so schedules sort the same.
+#[derive(Default, PartialEq, Eq, Clone, Copy, Hash)]
+pub(crate) struct BitSet(u64);
+
+impl BitSet {
+    pub(crate) const fn new() -> BitSet {
+        BitSet(0)
+    }
+
+    /// Returns the set of the values from `start` to `end` inclusive.
+    pub(crate) const fn span(start: u32, end: u32) -> BitSet {
+        BitSet(mask(start, end))
+    }
+
+    pub(crate) fn contains(&self, value: u32) -> bool {
+        value < 64 && self.0 & (1 << value) != 0
+    }
+
+    /// Adds a value, returning whether it was not already present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is 64 or more.
+    pub(crate) fn insert(&mut self, value: u32) -> bool {
+        assert!(value < 64, "{} does not fit in a bit set", value);
+        let inserted = !self.contains(value);
+        self.0 |= 1 << value;
+        inserted
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub(crate) fn first(&self) -> Option<u32> {
+        self.iter().next()
+    }
+
+    pub(crate) fn is_subset(&self, other: &BitSet) -> bool {
+        self.0 & !other.0 == 0
+    }
+
+    pub(crate) fn intersection(&self, other: &BitSet) -> BitSet {
+        BitSet(self.0 & other.0)
+    }
+
+    pub(crate) fn union(&self, other: &BitSet) -> BitSet {
+        BitSet(self.0 | other.0)
+    }
+
+    pub(crate) fn iter(&self) -> Iter {
+        Iter(self.0)
+    }
+
+    /// Iterates the values within the range in ascending order.
+    pub(crate) fn range<R: RangeBounds<u32>>(&self, range: R) -> Iter {
+        let start = match range.start_bound() {
+            Bound::Included(start) => *start,
+            Bound::Excluded(start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(end) => *end,
+            Bound::Excluded(0) => return Iter(0),
+            Bound::Excluded(end) => end - 1,
+            Bound::Unbounded => 63,
+        };
+        Iter(self.0 & mask(start, end))
+    }
+}
+
+/// Returns the bits from `start` to `end` inclusive, clamped to the bits of a `u64`.
+const fn mask(start: u32, end: u32) -> u64 {
+    let end = if end > 63 { 63 } else { end };
+    if start > end {
+        return 0;
+    }
+    (u64::MAX >> (63 - end)) & (u64::MAX << start)
+}
+
+impl fmt::Debug for BitSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl PartialOrd for BitSet {
+    fn partial_cmp(&self, other: &BitSet) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Compares the values in ascending order as `BTreeSet` does.
+impl Ord for BitSet {
+    fn cmp(&self, other: &BitSet) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl FromIterator<u32> for BitSet {
+    fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> BitSet {
+        let mut set = BitSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<u32> for BitSet {
+    fn extend<I: IntoIterator<Item = u32>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<const N: usize> From<[u32; N]> for BitSet {
+    fn from(values: [u32; N]) -> BitSet {
+        values.iter().cloned().collect()
+    }
+}
+
+impl IntoIterator for BitSet {
+    type Item = u32;
+    type IntoIter = Iter;
+
+    fn into_iter(self) -> Iter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for &BitSet {
+    type Item = u32;
+    type IntoIter = Iter;
+
+    fn into_iter(self) -> Iter {
+        self.iter()
+    }
+}
+
+/// Iterates the values of a `BitSet`, removing the lowest or highest bit left at each step.
+#[derive(Debug, Clone)]
+pub(crate) struct Iter(u64);
+
+impl Iterator for Iter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.0 == 0 {
+            return None;
+        }
+        let value = self.0.trailing_zeros();
+        self.0 &= self.0 - 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Iter {
+    fn next_back(&mut self) -> Option<u32> {
+        if self.0 == 0 {
+            return None;
+        }
+        let value = 63 - self.0.leading_zeros();
+        self.0 &= !(1 << value);
+        Some(value)
+    }
+}
+
+impl ExactSizeIterator for Iter {}
+
+impl FusedIterator for Iter {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn behaves_like_btree_set() {
+        let values = [0, 1, 5, 31, 32, 59, 63];
+        let set = BitSet::from(values);
+        let btree = BTreeSet::from(values);
+        assert_eq!(7, set.len());
+        assert_eq!(Some(0), set.first());
+        assert_eq!(Some(63), set.iter().next_back());
+        assert!(set.contains(59) && !set.contains(58) && !set.contains(64));
+        assert_eq!(
+            btree.iter().cloned().collect::<Vec<_>>(),
+            set.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            btree.range(2..=59).rev().cloned().collect::<Vec<_>>(),
+            set.range(2..=59).rev().collect::<Vec<_>>()
+        );
+        assert_eq!(vec![63], set.range(32..).skip(2).collect::<Vec<_>>());
+        assert_eq!(0, set.range(1..1).count());
+        assert_eq!(0, set.range(..0).count());
+        assert_eq!(vec![0, 1], set.range(..5).collect::<Vec<_>>());
+        assert_eq!("{0, 1, 5, 31, 32, 59, 63}", format!("{:?}", set));
+    }
+
+    #[test]
+    fn orders_like_btree_set() {
+        let sets = [vec![1, 2], vec![1, 3], vec![2], vec![], vec![1]];
+        for a in &sets {
+            for b in &sets {
+                let bits = |v: &Vec<u32>| v.iter().cloned().collect::<BitSet>();
+                let btree = |v: &Vec<u32>| v.iter().cloned().collect::<BTreeSet<u32>>();
+                assert_eq!(btree(a).cmp(&btree(b)), bits(a).cmp(&bits(b)));
+            }
+        }
+    }
+
+    #[test]
+    fn insert_and_subset() {
+        let mut set = BitSet::span(1, 3);
+        assert!(!set.insert(2));
+        assert!(set.insert(10));
+        assert!(BitSet::from([2, 3]).is_subset(&set));
+        assert!(!set.is_subset(&BitSet::span(0, 9)));
+        assert_eq!(BitSet::from([1, 2, 3, 10]), set);
+        assert_eq!(BitSet::from([2, 3]), set.intersection(&BitSet::span(2, 5)));
+    }
+}
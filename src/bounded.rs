@@ -0,0 +1,169 @@
+use crate::schedule::Schedule;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+/// A schedule only active within a window, created with `Schedule::bounded`, e.g. for a
+/// campaign with start and end dates.
+///
+/// Both bounds are inclusive.
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use cron_exp::Schedule;
+/// use std::str::FromStr;
+///
+/// let schedule = Schedule::from_str("0 9 * * *").unwrap();
+/// let campaign = schedule.bounded(
+///     &Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap(),
+///     &Utc.with_ymd_and_hms(2022, 6, 3, 9, 0, 0).unwrap(),
+/// );
+///
+/// let from = Utc.with_ymd_and_hms(2022, 5, 1, 0, 0, 0).unwrap();
+/// let days: Vec<String> = campaign
+///     .iter_from(&from)
+///     .map(|dt| dt.format("%d").to_string())
+///     .collect();
+/// assert_eq!(vec!["01", "02", "03"], days);
+/// assert!(!campaign.includes(&Utc.with_ymd_and_hms(2022, 6, 4, 9, 0, 0).unwrap()));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Bounded {
+    schedule: Schedule,
+    not_before: DateTime<Utc>,
+    not_after: DateTime<Utc>,
+}
+
+impl Schedule {
+    /// Returns the schedule restricted to the occurrences from `not_before` to `not_after`
+    /// inclusive, see `Bounded`.
+    pub fn bounded<Z>(&self, not_before: &DateTime<Z>, not_after: &DateTime<Z>) -> Bounded
+    where
+        Z: TimeZone,
+    {
+        Bounded {
+            schedule: self.clone(),
+            not_before: not_before.with_timezone(&Utc),
+            not_after: not_after.with_timezone(&Utc),
+        }
+    }
+}
+
+impl Bounded {
+    /// Returns the wrapped schedule.
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    /// Returns the earliest instant an occurrence may fire.
+    pub fn not_before(&self) -> DateTime<Utc> {
+        self.not_before
+    }
+
+    /// Returns the latest instant an occurrence may fire.
+    pub fn not_after(&self) -> DateTime<Utc> {
+        self.not_after
+    }
+
+    /// Returns the next occurrence within the window strictly after the supplied datetime.
+    pub fn next_after<Z>(&self, dt: &DateTime<Z>) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        let next = if dt < &self.not_before {
+            // an occurrence at the start of the window is included
+            let start = self.not_before.with_timezone(&dt.timezone());
+            self.schedule.after(&(start - Duration::nanoseconds(1)))?
+        } else {
+            self.schedule.after(dt)?
+        };
+        if next > self.not_after {
+            return None;
+        }
+        Some(next)
+    }
+
+    /// Returns the previous occurrence within the window strictly before the supplied datetime.
+    pub fn prev_before<Z>(&self, dt: &DateTime<Z>) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        let prev = if dt > &self.not_after {
+            // an occurrence at the end of the window is included, searching from the next minute
+            // as schedules without seconds search to the minute
+            let end = self.not_after.with_timezone(&dt.timezone());
+            let mut prev = self.schedule.before(&(end + Duration::minutes(1)))?;
+            while prev > self.not_after {
+                prev = self.schedule.before(&prev)?;
+            }
+            prev
+        } else {
+            self.schedule.before(dt)?
+        };
+        if prev < self.not_before {
+            return None;
+        }
+        Some(prev)
+    }
+
+    /// Returns whether the supplied instant is an occurrence within the window.
+    pub fn includes<Z>(&self, dt: &DateTime<Z>) -> bool
+    where
+        Z: TimeZone,
+    {
+        dt >= &self.not_before && dt <= &self.not_after && self.schedule.includes(dt)
+    }
+
+    /// Iterates the occurrences within the window after the supplied datetime.
+    pub fn iter_from<'a, Z>(&'a self, dt: &DateTime<Z>) -> impl Iterator<Item = DateTime<Z>> + 'a
+    where
+        Z: TimeZone + 'a,
+    {
+        let mut cursor = Some(dt.clone());
+        std::iter::from_fn(move || {
+            let next = self.next_after(cursor.as_ref()?);
+            cursor = next.clone();
+            next
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+    use std::str::FromStr;
+
+    #[test]
+    fn window_is_inclusive() {
+        let schedule = Schedule::from_str("0 * * * *").unwrap();
+        let at = |h| Utc.with_ymd_and_hms(2022, 6, 1, h, 0, 0).unwrap();
+        let bounded = schedule.bounded(&at(9), &at(12));
+
+        let occurrences: Vec<_> = bounded.iter_from(&at(0)).collect();
+        assert_eq!(vec![at(9), at(10), at(11), at(12)], occurrences);
+        assert_eq!(Some(at(11)), bounded.next_after(&at(10)));
+        assert_eq!(None, bounded.next_after(&at(12)));
+        assert_eq!(Some(at(12)), bounded.prev_before(&at(20)));
+        assert_eq!(None, bounded.prev_before(&at(9)));
+        assert!(bounded.includes(&at(9)) && bounded.includes(&at(12)));
+        assert!(!bounded.includes(&at(8)) && !bounded.includes(&at(13)));
+    }
+
+    #[test]
+    fn bounds_are_instants() {
+        let schedule = Schedule::from_str("30 * * * *").unwrap();
+        let east = FixedOffset::east_opt(2 * 3600).unwrap();
+        // 10:00 to 12:00 in UTC+2 is 08:00 to 10:00 in UTC
+        let bounded = schedule.bounded(
+            &east.with_ymd_and_hms(2022, 6, 1, 10, 0, 0).unwrap(),
+            &east.with_ymd_and_hms(2022, 6, 1, 12, 0, 0).unwrap(),
+        );
+        let from = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap();
+        let hours: Vec<String> = bounded
+            .iter_from(&from)
+            .map(|dt| dt.format("%H:%M").to_string())
+            .collect();
+        assert_eq!(vec!["08:30", "09:30"], hours);
+
+        let empty = schedule.bounded(&from, &(from - Duration::hours(1)));
+        assert_eq!(None, empty.next_after(&(from - Duration::days(1))));
+    }
+}
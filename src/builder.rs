@@ -0,0 +1,307 @@
+use crate::errors::{Field, ParseScheduleError};
+use crate::format::{CronFormat, DayOfWeekNumbering};
+use crate::schedule::Schedule;
+use chrono::Weekday;
+use std::ops::{Range, RangeInclusive};
+
+/// Values accepted by the fields of a `ScheduleBuilder`, a single value, an array, a `Vec` or a
+/// range.
+pub trait FieldValues<T> {
+    /// Returns the values in order.
+    fn into_values(self) -> Vec<T>;
+}
+
+impl FieldValues<u32> for u32 {
+    fn into_values(self) -> Vec<u32> {
+        vec![self]
+    }
+}
+
+impl FieldValues<Weekday> for Weekday {
+    fn into_values(self) -> Vec<Weekday> {
+        vec![self]
+    }
+}
+
+impl<T, const N: usize> FieldValues<T> for [T; N] {
+    fn into_values(self) -> Vec<T> {
+        Vec::from(self)
+    }
+}
+
+impl<T> FieldValues<T> for Vec<T> {
+    fn into_values(self) -> Vec<T> {
+        self
+    }
+}
+
+impl FieldValues<u32> for RangeInclusive<u32> {
+    fn into_values(self) -> Vec<u32> {
+        self.collect()
+    }
+}
+
+impl FieldValues<u32> for Range<u32> {
+    fn into_values(self) -> Vec<u32> {
+        self.collect()
+    }
+}
+
+/// Builds a `Schedule` from field values rather than an expression, fields left unset match
+/// every value as `*` does.
+///
+/// Values are validated as when parsing, a seconds field is only present when set and a years
+/// field only when set.
+/// ```rust
+/// use chrono::Weekday::{Fri, Mon};
+/// use cron_exp::Schedule;
+/// use std::str::FromStr;
+///
+/// let schedule = Schedule::builder()
+///     .seconds(0)
+///     .minutes([15, 45])
+///     .hours(9..=17)
+///     .days_of_week([Mon, Fri])
+///     .build()
+///     .unwrap();
+/// assert_eq!(
+///     Schedule::from_str("0 15,45 9-17 * * Mon,Fri").unwrap(),
+///     schedule
+/// );
+/// assert!(Schedule::builder().hours(24).build().is_err());
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ScheduleBuilder {
+    seconds: Option<Vec<u32>>,
+    minutes: Option<Vec<u32>>,
+    hours: Option<Vec<u32>>,
+    days_of_month: Option<Vec<u32>>,
+    months: Option<Vec<u32>>,
+    days_of_week: Option<(Vec<u32>, DayOfWeekNumbering)>,
+    years: Option<Vec<u32>>,
+}
+
+impl Schedule {
+    /// Returns a builder to construct a schedule from field values.
+    pub fn builder() -> ScheduleBuilder {
+        ScheduleBuilder::default()
+    }
+}
+
+impl ScheduleBuilder {
+    /// Sets the seconds, 0-59, adding a seconds field.
+    pub fn seconds<V: FieldValues<u32>>(mut self, values: V) -> Self {
+        self.seconds = Some(values.into_values());
+        self
+    }
+
+    /// Sets the minutes, 0-59.
+    pub fn minutes<V: FieldValues<u32>>(mut self, values: V) -> Self {
+        self.minutes = Some(values.into_values());
+        self
+    }
+
+    /// Sets the hours, 0-23.
+    pub fn hours<V: FieldValues<u32>>(mut self, values: V) -> Self {
+        self.hours = Some(values.into_values());
+        self
+    }
+
+    /// Sets the days of month, 1-31.
+    pub fn days_of_month<V: FieldValues<u32>>(mut self, values: V) -> Self {
+        self.days_of_month = Some(values.into_values());
+        self
+    }
+
+    /// Sets the months, 1-12.
+    pub fn months<V: FieldValues<u32>>(mut self, values: V) -> Self {
+        self.months = Some(values.into_values());
+        self
+    }
+
+    /// Sets the days of week.
+    pub fn days_of_week<V: FieldValues<Weekday>>(mut self, values: V) -> Self {
+        let numbering = DayOfWeekNumbering::SundayOne;
+        let days = values
+            .into_values()
+            .into_iter()
+            .map(|day| numbering.number(day))
+            .collect();
+        self.days_of_week = Some((days, numbering));
+        self
+    }
+
+    /// Sets the days of week from their numbers in the supplied convention, e.g. 1-5 for Monday
+    /// to Friday with `DayOfWeekNumbering::MondayOne`.
+    pub fn days_of_week_numbered<V: FieldValues<u32>>(
+        mut self,
+        values: V,
+        numbering: DayOfWeekNumbering,
+    ) -> Self {
+        self.days_of_week = Some((values.into_values(), numbering));
+        self
+    }
+
+    /// Sets the years, 1970-2099, adding a years field.
+    pub fn years<V: FieldValues<u32>>(mut self, values: V) -> Self {
+        self.years = Some(values.into_values());
+        self
+    }
+
+    /// Builds the schedule, failing on values outside their field or fields set without any.
+    pub fn build(&self) -> Result<Schedule, ParseScheduleError> {
+        let format = match (&self.seconds, &self.years) {
+            (Some(_), Some(_)) => CronFormat::VixieWithYear,
+            (Some(_), None) => CronFormat::VixieWithSeconds,
+            (None, Some(_)) => CronFormat::CrontabWithYear,
+            (None, None) => CronFormat::Crontab,
+        };
+        let days_of_week = match &self.days_of_week {
+            Some((days, numbering)) => Some(
+                days.iter()
+                    .map(|day| {
+                        numbering
+                            .weekday(*day)
+                            .map(|weekday| format.day_of_week_numbering().number(weekday))
+                            .ok_or_else(|| ParseScheduleError::InvalidRange {
+                                field: Field::DaysOfWeek,
+                                value: day.to_string(),
+                            })
+                    })
+                    .collect::<Result<Vec<u32>, _>>()?,
+            ),
+            None => None,
+        };
+
+        let mut fields = Vec::new();
+        if let Some(seconds) = &self.seconds {
+            fields.push(format_field(Field::Seconds, seconds)?);
+        }
+        for (field, values) in [
+            (Field::Minutes, &self.minutes),
+            (Field::Hours, &self.hours),
+            (Field::DaysOfMonth, &self.days_of_month),
+            (Field::Months, &self.months),
+            (Field::DaysOfWeek, &days_of_week),
+        ] {
+            match values {
+                Some(values) => fields.push(format_field(field, values)?),
+                None => fields.push("*".to_string()),
+            }
+        }
+        if let Some(years) = &self.years {
+            fields.push(format_field(Field::Years, years)?);
+        }
+        Schedule::parse_with_format(&fields.join(" "), format)
+    }
+}
+
+fn format_field(field: Field, values: &[u32]) -> Result<String, ParseScheduleError> {
+    if values.is_empty() {
+        return Err(ParseScheduleError::InvalidRange {
+            field,
+            value: String::new(),
+        });
+    }
+    let values: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+    Ok(values.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Capabilities;
+    use chrono::Weekday::{Fri, Mon, Sun};
+    use std::str::FromStr;
+
+    #[test]
+    fn builder() {
+        let parse = |e| Schedule::from_str(e).unwrap();
+
+        assert_eq!(parse("* * * * *"), Schedule::builder().build().unwrap());
+        assert_eq!(
+            parse("30 9 * * Mon-Fri"),
+            Schedule::builder()
+                .minutes(30)
+                .hours(9)
+                .days_of_week(vec![Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Fri])
+                .build()
+                .unwrap()
+        );
+        assert_eq!(
+            parse("0 0 0 1 1-6 Sun 2030"),
+            Schedule::builder()
+                .seconds(0)
+                .minutes(0)
+                .hours(0)
+                .days_of_month(1)
+                .months(1..7)
+                .days_of_week(Sun)
+                .years(2030)
+                .build()
+                .unwrap()
+        );
+        let with_year = Schedule::builder().minutes(0).years([2030, 2031]).build();
+        assert_eq!(
+            Capabilities::YEARS,
+            with_year.as_ref().unwrap().capabilities()
+        );
+        assert_eq!(
+            parse("0 0 * * * * 2030,2031"),
+            with_year.unwrap().to_seven_field()
+        );
+    }
+
+    #[test]
+    fn days_of_week_numbered() {
+        let weekdays = Schedule::builder()
+            .days_of_week(vec![Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Fri])
+            .build();
+        assert_eq!(
+            weekdays,
+            Schedule::builder()
+                .days_of_week_numbered(1..=5, DayOfWeekNumbering::MondayOne)
+                .build()
+        );
+        assert_eq!(
+            weekdays,
+            Schedule::builder()
+                .days_of_week_numbered(2..=6, DayOfWeekNumbering::SundayOne)
+                .build()
+        );
+        assert_eq!(
+            Err(ParseScheduleError::InvalidRange {
+                field: Field::DaysOfWeek,
+                value: "0".into()
+            }),
+            Schedule::builder()
+                .days_of_week_numbered(0, DayOfWeekNumbering::MondayOne)
+                .build()
+        );
+    }
+
+    #[test]
+    fn builder_validation() {
+        assert_eq!(
+            Err(ParseScheduleError::InvalidRange {
+                field: Field::Seconds,
+                value: "60".into()
+            }),
+            Schedule::builder().seconds([0, 60]).build()
+        );
+        assert_eq!(
+            Err(ParseScheduleError::InvalidRange {
+                field: Field::DaysOfMonth,
+                value: "0".into()
+            }),
+            Schedule::builder().days_of_month(0).build()
+        );
+        assert_eq!(
+            Err(ParseScheduleError::InvalidRange {
+                field: Field::Hours,
+                value: String::new()
+            }),
+            Schedule::builder().hours(Vec::new()).build()
+        );
+    }
+}
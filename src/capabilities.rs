@@ -0,0 +1,262 @@
+use crate::schedule::{Schedule, Seconds};
+use std::fmt;
+use std::ops::{BitOr, BitOrAssign};
+
+/// A set of the nonstandard CRON extensions used by an expression.
+///
+/// Useful to reject or warn about expressions that won't be portable to a plain Crontab, e.g.
+/// before handing them to another scheduler.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Hash)]
+pub struct Capabilities(u8);
+
+impl Capabilities {
+    /// No extensions, a plain 5 field Crontab expression using numbers only.
+    pub const NONE: Capabilities = Capabilities(0);
+    /// A leading seconds field.
+    pub const SECONDS: Capabilities = Capabilities(1);
+    /// A trailing years field.
+    pub const YEARS: Capabilities = Capabilities(1 << 1);
+    /// Month or day of week names such as `Jan` or `Mon`.
+    pub const NAMES: Capabilities = Capabilities(1 << 2);
+    /// Step values such as `*/5` or `1-30/2`.
+    pub const STEPS: Capabilities = Capabilities(1 << 3);
+    /// A seconds interval beyond a single minute such as `*/90s`.
+    pub const INTERVAL: Capabilities = Capabilities(1 << 4);
+    /// Quartz day terms such as `L` for the last day of the month, `15W` for the weekday
+    /// nearest the 15th, `FriL` for the last Friday, `Fri#2` for the second or the `?`
+    /// wildcard.
+    pub const QUARTZ: Capabilities = Capabilities(1 << 5);
+    /// Jenkins hashed values such as `H` or `H/15`, see `Schedule::from_str_hashed`.
+    pub const HASHED: Capabilities = Capabilities(1 << 6);
+    /// OpenBSD random values such as `~` or `0~30`, see `Schedule::from_str_random`.
+    pub const RANDOM: Capabilities = Capabilities(1 << 7);
+
+    /// Returns true if every extension in `other` is also in `self`.
+    pub fn contains(self, other: Capabilities) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns true if no extensions are used.
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub(crate) fn remove(&mut self, other: Capabilities) {
+        self.0 &= !other.0;
+    }
+
+    /// Returns the extensions as a bit mask, see `Schedule::to_bytes`.
+    pub(crate) fn bits(self) -> u8 {
+        self.0
+    }
+
+    pub(crate) fn from_bits(bits: u8) -> Capabilities {
+        Capabilities(bits)
+    }
+
+    /// Detects the name, step, interval and Quartz extensions used by the raw expression fields.
+    pub(crate) fn from_fields(fields: &[&str], has_seconds: bool) -> Capabilities {
+        let mut capabilities = Capabilities::NONE;
+        if fields.iter().any(|f| f.contains('/')) {
+            capabilities |= Capabilities::STEPS;
+        }
+
+        let rest = match fields.split_first() {
+            Some((seconds, rest)) if has_seconds => {
+                if seconds.ends_with('s') {
+                    capabilities |= Capabilities::INTERVAL;
+                }
+                rest
+            }
+            _ => fields,
+        };
+        for (i, field) in rest.iter().enumerate() {
+            for item in field.split(',') {
+                // Quartz terms are written with letters without being names
+                let item = match strip_quartz(i, item) {
+                    Some(rest) => {
+                        capabilities |= Capabilities::QUARTZ;
+                        rest
+                    }
+                    None => item,
+                };
+                if item.chars().any(|c| c.is_ascii_alphabetic()) {
+                    capabilities |= Capabilities::NAMES;
+                }
+            }
+        }
+        capabilities
+    }
+}
+
+/// The indexes of the day fields after any seconds field.
+const DAY_OF_MONTH: usize = 2;
+const DAY_OF_WEEK: usize = 4;
+
+/// Returns the remainder of a list item of the field at index `i` without its Quartz term
+/// markers, or `None` if it isn't a Quartz term.
+fn strip_quartz(i: usize, item: &str) -> Option<&str> {
+    match i {
+        DAY_OF_MONTH | DAY_OF_WEEK if item == "?" => Some(""),
+        DAY_OF_MONTH if is_quartz_day_of_month(item) => Some(""),
+        DAY_OF_WEEK if item.contains('#') => item.split('#').next(),
+        DAY_OF_WEEK => item
+            .strip_suffix('L')
+            .or_else(|| item.strip_suffix('l'))
+            .filter(|day| !day.is_empty()),
+        _ => None,
+    }
+}
+
+fn is_quartz_day_of_month(item: &str) -> bool {
+    let upper = item.to_ascii_uppercase();
+    upper == "L"
+        || upper == "LW"
+        || upper.starts_with("L-")
+        || upper
+            .strip_suffix('W')
+            .is_some_and(|day| day.chars().all(|c| c.is_ascii_digit()))
+}
+
+impl BitOr for Capabilities {
+    type Output = Capabilities;
+
+    fn bitor(self, rhs: Capabilities) -> Capabilities {
+        Capabilities(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Capabilities {
+    fn bitor_assign(&mut self, rhs: Capabilities) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl fmt::Display for Capabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names = [
+            (Capabilities::SECONDS, "seconds"),
+            (Capabilities::YEARS, "years"),
+            (Capabilities::NAMES, "names"),
+            (Capabilities::STEPS, "steps"),
+            (Capabilities::INTERVAL, "interval"),
+            (Capabilities::QUARTZ, "quartz"),
+            (Capabilities::HASHED, "hashed"),
+            (Capabilities::RANDOM, "random"),
+        ];
+        let used: Vec<&str> = names
+            .iter()
+            .filter(|(c, _)| self.contains(*c))
+            .map(|(_, name)| *name)
+            .collect();
+        if used.is_empty() {
+            f.write_str("none")
+        } else {
+            f.write_str(&used.join(", "))
+        }
+    }
+}
+
+impl Schedule {
+    /// Returns the nonstandard extensions the expression was written with.
+    ///
+    /// Capabilities describe the syntax rather than when the schedule fires so they don't take
+    /// part in equality, ordering or the fingerprint.
+    /// ```rust
+    /// use cron_exp::{Capabilities, Schedule};
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 */5 * * * Mon-Fri").unwrap();
+    /// let capabilities = schedule.capabilities();
+    /// assert!(capabilities.contains(Capabilities::SECONDS | Capabilities::STEPS));
+    /// assert!(capabilities.contains(Capabilities::NAMES));
+    /// assert!(!capabilities.contains(Capabilities::YEARS));
+    ///
+    /// assert!(Schedule::from_str("30 9 * * 1").unwrap().capabilities().is_empty());
+    /// ```
+    pub fn capabilities(&self) -> Capabilities {
+        let mut capabilities = self.syntax;
+        if self.seconds != Seconds::Ignore {
+            capabilities |= Capabilities::SECONDS;
+        }
+        capabilities
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DropPolicy;
+    use std::str::FromStr;
+
+    #[test]
+    fn capabilities() {
+        let caps = |e: &str| Schedule::from_str(e).unwrap().capabilities();
+
+        assert_eq!(Capabilities::NONE, caps("0 9 1 1 0"));
+        assert_eq!(Capabilities::NAMES, caps("0 9 * Jan Sun"));
+        assert_eq!(Capabilities::STEPS, caps("*/15 * * * *"));
+        assert_eq!(Capabilities::SECONDS, caps("0 0 9 * * *"));
+        assert_eq!(
+            Capabilities::SECONDS | Capabilities::YEARS,
+            caps("0 0 9 * * * *")
+        );
+        assert_eq!(
+            Capabilities::SECONDS | Capabilities::YEARS | Capabilities::STEPS,
+            caps("0 0 9 * * * 2020/2")
+        );
+        assert_eq!("seconds, names", caps("0 0 9 * * Mon").to_string());
+        assert_eq!("none", caps("0 9 * * *").to_string());
+        assert_eq!(Capabilities::QUARTZ, caps("0 9 L * *"));
+        assert_eq!(
+            Capabilities::QUARTZ | Capabilities::NAMES,
+            caps("0 9 1,L-2 * Mon")
+        );
+        assert_eq!(
+            Capabilities::QUARTZ | Capabilities::NAMES,
+            caps("0 9 * * FriL")
+        );
+        assert_eq!(Capabilities::QUARTZ, caps("0 9 * * 5L"));
+        assert_eq!(Capabilities::QUARTZ, caps("0 9 15W,LW * *"));
+        assert_eq!(Capabilities::QUARTZ, caps("0 9 * * 5#2"));
+        assert_eq!(
+            Capabilities::SECONDS | Capabilities::QUARTZ,
+            caps("0 0 9 ? * *")
+        );
+    }
+
+    #[test]
+    fn capabilities_ignored_by_equality() {
+        let names = Schedule::from_str("0 9 * Jan Mon").unwrap();
+        let numbers = Schedule::from_str("0 9 * 1 1").unwrap();
+        assert_eq!(names, numbers);
+        assert_eq!(names.fingerprint(), numbers.fingerprint());
+        assert_ne!(names.capabilities(), numbers.capabilities());
+    }
+
+    #[test]
+    fn capabilities_follow_conversions() {
+        let schedule = Schedule::from_str("0 0 9 * * Mon *").unwrap();
+        assert_eq!(
+            Capabilities::NAMES,
+            schedule
+                .to_five_field(DropPolicy::Reject)
+                .unwrap()
+                .capabilities()
+        );
+        assert_eq!(
+            Capabilities::SECONDS | Capabilities::NAMES,
+            schedule
+                .to_six_field(DropPolicy::Reject)
+                .unwrap()
+                .capabilities()
+        );
+        assert_eq!(
+            Capabilities::SECONDS | Capabilities::YEARS,
+            Schedule::from_str("0 9 * * *")
+                .unwrap()
+                .to_seven_field()
+                .capabilities()
+        );
+    }
+}
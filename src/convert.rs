@@ -0,0 +1,96 @@
+use crate::bitset::BitSet;
+use crate::capabilities::Capabilities;
+use crate::errors::ConversionError;
+use crate::schedule::{Schedule, Seconds, Years};
+
+/// Determines what happens when converting to a form lacking a field the schedule constrains.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DropPolicy {
+    /// Return an error rather than changing when the schedule fires.
+    Reject,
+    /// Drop the field regardless, firing at the start of every matching minute for seconds and
+    /// in every year for years.
+    Discard,
+}
+
+impl Schedule {
+    /// Converts the schedule into its 5 field Crontab form dropping the seconds and years fields.
+    ///
+    /// A seconds field of exactly `0` is always dropped as it is implied by Crontab, other
+    /// constrained seconds or years are handled according to the `DropPolicy`.
+    /// ```rust
+    /// use cron_exp::{DropPolicy, Schedule};
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 30 9 * * *").unwrap();
+    /// assert_eq!(
+    ///     Schedule::from_str("30 9 * * *").unwrap(),
+    ///     schedule.to_five_field(DropPolicy::Reject).unwrap()
+    /// );
+    /// ```
+    pub fn to_five_field(&self, policy: DropPolicy) -> Result<Schedule, ConversionError> {
+        let seconds = match &self.seconds {
+            Seconds::Ignore => Seconds::Ignore,
+            Seconds::Constrained(s) if s.len() == 1 && s.contains(0) => Seconds::Ignore,
+            _ if policy == DropPolicy::Discard => Seconds::Ignore,
+            _ => return Err(ConversionError::LossySeconds),
+        };
+        let years = match &self.years {
+            Years::Constrained(_) if policy == DropPolicy::Reject => {
+                return Err(ConversionError::LossyYears)
+            }
+            _ => Years::Unbound,
+        };
+        Ok(Schedule {
+            seconds,
+            years,
+            syntax: self.without_years_syntax(),
+            ..self.clone()
+        })
+    }
+
+    /// Converts the schedule into its 6 field Vixie CRON form dropping the years field and
+    /// filling in a seconds field of `0` when absent.
+    pub fn to_six_field(&self, policy: DropPolicy) -> Result<Schedule, ConversionError> {
+        let years = match &self.years {
+            Years::Constrained(_) if policy == DropPolicy::Reject => {
+                return Err(ConversionError::LossyYears)
+            }
+            _ => Years::All,
+        };
+        Ok(Schedule {
+            seconds: seconds_or_zero(&self.seconds),
+            years,
+            syntax: self.without_years_syntax(),
+            ..self.clone()
+        })
+    }
+
+    /// Converts the schedule into its 7 field Vixie CRON form filling in a seconds field of `0`
+    /// and a years field of `*` when absent.
+    pub fn to_seven_field(&self) -> Schedule {
+        let years = match &self.years {
+            Years::Unbound => Years::All,
+            years => years.clone(),
+        };
+        Schedule {
+            seconds: seconds_or_zero(&self.seconds),
+            years,
+            syntax: self.syntax | Capabilities::YEARS,
+            ..self.clone()
+        }
+    }
+
+    fn without_years_syntax(&self) -> Capabilities {
+        let mut syntax = self.syntax;
+        syntax.remove(Capabilities::YEARS);
+        syntax
+    }
+}
+
+fn seconds_or_zero(seconds: &Seconds) -> Seconds {
+    match seconds {
+        Seconds::Ignore => Seconds::Constrained(BitSet::from([0])),
+        seconds => *seconds,
+    }
+}
@@ -0,0 +1,650 @@
+//! Parsing and writing of crontab files.
+use crate::schedule::{split_timezone_prefix, REBOOT};
+use crate::{DaySemantics, ParseOptions, ParseScheduleError, Schedule};
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+use thiserror::Error;
+
+const SCHEDULE_FIELDS: usize = 5;
+
+/// The expressions the `@` macros of a crontab stand for, `@reboot` is parsed by the schedule.
+const MACROS: [(&str, &str); 7] = [
+    ("@yearly", "0 0 1 1 *"),
+    ("@annually", "0 0 1 1 *"),
+    ("@monthly", "0 0 1 * *"),
+    ("@weekly", "0 0 * * 0"),
+    ("@daily", "0 0 * * *"),
+    ("@midnight", "0 0 * * *"),
+    ("@hourly", "0 * * * *"),
+];
+
+/// The variables naming the zone the schedules of the following entries are evaluated in, in
+/// order of precedence.
+const TZ_VARIABLES: [&str; 2] = ["CRON_TZ", "TZ"];
+
+/// The entries of a crontab file in order along with its comments and environment variables.
+/// ```rust
+/// use cron_exp::crontab::{Crontab, CrontabLine};
+/// use std::str::FromStr;
+///
+/// let crontab =
+///     Crontab::from_str("MAILTO=ops\n# nightly\n@daily backup.sh\n*/5 * * * * check.sh\n").unwrap();
+/// assert_eq!(2, crontab.entries.len());
+/// assert_eq!("check.sh", crontab.entries[1].command);
+/// assert_eq!(Some("ops"), crontab.variable("MAILTO"));
+/// assert_eq!(CrontabLine::Comment("nightly".to_string()), crontab.lines[1]);
+/// ```
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct Crontab {
+    pub entries: Vec<CrontabEntry>,
+    /// The comments, assignments and entries of the file in order, blank lines aside.
+    pub lines: Vec<CrontabLine>,
+}
+
+/// A line of a crontab file, as kept in order by `Crontab::lines`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CrontabLine {
+    /// A comment, without its leading `#` and the space after it.
+    Comment(String),
+    /// A `NAME=value` assignment, without the quotes around the value.
+    Variable { name: String, value: String },
+    /// The place of the next of `Crontab::entries`.
+    Entry,
+}
+
+/// A schedule and command pair of a crontab file.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CrontabEntry {
+    /// The schedule of the entry, matching days by either day field as cron does.
+    pub schedule: Schedule,
+    pub command: String,
+    /// The 1 based line number of the entry.
+    pub line: usize,
+}
+
+#[derive(Error, Debug)]
+pub enum CrontabError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+
+    #[error("Invalid schedule on line {line}: {source}")]
+    InvalidSchedule {
+        line: usize,
+        source: ParseScheduleError,
+    },
+
+    #[error("Missing command on line {0}")]
+    MissingCommand(usize),
+
+    #[error("Unknown macro {name} on line {line}")]
+    UnknownMacro { line: usize, name: String },
+
+    #[error("Schedule {0} can't be written as a crontab entry")]
+    UnsupportedSchedule(String),
+}
+
+impl Crontab {
+    /// Reads every line of the crontab, failing on the first invalid one.
+    pub fn read<R: BufRead>(reader: R) -> Result<Crontab, CrontabError> {
+        let mut reader = CrontabReader::new(reader);
+        let mut crontab = Crontab::default();
+        while let Some(line) = reader.next_line() {
+            match line? {
+                Line::Entry(entry) => {
+                    crontab.entries.push(entry);
+                    crontab.lines.push(CrontabLine::Entry);
+                }
+                Line::Other(line) => crontab.lines.push(line),
+            }
+        }
+        Ok(crontab)
+    }
+
+    /// Writes the lines in order, each `CrontabLine::Entry` as the next of the entries and the
+    /// entries beyond the lines last, see `CrontabWriter`.
+    pub fn write<W: Write>(&self, writer: W) -> Result<(), CrontabError> {
+        let mut writer = CrontabWriter::new(writer);
+        let mut entries = self.entries.iter();
+        for line in &self.lines {
+            match line {
+                CrontabLine::Comment(text) => writer.comment(text)?,
+                CrontabLine::Variable { name, value } => writer.variable(name, value)?,
+                CrontabLine::Entry => {
+                    if let Some(entry) = entries.next() {
+                        writer.entry(entry)?;
+                    }
+                }
+            }
+        }
+        for entry in entries {
+            writer.entry(entry)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the value last assigned to the environment variable, e.g. `MAILTO` or `SHELL`.
+    pub fn variable(&self, name: &str) -> Option<&str> {
+        self.lines.iter().rev().find_map(|line| match line {
+            CrontabLine::Variable { name: n, value } if n == name => Some(value.as_str()),
+            _ => None,
+        })
+    }
+}
+
+impl FromStr for Crontab {
+    type Err = CrontabError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Crontab::read(s.as_bytes())
+    }
+}
+
+/// Incrementally parses crontab entries from any `BufRead` one line at a time, so arbitrarily
+/// large crontabs can be audited without loading them into memory.
+///
+/// Blank lines and comments are skipped and `@` macros such as `@daily` are expanded to their
+/// expressions. Environment variable assignments are collected, a `CRON_TZ` or `TZ` zone applies
+/// to the schedules of the entries following it as a `CRON_TZ=` prefix would, so requires the
+/// `tz` feature.
+/// ```rust
+/// use cron_exp::crontab::CrontabReader;
+///
+/// let crontab = "# nightly backup\n0 2 * * * /usr/bin/backup --all\n";
+/// for entry in CrontabReader::new(crontab.as_bytes()) {
+///     let entry = entry.unwrap();
+///     assert_eq!(entry.line, 2);
+///     assert_eq!(entry.command, "/usr/bin/backup --all");
+/// }
+/// ```
+#[derive(Debug)]
+pub struct CrontabReader<R> {
+    reader: R,
+    buf: String,
+    line: usize,
+    environment: BTreeMap<String, String>,
+}
+
+impl<R> CrontabReader<R>
+where
+    R: BufRead,
+{
+    pub fn new(reader: R) -> Self {
+        CrontabReader {
+            reader,
+            buf: String::new(),
+            line: 0,
+            environment: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the environment variables assigned by the lines read so far.
+    pub fn environment(&self) -> &BTreeMap<String, String> {
+        &self.environment
+    }
+
+    /// Reads the next line that isn't blank.
+    fn next_line(&mut self) -> Option<Result<Line, CrontabError>> {
+        loop {
+            self.buf.clear();
+            match self.reader.read_line(&mut self.buf) {
+                Ok(0) => return None,
+                Ok(_) => self.line += 1,
+                Err(e) => return Some(Err(e.into())),
+            }
+
+            let line = self.buf.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(text) = line.strip_prefix('#') {
+                let text = text.strip_prefix(' ').unwrap_or(text);
+                return Some(Ok(Line::Other(CrontabLine::Comment(text.to_string()))));
+            }
+            if let Some((name, value)) = parse_assignment(line) {
+                self.environment.insert(name.to_string(), value.to_string());
+                return Some(Ok(Line::Other(CrontabLine::Variable {
+                    name: name.to_string(),
+                    value: value.to_string(),
+                })));
+            }
+            let timezone = TZ_VARIABLES
+                .iter()
+                .filter_map(|name| self.environment.get(*name))
+                .find(|zone| !zone.is_empty());
+            return Some(
+                parse_entry(line, self.line, timezone.map(String::as_str)).map(Line::Entry),
+            );
+        }
+    }
+}
+
+/// A line read by `CrontabReader`.
+enum Line {
+    Entry(CrontabEntry),
+    Other(CrontabLine),
+}
+
+impl<R> Iterator for CrontabReader<R>
+where
+    R: BufRead,
+{
+    type Item = Result<CrontabEntry, CrontabError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.next_line()? {
+                Ok(Line::Entry(entry)) => return Some(Ok(entry)),
+                Ok(Line::Other(_)) => {}
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+/// Writes comments, environment variables and entries as crontab lines parsing back into the
+/// same `Crontab`, the reverse of `CrontabReader`.
+///
+/// Entries are written as their schedule's 5 fields or `@reboot` followed by the command, each
+/// separated by a single space. A `CRON_TZ` line is written before an entry whose schedule is
+/// evaluated in another zone than the previous one.
+/// ```rust
+/// use cron_exp::crontab::{Crontab, CrontabWriter};
+/// use std::str::FromStr;
+///
+/// let crontab = Crontab::from_str("0 2 * * Mon-Fri  backup.sh --all").unwrap();
+/// let mut writer = CrontabWriter::new(Vec::new());
+/// writer.comment("nightly backup").unwrap();
+/// writer.variable("MAILTO", "ops").unwrap();
+/// writer.entry(&crontab.entries[0]).unwrap();
+/// assert_eq!(
+///     "# nightly backup\nMAILTO=ops\n0 2 * * 1-5 backup.sh --all\n",
+///     String::from_utf8(writer.into_inner()).unwrap()
+/// );
+/// ```
+#[derive(Debug)]
+pub struct CrontabWriter<W> {
+    writer: W,
+    environment: BTreeMap<String, String>,
+}
+
+impl<W> CrontabWriter<W>
+where
+    W: Write,
+{
+    pub fn new(writer: W) -> Self {
+        CrontabWriter {
+            writer,
+            environment: BTreeMap::new(),
+        }
+    }
+
+    /// Writes the text as comment lines.
+    pub fn comment(&mut self, text: &str) -> Result<(), CrontabError> {
+        for line in text.lines() {
+            writeln!(self.writer, "# {}", line)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a `NAME=value` line, quoting values with surrounding whitespace or quotes.
+    pub fn variable(&mut self, name: &str, value: &str) -> Result<(), CrontabError> {
+        if value.trim() != value || value.starts_with(['"', '\''].as_ref()) {
+            writeln!(self.writer, "{}=\"{}\"", name, value)?;
+        } else {
+            writeln!(self.writer, "{}={}", name, value)?;
+        }
+        self.environment.insert(name.to_string(), value.to_string());
+        Ok(())
+    }
+
+    /// Writes the entry's schedule and command, preceded by a `CRON_TZ` line when its zone
+    /// differs from the zone in effect.
+    pub fn entry(&mut self, entry: &CrontabEntry) -> Result<(), CrontabError> {
+        let expression = entry.schedule.to_string();
+        let unsupported = || CrontabError::UnsupportedSchedule(expression.clone());
+        let (_, fields) = split_timezone_prefix(&expression).map_err(|_| unsupported())?;
+        let fields = fields.trim_start();
+        let count = fields.split_whitespace().count();
+        if count != SCHEDULE_FIELDS && fields != REBOOT {
+            return Err(unsupported());
+        }
+
+        let zone = entry.schedule.timezone_name();
+        if zone != self.timezone() {
+            self.variable("CRON_TZ", zone.unwrap_or_default())?;
+            if zone != self.timezone() {
+                // an empty CRON_TZ falls back to TZ
+                return Err(unsupported());
+            }
+        }
+        writeln!(self.writer, "{} {}", fields, entry.command)?;
+        Ok(())
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Returns the zone the schedules of the following entries are evaluated in.
+    fn timezone(&self) -> Option<&str> {
+        TZ_VARIABLES
+            .iter()
+            .filter_map(|name| self.environment.get(*name))
+            .find(|zone| !zone.is_empty())
+            .map(String::as_str)
+    }
+}
+
+/// Splits a `NAME=value` line into its name and value, without the quotes around the value.
+fn parse_assignment(line: &str) -> Option<(&str, &str)> {
+    let name = line
+        .split(|c: char| c == '=' || c.is_whitespace())
+        .next()
+        .unwrap_or_default();
+    let value = line[name.len()..].trim_start().strip_prefix('=')?.trim();
+    let unquoted = ['"', '\'']
+        .iter()
+        .find_map(|&q| value.strip_prefix(q).and_then(|v| v.strip_suffix(q)));
+    Some((name, unquoted.unwrap_or(value)))
+}
+
+fn parse_entry(
+    line: &str,
+    line_number: usize,
+    timezone: Option<&str>,
+) -> Result<CrontabEntry, CrontabError> {
+    let n = if line.starts_with('@') {
+        1
+    } else {
+        SCHEDULE_FIELDS
+    };
+    let (fields, command) = split_fields(line, n);
+    if command.is_empty() {
+        return Err(CrontabError::MissingCommand(line_number));
+    }
+
+    let expression = match fields.strip_prefix('@') {
+        Some(_) if fields == REBOOT => fields,
+        Some(_) => MACROS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(fields))
+            .map(|(_, expression)| *expression)
+            .ok_or_else(|| CrontabError::UnknownMacro {
+                line: line_number,
+                name: fields.to_string(),
+            })?,
+        None => fields,
+    };
+    let expression = match timezone {
+        Some(zone) => format!("CRON_TZ={} {}", zone, expression),
+        None => expression.to_string(),
+    };
+    let options = ParseOptions::default().day_semantics(DaySemantics::Or);
+    let schedule = Schedule::parse_with(&expression, &options).map_err(|source| {
+        CrontabError::InvalidSchedule {
+            line: line_number,
+            source,
+        }
+    })?;
+    Ok(CrontabEntry {
+        schedule,
+        command: command.to_string(),
+        line: line_number,
+    })
+}
+
+/// Splits the line after its first `n` whitespace separated fields returning the fields and the
+/// remainder.
+fn split_fields(line: &str, n: usize) -> (&str, &str) {
+    let mut end = 0;
+    for _ in 0..n {
+        let rest = &line[end..];
+        let start = end + (rest.len() - rest.trim_start().len());
+        let rest = &line[start..];
+        end = start + rest.find(char::is_whitespace).unwrap_or(rest.len());
+    }
+    (&line[..end], line[end..].trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn read_entries() {
+        let crontab = "\
+# comment
+SHELL=/bin/bash
+
+*/5 * * * *   /usr/bin/check   --quiet
+0 2 * * Mon-Fri backup.sh
+bad * * * * nope
+0 3 * * *
+@reboot /usr/bin/warm-cache
+";
+        let entries: Vec<_> = CrontabReader::new(crontab.as_bytes()).collect();
+        assert_eq!(entries.len(), 5);
+
+        let first = entries[0].as_ref().unwrap();
+        assert_eq!(first.line, 4);
+        assert_eq!(first.command, "/usr/bin/check   --quiet");
+        assert_eq!(first.schedule, Schedule::from_str("*/5 * * * *").unwrap());
+
+        let second = entries[1].as_ref().unwrap();
+        assert_eq!(second.line, 5);
+        assert_eq!(second.command, "backup.sh");
+
+        assert!(matches!(
+            entries[2],
+            Err(CrontabError::InvalidSchedule { line: 6, .. })
+        ));
+        assert!(matches!(entries[3], Err(CrontabError::MissingCommand(7))));
+
+        let reboot = entries[4].as_ref().unwrap();
+        assert!(reboot.schedule.is_reboot());
+        assert_eq!(reboot.command, "/usr/bin/warm-cache");
+    }
+    #[test]
+    fn either_day_field_matches() {
+        let crontab = Crontab::from_str(
+            "0 0 1,15 * Mon report.sh
+0 0 * * Mon weekly.sh
+",
+        )
+        .unwrap();
+        let report = &crontab.entries[0].schedule;
+        assert_eq!(DaySemantics::Or, report.day_semantics());
+        let from = Utc.with_ymd_and_hms(2022, 6, 1, 12, 0, 0).unwrap();
+        let at = |d| Utc.with_ymd_and_hms(2022, 6, d, 0, 0, 0).unwrap();
+        assert_eq!(
+            vec![at(6), at(13), at(15), at(20)],
+            report.iter_after(&from).take(4).collect::<Vec<_>>()
+        );
+        // a wildcard day field leaves the other alone to match
+        assert_eq!(Some(at(6)), crontab.entries[1].schedule.next_after(&from));
+    }
+
+    #[test]
+    fn macros() {
+        let crontab = Crontab::from_str(
+            "@yearly a\n@ANNUALLY b\n@monthly c\n@weekly d\n@daily e\n@midnight f\n@hourly g\n",
+        )
+        .unwrap();
+        let expected = [
+            "0 0 1 1 *",
+            "0 0 1 1 *",
+            "0 0 1 * *",
+            "0 0 * * 0",
+            "0 0 * * *",
+            "0 0 * * *",
+            "0 * * * *",
+        ];
+        assert_eq!(expected.len(), crontab.entries.len());
+        for (entry, expression) in crontab.entries.iter().zip(expected.iter()) {
+            assert_eq!(Schedule::from_str(expression).unwrap(), entry.schedule);
+        }
+        assert_eq!("g", crontab.entries[6].command);
+
+        assert!(matches!(
+            Crontab::from_str("# comment\n@fortnightly backup.sh"),
+            Err(CrontabError::UnknownMacro { line: 2, ref name }) if name == "@fortnightly"
+        ));
+        assert!(matches!(
+            Crontab::from_str("@daily"),
+            Err(CrontabError::MissingCommand(1))
+        ));
+    }
+
+    #[test]
+    fn parse_crontab() {
+        let crontab = Crontab::from_str("\n# only comments\n\n").unwrap();
+        assert!(crontab.entries.is_empty());
+        assert_eq!(
+            vec![CrontabLine::Comment("only comments".to_string())],
+            crontab.lines
+        );
+        assert_eq!(Crontab::default(), Crontab::from_str("\n\n").unwrap());
+
+        let crontab =
+            Crontab::read("MAILTO=ops\n30 9 * * Mon-Fri report.sh\n@reboot warm.sh\n".as_bytes())
+                .unwrap();
+        assert_eq!(2, crontab.entries.len());
+        assert_eq!(2, crontab.entries[0].line);
+        assert!(crontab.entries[1].schedule.is_reboot());
+        assert!(matches!(
+            Crontab::from_str("0 0 * * * ok.sh\n0 25 * * * bad.sh"),
+            Err(CrontabError::InvalidSchedule { line: 2, .. })
+        ));
+    }
+    #[test]
+    fn environment() {
+        let crontab = Crontab::from_str(
+            "SHELL=/bin/bash\nMAILTO = \"ops@example.com\"\nPATH='/usr/bin'\nMAILTO=\n0 2 * * * backup.sh\n",
+        )
+        .unwrap();
+        assert_eq!(Some("/bin/bash"), crontab.variable("SHELL"));
+        assert_eq!(Some("/usr/bin"), crontab.variable("PATH"));
+        assert_eq!(Some(""), crontab.variable("MAILTO"));
+        assert_eq!(None, crontab.variable("TZ"));
+        assert_eq!(5, crontab.lines.len());
+
+        let mut reader = CrontabReader::new("A=1\n* * * * * a\nB=2\n".as_bytes());
+        reader.next().unwrap().unwrap();
+        assert_eq!(1, reader.environment().len());
+        assert!(reader.next().is_none());
+        assert_eq!(2, reader.environment().len());
+    }
+
+    #[cfg(feature = "tz")]
+    #[test]
+    fn timezone_variables() {
+        let crontab = Crontab::from_str(
+            "0 9 * * * utc.sh\nTZ=Europe/Paris\n0 9 * * * paris.sh\nCRON_TZ=Asia/Tokyo\n@daily tokyo.sh\nCRON_TZ=\n0 9 * * * again.sh\n",
+        )
+        .unwrap();
+        let zones: Vec<_> = crontab
+            .entries
+            .iter()
+            .map(|entry| entry.schedule.timezone().map(|tz| tz.name()))
+            .collect();
+        assert_eq!(
+            vec![
+                None,
+                Some("Europe/Paris"),
+                Some("Asia/Tokyo"),
+                // an empty CRON_TZ falls back to TZ
+                Some("Europe/Paris")
+            ],
+            zones
+        );
+        assert!(matches!(
+            Crontab::from_str("TZ=Nowhere\n0 9 * * * a.sh"),
+            Err(CrontabError::InvalidSchedule {
+                line: 2,
+                source: ParseScheduleError::InvalidTimezone(_)
+            })
+        ));
+    }
+    #[test]
+    fn write_crontab() {
+        let text = "\
+SHELL=/bin/bash
+MAILTO=\" ops\"
+*/5 * * * *   /usr/bin/check   --quiet
+0 2 * * Mon-Fri backup.sh
+@daily rotate.sh
+@reboot warm.sh
+";
+        let crontab = Crontab::from_str(text).unwrap();
+        let mut written = Vec::new();
+        crontab.write(&mut written).unwrap();
+        let written = String::from_utf8(written).unwrap();
+        assert_eq!(
+            "\
+SHELL=/bin/bash
+MAILTO=\" ops\"
+*/5 * * * * /usr/bin/check   --quiet
+0 2 * * 1-5 backup.sh
+0 0 * * * rotate.sh
+@reboot warm.sh
+",
+            written
+        );
+        let reread = Crontab::from_str(&written).unwrap();
+        assert_eq!(crontab.lines, reread.lines);
+        for (a, b) in crontab.entries.iter().zip(reread.entries.iter()) {
+            assert_eq!((&a.schedule, &a.command), (&b.schedule, &b.command));
+        }
+
+        let mut writer = CrontabWriter::new(Vec::new());
+        writer.comment("first\nsecond").unwrap();
+        writer.variable("EMPTY", "").unwrap();
+        assert_eq!(
+            "# first\n# second\nEMPTY=\n",
+            String::from_utf8(writer.into_inner()).unwrap()
+        );
+
+        // assignments and comments stay in place between the entries
+        let text = "# alerts\nMAILTO=a\n0 1 * * * one\nMAILTO=b\n# two\n0 2 * * * two\n";
+        let mut crontab = Crontab::from_str(text).unwrap();
+        let mut written = Vec::new();
+        crontab.write(&mut written).unwrap();
+        assert_eq!(text, String::from_utf8(written).unwrap());
+        // entries beyond the lines are written last
+        crontab.entries.push(crontab.entries[0].clone());
+        let mut written = Vec::new();
+        crontab.write(&mut written).unwrap();
+        assert_eq!(
+            format!("{}0 1 * * * one\n", text),
+            String::from_utf8(written).unwrap()
+        );
+
+        let seconds = CrontabEntry {
+            schedule: Schedule::from_str("*/10 * * * * *").unwrap(),
+            command: "poll.sh".to_string(),
+            line: 1,
+        };
+        assert!(matches!(
+            CrontabWriter::new(Vec::new()).entry(&seconds),
+            Err(CrontabError::UnsupportedSchedule(_))
+        ));
+    }
+
+    #[cfg(feature = "tz")]
+    #[test]
+    fn write_timezones() {
+        let crontab = Crontab::from_str(
+            "0 9 * * * utc.sh\nCRON_TZ=Europe/Paris\n0 9 * * * paris.sh\n0 10 * * * paris2.sh\nCRON_TZ=\n0 9 * * * none.sh\n",
+        )
+        .unwrap();
+        let mut written = Vec::new();
+        crontab.write(&mut written).unwrap();
+        assert_eq!(
+            "0 9 * * * utc.sh\nCRON_TZ=Europe/Paris\n0 9 * * * paris.sh\n0 10 * * * paris2.sh\nCRON_TZ=\n0 9 * * * none.sh\n",
+            String::from_utf8(written).unwrap()
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use crate::schedule::Schedule;
+use chrono::{DateTime, TimeZone};
+
+/// A position in the occurrences of a schedule moving forwards and backwards one occurrence at
+/// a time, created with `Schedule::cursor`, e.g. for a calendar paging through occurrences.
+///
+/// Moving puts the cursor on the occurrence moved to, so the neighbours are always the
+/// occurrences strictly after and strictly before its position. Moving past the first or last
+/// occurrence leaves the cursor where it was.
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use cron_exp::Schedule;
+/// use std::str::FromStr;
+///
+/// let schedule = Schedule::from_str("0 9 * * Mon-Fri").unwrap();
+/// let at = |d| Utc.with_ymd_and_hms(2022, 6, d, 9, 0, 0).unwrap();
+/// let mut cursor = schedule.cursor(&at(1));
+///
+/// assert_eq!(Some(at(2)), cursor.advance());
+/// assert_eq!(Some(at(3)), cursor.advance());
+/// // the weekend is skipped
+/// assert_eq!(Some(at(6)), cursor.peek_next());
+/// assert_eq!(Some(at(2)), cursor.retreat());
+/// assert_eq!(Some(at(1)), cursor.peek_prev());
+///
+/// cursor.seek(&at(13));
+/// assert_eq!(Some(at(14)), cursor.advance());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ScheduleCursor<'a, Z>
+where
+    Z: TimeZone,
+{
+    schedule: &'a Schedule,
+    position: DateTime<Z>,
+}
+
+impl Schedule {
+    /// Returns a cursor positioned at the supplied datetime, see `ScheduleCursor`.
+    pub fn cursor<Z>(&self, dt: &DateTime<Z>) -> ScheduleCursor<'_, Z>
+    where
+        Z: TimeZone,
+    {
+        ScheduleCursor {
+            schedule: self,
+            position: dt.clone(),
+        }
+    }
+}
+
+impl<'a, Z> ScheduleCursor<'a, Z>
+where
+    Z: TimeZone,
+{
+    /// Returns the schedule the cursor moves through.
+    pub fn schedule(&self) -> &'a Schedule {
+        self.schedule
+    }
+
+    /// Returns the position of the cursor, the occurrence it last moved to or the datetime it
+    /// was created or sought at.
+    pub fn position(&self) -> &DateTime<Z> {
+        &self.position
+    }
+
+    /// Returns the occurrence strictly after the position without moving.
+    pub fn peek_next(&self) -> Option<DateTime<Z>> {
+        self.schedule.next_after(&self.position)
+    }
+
+    /// Returns the occurrence strictly before the position without moving.
+    pub fn peek_prev(&self) -> Option<DateTime<Z>> {
+        self.schedule.prev_before(&self.position)
+    }
+
+    /// Moves to the occurrence strictly after the position and returns it, or returns `None`
+    /// without moving when there is none.
+    pub fn advance(&mut self) -> Option<DateTime<Z>> {
+        let next = self.peek_next()?;
+        self.position = next.clone();
+        Some(next)
+    }
+
+    /// Moves to the occurrence strictly before the position and returns it, or returns `None`
+    /// without moving when there is none.
+    pub fn retreat(&mut self) -> Option<DateTime<Z>> {
+        let prev = self.peek_prev()?;
+        self.position = prev.clone();
+        Some(prev)
+    }
+
+    /// Moves to the supplied datetime, which needn't be an occurrence.
+    pub fn seek(&mut self, dt: &DateTime<Z>) {
+        self.position = dt.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::str::FromStr;
+
+    #[test]
+    fn pages_forwards_and_backwards() {
+        let schedule = Schedule::from_str("0 */15 * * * *").unwrap();
+        let from = Utc.with_ymd_and_hms(2022, 6, 1, 9, 5, 0).unwrap();
+        let mut cursor = schedule.cursor(&from);
+
+        let page: Vec<_> = (0..4).map_while(|_| cursor.advance()).collect();
+        assert_eq!(schedule.iter_from(&from).take(4).collect::<Vec<_>>(), page);
+        assert_eq!(&page[3], cursor.position());
+
+        let back: Vec<_> = (0..3).map_while(|_| cursor.retreat()).collect();
+        let expected: Vec<_> = page.iter().rev().skip(1).cloned().collect();
+        assert_eq!(expected, back);
+        assert_eq!(Some(page[1]), cursor.peek_next());
+    }
+
+    #[test]
+    fn stays_put_at_the_ends() {
+        let schedule = Schedule::from_str("0 0 0 1 1 * 2020-2021").unwrap();
+        let first = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+        let last = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+        let mut cursor = schedule.cursor(&first);
+
+        assert_eq!(None, cursor.retreat());
+        assert_eq!(&first, cursor.position());
+        assert_eq!(Some(last), cursor.advance());
+        assert_eq!(None, cursor.advance());
+        assert_eq!(None, cursor.peek_next());
+        assert_eq!(&last, cursor.position());
+
+        cursor.seek(&Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap());
+        assert_eq!(Some(last), cursor.retreat());
+    }
+}
@@ -0,0 +1,408 @@
+use crate::bitset::BitSet;
+use crate::locale::{fill, Locale, Phrases, UnitPhrases};
+use crate::options::DaySemantics;
+use crate::schedule::{
+    DayOfMonthTerm, DayOfWeekTerm, Schedule, Seconds, TimeRange, Years, MAX_YEAR, MIN_YEAR,
+};
+
+/// Times of day are listed individually up to this many hours, beyond it the minutes and hours
+/// are described separately.
+const MAX_LISTED_HOURS: usize = 3;
+
+impl Schedule {
+    /// Returns an English description of when the schedule fires, e.g. to show users what an
+    /// expression does.
+    /// ```rust
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 30 9 1,15 May-Aug Mon,Wed,Fri").unwrap();
+    /// assert_eq!(
+    ///     "At 09:30 on day 1 and 15 of May through August, only on Monday, Wednesday and Friday",
+    ///     schedule.describe()
+    /// );
+    /// let schedule = Schedule::from_str("*/15 9-17 * * Mon-Fri").unwrap();
+    /// assert_eq!(
+    ///     "Every 15 minutes, between 09:00 and 17:59, only on Monday through Friday",
+    ///     schedule.describe()
+    /// );
+    /// ```
+    pub fn describe(&self) -> String {
+        self.describe_in(Locale::En)
+    }
+
+    /// Returns a description of when the schedule fires in the language of the locale.
+    /// ```rust
+    /// use cron_exp::{Locale, Schedule};
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 9 * * Mon").unwrap();
+    /// assert_eq!("At 09:00, only on Monday", schedule.describe_in(Locale::En));
+    /// ```
+    pub fn describe_in(&self, locale: Locale) -> String {
+        let p = locale.phrases();
+        let mut s = if self.reboot {
+            p.reboot.to_string()
+        } else {
+            let mut s = self.describe_time(p);
+            s.push_str(&self.describe_date(p));
+            s
+        };
+        if let Some(name) = self.timezone_name() {
+            s.push_str(&fill(p.in_zone, &[name]));
+        }
+
+        let mut chars = s.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => s,
+        }
+    }
+
+    fn describe_time(&self, p: &Phrases) -> String {
+        // a single second of zero reads as no seconds at all
+        let second = match &self.seconds {
+            Seconds::Ignore => Some(0),
+            Seconds::Constrained(set) if set.len() == 1 => set.first(),
+            _ => None,
+        };
+        let minute = match &self.minutes {
+            TimeRange::Constrained(set) if set.len() == 1 => set.first(),
+            _ => None,
+        };
+        if let (Some(second), Some(minute), TimeRange::Constrained(hours)) =
+            (second, minute, &self.hours)
+        {
+            if hours.len() <= MAX_LISTED_HOURS {
+                let times: Vec<String> = hours
+                    .iter()
+                    .map(|hour| match second {
+                        0 => format!("{:02}:{:02}", hour, minute),
+                        _ => format!("{:02}:{:02}:{:02}", hour, minute, second),
+                    })
+                    .collect();
+                return fill(p.at, &[&join(p, &times)]);
+            }
+        }
+
+        let mut parts = Vec::new();
+        match &self.seconds {
+            Seconds::Ignore => {}
+            Seconds::All => parts.push(p.seconds.every.to_string()),
+            Seconds::Interval(interval) => {
+                parts.push(fill(p.seconds.every_n, &[&interval.to_string()]))
+            }
+            Seconds::Constrained(set) if second == Some(0) && set.len() == 1 => {}
+            Seconds::Constrained(set) => parts.push(describe_unit(p, &p.seconds, set)),
+        }
+        let every_second = matches!(self.seconds, Seconds::All | Seconds::Interval(_))
+            || matches!(&self.seconds, Seconds::Constrained(set) if step(set, 0, 59).is_some());
+        match (&self.minutes, minute) {
+            (TimeRange::All, _) if every_second => {}
+            (TimeRange::All, _) => parts.push(p.minutes.every.to_string()),
+            (_, Some(minute)) => parts.push(fill(p.minutes_past_hour, &[&minute.to_string()])),
+            (TimeRange::Constrained(set), None) => parts.push(describe_unit(p, &p.minutes, set)),
+        }
+        if let TimeRange::Constrained(hours) = &self.hours {
+            parts.push(describe_hours(p, hours));
+        }
+        parts.join(", ")
+    }
+
+    fn describe_date(&self, p: &Phrases) -> String {
+        let mut s = String::new();
+        // a day field covering every day matches every day when either field matches
+        let every_day = self.effective_day_semantics() == DaySemantics::Or
+            && ((self.days_of_month == TimeRange::All && self.day_of_month_terms.is_empty())
+                || (self.days_of_week == TimeRange::All && self.day_of_week_terms.is_empty()));
+
+        let mut days = Vec::new();
+        if let TimeRange::Constrained(set) = &self.days_of_month {
+            if !set.is_empty() {
+                let values = describe_values(p, set, |v| v.to_string());
+                days.push(fill(p.day, &[&values]));
+            }
+        }
+        days.extend(self.day_of_month_terms.iter().map(|term| match term {
+            DayOfMonthTerm::Last(0) => p.last_day.to_string(),
+            DayOfMonthTerm::Last(1) => p.one_day_before_last.to_string(),
+            DayOfMonthTerm::Last(offset) => fill(p.days_before_last, &[&offset.to_string()]),
+            DayOfMonthTerm::NearestWeekday(day) => fill(p.nearest_weekday, &[&day.to_string()]),
+            DayOfMonthTerm::LastWeekday => p.last_weekday.to_string(),
+        }));
+        let months = match &self.months {
+            TimeRange::All => None,
+            TimeRange::Constrained(set) => Some(describe_values(p, set, |v| {
+                p.months[v as usize - 1].to_string()
+            })),
+        };
+        if every_day {
+            days.clear();
+        }
+        match (days.is_empty(), &months) {
+            (false, None) => s.push_str(&fill(p.on_days_of_month, &[&join(p, &days)])),
+            (false, Some(months)) => s.push_str(&fill(p.on_days_in, &[&join(p, &days), months])),
+            (true, Some(months)) => s.push_str(&fill(p.only_in, &[months])),
+            (true, None) => {}
+        }
+
+        let mut weekdays = Vec::new();
+        if let TimeRange::Constrained(set) = &self.days_of_week {
+            if !set.is_empty() {
+                weekdays.push(describe_values(p, set, |v| {
+                    p.weekdays[v as usize - 1].to_string()
+                }));
+            }
+        }
+        weekdays.extend(self.day_of_week_terms.iter().map(|term| match term {
+            DayOfWeekTerm::Last(day) => {
+                fill(p.last_weekday_of_month, &[p.weekdays[*day as usize - 1]])
+            }
+            DayOfWeekTerm::Nth(day, n) => fill(
+                p.nth_weekday_of_month,
+                &[p.ordinals[*n as usize - 1], p.weekdays[*day as usize - 1]],
+            ),
+        }));
+        if !weekdays.is_empty() && !every_day {
+            let phrase = match self.effective_day_semantics() {
+                DaySemantics::And => p.only_on,
+                DaySemantics::Or => p.or_on,
+            };
+            s.push_str(&fill(phrase, &[&join(p, &weekdays)]));
+        }
+
+        if let Years::Constrained(years) = &self.years {
+            match step(years.iter().cloned(), MIN_YEAR, MAX_YEAR) {
+                Some((start, step)) => s.push_str(&fill(
+                    p.every_n_years_from,
+                    &[&step.to_string(), &start.to_string()],
+                )),
+                None => {
+                    let years = describe_values(p, years.iter().cloned(), |v| v.to_string());
+                    s.push_str(&fill(p.only_in, &[&years]));
+                }
+            }
+        }
+        s
+    }
+}
+
+/// Describes the values of the seconds or minutes field.
+fn describe_unit(p: &Phrases, unit: &UnitPhrases, set: &BitSet) -> String {
+    match step(set, 0, 59) {
+        Some((0, step)) => fill(unit.every_n, &[&step.to_string()]),
+        Some((start, step)) => fill(unit.every_n_from, &[&step.to_string(), &start.to_string()]),
+        None if set.len() == 1 => fill(unit.at_one, &[&describe_values(p, set, |v| v.to_string())]),
+        None => fill(unit.at_many, &[&describe_values(p, set, |v| v.to_string())]),
+    }
+}
+
+fn describe_hours(p: &Phrases, set: &BitSet) -> String {
+    let values: Vec<u32> = set.iter().collect();
+    let (first, last) = (values[0], values[values.len() - 1]);
+    let time = |hour: u32| format!("{:02}:00", hour);
+    if let Some((start, step)) = step(set, 0, 23) {
+        return match start {
+            0 => fill(p.every_n_hours, &[&step.to_string()]),
+            _ => fill(p.every_n_hours_from, &[&step.to_string(), &time(start)]),
+        };
+    }
+    if last - first + 1 == values.len() as u32 {
+        return fill(p.between_hours, &[&time(first), &format!("{:02}:59", last)]);
+    }
+    fill(p.at, &[&describe_values(p, set, time)])
+}
+
+/// Returns the start and step of values evenly spaced up to the end of the field, as written
+/// with `/`.
+fn step<I>(set: I, min: u32, max: u32) -> Option<(u32, u32)>
+where
+    I: IntoIterator<Item = u32>,
+{
+    let values: Vec<u32> = set.into_iter().collect();
+    if values.len() < 3 || values[0] < min {
+        return None;
+    }
+    let step = values[1] - values[0];
+    let last = values[values.len() - 1];
+    if step > 1 && values.windows(2).all(|w| w[1] - w[0] == step) && last + step > max {
+        Some((values[0], step))
+    } else {
+        None
+    }
+}
+
+/// Lists the values collapsing runs of three or more into ranges.
+fn describe_values<I, F>(p: &Phrases, set: I, name: F) -> String
+where
+    I: IntoIterator<Item = u32>,
+    F: Fn(u32) -> String,
+{
+    let values: Vec<u32> = set.into_iter().collect();
+    let mut items = Vec::new();
+    let mut i = 0;
+    while i < values.len() {
+        let start = values[i];
+        let mut end = start;
+        while i + 1 < values.len() && values[i + 1] == end + 1 {
+            i += 1;
+            end = values[i];
+        }
+        match end - start {
+            0 => items.push(name(start)),
+            1 => {
+                items.push(name(start));
+                items.push(name(end));
+            }
+            _ => items.push(fill(p.through, &[&name(start), &name(end)])),
+        }
+        i += 1;
+    }
+    join(p, &items)
+}
+
+/// Joins the items as a list, `a, b and c` in English.
+fn join(p: &Phrases, items: &[String]) -> String {
+    match items.split_last() {
+        None => String::new(),
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{}{}{}", rest.join(", "), p.and, last),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn describe() {
+        let describe = |e: &str| Schedule::from_str(e).unwrap().describe();
+        let tests = [
+            ("* * * * *", "Every minute"),
+            ("* * * * * *", "Every second"),
+            ("0 0 * * *", "At 00:00"),
+            ("15 30 9 * * *", "At 09:30:15"),
+            ("30 9,12,15 * * *", "At 09:30, 12:30 and 15:30"),
+            ("30 * * * *", "At 30 minutes past the hour"),
+            ("0 */2 * * *", "At 0 minutes past the hour, every 2 hours"),
+            (
+                "0 8-20/4 * * *",
+                "At 0 minutes past the hour, every 4 hours starting at 08:00",
+            ),
+            (
+                "0 0 9-17 * * *",
+                "At 0 minutes past the hour, between 09:00 and 17:59",
+            ),
+            ("*/10 * * * * *", "Every 10 seconds"),
+            ("*/90s * * * * *", "Every 90 seconds"),
+            ("5/20 * * * *", "Every 20 minutes starting at minute 5"),
+            (
+                "0,30 * 9 * * *",
+                "At seconds 0 and 30, every minute, between 09:00 and 09:59",
+            ),
+            (
+                "1-5 0 * * *",
+                "At minutes 1 through 5, between 00:00 and 00:59",
+            ),
+            ("0 0 L * *", "At 00:00 on the last day of the month"),
+            (
+                "0 0 L-2 * *",
+                "At 00:00 on 2 days before the last day of the month",
+            ),
+            (
+                "0 0 1,15W * *",
+                "At 00:00 on day 1 and the weekday nearest day 15 of the month",
+            ),
+            ("0 0 LW * *", "At 00:00 on the last weekday of the month"),
+            ("0 0 * Jan,Jul *", "At 00:00, only in January and July"),
+            (
+                "0 9 * * 5L",
+                "At 09:00, only on the last Friday of the month",
+            ),
+            (
+                "0 0 9 ? * Mon#2",
+                "At 09:00, only on the second Monday of the month",
+            ),
+            (
+                "0 0 0 1 1 * 2030",
+                "At 00:00 on day 1 of January, only in 2030",
+            ),
+            (
+                "0 30 9 * * * 2018/2",
+                "At 09:30, every 2 years starting in 2018",
+            ),
+            ("@reboot", "At reboot"),
+        ];
+        for (expression, expected) in tests.iter() {
+            assert_eq!(*expected, describe(expression), "{}", expression);
+        }
+
+        let or = Schedule::from_str("0 0 1,15 * Mon")
+            .unwrap()
+            .with_day_semantics(DaySemantics::Or);
+        assert_eq!(
+            "At 00:00 on day 1 and 15 of the month, or on Monday",
+            or.describe()
+        );
+        let every_day = Schedule::from_str("0 0 1-31 * Mon")
+            .unwrap()
+            .with_day_semantics(DaySemantics::Or);
+        assert_eq!("At 00:00", every_day.describe());
+    }
+
+    #[test]
+    fn join_items() {
+        let items = |v: &[&str]| {
+            let items: Vec<String> = v.iter().map(|s| s.to_string()).collect();
+            join(Locale::En.phrases(), &items)
+        };
+        assert_eq!("", items(&[]));
+        assert_eq!("a", items(&["a"]));
+        assert_eq!("a and b", items(&["a", "b"]));
+        assert_eq!("a, b and c", items(&["a", "b", "c"]));
+    }
+
+    #[cfg(feature = "locale-de")]
+    #[test]
+    fn describe_de() {
+        let describe = |e: &str| Schedule::from_str(e).unwrap().describe_in(Locale::De);
+        assert_eq!(
+            "Um 09:30 am Tag 1 und 15 im Mai bis August, nur am Montag, Mittwoch und Freitag",
+            describe("0 30 9 1,15 May-Aug Mon,Wed,Fri")
+        );
+        assert_eq!(
+            "Alle 15 Minuten, zwischen 09:00 und 17:59, nur am ersten Montag des Monats",
+            describe("*/15 9-17 ? * Mon#1")
+        );
+        assert_eq!("Beim Systemstart", describe("@reboot"));
+    }
+
+    #[cfg(feature = "locale-fr")]
+    #[test]
+    fn describe_fr() {
+        let describe = |e: &str| Schedule::from_str(e).unwrap().describe_in(Locale::Fr);
+        assert_eq!(
+            "À 09:30 le jour 1 et 15 de mai à août, uniquement le lundi, mercredi et vendredi",
+            describe("0 30 9 1,15 May-Aug Mon,Wed,Fri")
+        );
+        assert_eq!(
+            "Chaque minute le dernier jour du mois",
+            describe("* * L * *")
+        );
+    }
+
+    #[cfg(feature = "locale-es")]
+    #[test]
+    fn describe_es() {
+        let describe = |e: &str| Schedule::from_str(e).unwrap().describe_in(Locale::Es);
+        assert_eq!(
+            "A las 09:30 el día 1 y 15 de mayo a agosto, solo el lunes, miércoles y viernes",
+            describe("0 30 9 1,15 May-Aug Mon,Wed,Fri")
+        );
+        assert_eq!(
+            "Cada 10 segundos, solo en 2030",
+            describe("*/10 * * * * * 2030")
+        );
+    }
+}
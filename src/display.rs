@@ -0,0 +1,442 @@
+use crate::options::DaySemantics;
+use crate::schedule::{
+    DayOfMonthTerm, DayOfWeekTerm, Schedule, Seconds, TimeRange, Years, MAX_YEAR, MIN_YEAR, REBOOT,
+};
+use std::fmt;
+use std::str::FromStr;
+
+impl Schedule {
+    /// Returns the schedule in its normal form, as parsed from its displayed expression, so
+    /// schedules firing alike written with names, lists or ranges become identical including
+    /// their capabilities. The day semantics and DST policies, which aren't displayed, are kept.
+    ///
+    /// Crontab expressions with a years field gain a seconds field of `0` as when displayed.
+    /// ```rust
+    /// use cron_exp::{Capabilities, Schedule};
+    /// use std::str::FromStr;
+    ///
+    /// let names = Schedule::from_str("0-59 9 * * MON,TUE,WED,THU,FRI").unwrap().canonical();
+    /// let numbers = Schedule::from_str("* 9 * * 1-5").unwrap().canonical();
+    /// assert_eq!(names, numbers);
+    /// assert_eq!("* 9 * * 1-5", names.to_string());
+    /// assert_eq!(Capabilities::NONE, names.capabilities());
+    /// ```
+    pub fn canonical(&self) -> Schedule {
+        let canonical = Schedule::from_str(&self.to_string())
+            .expect("a displayed schedule is a valid expression");
+        canonical
+            .with_day_semantics(self.day_semantics)
+            .with_dst_gap_policy(self.dst_gap_policy)
+            .with_ambiguous_time_policy(self.ambiguous_time_policy)
+    }
+}
+
+impl fmt::Display for Schedule {
+    /// Writes the schedule as an expression parsing back into an equal schedule, using numbers
+    /// rather than names and collapsing values into ranges and steps where possible.
+    ///
+    /// Schedules without seconds are written as 5 field Crontab expressions. Crontab expressions
+    /// with a years field are written in the equivalent 7 field form with a seconds field of `0`,
+    /// as 6 field expressions are read as leading with seconds.
+    /// ```rust
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 0,15,30,45 9-17 * * Mon-Fri").unwrap();
+    /// assert_eq!("0 */15 9-17 * * 2-6", schedule.to_string());
+    /// assert_eq!(schedule, Schedule::from_str(&schedule.to_string()).unwrap());
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(name) = self.timezone_name() {
+            write!(f, "CRON_TZ={} ", name)?;
+        }
+        if self.reboot {
+            return f.write_str(REBOOT);
+        }
+
+        let is_vixie = self.seconds != Seconds::Ignore || self.years != Years::Unbound;
+        if is_vixie {
+            match &self.seconds {
+                Seconds::Ignore => f.write_str("0 ")?,
+                Seconds::All => f.write_str("* ")?,
+                Seconds::Constrained(seconds) => write!(f, "{} ", format_values(seconds, 0, 59))?,
+                Seconds::Interval(interval) => write!(f, "*/{}s ", interval)?,
+            }
+        }
+        write!(
+            f,
+            "{} {} {} {} {}",
+            format_time_range(&self.minutes, 0, 59),
+            format_time_range(&self.hours, 0, 23),
+            self.format_days_of_month(),
+            format_time_range(&self.months, 1, 12),
+            self.format_days_of_week(is_vixie),
+        )?;
+        if let Years::Constrained(years) = &self.years {
+            write!(
+                f,
+                " {}",
+                format_values(years.iter().cloned(), MIN_YEAR, MAX_YEAR)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Schedule {
+    fn format_days_of_month(&self) -> String {
+        let mut items = Vec::new();
+        match &self.days_of_month {
+            // every day restricts the field when either field matches
+            TimeRange::All if self.effective_day_semantics() == DaySemantics::Or => {
+                items.push(format_values(1..=31, 1, 31))
+            }
+            TimeRange::All => return "*".to_string(),
+            TimeRange::Constrained(days) if !days.is_empty() => {
+                items.push(format_values(days, 1, 31))
+            }
+            TimeRange::Constrained(_) => {}
+        }
+        items.extend(self.day_of_month_terms.iter().map(|term| match term {
+            DayOfMonthTerm::Last(0) => "L".to_string(),
+            DayOfMonthTerm::Last(offset) => format!("L-{}", offset),
+            DayOfMonthTerm::NearestWeekday(day) => format!("{}W", day),
+            DayOfMonthTerm::LastWeekday => "LW".to_string(),
+        }));
+        items.join(",")
+    }
+
+    /// Formats the days of week numbered from 1 for Vixie CRON or from 0 for Crontab, where steps
+    /// run up to Sunday numbered 7.
+    fn format_days_of_week(&self, is_vixie: bool) -> String {
+        let (shift, min, max) = if is_vixie { (0, 1, 7) } else { (1, 0, 6) };
+        let mut items = Vec::new();
+        match &self.days_of_week {
+            TimeRange::All if self.effective_day_semantics() == DaySemantics::Or => {
+                items.push(format_values(min..=max, min, max))
+            }
+            TimeRange::All => return "*".to_string(),
+            TimeRange::Constrained(days) if !days.is_empty() => {
+                items.push(format_values(days.iter().map(|d| d - shift), min, 7))
+            }
+            TimeRange::Constrained(_) => {}
+        }
+        items.extend(self.day_of_week_terms.iter().map(|term| match term {
+            DayOfWeekTerm::Last(day) => format!("{}L", day - shift),
+            DayOfWeekTerm::Nth(day, n) => format!("{}#{}", day - shift, n),
+        }));
+        items.join(",")
+    }
+}
+
+fn format_time_range(field: &TimeRange, min: u32, max: u32) -> String {
+    match field {
+        TimeRange::All => "*".to_string(),
+        TimeRange::Constrained(set) => format_values(set, min, max),
+    }
+}
+
+/// Formats the values as a step when evenly spaced up to the end of the field, otherwise as a
+/// list collapsing runs of three or more values into ranges.
+fn format_values<I>(set: I, min: u32, max: u32) -> String
+where
+    I: IntoIterator<Item = u32>,
+{
+    let values: Vec<u32> = set.into_iter().collect();
+
+    if values.len() > 2 {
+        let step = values[1] - values[0];
+        let last = values[values.len() - 1];
+        if step > 1 && values.windows(2).all(|w| w[1] - w[0] == step) && last + step > max {
+            return if values[0] == min {
+                format!("*/{}", step)
+            } else {
+                format!("{}/{}", values[0], step)
+            };
+        }
+    }
+
+    let mut items = Vec::new();
+    let mut i = 0;
+    while i < values.len() {
+        let start = values[i];
+        let mut end = start;
+        while i + 1 < values.len() && values[i + 1] == end + 1 {
+            i += 1;
+            end = values[i];
+        }
+        match end - start {
+            0 => items.push(start.to_string()),
+            1 => {
+                items.push(start.to_string());
+                items.push(end.to_string());
+            }
+            _ => items.push(format!("{}-{}", start, end)),
+        }
+        i += 1;
+    }
+    items.join(",")
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use super::*;
+    use crate::options::{AmbiguousTimePolicy, DstGapPolicy};
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// The accepted forms, an expression or a structured one along with the settings that
+    /// aren't displayed.
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Repr {
+        Expression(String),
+        Structured(Structured),
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Structured {
+        expression: String,
+        #[serde(default, skip_serializing_if = "is_default")]
+        day_semantics: DaySemantics,
+        #[serde(default, skip_serializing_if = "is_default")]
+        dst_gap_policy: DstGapPolicy,
+        #[serde(default, skip_serializing_if = "is_default")]
+        ambiguous_time_policy: AmbiguousTimePolicy,
+    }
+
+    fn is_default<T: Default + PartialEq>(value: &T) -> bool {
+        *value == T::default()
+    }
+
+    /// Serializes as the displayed expression, or as a structure along with the day semantics
+    /// and DST policies when they aren't the defaults.
+    impl Serialize for Schedule {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            if is_default(&self.day_semantics)
+                && is_default(&self.dst_gap_policy)
+                && is_default(&self.ambiguous_time_policy)
+            {
+                return serializer.collect_str(self);
+            }
+            Structured {
+                expression: self.to_string(),
+                day_semantics: self.day_semantics,
+                dst_gap_policy: self.dst_gap_policy,
+                ambiguous_time_policy: self.ambiguous_time_policy,
+            }
+            .serialize(serializer)
+        }
+    }
+
+    /// Deserializes any expression accepted by `Schedule::from_str`, or the structure it is
+    /// serialized as.
+    impl<'de> Deserialize<'de> for Schedule {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            match Repr::deserialize(deserializer)? {
+                Repr::Expression(expression) => {
+                    Schedule::from_str(&expression).map_err(D::Error::custom)
+                }
+                Repr::Structured(structured) => Ok(Schedule::from_str(&structured.expression)
+                    .map_err(D::Error::custom)?
+                    .with_day_semantics(structured.day_semantics)
+                    .with_dst_gap_policy(structured.dst_gap_policy)
+                    .with_ambiguous_time_policy(structured.ambiguous_time_policy)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AmbiguousTimePolicy, Capabilities, CronFormat, DropPolicy, DstGapPolicy};
+
+    #[test]
+    fn display() {
+        let display = |e: &str| Schedule::from_str(e).unwrap().to_string();
+
+        assert_eq!("* * * * *", display("* * * * *"));
+        assert_eq!("*/15 9-17 * * 1-5", display("*/15 9-17 * * Mon-Fri"));
+        assert_eq!("0 0 * * 0,6", display("0 0 * * Sat,Sun"));
+        assert_eq!("0 0 * * 0,6", display("0 0 * * 6-7"));
+        assert_eq!("0 0 * * 1,3,5", display("0 0 * * Mon,Wed,Fri"));
+        assert_eq!("0 0 * * */2", display("0 0 * * Sun,Tue,Thu,Sat"));
+        assert_eq!("5/10 * * * *", display("5-59/10 * * * *"));
+        assert_eq!("0 0-2,22,23 * * *", display("0 22-2 * * *"));
+        assert_eq!(
+            "0 30 9 1,15 */3 *",
+            display("0 30 9 1,15 Jan,Apr,Jul,Oct *")
+        );
+        assert_eq!(
+            "0 30 9 * * 2-6 2020/2",
+            display("0 30 9 * * Mon-Fri 2020/2")
+        );
+        assert_eq!("*/90s * * * * *", display("*/90s * * * * *"));
+        assert_eq!("0 9 1,L-2 * *", display("0 9 L-2,1 * *"));
+        assert_eq!("0 9 15W,LW * *", display("0 9 LW,15W * *"));
+        assert_eq!("0 9 * * 5L", display("0 9 * * FriL"));
+        assert_eq!("0 0 9 * * 6#2", display("0 0 9 ? * Fri#2"));
+        assert_eq!("@reboot", display("@reboot"));
+        assert_eq!(
+            "0 0 12 * * 2 2030",
+            Schedule::parse_with_format("0 12 * * 1 2030", CronFormat::CrontabWithYear)
+                .unwrap()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn display_round_trips() {
+        let expressions = [
+            "* * * * *",
+            "*/5 1,2,3,7 */2 * Sun",
+            "0 0 1-10/3 Feb-Nov/2 0-2,4",
+            "0 0 * * 1/2",
+            "0 0 * * 1,3,5",
+            "1,2,3,5,8,13 0 L * 1#3",
+            "0 30 9 * * Mon-Fri",
+            "15 */7 0-23/5 L-3,20W * ?",
+            "*/45s * 9-17 * * *",
+            "0 0 0 29 2 * 2024-2099/4",
+            "0 0 0 LW * 2,3,4,6L 1970,1980",
+        ];
+        for expression in expressions.iter() {
+            let schedule = Schedule::from_str(expression).unwrap();
+            assert_eq!(
+                schedule,
+                Schedule::from_str(&schedule.to_string()).unwrap(),
+                "{}",
+                expression
+            );
+            let five = schedule.to_five_field(DropPolicy::Discard).unwrap();
+            assert_eq!(five, Schedule::from_str(&five.to_string()).unwrap());
+        }
+    }
+
+    #[test]
+    fn canonical() {
+        let canonical = |e: &str| Schedule::from_str(e).unwrap().canonical();
+        let equivalent = [
+            ("0-59 * * * *", "* * * * *"),
+            ("0 9 * * 1,2,3,4,5", "0 9 * * Mon-Fri"),
+            ("0 9 * Jan,Feb * ", "0 9 * 1-2 *"),
+            ("0 0 ? * Mon,Wed", "0 0 * * 1,3"),
+            ("0 0 * * 5,5L", "0 0 * * 5"),
+            ("0 0 1 * 0-6", "0 0 1 * *"),
+            ("0 0 0 * * Sun,Sat", "0 0 0 * * 1,7"),
+        ];
+        for (a, b) in equivalent.iter() {
+            assert_eq!(canonical(a), canonical(b), "{} {}", a, b);
+            assert_eq!(canonical(a).to_string(), canonical(b).to_string());
+            assert_eq!(canonical(a).capabilities(), canonical(b).capabilities());
+        }
+        assert_eq!(
+            Capabilities::SECONDS | Capabilities::QUARTZ,
+            canonical("0 0 9 ? * FRI#2").capabilities()
+        );
+        assert!(canonical("@reboot").is_reboot());
+
+        let or = Schedule::from_str("0 0 1-31 * Mon,Tue")
+            .unwrap()
+            .with_day_semantics(DaySemantics::Or);
+        assert_eq!(DaySemantics::Or, or.canonical().day_semantics());
+        assert_eq!(or, or.canonical());
+        assert_ne!(canonical("0 0 * * Mon,Tue"), or.canonical());
+        let gap_end = Schedule::from_str("30 2 * * *")
+            .unwrap()
+            .with_dst_gap_policy(DstGapPolicy::FireAtGapEnd);
+        assert_eq!(
+            DstGapPolicy::FireAtGapEnd,
+            gap_end.canonical().dst_gap_policy()
+        );
+        assert_eq!(gap_end, gap_end.canonical());
+        let both = Schedule::from_str("30 1 * * *")
+            .unwrap()
+            .with_ambiguous_time_policy(AmbiguousTimePolicy::Both);
+        assert_eq!(
+            AmbiguousTimePolicy::Both,
+            both.canonical().ambiguous_time_policy()
+        );
+        assert_eq!(both, both.canonical());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let schedule = Schedule::from_str("0 30 9 * * Mon-Fri").unwrap();
+        let json = serde_json::to_string(&schedule).unwrap();
+        assert_eq!(r#""0 30 9 * * 2-6""#, json);
+        assert_eq!(schedule, serde_json::from_str::<Schedule>(&json).unwrap());
+
+        let schedules: Vec<Schedule> =
+            serde_json::from_str(r#"["@reboot", "*/15 * * * *", "0 0 12 L * ? 2030"]"#).unwrap();
+        assert_eq!(3, schedules.len());
+
+        let or = Schedule::from_str("0 0 1-31 * Mon")
+            .unwrap()
+            .with_day_semantics(DaySemantics::Or);
+        let json = serde_json::to_string(&or).unwrap();
+        assert_eq!(
+            r#"{"expression":"0 0 1-31 * 1","day_semantics":"Or"}"#,
+            json
+        );
+        let deserialized = serde_json::from_str::<Schedule>(&json).unwrap();
+        assert_eq!(or, deserialized);
+        assert_eq!(DaySemantics::Or, deserialized.day_semantics());
+        assert_eq!(
+            schedule,
+            serde_json::from_str(r#"{"expression":"0 30 9 * * Mon-Fri"}"#).unwrap()
+        );
+        let gap_end = Schedule::from_str("30 2 * * *")
+            .unwrap()
+            .with_dst_gap_policy(DstGapPolicy::FireAtGapEnd);
+        let json = serde_json::to_string(&gap_end).unwrap();
+        assert_eq!(
+            r#"{"expression":"30 2 * * *","dst_gap_policy":"FireAtGapEnd"}"#,
+            json
+        );
+        let deserialized = serde_json::from_str::<Schedule>(&json).unwrap();
+        assert_eq!(gap_end, deserialized);
+        assert_eq!(DstGapPolicy::FireAtGapEnd, deserialized.dst_gap_policy());
+        let policies = gap_end
+            .with_day_semantics(DaySemantics::Or)
+            .with_ambiguous_time_policy(AmbiguousTimePolicy::Latest);
+        let json = serde_json::to_string(&policies).unwrap();
+        assert!(
+            json.contains(r#""ambiguous_time_policy":"Latest""#),
+            "{}",
+            json
+        );
+        let deserialized = serde_json::from_str::<Schedule>(&json).unwrap();
+        assert_eq!(policies, deserialized);
+        assert_eq!(DaySemantics::Or, deserialized.day_semantics());
+        assert_eq!(
+            AmbiguousTimePolicy::Latest,
+            deserialized.ambiguous_time_policy()
+        );
+
+        let error = serde_json::from_str::<Schedule>(r#""0 99 * * *""#).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Invalid Range '99' in the hours field"),
+            "{}",
+            error
+        );
+    }
+
+    #[cfg(feature = "tz")]
+    #[test]
+    fn display_timezone() {
+        let schedule = Schedule::from_str("TZ=Europe/Paris 0 30 9 * * *").unwrap();
+        assert_eq!("CRON_TZ=Europe/Paris 0 30 9 * * *", schedule.to_string());
+        assert_eq!(schedule, Schedule::from_str(&schedule.to_string()).unwrap());
+    }
+}
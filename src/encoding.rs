@@ -0,0 +1,512 @@
+use crate::capabilities::Capabilities;
+use crate::errors::{DecodeError, Field};
+use crate::options::{AmbiguousTimePolicy, DaySemantics, DstGapPolicy};
+use crate::schedule::{
+    DayOfMonthTerm, DayOfWeekTerm, Schedule, Seconds, TimeRange, Years, MAX_YEAR, MIN_YEAR,
+    SECONDS_PER_DAY,
+};
+#[cfg(feature = "tz")]
+use chrono_tz::Tz;
+use std::collections::BTreeSet;
+#[cfg(feature = "tz")]
+use std::str::FromStr;
+
+/// The version written as the first byte, bumped whenever the layout changes.
+const VERSION: u8 = 1;
+
+const FLAG_REBOOT: u8 = 1;
+const FLAG_SECONDS: u8 = 1 << 1;
+const FLAG_INTERVAL: u8 = 1 << 2;
+const FLAG_YEARS: u8 = 1 << 3;
+const FLAG_CONSTRAINED_YEARS: u8 = 1 << 4;
+const FLAG_TIMEZONE: u8 = 1 << 5;
+const FLAG_DAYS_OR: u8 = 1 << 6;
+
+/// Marks the days of month and days of week fields written as `*` in the wildcards byte.
+const WILDCARD_DAYS_OF_MONTH: u8 = 1 << 6;
+const WILDCARD_DAYS_OF_WEEK: u8 = 1 << 7;
+
+const TAG_LAST: u8 = 0;
+const TAG_NEAREST_WEEKDAY: u8 = 1;
+const TAG_LAST_WEEKDAY: u8 = 2;
+const TAG_NTH: u8 = 1;
+
+/// The values a field may hold, written as a bit mask of `(max - min) / 8 + 1` bytes with the
+/// bit of `min` first.
+struct Mask {
+    field: Field,
+    min: u32,
+    max: u32,
+}
+
+const SECONDS: Mask = Mask::new(Field::Seconds, 0, 59);
+const MINUTES: Mask = Mask::new(Field::Minutes, 0, 59);
+const HOURS: Mask = Mask::new(Field::Hours, 0, 23);
+const DAYS_OF_MONTH: Mask = Mask::new(Field::DaysOfMonth, 1, 31);
+const MONTHS: Mask = Mask::new(Field::Months, 1, 12);
+const DAYS_OF_WEEK: Mask = Mask::new(Field::DaysOfWeek, 1, 7);
+const YEARS: Mask = Mask::new(Field::Years, MIN_YEAR, MAX_YEAR);
+
+impl Mask {
+    const fn new(field: Field, min: u32, max: u32) -> Mask {
+        Mask { field, min, max }
+    }
+
+    fn width(&self) -> usize {
+        ((self.max - self.min) / 8 + 1) as usize
+    }
+
+    fn write<I>(&self, bytes: &mut Vec<u8>, values: I)
+    where
+        I: IntoIterator<Item = u32>,
+    {
+        let mut mask = vec![0; self.width()];
+        for value in values {
+            let bit = (value - self.min) as usize;
+            mask[bit / 8] |= 1 << (bit % 8);
+        }
+        bytes.extend(mask);
+    }
+
+    /// Reads the values of the mask, failing on bits beyond the field.
+    fn read(&self, reader: &mut Reader<'_>) -> Result<Vec<u32>, DecodeError> {
+        let mut values = Vec::new();
+        for (i, byte) in reader.take(self.width())?.iter().enumerate() {
+            for bit in (0..8).filter(|bit| byte & (1 << bit) != 0) {
+                let value = self.min + (i * 8 + bit) as u32;
+                if value > self.max {
+                    return Err(DecodeError::InvalidValue(self.field));
+                }
+                values.push(value);
+            }
+        }
+        Ok(values)
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], DecodeError> {
+        if self.0.len() < n {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+        let (taken, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    /// Reads a byte, failing unless `valid` holds for it.
+    fn value(&mut self, field: Field, valid: impl Fn(u8) -> bool) -> Result<u32, DecodeError> {
+        let value = self.u8()?;
+        if !valid(value) {
+            return Err(DecodeError::InvalidValue(field));
+        }
+        Ok(u32::from(value))
+    }
+}
+
+impl Schedule {
+    /// Encodes the schedule into a small binary form decoded with `Schedule::from_bytes`, e.g.
+    /// to embed schedules in cache keys, database blobs or message payloads.
+    ///
+    /// The encoding leads with a version byte and writes each field as a bit mask, taking under
+    /// 30 bytes for typical expressions, so later versions of the crate can change the layout
+    /// and still decode existing encodings.
+    /// ```rust
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 30 9 * * Mon-Fri").unwrap();
+    /// let bytes = schedule.to_bytes();
+    /// assert!(bytes.len() < 32);
+    /// assert_eq!(schedule, Schedule::from_bytes(&bytes).unwrap());
+    /// assert!(Schedule::from_bytes(&bytes[..4]).is_err());
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut flags = 0;
+        if self.reboot {
+            flags |= FLAG_REBOOT;
+        }
+        match self.seconds {
+            Seconds::Ignore => {}
+            Seconds::Interval(_) => flags |= FLAG_SECONDS | FLAG_INTERVAL,
+            Seconds::All | Seconds::Constrained(_) => flags |= FLAG_SECONDS,
+        }
+        match self.years {
+            Years::Unbound => {}
+            Years::All => flags |= FLAG_YEARS,
+            Years::Constrained(_) => flags |= FLAG_YEARS | FLAG_CONSTRAINED_YEARS,
+        }
+        let timezone = self.timezone_name();
+        if timezone.is_some() {
+            flags |= FLAG_TIMEZONE;
+        }
+        if self.day_semantics == DaySemantics::Or {
+            flags |= FLAG_DAYS_OR;
+        }
+
+        let ranges = [
+            &self.minutes,
+            &self.hours,
+            &self.days_of_month,
+            &self.months,
+            &self.days_of_week,
+        ];
+        // a set bit marks a field matching every value, whose mask is left out
+        let mut wildcards = u8::from(self.seconds == Seconds::All);
+        for (i, range) in ranges.iter().enumerate() {
+            if **range == TimeRange::All {
+                wildcards |= 1 << (i + 1);
+            }
+        }
+        if self.days_of_month_wildcard {
+            wildcards |= WILDCARD_DAYS_OF_MONTH;
+        }
+        if self.days_of_week_wildcard {
+            wildcards |= WILDCARD_DAYS_OF_WEEK;
+        }
+
+        let mut bytes = vec![
+            VERSION,
+            flags,
+            self.dst_gap_policy as u8 | (self.ambiguous_time_policy as u8) << 4,
+            self.syntax.bits(),
+            wildcards,
+        ];
+        match self.seconds {
+            Seconds::Constrained(seconds) => SECONDS.write(&mut bytes, seconds),
+            Seconds::Interval(interval) => bytes.extend(interval.to_le_bytes()),
+            Seconds::Ignore | Seconds::All => {}
+        }
+        for (mask, range) in [MINUTES, HOURS, DAYS_OF_MONTH, MONTHS, DAYS_OF_WEEK]
+            .iter()
+            .zip(ranges.iter())
+        {
+            if let TimeRange::Constrained(set) = range {
+                mask.write(&mut bytes, *set);
+            }
+        }
+        if let Years::Constrained(years) = &self.years {
+            YEARS.write(&mut bytes, years.iter().cloned());
+        }
+
+        bytes.push(self.day_of_month_terms.len() as u8);
+        for term in &self.day_of_month_terms {
+            match term {
+                DayOfMonthTerm::Last(offset) => bytes.extend([TAG_LAST, *offset as u8]),
+                DayOfMonthTerm::NearestWeekday(day) => {
+                    bytes.extend([TAG_NEAREST_WEEKDAY, *day as u8])
+                }
+                DayOfMonthTerm::LastWeekday => bytes.push(TAG_LAST_WEEKDAY),
+            }
+        }
+        bytes.push(self.day_of_week_terms.len() as u8);
+        for term in &self.day_of_week_terms {
+            match term {
+                DayOfWeekTerm::Last(day) => bytes.extend([TAG_LAST, *day as u8]),
+                DayOfWeekTerm::Nth(day, n) => bytes.extend([TAG_NTH, *day as u8, *n as u8]),
+            }
+        }
+        if let Some(name) = timezone {
+            // zone names are at most a few dozen ASCII characters
+            bytes.push(name.len() as u8);
+            bytes.extend(name.bytes());
+        }
+        bytes
+    }
+
+    /// Decodes a schedule encoded by `Schedule::to_bytes`, failing on encodings of an unknown
+    /// version or holding values no expression could produce.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Schedule, DecodeError> {
+        let mut reader = Reader(bytes);
+        let version = reader.u8()?;
+        if version != VERSION {
+            return Err(DecodeError::UnsupportedVersion(version));
+        }
+        let flags = reader.u8()?;
+        let policies = reader.u8()?;
+        let syntax = Capabilities::from_bits(reader.u8()?);
+        let wildcards = reader.u8()?;
+        let invalid_flags = flags & (1 << 7) != 0
+            || (flags & FLAG_INTERVAL != 0 && flags & FLAG_SECONDS == 0)
+            || (flags & FLAG_CONSTRAINED_YEARS != 0 && flags & FLAG_YEARS == 0)
+            || (wildcards & 1 != 0 && flags & (FLAG_SECONDS | FLAG_INTERVAL) != FLAG_SECONDS)
+            || (wildcards & WILDCARD_DAYS_OF_MONTH != 0 && wildcards & (1 << 3) == 0)
+            || (wildcards & WILDCARD_DAYS_OF_WEEK != 0 && wildcards & (1 << 5) == 0);
+        if invalid_flags {
+            return Err(DecodeError::InvalidFlags(flags));
+        }
+        let dst_gap_policy = match policies & 0x0f {
+            0 => DstGapPolicy::Skip,
+            1 => DstGapPolicy::FireAtGapEnd,
+            2 => DstGapPolicy::FireBeforeGap,
+            _ => return Err(DecodeError::InvalidFlags(policies)),
+        };
+        let ambiguous_time_policy = match policies >> 4 {
+            0 => AmbiguousTimePolicy::Skip,
+            1 => AmbiguousTimePolicy::Earliest,
+            2 => AmbiguousTimePolicy::Latest,
+            3 => AmbiguousTimePolicy::Both,
+            _ => return Err(DecodeError::InvalidFlags(policies)),
+        };
+
+        let seconds = if flags & FLAG_INTERVAL != 0 {
+            let interval =
+                u32::from_le_bytes([reader.u8()?, reader.u8()?, reader.u8()?, reader.u8()?]);
+            if interval == 0 || interval > SECONDS_PER_DAY {
+                return Err(DecodeError::InvalidValue(Field::Seconds));
+            }
+            Seconds::Interval(interval)
+        } else if flags & FLAG_SECONDS == 0 {
+            Seconds::Ignore
+        } else if wildcards & 1 != 0 {
+            Seconds::All
+        } else {
+            Seconds::Constrained(SECONDS.read(&mut reader)?.into_iter().collect())
+        };
+        let mut range = |bit: u8, mask: &Mask| -> Result<TimeRange, DecodeError> {
+            if wildcards & (1 << bit) != 0 {
+                return Ok(TimeRange::All);
+            }
+            Ok(TimeRange::Constrained(
+                mask.read(&mut reader)?.into_iter().collect(),
+            ))
+        };
+        let minutes = range(1, &MINUTES)?;
+        let hours = range(2, &HOURS)?;
+        let days_of_month = range(3, &DAYS_OF_MONTH)?;
+        let months = range(4, &MONTHS)?;
+        let days_of_week = range(5, &DAYS_OF_WEEK)?;
+        let years = if flags & FLAG_CONSTRAINED_YEARS != 0 {
+            Years::Constrained(YEARS.read(&mut reader)?.into_iter().collect())
+        } else if flags & FLAG_YEARS != 0 {
+            Years::All
+        } else {
+            Years::Unbound
+        };
+
+        let mut day_of_month_terms = BTreeSet::new();
+        for _ in 0..reader.u8()? {
+            let field = Field::DaysOfMonth;
+            day_of_month_terms.insert(match reader.u8()? {
+                TAG_LAST => DayOfMonthTerm::Last(reader.value(field, |offset| offset <= 30)?),
+                TAG_NEAREST_WEEKDAY => DayOfMonthTerm::NearestWeekday(
+                    reader.value(field, |day| (1..=31).contains(&day))?,
+                ),
+                TAG_LAST_WEEKDAY => DayOfMonthTerm::LastWeekday,
+                _ => return Err(DecodeError::InvalidValue(field)),
+            });
+        }
+        let mut day_of_week_terms = BTreeSet::new();
+        for _ in 0..reader.u8()? {
+            let field = Field::DaysOfWeek;
+            let tag = reader.u8()?;
+            let day = reader.value(field, |day| (1..=7).contains(&day))?;
+            day_of_week_terms.insert(match tag {
+                TAG_LAST => DayOfWeekTerm::Last(day),
+                TAG_NTH => DayOfWeekTerm::Nth(day, reader.value(field, |n| (1..=5).contains(&n))?),
+                _ => return Err(DecodeError::InvalidValue(field)),
+            });
+        }
+        let timezone = if flags & FLAG_TIMEZONE != 0 {
+            let len = reader.u8()? as usize;
+            Some(String::from_utf8_lossy(reader.take(len)?).into_owned())
+        } else {
+            None
+        };
+        if !reader.0.is_empty() {
+            return Err(DecodeError::TrailingBytes);
+        }
+
+        // only the day fields may be left empty, when their terms match instead
+        let empty = |range: &TimeRange| *range == TimeRange::Constrained(Default::default());
+        let empty_field = [
+            (
+                Field::Seconds,
+                seconds == Seconds::Constrained(Default::default()),
+            ),
+            (Field::Minutes, empty(&minutes)),
+            (Field::Hours, empty(&hours)),
+            (
+                Field::DaysOfMonth,
+                empty(&days_of_month) && day_of_month_terms.is_empty(),
+            ),
+            (Field::Months, empty(&months)),
+            (
+                Field::DaysOfWeek,
+                empty(&days_of_week) && day_of_week_terms.is_empty(),
+            ),
+            (Field::Years, years == Years::Constrained(BTreeSet::new())),
+        ]
+        .iter()
+        .find(|(_, empty)| *empty)
+        .map(|(field, _)| *field);
+        if let Some(field) = empty_field {
+            return Err(DecodeError::InvalidValue(field));
+        }
+
+        #[cfg(feature = "tz")]
+        let timezone = match timezone {
+            Some(name) => {
+                Some(Tz::from_str(&name).map_err(|_| DecodeError::InvalidTimezone(name))?)
+            }
+            None => None,
+        };
+        #[cfg(not(feature = "tz"))]
+        if let Some(name) = timezone {
+            return Err(DecodeError::InvalidTimezone(name));
+        }
+
+        Ok(Schedule {
+            seconds,
+            minutes,
+            hours,
+            days_of_month,
+            day_of_month_terms,
+            days_of_month_wildcard: wildcards & WILDCARD_DAYS_OF_MONTH != 0,
+            months,
+            days_of_week,
+            day_of_week_terms,
+            days_of_week_wildcard: wildcards & WILDCARD_DAYS_OF_WEEK != 0,
+            years,
+            syntax,
+            reboot: flags & FLAG_REBOOT != 0,
+            day_semantics: if flags & FLAG_DAYS_OR != 0 {
+                DaySemantics::Or
+            } else {
+                DaySemantics::And
+            },
+            dst_gap_policy,
+            ambiguous_time_policy,
+            #[cfg(feature = "tz")]
+            timezone,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CronFormat;
+    use std::str::FromStr;
+
+    #[test]
+    fn round_trips() {
+        let expressions = [
+            "* * * * *",
+            "*/5 1,2,3,7 */2 * Sun",
+            "0 0 1-10/3 Feb-Nov/2 0-2,4",
+            "0 30 9 * * Mon-Fri",
+            "15 */7 0-23/5 L-3,20W * ?",
+            "*/45s * 9-17 * * *",
+            "*/90s * * * * *",
+            "0 0 0 29 2 * 2024-2099/4",
+            "0 0 0 LW * 2,3,4,6L 1970,1980",
+            "0 0 9 ? * Fri#2 *",
+            "@reboot",
+        ];
+        for expression in expressions.iter() {
+            let schedule = Schedule::from_str(expression).unwrap();
+            let decoded = Schedule::from_bytes(&schedule.to_bytes()).unwrap();
+            assert_eq!(schedule, decoded, "{}", expression);
+            assert_eq!(schedule.capabilities(), decoded.capabilities());
+            assert_eq!(schedule.to_string(), decoded.to_string());
+        }
+
+        let with_year =
+            Schedule::parse_with_format("0 12 * * 1 2030", CronFormat::CrontabWithYear).unwrap();
+        let policies = Schedule::from_str("0 0 1 * Mon")
+            .unwrap()
+            .with_day_semantics(DaySemantics::Or)
+            .with_dst_gap_policy(DstGapPolicy::FireBeforeGap)
+            .with_ambiguous_time_policy(AmbiguousTimePolicy::Both);
+        let every_day = Schedule::from_str("0 0 1-31 * Mon")
+            .unwrap()
+            .with_day_semantics(DaySemantics::Or);
+        for schedule in [with_year, policies, every_day].iter() {
+            let decoded = Schedule::from_bytes(&schedule.to_bytes()).unwrap();
+            assert_eq!(schedule, &decoded);
+            assert_eq!(schedule.day_semantics(), decoded.day_semantics());
+            assert_eq!(
+                schedule.days_of_month_wildcard,
+                decoded.days_of_month_wildcard
+            );
+        }
+        #[cfg(feature = "tz")]
+        {
+            let paris = Schedule::from_str("CRON_TZ=Europe/Paris 0 9 * * *").unwrap();
+            let decoded = Schedule::from_bytes(&paris.to_bytes()).unwrap();
+            assert_eq!(paris.timezone(), decoded.timezone());
+        }
+    }
+
+    #[test]
+    fn layout() {
+        let schedule = Schedule::from_str("30 9 * * *").unwrap();
+        assert_eq!(
+            vec![
+                VERSION,
+                0,
+                0,
+                0,
+                0b1111_1000,
+                // minutes
+                0,
+                0,
+                0,
+                0b0100_0000,
+                0,
+                0,
+                0,
+                0,
+                // hours
+                0,
+                0b10,
+                0,
+                // no day terms
+                0,
+                0
+            ],
+            schedule.to_bytes()
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let bytes = Schedule::from_str("30 9 * * *").unwrap().to_bytes();
+        let with = |i: usize, byte: u8| {
+            let mut bytes = bytes.clone();
+            bytes[i] = byte;
+            Schedule::from_bytes(&bytes)
+        };
+
+        assert_eq!(Err(DecodeError::UnsupportedVersion(2)), with(0, 2));
+        assert_eq!(Err(DecodeError::InvalidFlags(0x04)), with(1, 0x04));
+        assert_eq!(Err(DecodeError::InvalidFlags(0x07)), with(2, 0x07));
+        // a days of month field written as `*` matches every day
+        assert_eq!(Err(DecodeError::InvalidFlags(0)), with(4, 0b1111_0000));
+        // the last byte of the minutes mask holds the bits of 56 to 63
+        assert_eq!(
+            Err(DecodeError::InvalidValue(Field::Minutes)),
+            with(12, 0x10)
+        );
+        assert_eq!(Err(DecodeError::InvalidValue(Field::Minutes)), with(8, 0));
+        assert_eq!(
+            Err(DecodeError::UnexpectedEnd),
+            Schedule::from_bytes(&bytes[..10])
+        );
+        assert_eq!(Err(DecodeError::UnexpectedEnd), Schedule::from_bytes(&[]));
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(
+            Err(DecodeError::TrailingBytes),
+            Schedule::from_bytes(&trailing)
+        );
+    }
+}
@@ -1,23 +1,230 @@
-use std::num::ParseIntError;
+use std::fmt;
+use std::ops::Range;
 use thiserror::Error;
 
-#[derive(Error, Debug, PartialEq)]
+/// A field of a CRON expression.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Field {
+    Seconds,
+    Minutes,
+    Hours,
+    DaysOfMonth,
+    Months,
+    DaysOfWeek,
+    Years,
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Field::Seconds => "seconds",
+            Field::Minutes => "minutes",
+            Field::Hours => "hours",
+            Field::DaysOfMonth => "day of month",
+            Field::Months => "month",
+            Field::DaysOfWeek => "day of week",
+            Field::Years => "years",
+        })
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Clone)]
 pub enum ParseScheduleError {
-    #[error(transparent)]
-    ParseIntError(#[from] ParseIntError),
+    #[error("Invalid Number '{value}' in the {field} field")]
+    InvalidNumber { field: Field, value: String },
 
     #[error("Invalid number of arguments, 5 for Crontab 6 or 7 for Vixie CRON")]
     ArgumentCount,
 
-    #[error("Invalid Step Range {0}")]
-    InvalidStepRange(String),
+    #[error("Invalid Step Range '{value}' in the {field} field, steps must be between 1 and the number of values in the field")]
+    InvalidStepRange { field: Field, value: String },
+
+    #[error("Invalid Range '{value}' in the {field} field")]
+    InvalidRange { field: Field, value: String },
+
+    #[error("Invalid Month '{input}'{}", did_you_mean(.suggestion))]
+    InvalidMonthIndicator {
+        input: String,
+        suggestion: Option<&'static str>,
+    },
+
+    #[error("Invalid Day of Week '{input}'{}", did_you_mean(.suggestion))]
+    InvalidDayOfWeekIndicator {
+        input: String,
+        suggestion: Option<&'static str>,
+    },
+
+    #[error("Invalid Timezone {0}")]
+    InvalidTimezone(String),
+
+    #[error("Unexpected Timezone {0}, the zone of the schedule is already supplied")]
+    UnexpectedTimezone(String),
+
+    #[error("Invalid nth Day of Week {0}, must be between 1 and 5")]
+    InvalidNthDayOfWeek(String),
+
+    #[error("Nth Day of Week {0} never falls on the days of month in the months")]
+    ImpossibleNthDayOfWeek(String),
+
+    #[error("A seconds field is not allowed by the parse options")]
+    SecondsNotAllowed,
+
+    #[error("The format must be supplied when the parse options don't allow detecting it")]
+    FormatRequired,
+}
+
+/// Formats the suggested name of a misspelled month or day of week, if any.
+fn did_you_mean(suggestion: &Option<&'static str>) -> String {
+    match suggestion {
+        Some(name) => format!(", did you mean '{}'?", name),
+        None => String::new(),
+    }
+}
+
+impl ParseScheduleError {
+    /// Returns the field the error is about, `None` for errors about the whole expression such
+    /// as its number of fields or zone.
+    pub fn field(&self) -> Option<Field> {
+        match self {
+            ParseScheduleError::InvalidNumber { field, .. }
+            | ParseScheduleError::InvalidStepRange { field, .. }
+            | ParseScheduleError::InvalidRange { field, .. } => Some(*field),
+            ParseScheduleError::InvalidMonthIndicator { .. } => Some(Field::Months),
+            ParseScheduleError::InvalidDayOfWeekIndicator { .. }
+            | ParseScheduleError::InvalidNthDayOfWeek(_)
+            | ParseScheduleError::ImpossibleNthDayOfWeek(_) => Some(Field::DaysOfWeek),
+            ParseScheduleError::SecondsNotAllowed => Some(Field::Seconds),
+            ParseScheduleError::ArgumentCount
+            | ParseScheduleError::InvalidTimezone(_)
+            | ParseScheduleError::UnexpectedTimezone(_)
+            | ParseScheduleError::FormatRequired => None,
+        }
+    }
+
+    /// Returns the token of the expression the error is about, if any.
+    fn token(&self) -> Option<&str> {
+        match self {
+            ParseScheduleError::InvalidNumber { value, .. }
+            | ParseScheduleError::InvalidStepRange { value, .. }
+            | ParseScheduleError::InvalidRange { value, .. }
+            | ParseScheduleError::InvalidMonthIndicator { input: value, .. }
+            | ParseScheduleError::InvalidDayOfWeekIndicator { input: value, .. }
+            | ParseScheduleError::InvalidTimezone(value)
+            | ParseScheduleError::UnexpectedTimezone(value)
+            | ParseScheduleError::InvalidNthDayOfWeek(value)
+            | ParseScheduleError::ImpossibleNthDayOfWeek(value) => Some(value),
+            ParseScheduleError::ArgumentCount
+            | ParseScheduleError::SecondsNotAllowed
+            | ParseScheduleError::FormatRequired => None,
+        }
+    }
+}
+
+/// A `ParseScheduleError` located within the expression, see `Schedule::parse_spanned`.
+#[derive(Error, Debug, PartialEq, Clone)]
+#[error("{error} at bytes {}..{}", .span.start, .span.end)]
+pub struct SpannedParseError {
+    field: Option<Field>,
+    span: Range<usize>,
+    #[source]
+    error: ParseScheduleError,
+}
+
+impl SpannedParseError {
+    pub(crate) fn new(error: ParseScheduleError, field: Option<Field>, span: Range<usize>) -> Self {
+        SpannedParseError { field, span, error }
+    }
+
+    /// Locates the error within the field starting at `offset`, narrowing the span to the
+    /// offending token of the field when known.
+    pub(crate) fn in_field(
+        error: ParseScheduleError,
+        field: Field,
+        (offset, value): (usize, &str),
+    ) -> Self {
+        let span = match error
+            .token()
+            .and_then(|token| Some((value.find(token)?, token.len())))
+        {
+            Some((start, len)) => offset + start..offset + start + len,
+            None => offset..offset + value.len(),
+        };
+        SpannedParseError::new(error, Some(field), span)
+    }
+
+    /// Moves the span by the supplied number of bytes.
+    pub(crate) fn offset_by(mut self, offset: usize) -> Self {
+        self.span = self.span.start + offset..self.span.end + offset;
+        self
+    }
+
+    /// Returns the field the error was found in, `None` for errors about the whole expression
+    /// such as its number of fields.
+    pub fn field(&self) -> Option<Field> {
+        self.field
+    }
+
+    /// Returns the byte range of the offending token within the expression.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Returns the underlying error.
+    pub fn error(&self) -> &ParseScheduleError {
+        &self.error
+    }
+}
+
+impl From<SpannedParseError> for ParseScheduleError {
+    fn from(e: SpannedParseError) -> Self {
+        e.error
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum OnCalendarError {
+    #[error("Schedule cannot be expressed as a systemd OnCalendar value: {0}")]
+    Inexpressible(String),
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ConversionError {
+    #[error("Dropping the seconds field would change when the schedule fires")]
+    LossySeconds,
+
+    #[error("Dropping the years field would change when the schedule fires")]
+    LossyYears,
+}
+
+#[derive(Error, Debug, PartialEq, Eq, Clone)]
+pub enum DecodeError {
+    #[error("Unsupported encoding version {0}")]
+    UnsupportedVersion(u8),
+
+    #[error("The encoded schedule ended unexpectedly")]
+    UnexpectedEnd,
+
+    #[error("Unexpected bytes after the encoded schedule")]
+    TrailingBytes,
+
+    #[error("Invalid flags {0:#04x}")]
+    InvalidFlags(u8),
+
+    #[error("Invalid value in the {0} field")]
+    InvalidValue(Field),
+
+    #[error("Invalid Timezone {0}")]
+    InvalidTimezone(String),
+}
 
-    #[error("Invalid Range {0}")]
-    InvalidRange(String),
+#[derive(Error, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ExhaustionReason {
+    #[error("The supported year range 1970-2099 was exhausted")]
+    YearRangeExhausted,
 
-    #[error("Invalid Month {0}")]
-    InvalidMonthIndicator(String),
+    #[error("No occurrence was found within the search horizon of 400 years")]
+    HorizonReached,
 
-    #[error("Invalid Day of Week {0}")]
-    InvalidDayOfWeekIndicator(String),
+    #[error("The combination of day of month, month, day of week and years never occurs")]
+    Unsatisfiable,
 }
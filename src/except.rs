@@ -0,0 +1,136 @@
+use crate::schedule::Schedule;
+use chrono::{DateTime, TimeZone};
+
+/// A schedule with the instants matched by an exclusion schedule removed, created with
+/// `Schedule::except`, e.g. for blackout periods.
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use cron_exp::Schedule;
+/// use std::str::FromStr;
+///
+/// let hourly = Schedule::from_str("0 * * * *").unwrap();
+/// let sunday_night = Schedule::from_str("* 0-6 * * Sun").unwrap();
+/// let schedule = hourly.except(&sunday_night);
+///
+/// // the 5th of June 2022 is a Sunday
+/// let from = Utc.with_ymd_and_hms(2022, 6, 4, 23, 30, 0).unwrap();
+/// assert_eq!(
+///     Some(Utc.with_ymd_and_hms(2022, 6, 5, 7, 0, 0).unwrap()),
+///     schedule.next_after(&from)
+/// );
+/// assert!(!schedule.includes(&Utc.with_ymd_and_hms(2022, 6, 5, 3, 0, 0).unwrap()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Excluding {
+    schedule: Schedule,
+    exclusion: Schedule,
+    /// Whether every occurrence is excluded, so searches don't skip occurrences forever.
+    never_fires: bool,
+}
+
+impl Schedule {
+    /// Returns the schedule without the instants matched by `exclusion`, see `Excluding`.
+    pub fn except(&self, exclusion: &Schedule) -> Excluding {
+        Excluding {
+            schedule: self.clone(),
+            exclusion: exclusion.clone(),
+            never_fires: self.is_subset_of(exclusion),
+        }
+    }
+}
+
+impl Excluding {
+    /// Returns the schedule occurrences are excluded from.
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    /// Returns the schedule matching the excluded instants.
+    pub fn exclusion(&self) -> &Schedule {
+        &self.exclusion
+    }
+
+    /// Returns the next occurrence strictly after the supplied datetime that isn't excluded.
+    pub fn next_after<Z>(&self, dt: &DateTime<Z>) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        if self.never_fires {
+            return None;
+        }
+        let mut cursor = self.schedule.after(dt)?;
+        while self.exclusion.includes(&cursor) {
+            cursor = self.schedule.after(&cursor)?;
+        }
+        Some(cursor)
+    }
+
+    /// Returns the previous occurrence strictly before the supplied datetime that isn't
+    /// excluded.
+    pub fn prev_before<Z>(&self, dt: &DateTime<Z>) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        if self.never_fires {
+            return None;
+        }
+        let mut cursor = self.schedule.before(dt)?;
+        while self.exclusion.includes(&cursor) {
+            cursor = self.schedule.before(&cursor)?;
+        }
+        Some(cursor)
+    }
+
+    /// Returns whether the supplied instant is an occurrence of the schedule that isn't
+    /// excluded.
+    pub fn includes<Z>(&self, dt: &DateTime<Z>) -> bool
+    where
+        Z: TimeZone,
+    {
+        self.schedule.includes(dt) && !self.exclusion.includes(dt)
+    }
+
+    /// Iterates the occurrences after the supplied datetime that aren't excluded.
+    pub fn iter_from<'a, Z>(&'a self, dt: &DateTime<Z>) -> impl Iterator<Item = DateTime<Z>> + 'a
+    where
+        Z: TimeZone + 'a,
+    {
+        let mut cursor = Some(dt.clone());
+        std::iter::from_fn(move || {
+            let next = self.next_after(cursor.as_ref()?);
+            cursor = next.clone();
+            next
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::str::FromStr;
+
+    #[test]
+    fn excluded_instants_are_skipped() {
+        let schedule = Schedule::from_str("*/20 * * * *").unwrap();
+        let exclusion = Schedule::from_str("20-40 9 * * *").unwrap();
+        let excluding = schedule.except(&exclusion);
+        let at = |h, m| Utc.with_ymd_and_hms(2022, 6, 1, h, m, 0).unwrap();
+
+        let upcoming: Vec<_> = excluding.iter_from(&at(8, 50)).take(3).collect();
+        assert_eq!(vec![at(9, 0), at(10, 0), at(10, 20)], upcoming);
+        assert_eq!(Some(at(9, 0)), excluding.prev_before(&at(10, 0)));
+        assert!(excluding.includes(&at(9, 0)));
+        assert!(!excluding.includes(&at(9, 40)));
+    }
+
+    #[test]
+    fn excluding_every_occurrence() {
+        let schedule = Schedule::from_str("0 9 * * Mon-Fri").unwrap();
+        let excluding = schedule.except(&Schedule::from_str("0 * * * *").unwrap());
+        let from = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap();
+        assert_eq!(None, excluding.next_after(&from));
+        assert_eq!(None, excluding.prev_before(&from));
+        assert_eq!(0, excluding.iter_from(&from).count());
+    }
+}
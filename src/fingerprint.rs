@@ -0,0 +1,148 @@
+use crate::options::{AmbiguousTimePolicy, DaySemantics, DstGapPolicy};
+use crate::schedule::{DayOfMonthTerm, DayOfWeekTerm, Schedule, Seconds, TimeRange, Years};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+const TAG_ALL: u8 = 0;
+const TAG_CONSTRAINED: u8 = 1;
+const TAG_IGNORE: u8 = 2;
+const TAG_UNBOUND: u8 = 3;
+const TAG_INTERVAL: u8 = 4;
+const TAG_REBOOT: u8 = 5;
+const TAG_LAST: u8 = 6;
+const TAG_NEAREST_WEEKDAY: u8 = 7;
+const TAG_LAST_WEEKDAY: u8 = 8;
+const TAG_NTH: u8 = 9;
+const TAG_TIMEZONE: u8 = 10;
+const TAG_DAYS_OR: u8 = 11;
+const TAG_DST_GAP: u8 = 12;
+const TAG_AMBIGUOUS: u8 = 13;
+
+/// FNV-1a 64 bit hasher, chosen because its output is fully specified and therefore identical
+/// across Rust versions, platforms and processes.
+pub(crate) struct Fnv64(pub(crate) u64);
+
+impl Fnv64 {
+    pub(crate) fn new() -> Self {
+        Fnv64(FNV_OFFSET_BASIS)
+    }
+
+    pub(crate) fn write_u8(&mut self, b: u8) {
+        self.0 ^= u64::from(b);
+        self.0 = self.0.wrapping_mul(FNV_PRIME);
+    }
+
+    fn write_u32(&mut self, v: u32) {
+        for b in v.to_le_bytes().iter() {
+            self.write_u8(*b);
+        }
+    }
+
+    fn write_set<I>(&mut self, set: I)
+    where
+        I: IntoIterator<Item = u32>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let values = set.into_iter();
+        self.write_u8(TAG_CONSTRAINED);
+        self.write_u32(values.len() as u32);
+        for v in values {
+            self.write_u32(v);
+        }
+    }
+
+    fn write_time_range(&mut self, field: &TimeRange) {
+        match field {
+            TimeRange::All => self.write_u8(TAG_ALL),
+            TimeRange::Constrained(set) => self.write_set(set),
+        }
+    }
+}
+
+impl Schedule {
+    /// Returns a stable 64 bit fingerprint of the schedule.
+    ///
+    /// Unlike the `std::hash` machinery the value is derived from the canonical form of the
+    /// schedule using FNV-1a and is fixed across versions and platforms, so it can safely be used
+    /// to shard jobs across workers or deduplicate schedules in distributed systems.
+    /// ```rust
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let a = Schedule::from_str("0 0-59 * * * *").unwrap();
+    /// let b = Schedule::from_str("0 * * * * *").unwrap();
+    /// assert_eq!(a.fingerprint(), b.fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = Fnv64::new();
+
+        match &self.seconds {
+            Seconds::Ignore => hasher.write_u8(TAG_IGNORE),
+            Seconds::All => hasher.write_u8(TAG_ALL),
+            Seconds::Constrained(set) => hasher.write_set(set),
+            Seconds::Interval(interval) => {
+                hasher.write_u8(TAG_INTERVAL);
+                hasher.write_u32(*interval);
+            }
+        }
+        hasher.write_time_range(&self.minutes);
+        hasher.write_time_range(&self.hours);
+        hasher.write_time_range(&self.days_of_month);
+        for term in &self.day_of_month_terms {
+            match term {
+                DayOfMonthTerm::Last(offset) => {
+                    hasher.write_u8(TAG_LAST);
+                    hasher.write_u32(*offset);
+                }
+                DayOfMonthTerm::NearestWeekday(day) => {
+                    hasher.write_u8(TAG_NEAREST_WEEKDAY);
+                    hasher.write_u32(*day);
+                }
+                DayOfMonthTerm::LastWeekday => hasher.write_u8(TAG_LAST_WEEKDAY),
+            }
+        }
+        hasher.write_time_range(&self.months);
+        hasher.write_time_range(&self.days_of_week);
+        for term in &self.day_of_week_terms {
+            match term {
+                DayOfWeekTerm::Last(day_of_week) => {
+                    hasher.write_u8(TAG_LAST);
+                    hasher.write_u32(*day_of_week);
+                }
+                DayOfWeekTerm::Nth(day_of_week, n) => {
+                    hasher.write_u8(TAG_NTH);
+                    hasher.write_u32(*day_of_week);
+                    hasher.write_u32(*n);
+                }
+            }
+        }
+        match &self.years {
+            Years::All => hasher.write_u8(TAG_ALL),
+            Years::Constrained(set) => hasher.write_set(set.iter().cloned()),
+            Years::Unbound => hasher.write_u8(TAG_UNBOUND),
+        }
+        // only tagged when set so the fingerprints of existing schedules are unchanged
+        if self.reboot {
+            hasher.write_u8(TAG_REBOOT);
+        }
+        if self.effective_day_semantics() == DaySemantics::Or {
+            hasher.write_u8(TAG_DAYS_OR);
+        }
+        if self.dst_gap_policy != DstGapPolicy::Skip {
+            hasher.write_u8(TAG_DST_GAP);
+            hasher.write_u8(self.dst_gap_policy as u8);
+        }
+        if self.ambiguous_time_policy != AmbiguousTimePolicy::Skip {
+            hasher.write_u8(TAG_AMBIGUOUS);
+            hasher.write_u8(self.ambiguous_time_policy as u8);
+        }
+        if let Some(name) = self.timezone_name() {
+            hasher.write_u8(TAG_TIMEZONE);
+            for b in name.bytes() {
+                hasher.write_u8(b);
+            }
+        }
+        hasher.0
+    }
+}
@@ -0,0 +1,185 @@
+use crate::errors::ParseScheduleError;
+use crate::options::ParseOptions;
+use crate::schedule::{parse_expression, Schedule};
+use chrono::Weekday;
+
+/// The layout of an expression's fields, detected from their number by `Schedule::from_str`
+/// or forced with `Schedule::parse_with_format`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum CronFormat {
+    /// `minute hour day-of-month month day-of-week`, days of week numbered 0-6 from Sunday
+    /// with 7 also Sunday.
+    Crontab,
+    /// `minute hour day-of-month month day-of-week year`, days of week numbered as in
+    /// `Crontab`.
+    CrontabWithYear,
+    /// `second minute hour day-of-month month day-of-week`, days of week numbered 1-7 from
+    /// Sunday.
+    VixieWithSeconds,
+    /// `second minute hour day-of-month month day-of-week year`, days of week numbered as in
+    /// `VixieWithSeconds`.
+    VixieWithYear,
+}
+
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Sun,
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+];
+
+/// How days of week are numbered in expressions.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum DayOfWeekNumbering {
+    /// 0-6 from Sunday with 7 also Sunday, as in Crontab.
+    SundayZero,
+    /// 1-7 from Sunday, as in Vixie CRON.
+    SundayOne,
+    /// 1-7 from Monday, as in ISO 8601.
+    MondayOne,
+}
+
+impl DayOfWeekNumbering {
+    /// Returns the day of week numbered `n`, if any.
+    /// ```rust
+    /// use chrono::Weekday;
+    /// use cron_exp::DayOfWeekNumbering;
+    ///
+    /// assert_eq!(Some(Weekday::Sun), DayOfWeekNumbering::SundayZero.weekday(7));
+    /// assert_eq!(Some(Weekday::Sun), DayOfWeekNumbering::MondayOne.weekday(7));
+    /// assert_eq!(None, DayOfWeekNumbering::SundayOne.weekday(0));
+    /// ```
+    pub fn weekday(self, n: u32) -> Option<Weekday> {
+        let days_from_sunday = match self {
+            DayOfWeekNumbering::SundayZero if n <= 7 => n % 7,
+            DayOfWeekNumbering::SundayOne if (1..=7).contains(&n) => n - 1,
+            DayOfWeekNumbering::MondayOne if (1..=7).contains(&n) => n % 7,
+            _ => return None,
+        };
+        Some(WEEKDAYS[days_from_sunday as usize])
+    }
+
+    /// Returns the number of the day of week.
+    pub fn number(self, weekday: Weekday) -> u32 {
+        match self {
+            DayOfWeekNumbering::SundayZero => weekday.num_days_from_sunday(),
+            DayOfWeekNumbering::SundayOne => weekday.number_from_sunday(),
+            DayOfWeekNumbering::MondayOne => weekday.number_from_monday(),
+        }
+    }
+
+    /// Returns the number of a day of week stored numbered 1-7 from Sunday.
+    pub(crate) fn weekday_number(self, day: u32) -> Option<u32> {
+        DayOfWeekNumbering::SundayOne
+            .weekday(day)
+            .map(|weekday| self.number(weekday))
+    }
+}
+
+impl CronFormat {
+    /// Returns how the days of week of the format are numbered.
+    pub fn day_of_week_numbering(self) -> DayOfWeekNumbering {
+        if self.has_seconds() {
+            DayOfWeekNumbering::SundayOne
+        } else {
+            DayOfWeekNumbering::SundayZero
+        }
+    }
+
+    /// Returns the number of fields of the format.
+    pub fn field_count(self) -> usize {
+        match self {
+            CronFormat::Crontab => 5,
+            CronFormat::CrontabWithYear | CronFormat::VixieWithSeconds => 6,
+            CronFormat::VixieWithYear => 7,
+        }
+    }
+
+    pub(crate) fn has_seconds(self) -> bool {
+        matches!(
+            self,
+            CronFormat::VixieWithSeconds | CronFormat::VixieWithYear
+        )
+    }
+
+    /// Returns the format assumed for the number of fields, a 6 field expression is taken to
+    /// lead with seconds.
+    pub(crate) fn detect(field_count: usize) -> Option<CronFormat> {
+        match field_count {
+            5 => Some(CronFormat::Crontab),
+            6 => Some(CronFormat::VixieWithSeconds),
+            7 => Some(CronFormat::VixieWithYear),
+            _ => None,
+        }
+    }
+}
+
+impl Schedule {
+    /// Parses the expression in the supplied format rather than detecting it from the number
+    /// of fields, e.g. to read a 6 field expression ending in a years field.
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use cron_exp::{CronFormat, Schedule};
+    ///
+    /// let schedule = Schedule::parse_with_format("0 12 * * 1 2030", CronFormat::CrontabWithYear)
+    ///     .unwrap();
+    /// let from = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap();
+    /// assert_eq!(
+    ///     "2030-01-07T12:00:00+00:00",
+    ///     schedule.iter_from(&from).next().unwrap().to_rfc3339()
+    /// );
+    /// ```
+    pub fn parse_with_format(
+        expression: &str,
+        format: CronFormat,
+    ) -> Result<Schedule, ParseScheduleError> {
+        Ok(parse_expression(
+            expression,
+            &ParseOptions::default().format(format),
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Capabilities;
+    use std::str::FromStr;
+
+    #[test]
+    fn parse_with_format() {
+        let parse = Schedule::parse_with_format;
+
+        assert_eq!(
+            Schedule::from_str("0 0 12 * * Mon").unwrap(),
+            parse("0 0 12 * * Mon", CronFormat::VixieWithSeconds).unwrap()
+        );
+        assert_eq!(
+            Schedule::from_str("0 0 12 * * 2 2030").unwrap(),
+            parse("0 12 * * 1 2030", CronFormat::CrontabWithYear)
+                .unwrap()
+                .to_seven_field()
+        );
+        assert_eq!(
+            Schedule::from_str("30 9 * * 1-5").unwrap(),
+            parse("30 9 * * 1-5", CronFormat::Crontab).unwrap()
+        );
+
+        let with_year = parse("0 12 L * ? 2030-2035", CronFormat::CrontabWithYear).unwrap();
+        assert_eq!(
+            Capabilities::YEARS | Capabilities::QUARTZ,
+            with_year.capabilities()
+        );
+        assert!(parse("@reboot", CronFormat::VixieWithYear)
+            .unwrap()
+            .is_reboot());
+
+        assert_eq!(
+            Err(ParseScheduleError::ArgumentCount),
+            parse("0 12 * * *", CronFormat::VixieWithSeconds)
+        );
+    }
+}
@@ -0,0 +1,275 @@
+//! A gRPC service exposing the schedule engine to services written in other languages.
+//!
+//! The service definition is available in `proto/cron_exp.proto` for generating clients.
+//! ```rust,no_run
+//! use cron_exp::grpc::{CronExpService, ScheduleServiceServer};
+//!
+//! let server = ScheduleServiceServer::new(CronExpService::default());
+//! // serve using tonic::transport::Server::builder().add_service(server)
+//! ```
+use crate::{Locale, Schedule};
+use chrono::{DateTime, TimeZone};
+use chrono_tz::Tz;
+use std::fmt::Display;
+use std::str::FromStr;
+use tonic::{Request, Response, Status};
+
+mod generated {
+    include!(concat!(env!("OUT_DIR"), "/cron_exp.v1.ScheduleService.rs"));
+}
+
+pub use generated::schedule_service_server::{ScheduleService, ScheduleServiceServer};
+
+const MAX_OCCURRENCES: u32 = 1000;
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ParseRequest {
+    #[prost(string, tag = "1")]
+    pub expression: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ParseResponse {
+    #[prost(uint64, tag = "1")]
+    pub fingerprint: u64,
+    #[prost(string, tag = "2")]
+    pub canonical: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ValidateRequest {
+    #[prost(string, tag = "1")]
+    pub expression: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ValidateResponse {
+    #[prost(bool, tag = "1")]
+    pub valid: bool,
+    #[prost(string, tag = "2")]
+    pub error: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct OccurrencesRequest {
+    #[prost(string, tag = "1")]
+    pub expression: String,
+    #[prost(string, tag = "2")]
+    pub from: String,
+    #[prost(string, tag = "3")]
+    pub timezone: String,
+    #[prost(uint32, tag = "4")]
+    pub count: u32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct OccurrencesResponse {
+    #[prost(string, repeated, tag = "1")]
+    pub occurrences: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct DescribeRequest {
+    #[prost(string, tag = "1")]
+    pub expression: String,
+    #[prost(string, tag = "2")]
+    pub locale: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct DescribeResponse {
+    #[prost(string, tag = "1")]
+    pub description: String,
+}
+
+/// The `ScheduleService` implementation backed by this crate.
+#[derive(Debug, Default, Clone)]
+pub struct CronExpService;
+
+#[tonic::async_trait]
+impl ScheduleService for CronExpService {
+    async fn parse(
+        &self,
+        request: Request<ParseRequest>,
+    ) -> Result<Response<ParseResponse>, Status> {
+        let schedule = parse(&request.get_ref().expression)?;
+        Ok(Response::new(ParseResponse {
+            fingerprint: schedule.fingerprint(),
+            canonical: schedule.to_string(),
+        }))
+    }
+
+    async fn validate(
+        &self,
+        request: Request<ValidateRequest>,
+    ) -> Result<Response<ValidateResponse>, Status> {
+        let response = match Schedule::from_str(&request.get_ref().expression) {
+            Ok(_) => ValidateResponse {
+                valid: true,
+                error: String::new(),
+            },
+            Err(e) => ValidateResponse {
+                valid: false,
+                error: e.to_string(),
+            },
+        };
+        Ok(Response::new(response))
+    }
+
+    async fn next(
+        &self,
+        request: Request<OccurrencesRequest>,
+    ) -> Result<Response<OccurrencesResponse>, Status> {
+        occurrences(request.get_ref(), true).map(Response::new)
+    }
+
+    async fn prev(
+        &self,
+        request: Request<OccurrencesRequest>,
+    ) -> Result<Response<OccurrencesResponse>, Status> {
+        occurrences(request.get_ref(), false).map(Response::new)
+    }
+
+    async fn describe(
+        &self,
+        request: Request<DescribeRequest>,
+    ) -> Result<Response<DescribeResponse>, Status> {
+        let request = request.get_ref();
+        let schedule = parse(&request.expression)?;
+        let locale = locale(&request.locale)?;
+        Ok(Response::new(DescribeResponse {
+            description: schedule.describe_in(locale),
+        }))
+    }
+}
+
+fn parse(expression: &str) -> Result<Schedule, Status> {
+    Schedule::from_str(expression).map_err(|e| Status::invalid_argument(e.to_string()))
+}
+
+/// Returns the locale of the language code, English when empty.
+fn locale(code: &str) -> Result<Locale, Status> {
+    match code {
+        "" | "en" => Ok(Locale::En),
+        #[cfg(feature = "locale-de")]
+        "de" => Ok(Locale::De),
+        #[cfg(feature = "locale-fr")]
+        "fr" => Ok(Locale::Fr),
+        #[cfg(feature = "locale-es")]
+        "es" => Ok(Locale::Es),
+        _ => Err(Status::invalid_argument(format!(
+            "unsupported locale: {}",
+            code
+        ))),
+    }
+}
+
+fn occurrences(request: &OccurrencesRequest, forward: bool) -> Result<OccurrencesResponse, Status> {
+    let schedule = parse(&request.expression)?;
+    let from = DateTime::parse_from_rfc3339(&request.from)
+        .map_err(|e| Status::invalid_argument(format!("invalid from datetime: {}", e)))?;
+    let count = request.count.clamp(1, MAX_OCCURRENCES) as usize;
+
+    let occurrences = if request.timezone.is_empty() {
+        collect(&schedule, &from, count, forward)
+    } else {
+        let tz = Tz::from_str(&request.timezone)
+            .map_err(|e| Status::invalid_argument(format!("invalid timezone: {}", e)))?;
+        collect(&schedule, &from.with_timezone(&tz), count, forward)
+    };
+    Ok(OccurrencesResponse { occurrences })
+}
+
+fn collect<Z>(schedule: &Schedule, from: &DateTime<Z>, count: usize, forward: bool) -> Vec<String>
+where
+    Z: TimeZone,
+    Z::Offset: Display,
+{
+    if forward {
+        let iter = schedule.iter_after(from);
+        iter.take(count).map(|dt| dt.to_rfc3339()).collect()
+    } else {
+        let iter = schedule.iter_before(from);
+        iter.take(count).map(|dt| dt.to_rfc3339()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn next_and_prev() {
+        let service = CronExpService;
+        let request = OccurrencesRequest {
+            expression: "0 30 9 * * Mon-Fri".to_string(),
+            from: "2022-06-03T12:00:00Z".to_string(),
+            timezone: "Europe/Paris".to_string(),
+            count: 2,
+        };
+
+        let next = service.next(Request::new(request.clone())).await.unwrap();
+        assert_eq!(
+            next.get_ref().occurrences,
+            vec!["2022-06-06T09:30:00+02:00", "2022-06-07T09:30:00+02:00"]
+        );
+
+        let prev = service.prev(Request::new(request)).await.unwrap();
+        assert_eq!(
+            prev.get_ref().occurrences,
+            vec!["2022-06-03T09:30:00+02:00", "2022-06-02T09:30:00+02:00"]
+        );
+    }
+
+    #[tokio::test]
+    async fn parse_and_validate() {
+        let service = CronExpService;
+        let invalid = service
+            .parse(Request::new(ParseRequest {
+                expression: "* * *".to_string(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(invalid.code(), tonic::Code::InvalidArgument);
+
+        let validated = service
+            .validate(Request::new(ValidateRequest {
+                expression: "* * *".to_string(),
+            }))
+            .await
+            .unwrap();
+        assert!(!validated.get_ref().valid);
+        assert!(!validated.get_ref().error.is_empty());
+
+        let parsed = service
+            .parse(Request::new(ParseRequest {
+                expression: "0 30 9 * * Mon-Fri".to_string(),
+            }))
+            .await
+            .unwrap();
+        assert_eq!("0 30 9 * * 2-6", parsed.get_ref().canonical);
+    }
+
+    #[tokio::test]
+    async fn describe() {
+        let service = CronExpService;
+        let describe = |locale: &str| {
+            service.describe(Request::new(DescribeRequest {
+                expression: "0 9 * * Mon".to_string(),
+                locale: locale.to_string(),
+            }))
+        };
+        assert_eq!(
+            "At 09:00, only on Monday",
+            describe("").await.unwrap().get_ref().description
+        );
+        assert_eq!(
+            "At 09:00, only on Monday",
+            describe("en").await.unwrap().get_ref().description
+        );
+        assert_eq!(
+            tonic::Code::InvalidArgument,
+            describe("tlh").await.unwrap_err().code()
+        );
+    }
+}
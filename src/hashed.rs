@@ -0,0 +1,177 @@
+use crate::capabilities::Capabilities;
+use crate::errors::ParseScheduleError;
+use crate::fingerprint::Fnv64;
+use crate::schedule::Schedule;
+use crate::tokens::{self, Bounds, Syntax, Token};
+
+impl Schedule {
+    /// Parses an expression containing Jenkins style hashed tokens, which deterministically
+    /// pick a value derived from the seed, e.g. a job name, to spread many jobs across a range
+    /// without manual offsets.
+    ///
+    /// - `H` a value within the field
+    /// - `H(a-b)` a value within `a-b`
+    /// - `H/n` every `n` starting at a hashed offset, `H(a-b)/n` within `a-b`
+    ///
+    /// Every field hashes independently and the values are stable across versions and platforms.
+    /// Days of month stop at the 28th, as in Jenkins, so the value exists in every month. A
+    /// `CRON_TZ=` prefix is kept.
+    /// ```rust
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let backup = Schedule::from_str_hashed("H H(0-5) * * *", "backup").unwrap();
+    /// assert_eq!(backup, Schedule::from_str_hashed("H H(0-5) * * *", "backup").unwrap());
+    /// assert_ne!(backup, Schedule::from_str_hashed("H H(0-5) * * *", "reports").unwrap());
+    /// ```
+    pub fn from_str_hashed(expression: &str, seed: &str) -> Result<Schedule, ParseScheduleError> {
+        let syntax = Syntax {
+            token,
+            last_day_of_month: 28,
+            capability: Capabilities::HASHED,
+        };
+        tokens::parse_picking(expression, syntax, |field, _| hash(seed, field))
+    }
+}
+
+/// Returns the hash of the seed for the field at the supplied index.
+fn hash(seed: &str, field: usize) -> u64 {
+    let mut hasher = Fnv64::new();
+    for b in seed.bytes() {
+        hasher.write_u8(b);
+    }
+    hasher.write_u8(field as u8);
+    hasher.0
+}
+
+/// Returns the hashed token of a list item, such as `H(0-29)/10`.
+fn token(item: &str, bounds: Bounds) -> Result<Option<Token>, ParseScheduleError> {
+    let rest = match item.strip_prefix('H') {
+        Some(rest) => rest,
+        None => return Ok(None),
+    };
+    let (range, step) = match rest.split_once('/') {
+        Some((range, step)) => (range, Some(step)),
+        None => (rest, None),
+    };
+
+    let (low, high) = if range.is_empty() {
+        (bounds.min, bounds.max)
+    } else {
+        let (low, high) = range
+            .strip_prefix('(')
+            .and_then(|r| r.strip_suffix(')'))
+            .and_then(|r| r.split_once('-'))
+            .ok_or_else(|| ParseScheduleError::InvalidRange {
+                field: bounds.field,
+                value: item.into(),
+            })?;
+        (bounds.number(low)?, bounds.number(high)?)
+    };
+    let step = match step {
+        Some(step) => match bounds.number(step)? {
+            0 => {
+                return Err(ParseScheduleError::InvalidStepRange {
+                    field: bounds.field,
+                    value: item.into(),
+                })
+            }
+            step => Some(step),
+        },
+        None => None,
+    };
+    bounds.token(item, low, high, step).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::Field;
+    use chrono::{TimeZone, Timelike, Utc};
+    use std::str::FromStr;
+
+    #[test]
+    fn hashed() {
+        let from = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap();
+        for seed in ["backup", "reports", "cleanup", ""].iter() {
+            let schedule = Schedule::from_str_hashed("H(10-20) H * * *", seed).unwrap();
+            let mut occurrences = schedule.iter_from(&from).take(3);
+            let first = occurrences.next().unwrap();
+            assert!((10..=20).contains(&first.minute()), "{}", seed);
+            assert!(
+                occurrences.all(|dt| dt.minute() == first.minute() && dt.hour() == first.hour())
+            );
+
+            // a hashed offset keeps the step
+            let stepped = Schedule::from_str_hashed("0 H/15 * * * *", seed).unwrap();
+            let minutes: Vec<u32> = stepped
+                .iter_from(&from)
+                .take(4)
+                .map(|dt| dt.minute())
+                .collect();
+            assert!(minutes[0] < 15, "{}", seed);
+            assert_eq!(
+                vec![
+                    minutes[0],
+                    minutes[0] + 15,
+                    minutes[0] + 30,
+                    minutes[0] + 45
+                ],
+                minutes
+            );
+
+            assert!(schedule.capabilities().contains(Capabilities::HASHED));
+        }
+
+        // the fields hash independently of each other
+        assert_ne!(hash("backup", 0), hash("backup", 1));
+
+        assert_eq!(
+            Schedule::from_str("30 9 * * THU").unwrap().capabilities(),
+            Schedule::from_str_hashed("30 9 * * THU", "backup")
+                .unwrap()
+                .capabilities()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "tz")]
+    fn hashed_timezone() {
+        let schedule =
+            Schedule::from_str_hashed("CRON_TZ=Europe/Paris H 9 * * *", "backup").unwrap();
+        assert_eq!(Some(chrono_tz::Europe::Paris), schedule.timezone());
+        assert_eq!(
+            Schedule::from_str_hashed("H 9 * * *", "backup")
+                .unwrap()
+                .with_timezone(chrono_tz::Europe::Paris),
+            schedule
+        );
+    }
+
+    #[test]
+    fn invalid_hashed() {
+        let parse = |e| Schedule::from_str_hashed(e, "backup");
+        assert_eq!(
+            Err(ParseScheduleError::InvalidRange {
+                field: Field::Minutes,
+                value: "H(50-70)".into()
+            }),
+            parse("H(50-70) * * * *")
+        );
+        assert_eq!(
+            Err(ParseScheduleError::InvalidRange {
+                field: Field::Minutes,
+                value: "H(5)".into()
+            }),
+            parse("H(5) * * * *")
+        );
+        assert_eq!(
+            Err(ParseScheduleError::InvalidStepRange {
+                field: Field::Minutes,
+                value: "H/0".into()
+            }),
+            parse("H/0 * * * *")
+        );
+        assert_eq!(Err(ParseScheduleError::ArgumentCount), parse("H * *"));
+    }
+}
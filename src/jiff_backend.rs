@@ -0,0 +1,161 @@
+//! Occurrences as `jiff::Zoned` datetimes, with the `jiff` feature.
+use crate::backend::CalendarBackend;
+use crate::schedule::Schedule;
+use crate::tz::{ProvidedTz, TimeZoneProvider};
+use chrono::{DateTime, Datelike, FixedOffset, LocalResult, NaiveDateTime, TimeZone, Timelike};
+use jiff::civil;
+use jiff::tz::AmbiguousOffset;
+use jiff::{Timestamp, Zoned};
+
+/// Supplies the offsets of a `jiff` time zone, from jiff's own time zone database.
+#[derive(Debug)]
+struct JiffZone(jiff::tz::TimeZone);
+
+fn fixed(offset: jiff::tz::Offset) -> FixedOffset {
+    FixedOffset::east_opt(offset.seconds())
+        .unwrap_or_else(|| FixedOffset::east_opt(0).expect("UTC is a valid offset"))
+}
+
+impl TimeZoneProvider for JiffZone {
+    fn offset_from_utc(&self, utc: &NaiveDateTime) -> FixedOffset {
+        let utc = utc.and_utc();
+        match Timestamp::new(utc.timestamp(), utc.timestamp_subsec_nanos() as i32) {
+            Ok(timestamp) => fixed(self.0.to_offset(timestamp)),
+            Err(_) => fixed(jiff::tz::Offset::UTC),
+        }
+    }
+
+    fn offset_from_local(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset> {
+        let local = match civil::DateTime::new(
+            local.year() as i16,
+            local.month() as i8,
+            local.day() as i8,
+            local.hour() as i8,
+            local.minute() as i8,
+            local.second() as i8,
+            local.nanosecond() as i32,
+        ) {
+            Ok(local) => local,
+            Err(_) => return LocalResult::None,
+        };
+        match self.0.to_ambiguous_timestamp(local).offset() {
+            AmbiguousOffset::Unambiguous { offset } => LocalResult::Single(fixed(offset)),
+            AmbiguousOffset::Gap { .. } => LocalResult::None,
+            // the offset before a fold is ahead of the one after it, giving the earlier instant
+            AmbiguousOffset::Fold { before, after } => {
+                LocalResult::Ambiguous(fixed(before), fixed(after))
+            }
+        }
+    }
+}
+
+impl CalendarBackend for Zoned {
+    type Zone = ProvidedTz;
+
+    fn to_chrono(&self) -> Option<DateTime<ProvidedTz>> {
+        let timestamp = self.timestamp();
+        let utc =
+            DateTime::from_timestamp(timestamp.as_second(), timestamp.subsec_nanosecond() as u32)?;
+        let tz = ProvidedTz::new(JiffZone(self.time_zone().clone()));
+        Some(tz.from_utc_datetime(&utc.naive_utc()))
+    }
+
+    fn from_chrono(dt: &DateTime<ProvidedTz>, from: &Self) -> Option<Self> {
+        let timestamp = Timestamp::new(dt.timestamp(), dt.timestamp_subsec_nanos() as i32).ok()?;
+        Some(Zoned::new(timestamp, from.time_zone().clone()))
+    }
+}
+
+impl Schedule {
+    /// Returns the next occurrence after the supplied `jiff::Zoned`, computed and returned in its
+    /// time zone unless the schedule has its own.
+    /// ```rust
+    /// use cron_exp::Schedule;
+    /// use jiff::Zoned;
+    /// use std::str::FromStr;
+    ///
+    /// // clocks spring forward on the 28th of March 2021 in Paris
+    /// let from: Zoned = "2021-03-27T12:00[Europe/Paris]".parse().unwrap();
+    /// let schedule = Schedule::from_str("0 9 * * *").unwrap();
+    /// let next = schedule.next_after_zoned(&from).unwrap();
+    /// assert_eq!("2021-03-28T09:00:00+02:00[Europe/Paris]", next.to_string());
+    /// ```
+    pub fn next_after_zoned(&self, dt: &Zoned) -> Option<Zoned> {
+        self.next_after_backend(dt)
+    }
+
+    /// Returns the previous occurrence before the supplied `jiff::Zoned`.
+    pub fn prev_before_zoned(&self, dt: &Zoned) -> Option<Zoned> {
+        self.prev_before_backend(dt)
+    }
+
+    /// Iterates the occurrences after the supplied `jiff::Zoned`.
+    pub fn iter_after_zoned(&self, dt: &Zoned) -> impl Iterator<Item = Zoned> + '_ {
+        self.iter_after_backend(dt)
+    }
+
+    /// Iterates the occurrences before the supplied `jiff::Zoned` latest first.
+    pub fn iter_before_zoned(&self, dt: &Zoned) -> impl Iterator<Item = Zoned> + '_ {
+        self.iter_before_backend(dt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{AmbiguousTimePolicy, DstGapPolicy};
+    use std::str::FromStr;
+
+    fn zoned(s: &str) -> Zoned {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn follows_transitions() {
+        // clocks spring forward on the 28th of March and fall back on the 31st of October 2021
+        let schedule = Schedule::from_str("30 2 * * *").unwrap();
+        let from = zoned("2021-03-27T12:00[Europe/Paris]");
+        assert_eq!(
+            vec![
+                zoned("2021-03-29T02:30+02:00[Europe/Paris]"),
+                zoned("2021-03-30T02:30+02:00[Europe/Paris]")
+            ],
+            schedule.iter_after_zoned(&from).take(2).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(zoned("2021-03-28T03:00+02:00[Europe/Paris]")),
+            schedule
+                .clone()
+                .with_dst_gap_policy(DstGapPolicy::FireAtGapEnd)
+                .next_after_zoned(&from)
+        );
+
+        let from = zoned("2021-10-31T00:00[Europe/Paris]");
+        assert_eq!(
+            vec![
+                zoned("2021-10-31T02:30+02:00[Europe/Paris]"),
+                zoned("2021-10-31T02:30+01:00[Europe/Paris]")
+            ],
+            schedule
+                .clone()
+                .with_ambiguous_time_policy(AmbiguousTimePolicy::Both)
+                .iter_after_zoned(&from)
+                .take(2)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![
+                zoned("2021-10-30T02:30+02:00[Europe/Paris]"),
+                zoned("2021-10-29T02:30+02:00[Europe/Paris]")
+            ],
+            schedule
+                .iter_before_zoned(&from)
+                .take(2)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(zoned("2021-10-30T02:30+02:00[Europe/Paris]")),
+            schedule.prev_before_zoned(&from)
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use crate::fingerprint::Fnv64;
+use crate::schedule::Schedule;
+use chrono::{DateTime, Duration, TimeZone};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// A schedule whose occurrences are each delayed by a pseudo-random amount up to a maximum,
+/// created with `Schedule::with_jitter`, e.g. to spread many jobs sharing an expression so they
+/// don't all fire at once.
+///
+/// The delay is derived from the occurrence and the seed alone, so the same seed always yields
+/// the same fire times while different seeds, e.g. a hash of the host name, spread them.
+/// Fire times are yielded in order even when the maximum delay is longer than the spacing between
+/// occurrences and a later occurrence may fire first.
+/// ```rust
+/// use chrono::{Duration, TimeZone, Utc};
+/// use cron_exp::Schedule;
+/// use std::str::FromStr;
+///
+/// let schedule = Schedule::from_str("0 * * * *").unwrap();
+/// let jittered = schedule.with_jitter(Duration::minutes(5), 42);
+///
+/// let from = Utc.with_ymd_and_hms(2022, 6, 1, 9, 30, 0).unwrap();
+/// let next = jittered.next_after(&from).unwrap();
+/// let ten = Utc.with_ymd_and_hms(2022, 6, 1, 10, 0, 0).unwrap();
+/// assert!(next >= ten && next <= ten + Duration::minutes(5));
+/// assert_eq!(Some(next), schedule.with_jitter(Duration::minutes(5), 42).next_after(&from));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Jittered {
+    schedule: Schedule,
+    max: Duration,
+    seed: u64,
+}
+
+impl Schedule {
+    /// Returns the schedule with each occurrence delayed by up to `max`, see `Jittered`.
+    ///
+    /// A negative maximum is treated as no jitter.
+    pub fn with_jitter(&self, max: Duration, seed: u64) -> Jittered {
+        Jittered {
+            schedule: self.clone(),
+            max: std::cmp::max(max, Duration::zero()),
+            seed,
+        }
+    }
+}
+
+impl Jittered {
+    /// Returns the wrapped schedule.
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    /// Returns the maximum delay.
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+
+    /// Returns the seed the delays are derived from.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the delay applied to an occurrence of the wrapped schedule, to the millisecond.
+    pub fn jitter_of<Z>(&self, occurrence: &DateTime<Z>) -> Duration
+    where
+        Z: TimeZone,
+    {
+        let span = self.max.num_milliseconds() as u64 + 1;
+        let mut hasher = Fnv64::new();
+        for b in self.seed.to_le_bytes().iter() {
+            hasher.write_u8(*b);
+        }
+        for b in occurrence.timestamp().to_le_bytes().iter() {
+            hasher.write_u8(*b);
+        }
+        // the high bits are the best mixed by the multiplications
+        Duration::milliseconds(((hasher.0 >> 32) % span) as i64)
+    }
+
+    /// Returns the first fire time strictly after the supplied datetime, which may belong to an
+    /// occurrence before it delayed past it.
+    pub fn next_after<Z>(&self, dt: &DateTime<Z>) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        self.iter_from(dt).next()
+    }
+
+    /// Iterates the fire times strictly after the supplied datetime in chronological order.
+    ///
+    /// Occurrences closer together than the maximum delay may fire in either order, so the fire
+    /// times of the occurrences within the maximum delay ahead are held to yield them in order.
+    pub fn iter_from<'a, Z>(&'a self, dt: &DateTime<Z>) -> impl Iterator<Item = DateTime<Z>> + 'a
+    where
+        Z: TimeZone + 'a,
+    {
+        let dt = dt.clone();
+        // occurrences up to the maximum delay earlier may fire after the datetime
+        let mut occurrence = self
+            .schedule
+            .after(&(dt.clone() - self.max - Duration::seconds(1)));
+        let mut pending = BinaryHeap::new();
+        std::iter::from_fn(move || loop {
+            // later occurrences fire at or after the next one
+            let ready = match (pending.peek(), &occurrence) {
+                (Some(Reverse(first)), Some(next)) => first <= next,
+                (_, None) => true,
+                (None, Some(_)) => false,
+            };
+            if ready {
+                return pending.pop().map(|Reverse(fire_time)| fire_time);
+            }
+            let current = occurrence.take()?;
+            occurrence = self.schedule.after(&current);
+            let fire_time = self.fire_time(current);
+            if fire_time > dt {
+                pending.push(Reverse(fire_time));
+            }
+        })
+    }
+
+    fn fire_time<Z>(&self, occurrence: DateTime<Z>) -> DateTime<Z>
+    where
+        Z: TimeZone,
+    {
+        let jitter = self.jitter_of(&occurrence);
+        occurrence + jitter
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::str::FromStr;
+
+    #[test]
+    fn stable_jitter_within_bounds() {
+        let schedule = Schedule::from_str("0 * * * *").unwrap();
+        let from = Utc.with_ymd_and_hms(2022, 6, 1, 0, 30, 0).unwrap();
+        let max = Duration::minutes(10);
+        let jittered = schedule.with_jitter(max, 7);
+
+        let fires: Vec<_> = jittered.iter_from(&from).take(48).collect();
+        let occurrences: Vec<_> = schedule.iter_from(&from).take(48).collect();
+        for (fire, occurrence) in fires.iter().zip(&occurrences) {
+            assert!(fire >= occurrence && *fire <= *occurrence + max);
+        }
+        // reproducible, spread, and different for another seed
+        assert_eq!(
+            fires,
+            jittered.iter_from(&from).take(48).collect::<Vec<_>>()
+        );
+        assert!(fires
+            .iter()
+            .any(|fire| fire.timestamp() % 3600 != fires[0].timestamp() % 3600));
+        let other: Vec<_> = schedule
+            .with_jitter(max, 8)
+            .iter_from(&from)
+            .take(48)
+            .collect();
+        assert_ne!(fires, other);
+
+        // an occurrence delayed past the datetime still fires after it
+        let just_after = fires[1] - Duration::milliseconds(1);
+        assert_eq!(Some(fires[1]), jittered.next_after(&just_after));
+        assert_eq!(
+            fires[2..5],
+            jittered.iter_from(&fires[1]).take(3).collect::<Vec<_>>()[..]
+        );
+
+        let none = schedule.with_jitter(Duration::minutes(-1), 7);
+        assert_eq!(
+            occurrences[..3],
+            none.iter_from(&from).take(3).collect::<Vec<_>>()[..]
+        );
+    }
+
+    #[test]
+    fn delays_longer_than_the_spacing() {
+        let schedule = Schedule::from_str("* * * * *").unwrap();
+        let from = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 30).unwrap();
+        let jittered = schedule.with_jitter(Duration::minutes(10), 7);
+
+        // every occurrence delayed past the datetime fires once, in chronological order
+        let mut expected: Vec<_> = schedule
+            .iter_from(&(from - Duration::minutes(10)))
+            .take(200)
+            .map(|occurrence| occurrence + jittered.jitter_of(&occurrence))
+            .filter(|fire_time| *fire_time > from)
+            .collect();
+        expected.sort();
+        let fires: Vec<_> = jittered.iter_from(&from).take(100).collect();
+        assert_eq!(expected[..100], fires[..]);
+        assert_eq!(Some(fires[0]), jittered.next_after(&from));
+        assert_eq!(Some(fires[1]), jittered.next_after(&fires[0]));
+    }
+}
@@ -12,24 +12,22 @@
 //! - / step values
 //!
 //! ```rust
-//! use chrono::{DateTime, TimeZone, Utc};
+//! use chrono::{TimeZone, Utc};
 //! use cron_exp::Schedule;
 //! use std::str::FromStr;
 //! //               sec  min   hour   day of month   month   day of week   year
 //! let expression = "0   30   9,12,15     1,15       May-Aug  Mon,Wed,Fri  2018/2";
 //!
 //! let schedule = Schedule::from_str(expression).unwrap();
-//! let mut last: Option<DateTime<Utc>> = None;
-//! let from_date = Utc.ymd(2022, 6, 1).and_hms(8, 40, 1);
+//! let from_date = Utc.with_ymd_and_hms(2022, 6, 1, 8, 40, 1).unwrap();
 //!
 //! println!("Upcoming fire times:");
-//! for datetime in schedule.iter_from(&from_date).take(10) {
-//!     last = Some(datetime);
+//! for datetime in schedule.iter_after(&from_date).take(10) {
 //!     println!("next -> {:?}", datetime);
 //! }
 //!
 //! println!("Previous fire times:");
-//! for datetime in schedule.iter_from(&last.unwrap()).rev().take(40) {
+//! for datetime in schedule.iter_before(&from_date).take(3) {
 //!     println!("prev -> {:?}", datetime);
 //! }
 //! /*
@@ -46,23 +44,123 @@
 //! next -> 2022-07-15T09:30:00Z
 //!
 //! Previous fire times:
-//! prev -> 2022-07-01T15:30:00Z
-//! prev -> 2022-07-01T12:30:00Z
-//! prev -> 2022-07-01T09:30:00Z
-//! prev -> 2022-06-15T15:30:00Z
-//! prev -> 2022-06-15T12:30:00Z
-//! prev -> 2022-06-15T09:30:00Z
-//! prev -> 2022-06-01T15:30:00Z
-//! prev -> 2022-06-01T12:30:00Z
-//! prev -> 2022-06-01T09:30:00Z
 //! prev -> 2020-07-15T15:30:00Z
+//! prev -> 2020-07-15T12:30:00Z
+//! prev -> 2020-07-15T09:30:00Z
 //! */
 //! ```
+#[cfg(any(feature = "time", feature = "jiff"))]
+mod backend;
+mod batch;
+mod bitset;
+mod bounded;
+mod builder;
+mod capabilities;
+mod convert;
+pub mod crontab;
+mod cursor;
+mod describe;
+mod display;
+mod encoding;
 mod errors;
+mod except;
+mod fingerprint;
+mod format;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+mod hashed;
+#[cfg(feature = "jiff")]
+mod jiff_backend;
+mod jitter;
+mod locale;
+mod merge;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+mod mux;
+mod occurrences;
+mod offset;
+mod oncalendar;
+mod options;
+mod random;
+mod resolution;
 mod schedule;
+mod schedule_map;
+mod schedule_set;
+#[cfg(feature = "scheduler")]
+pub mod scheduler;
+mod spec;
+#[cfg(feature = "stream")]
+mod stream;
+mod subset;
+mod throttle;
+mod ticks;
+#[cfg(feature = "time")]
+mod time_backend;
+mod tokens;
+mod tz;
+mod verify;
+mod wait;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(any(feature = "axum", feature = "actix"))]
+pub mod web;
+#[cfg(feature = "tz")]
+mod zoned;
 
 #[doc(inline)]
-pub use errors::ParseScheduleError;
+pub use bounded::Bounded;
+#[doc(inline)]
+pub use builder::{FieldValues, ScheduleBuilder};
+#[doc(inline)]
+pub use capabilities::Capabilities;
+#[doc(inline)]
+pub use convert::DropPolicy;
+#[doc(inline)]
+pub use cursor::ScheduleCursor;
+
+#[doc(inline)]
+pub use errors::{
+    ConversionError, DecodeError, ExhaustionReason, Field, OnCalendarError, ParseScheduleError,
+    SpannedParseError,
+};
+#[doc(inline)]
+pub use except::Excluding;
+#[doc(inline)]
+pub use format::{CronFormat, DayOfWeekNumbering};
+#[doc(inline)]
+pub use jitter::Jittered;
+#[doc(inline)]
+pub use locale::Locale;
+#[doc(inline)]
+pub use merge::merge_iter;
+#[doc(inline)]
+pub use mux::{ScheduleMux, TieBreak};
+#[doc(inline)]
+pub use occurrences::OccurrenceStats;
+#[doc(inline)]
+pub use offset::Offset;
+#[doc(inline)]
+pub use options::{AmbiguousTimePolicy, DaySemantics, DstGapPolicy, ParseOptions};
 
+#[doc(inline)]
+pub use resolution::Resolution;
 #[doc(inline)]
 pub use schedule::Schedule;
+#[doc(inline)]
+pub use schedule_map::{ScheduleMap, ScheduleMapChange, ScheduleSnapshot};
+#[doc(inline)]
+pub use schedule_set::ScheduleSet;
+#[doc(inline)]
+pub use spec::ScheduleSpec;
+#[cfg(feature = "stream")]
+#[doc(inline)]
+pub use stream::ScheduleStream;
+#[doc(inline)]
+pub use throttle::{ThrottlePolicy, Throttled};
+#[doc(inline)]
+pub use tz::{ProvidedOffset, ProvidedTz, TimeZoneProvider, TransitionZone, ZoneRegistry};
+#[doc(inline)]
+pub use verify::{Divergence, SearchDirection};
+#[cfg(feature = "tz")]
+#[doc(inline)]
+pub use zoned::ZonedSchedule;
@@ -0,0 +1,356 @@
+/// The language of schedule descriptions and of the names accepted by `ParseOptions::locale`,
+/// languages other than English are each enabled by their `locale-*` feature.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Locale {
+    /// English.
+    #[default]
+    En,
+    /// German, with the `locale-de` feature.
+    #[cfg(feature = "locale-de")]
+    De,
+    /// French, with the `locale-fr` feature.
+    #[cfg(feature = "locale-fr")]
+    Fr,
+    /// Spanish, with the `locale-es` feature.
+    #[cfg(feature = "locale-es")]
+    Es,
+}
+
+impl Locale {
+    pub(crate) fn phrases(self) -> &'static Phrases {
+        match self {
+            Locale::En => &EN,
+            #[cfg(feature = "locale-de")]
+            Locale::De => &DE,
+            #[cfg(feature = "locale-fr")]
+            Locale::Fr => &FR,
+            #[cfg(feature = "locale-es")]
+            Locale::Es => &ES,
+        }
+    }
+}
+
+/// The phrases of a description for the seconds or minutes field, `{}` placeholders are
+/// filled in order.
+pub(crate) struct UnitPhrases {
+    pub(crate) every: &'static str,
+    pub(crate) every_n: &'static str,
+    pub(crate) every_n_from: &'static str,
+    pub(crate) at_one: &'static str,
+    pub(crate) at_many: &'static str,
+}
+
+/// The translation table of a description, `{}` placeholders are filled in order.
+pub(crate) struct Phrases {
+    pub(crate) months: [&'static str; 12],
+    pub(crate) weekdays: [&'static str; 7],
+    pub(crate) ordinals: [&'static str; 5],
+    pub(crate) and: &'static str,
+    pub(crate) through: &'static str,
+    pub(crate) reboot: &'static str,
+    pub(crate) at: &'static str,
+    pub(crate) seconds: UnitPhrases,
+    pub(crate) minutes: UnitPhrases,
+    pub(crate) minutes_past_hour: &'static str,
+    pub(crate) every_n_hours: &'static str,
+    pub(crate) every_n_hours_from: &'static str,
+    pub(crate) between_hours: &'static str,
+    pub(crate) day: &'static str,
+    pub(crate) last_day: &'static str,
+    pub(crate) one_day_before_last: &'static str,
+    pub(crate) days_before_last: &'static str,
+    pub(crate) nearest_weekday: &'static str,
+    pub(crate) last_weekday: &'static str,
+    pub(crate) on_days_of_month: &'static str,
+    pub(crate) on_days_in: &'static str,
+    pub(crate) only_in: &'static str,
+    pub(crate) only_on: &'static str,
+    pub(crate) or_on: &'static str,
+    pub(crate) last_weekday_of_month: &'static str,
+    pub(crate) nth_weekday_of_month: &'static str,
+    pub(crate) every_n_years_from: &'static str,
+    pub(crate) in_zone: &'static str,
+}
+
+/// Replaces the `{}` placeholders of the template with the arguments in order.
+pub(crate) fn fill(template: &str, args: &[&str]) -> String {
+    let mut s = String::new();
+    let mut args = args.iter();
+    let mut parts = template.split("{}");
+    if let Some(first) = parts.next() {
+        s.push_str(first);
+    }
+    for part in parts {
+        s.push_str(args.next().copied().unwrap_or_default());
+        s.push_str(part);
+    }
+    s
+}
+
+const EN: Phrases = Phrases {
+    months: [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ],
+    weekdays: [
+        "Sunday",
+        "Monday",
+        "Tuesday",
+        "Wednesday",
+        "Thursday",
+        "Friday",
+        "Saturday",
+    ],
+    ordinals: ["first", "second", "third", "fourth", "fifth"],
+    and: " and ",
+    through: "{} through {}",
+    reboot: "at reboot",
+    at: "at {}",
+    seconds: UnitPhrases {
+        every: "every second",
+        every_n: "every {} seconds",
+        every_n_from: "every {} seconds starting at second {}",
+        at_one: "at second {}",
+        at_many: "at seconds {}",
+    },
+    minutes: UnitPhrases {
+        every: "every minute",
+        every_n: "every {} minutes",
+        every_n_from: "every {} minutes starting at minute {}",
+        at_one: "at minute {}",
+        at_many: "at minutes {}",
+    },
+    minutes_past_hour: "at {} minutes past the hour",
+    every_n_hours: "every {} hours",
+    every_n_hours_from: "every {} hours starting at {}",
+    between_hours: "between {} and {}",
+    day: "day {}",
+    last_day: "the last day",
+    one_day_before_last: "1 day before the last day",
+    days_before_last: "{} days before the last day",
+    nearest_weekday: "the weekday nearest day {}",
+    last_weekday: "the last weekday",
+    on_days_of_month: " on {} of the month",
+    on_days_in: " on {} of {}",
+    only_in: ", only in {}",
+    only_on: ", only on {}",
+    or_on: ", or on {}",
+    last_weekday_of_month: "the last {} of the month",
+    nth_weekday_of_month: "the {} {} of the month",
+    every_n_years_from: ", every {} years starting in {}",
+    in_zone: " in {}",
+};
+
+#[cfg(feature = "locale-de")]
+const DE: Phrases = Phrases {
+    months: [
+        "Januar",
+        "Februar",
+        "März",
+        "April",
+        "Mai",
+        "Juni",
+        "Juli",
+        "August",
+        "September",
+        "Oktober",
+        "November",
+        "Dezember",
+    ],
+    weekdays: [
+        "Sonntag",
+        "Montag",
+        "Dienstag",
+        "Mittwoch",
+        "Donnerstag",
+        "Freitag",
+        "Samstag",
+    ],
+    ordinals: ["ersten", "zweiten", "dritten", "vierten", "fünften"],
+    and: " und ",
+    through: "{} bis {}",
+    reboot: "beim Systemstart",
+    at: "um {}",
+    seconds: UnitPhrases {
+        every: "jede Sekunde",
+        every_n: "alle {} Sekunden",
+        every_n_from: "alle {} Sekunden ab Sekunde {}",
+        at_one: "in Sekunde {}",
+        at_many: "in den Sekunden {}",
+    },
+    minutes: UnitPhrases {
+        every: "jede Minute",
+        every_n: "alle {} Minuten",
+        every_n_from: "alle {} Minuten ab Minute {}",
+        at_one: "in Minute {}",
+        at_many: "in den Minuten {}",
+    },
+    minutes_past_hour: "{} Minuten nach jeder vollen Stunde",
+    every_n_hours: "alle {} Stunden",
+    every_n_hours_from: "alle {} Stunden ab {}",
+    between_hours: "zwischen {} und {}",
+    day: "Tag {}",
+    last_day: "letzten Tag",
+    one_day_before_last: "1 Tag vor dem letzten Tag",
+    days_before_last: "{} Tage vor dem letzten Tag",
+    nearest_weekday: "Werktag nächst Tag {}",
+    last_weekday: "letzten Werktag",
+    on_days_of_month: " am {} des Monats",
+    on_days_in: " am {} im {}",
+    only_in: ", nur im {}",
+    only_on: ", nur am {}",
+    or_on: ", oder am {}",
+    last_weekday_of_month: "letzten {} des Monats",
+    nth_weekday_of_month: "{} {} des Monats",
+    every_n_years_from: ", alle {} Jahre ab {}",
+    in_zone: " in {}",
+};
+
+#[cfg(feature = "locale-fr")]
+const FR: Phrases = Phrases {
+    months: [
+        "janvier",
+        "février",
+        "mars",
+        "avril",
+        "mai",
+        "juin",
+        "juillet",
+        "août",
+        "septembre",
+        "octobre",
+        "novembre",
+        "décembre",
+    ],
+    weekdays: [
+        "dimanche", "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi",
+    ],
+    ordinals: ["premier", "deuxième", "troisième", "quatrième", "cinquième"],
+    and: " et ",
+    through: "{} à {}",
+    reboot: "au démarrage",
+    at: "à {}",
+    seconds: UnitPhrases {
+        every: "chaque seconde",
+        every_n: "toutes les {} secondes",
+        every_n_from: "toutes les {} secondes à partir de la seconde {}",
+        at_one: "à la seconde {}",
+        at_many: "aux secondes {}",
+    },
+    minutes: UnitPhrases {
+        every: "chaque minute",
+        every_n: "toutes les {} minutes",
+        every_n_from: "toutes les {} minutes à partir de la minute {}",
+        at_one: "à la minute {}",
+        at_many: "aux minutes {}",
+    },
+    minutes_past_hour: "à {} minutes après l'heure",
+    every_n_hours: "toutes les {} heures",
+    every_n_hours_from: "toutes les {} heures à partir de {}",
+    between_hours: "entre {} et {}",
+    day: "jour {}",
+    last_day: "dernier jour",
+    one_day_before_last: "1 jour avant le dernier jour",
+    days_before_last: "{} jours avant le dernier jour",
+    nearest_weekday: "jour ouvré le plus proche du jour {}",
+    last_weekday: "dernier jour ouvré",
+    on_days_of_month: " le {} du mois",
+    on_days_in: " le {} de {}",
+    only_in: ", uniquement en {}",
+    only_on: ", uniquement le {}",
+    or_on: ", ou le {}",
+    last_weekday_of_month: "dernier {} du mois",
+    nth_weekday_of_month: "{} {} du mois",
+    every_n_years_from: ", tous les {} ans à partir de {}",
+    in_zone: " ({})",
+};
+
+#[cfg(feature = "locale-es")]
+const ES: Phrases = Phrases {
+    months: [
+        "enero",
+        "febrero",
+        "marzo",
+        "abril",
+        "mayo",
+        "junio",
+        "julio",
+        "agosto",
+        "septiembre",
+        "octubre",
+        "noviembre",
+        "diciembre",
+    ],
+    weekdays: [
+        "domingo",
+        "lunes",
+        "martes",
+        "miércoles",
+        "jueves",
+        "viernes",
+        "sábado",
+    ],
+    ordinals: ["primer", "segundo", "tercer", "cuarto", "quinto"],
+    and: " y ",
+    through: "{} a {}",
+    reboot: "al reiniciar",
+    at: "a las {}",
+    seconds: UnitPhrases {
+        every: "cada segundo",
+        every_n: "cada {} segundos",
+        every_n_from: "cada {} segundos a partir del segundo {}",
+        at_one: "en el segundo {}",
+        at_many: "en los segundos {}",
+    },
+    minutes: UnitPhrases {
+        every: "cada minuto",
+        every_n: "cada {} minutos",
+        every_n_from: "cada {} minutos a partir del minuto {}",
+        at_one: "en el minuto {}",
+        at_many: "en los minutos {}",
+    },
+    minutes_past_hour: "a los {} minutos de cada hora",
+    every_n_hours: "cada {} horas",
+    every_n_hours_from: "cada {} horas a partir de las {}",
+    between_hours: "entre las {} y las {}",
+    day: "día {}",
+    last_day: "último día",
+    one_day_before_last: "1 día antes del último día",
+    days_before_last: "{} días antes del último día",
+    nearest_weekday: "día laborable más cercano al día {}",
+    last_weekday: "último día laborable",
+    on_days_of_month: " el {} del mes",
+    on_days_in: " el {} de {}",
+    only_in: ", solo en {}",
+    only_on: ", solo el {}",
+    or_on: ", o el {}",
+    last_weekday_of_month: "último {} del mes",
+    nth_weekday_of_month: "{} {} del mes",
+    every_n_years_from: ", cada {} años a partir de {}",
+    in_zone: " ({})",
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fill_placeholders() {
+        assert_eq!("every 5 minutes", fill("every {} minutes", &["5"]));
+        assert_eq!(
+            "between 09:00 and 17:59",
+            fill(EN.between_hours, &["09:00", "17:59"])
+        );
+        assert_eq!("at reboot", fill(EN.reboot, &[]));
+    }
+}
@@ -0,0 +1,103 @@
+use crate::schedule::Schedule;
+use chrono::{DateTime, TimeZone};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Iterates the occurrences of all the schedules after the supplied datetime in chronological
+/// order along with the tag of the schedule, e.g. for an agenda of several jobs.
+///
+/// The next occurrence of each schedule is kept in a binary heap so each step costs a logarithm
+/// of the number of schedules. Schedules firing at the same instant are yielded in the order
+/// they were supplied, see `ScheduleMux` for other tie breaks.
+/// ```rust
+/// use chrono::{TimeZone, Timelike, Utc};
+/// use cron_exp::{merge_iter, Schedule};
+/// use std::str::FromStr;
+///
+/// let report = Schedule::from_str("0 9 * * *").unwrap();
+/// let backup = Schedule::from_str("0 */3 * * *").unwrap();
+/// let from = Utc.with_ymd_and_hms(2022, 6, 1, 7, 0, 0).unwrap();
+/// let agenda: Vec<(u32, &str)> = merge_iter(&[(&report, "report"), (&backup, "backup")], &from)
+///     .take(3)
+///     .map(|(dt, tag)| (dt.hour(), tag))
+///     .collect();
+/// assert_eq!(vec![(9, "report"), (9, "backup"), (12, "backup")], agenda);
+/// ```
+pub fn merge_iter<'a, T, Z>(
+    schedules: &[(&'a Schedule, T)],
+    from: &DateTime<Z>,
+) -> impl Iterator<Item = (DateTime<Z>, T)> + 'a
+where
+    T: Clone + 'a,
+    Z: TimeZone + 'a,
+{
+    let heads = schedules
+        .iter()
+        .enumerate()
+        .filter_map(|(i, (schedule, _))| schedule.after(from).map(|next| Reverse((next, i))))
+        .collect();
+    MergeIterator {
+        schedules: schedules.to_vec(),
+        heads,
+    }
+}
+
+struct MergeIterator<'a, T, Z>
+where
+    Z: TimeZone,
+{
+    schedules: Vec<(&'a Schedule, T)>,
+    /// The next occurrence of each schedule that has one and its index, earliest first.
+    heads: BinaryHeap<Reverse<(DateTime<Z>, usize)>>,
+}
+
+impl<T, Z> Iterator for MergeIterator<'_, T, Z>
+where
+    T: Clone,
+    Z: TimeZone,
+{
+    type Item = (DateTime<Z>, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((next, i)) = self.heads.pop()?;
+        let (schedule, tag) = &self.schedules[i];
+        if let Some(after) = schedule.after(&next) {
+            self.heads.push(Reverse((after, i)));
+        }
+        Some((next, tag.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::str::FromStr;
+
+    #[test]
+    fn chronological_order() {
+        let hourly = Schedule::from_str("0 * * * *").unwrap();
+        let every_two_hours = Schedule::from_str("0 */2 * * *").unwrap();
+        let once = Schedule::from_str("0 30 0 1 6 * 2022").unwrap();
+        let from = Utc.with_ymd_and_hms(2022, 5, 31, 23, 30, 0).unwrap();
+        let at = |h, m| Utc.with_ymd_and_hms(2022, 6, 1, h, m, 0).unwrap();
+
+        let merged: Vec<_> = merge_iter(&[(&hourly, 1), (&every_two_hours, 2), (&once, 3)], &from)
+            .take(6)
+            .collect();
+        assert_eq!(
+            vec![
+                (at(0, 0), 1),
+                (at(0, 0), 2),
+                (at(0, 30), 3),
+                (at(1, 0), 1),
+                (at(2, 0), 1),
+                (at(2, 0), 2)
+            ],
+            merged
+        );
+
+        assert_eq!(0, merge_iter::<(), _>(&[], &from).count());
+        assert_eq!(1, merge_iter(&[(&once, ())], &from).count());
+    }
+}
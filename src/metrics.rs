@@ -0,0 +1,191 @@
+//! Prometheus metrics describing scheduled jobs.
+//!
+//! Every metric is labelled by `job` so a single `SchedulerMetrics` instance can be shared by
+//! all jobs of a scheduler.
+use chrono::{DateTime, TimeZone};
+use prometheus::{GaugeVec, IntCounterVec, Opts, Registry};
+
+#[cfg(feature = "scheduler")]
+use crate::scheduler::{JobEvent, JobListener, SkipReason};
+
+const JOB_LABEL: &str = "job";
+
+/// The per job gauges and counters reported by a scheduler.
+#[derive(Debug, Clone)]
+pub struct SchedulerMetrics {
+    next_run_timestamp_seconds: GaugeVec,
+    runs_total: IntCounterVec,
+    misses_total: IntCounterVec,
+    overlaps_skipped_total: IntCounterVec,
+}
+
+impl SchedulerMetrics {
+    /// Creates the metrics without a namespace, e.g. `runs_total`.
+    pub fn new() -> Result<Self, prometheus::Error> {
+        Self::with_namespace("")
+    }
+
+    /// Creates the metrics prefixed with the supplied namespace, e.g. `cron_runs_total`.
+    pub fn with_namespace(namespace: &str) -> Result<Self, prometheus::Error> {
+        let opts = |name: &str, help: &str| Opts::new(name, help).namespace(namespace);
+
+        Ok(SchedulerMetrics {
+            next_run_timestamp_seconds: GaugeVec::new(
+                opts(
+                    "next_run_timestamp_seconds",
+                    "Unix time of the next scheduled run of the job, in seconds.",
+                ),
+                &[JOB_LABEL],
+            )?,
+            runs_total: IntCounterVec::new(
+                opts("runs_total", "Total number of times the job was run."),
+                &[JOB_LABEL],
+            )?,
+            misses_total: IntCounterVec::new(
+                opts(
+                    "misses_total",
+                    "Total number of scheduled runs of the job that were missed.",
+                ),
+                &[JOB_LABEL],
+            )?,
+            overlaps_skipped_total: IntCounterVec::new(
+                opts(
+                    "overlaps_skipped_total",
+                    "Total number of runs skipped because the previous run was still in progress.",
+                ),
+                &[JOB_LABEL],
+            )?,
+        })
+    }
+
+    /// Registers all metrics with the supplied registry.
+    pub fn register(&self, registry: &Registry) -> Result<(), prometheus::Error> {
+        registry.register(Box::new(self.next_run_timestamp_seconds.clone()))?;
+        registry.register(Box::new(self.runs_total.clone()))?;
+        registry.register(Box::new(self.misses_total.clone()))?;
+        registry.register(Box::new(self.overlaps_skipped_total.clone()))?;
+        Ok(())
+    }
+
+    /// Records the next time the job is scheduled to run, as a timestamp so the time left until
+    /// then can be computed when queried, e.g. `next_run_timestamp_seconds - time()` in PromQL.
+    pub fn observe_next_run<Z>(&self, job: &str, next: &DateTime<Z>)
+    where
+        Z: TimeZone,
+    {
+        self.next_run_timestamp_seconds
+            .with_label_values(&[job])
+            .set(next.timestamp_millis() as f64 / 1000.0);
+    }
+
+    /// Increments the number of runs of the job.
+    pub fn record_run(&self, job: &str) {
+        self.runs_total.with_label_values(&[job]).inc();
+    }
+
+    /// Increments the number of missed runs of the job.
+    pub fn record_miss(&self, job: &str) {
+        self.misses_total.with_label_values(&[job]).inc();
+    }
+
+    /// Increments the number of runs of the job skipped due to an overlapping run.
+    pub fn record_overlap_skipped(&self, job: &str) {
+        self.overlaps_skipped_total.with_label_values(&[job]).inc();
+    }
+
+    /// Removes all series of a job, to be called when the job is unregistered.
+    pub fn remove_job(&self, job: &str) {
+        let _ = self.next_run_timestamp_seconds.remove_label_values(&[job]);
+        let _ = self.runs_total.remove_label_values(&[job]);
+        let _ = self.misses_total.remove_label_values(&[job]);
+        let _ = self.overlaps_skipped_total.remove_label_values(&[job]);
+    }
+}
+
+#[cfg(feature = "scheduler")]
+impl JobListener for SchedulerMetrics {
+    fn on_scheduled(&self, event: &JobEvent) {
+        self.observe_next_run(&event.job, &event.scheduled_time);
+    }
+
+    fn on_start(&self, event: &JobEvent) {
+        self.record_run(&event.job);
+    }
+
+    fn on_skip(&self, event: &JobEvent, reason: SkipReason) {
+        if reason == SkipReason::Overlap {
+            self.record_overlap_skipped(&event.job);
+        }
+    }
+
+    fn on_miss(&self, event: &JobEvent) {
+        self.record_miss(&event.job);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn record_metrics() {
+        let registry = Registry::new();
+        let metrics = SchedulerMetrics::with_namespace("cron").unwrap();
+        metrics.register(&registry).unwrap();
+
+        let next = Utc.with_ymd_and_hms(2021, 2, 1, 1, 2, 30).unwrap();
+        metrics.observe_next_run("backup", &next);
+        metrics.record_run("backup");
+        metrics.record_run("backup");
+        metrics.record_miss("backup");
+
+        assert_eq!(
+            metrics
+                .next_run_timestamp_seconds
+                .with_label_values(&["backup"])
+                .get(),
+            1612141350.0
+        );
+        assert_eq!(metrics.runs_total.with_label_values(&["backup"]).get(), 2);
+        assert_eq!(metrics.misses_total.with_label_values(&["backup"]).get(), 1);
+
+        let names: Vec<String> = registry
+            .gather()
+            .iter()
+            .map(|family| family.name().to_string())
+            .collect();
+        assert!(names.contains(&"cron_runs_total".to_string()));
+    }
+
+    #[cfg(feature = "scheduler")]
+    #[tokio::test(start_paused = true)]
+    async fn listen_to_a_scheduler() {
+        use crate::scheduler::testing::{start, TokioClock};
+        use crate::scheduler::{Listeners, Scheduler};
+        use crate::Schedule;
+        use std::str::FromStr;
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        let metrics = Arc::new(SchedulerMetrics::new().unwrap());
+        let mut listeners = Listeners::new();
+        listeners.register(metrics.clone());
+        let scheduler =
+            Scheduler::with_listeners(listeners).with_clock(Arc::new(TokioClock::new()));
+        let every_second = Schedule::from_str("* * * * * *").unwrap();
+        scheduler.add("tick", every_second, || async {});
+
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        scheduler.shutdown();
+        assert_eq!(metrics.runs_total.with_label_values(&["tick"]).get(), 1);
+        let next_run = start() + chrono::Duration::seconds(2);
+        assert_eq!(
+            metrics
+                .next_run_timestamp_seconds
+                .with_label_values(&["tick"])
+                .get(),
+            next_run.timestamp() as f64
+        );
+    }
+}
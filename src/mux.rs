@@ -0,0 +1,226 @@
+use crate::schedule::Schedule;
+use chrono::{DateTime, TimeZone};
+use std::collections::VecDeque;
+
+/// Determines the order of schedules in a `ScheduleMux` firing at the same instant.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TieBreak {
+    /// In the order the schedules were added.
+    InsertionOrder,
+    /// Highest priority first, then in the order the schedules were added.
+    Priority,
+    /// In ascending order of label, independent of the order the schedules were added so
+    /// replicas loading them in a different order still agree.
+    Label,
+}
+
+#[derive(Debug, Clone)]
+struct MuxEntry<L> {
+    label: L,
+    schedule: Schedule,
+    priority: i32,
+}
+
+/// Multiplexes labelled schedules into a single chronological stream of occurrences, ordering
+/// simultaneous occurrences by a `TieBreak` policy so dispatch is deterministic.
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use cron_exp::{Schedule, ScheduleMux, TieBreak};
+/// use std::str::FromStr;
+///
+/// let mut mux = ScheduleMux::new(TieBreak::Priority);
+/// mux.add_with_priority("report", Schedule::from_str("0 9 * * *").unwrap(), 0);
+/// mux.add_with_priority("backup", Schedule::from_str("0 */3 * * *").unwrap(), 10);
+///
+/// let from = Utc.with_ymd_and_hms(2022, 6, 1, 7, 0, 0).unwrap();
+/// let fired: Vec<&str> = mux.iter_from(&from).take(3).map(|(_, label)| *label).collect();
+/// assert_eq!(vec!["backup", "report", "backup"], fired);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ScheduleMux<L> {
+    entries: Vec<MuxEntry<L>>,
+    tie_break: TieBreak,
+}
+
+impl<L> ScheduleMux<L>
+where
+    L: Ord,
+{
+    /// Creates an empty mux using the supplied tie break policy.
+    pub fn new(tie_break: TieBreak) -> Self {
+        ScheduleMux {
+            entries: Vec::new(),
+            tie_break,
+        }
+    }
+
+    /// Adds a schedule with the default priority of `0`.
+    pub fn add(&mut self, label: L, schedule: Schedule) {
+        self.add_with_priority(label, schedule, 0);
+    }
+
+    /// Adds a schedule with the supplied priority, only used by `TieBreak::Priority`.
+    pub fn add_with_priority(&mut self, label: L, schedule: Schedule, priority: i32) {
+        self.entries.push(MuxEntry {
+            label,
+            schedule,
+            priority,
+        });
+    }
+
+    /// Returns the number of schedules.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if there are no schedules.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the next instant after the supplied datetime at which any schedule fires along
+    /// with the labels of all schedules firing then in tie break order.
+    pub fn next_after<Z>(&self, dt: &DateTime<Z>) -> Option<(DateTime<Z>, Vec<&L>)>
+    where
+        Z: TimeZone,
+    {
+        let heads: Vec<Option<DateTime<Z>>> =
+            self.entries.iter().map(|e| e.schedule.after(dt)).collect();
+        let next = heads.iter().flatten().min()?.clone();
+        let labels = self
+            .simultaneous(&heads, &next)
+            .into_iter()
+            .map(|i| &self.entries[i].label)
+            .collect();
+        Some((next, labels))
+    }
+
+    /// Iterates the occurrences of all schedules after the supplied datetime in chronological
+    /// order, yielding each schedule firing at the same instant in tie break order.
+    pub fn iter_from<'a, Z>(
+        &'a self,
+        dt: &DateTime<Z>,
+    ) -> impl Iterator<Item = (DateTime<Z>, &'a L)> + 'a
+    where
+        Z: TimeZone + 'a,
+    {
+        MuxIterator {
+            mux: self,
+            heads: self.entries.iter().map(|e| e.schedule.after(dt)).collect(),
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Returns the indexes of the entries whose head is `at` in tie break order.
+    fn simultaneous<Z>(&self, heads: &[Option<DateTime<Z>>], at: &DateTime<Z>) -> Vec<usize>
+    where
+        Z: TimeZone,
+    {
+        let mut indexes: Vec<usize> = heads
+            .iter()
+            .enumerate()
+            .filter(|(_, head)| head.as_ref() == Some(at))
+            .map(|(i, _)| i)
+            .collect();
+        match self.tie_break {
+            TieBreak::InsertionOrder => {}
+            TieBreak::Priority => {
+                indexes.sort_by_key(|i| std::cmp::Reverse(self.entries[*i].priority))
+            }
+            TieBreak::Label => indexes.sort_by(|a, b| {
+                self.entries[*a]
+                    .label
+                    .cmp(&self.entries[*b].label)
+                    .then(a.cmp(b))
+            }),
+        }
+        indexes
+    }
+}
+
+struct MuxIterator<'a, L, Z>
+where
+    Z: TimeZone,
+{
+    mux: &'a ScheduleMux<L>,
+    heads: Vec<Option<DateTime<Z>>>,
+    pending: VecDeque<(DateTime<Z>, &'a L)>,
+}
+
+impl<'a, L, Z> Iterator for MuxIterator<'a, L, Z>
+where
+    L: Ord,
+    Z: TimeZone,
+{
+    type Item = (DateTime<Z>, &'a L);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(item) = self.pending.pop_front() {
+            return Some(item);
+        }
+
+        let next = self.heads.iter().flatten().min()?.clone();
+        for i in self.mux.simultaneous(&self.heads, &next) {
+            let entry = &self.mux.entries[i];
+            self.pending.push_back((next.clone(), &entry.label));
+            self.heads[i] = entry.schedule.after(&next);
+        }
+        self.pending.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::str::FromStr;
+
+    fn mux(tie_break: TieBreak) -> ScheduleMux<&'static str> {
+        let mut mux = ScheduleMux::new(tie_break);
+        mux.add_with_priority("c", Schedule::from_str("0 * * * *").unwrap(), 1);
+        mux.add_with_priority("a", Schedule::from_str("0 */2 * * *").unwrap(), 5);
+        mux.add_with_priority("b", Schedule::from_str("0 0 * * *").unwrap(), 3);
+        mux
+    }
+
+    #[test]
+    fn tie_break() {
+        let from = Utc.with_ymd_and_hms(2022, 5, 31, 23, 30, 0).unwrap();
+        let midnight = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap();
+
+        assert_eq!(
+            Some((midnight, vec![&"c", &"a", &"b"])),
+            mux(TieBreak::InsertionOrder).next_after(&from)
+        );
+        assert_eq!(
+            Some((midnight, vec![&"a", &"b", &"c"])),
+            mux(TieBreak::Priority).next_after(&from)
+        );
+        assert_eq!(
+            Some((midnight, vec![&"a", &"b", &"c"])),
+            mux(TieBreak::Label).next_after(&from)
+        );
+
+        let mux = mux(TieBreak::Priority);
+        let fired: Vec<(u32, &str)> = mux
+            .iter_from(&from)
+            .take(6)
+            .map(|(dt, label)| (chrono::Timelike::hour(&dt), *label))
+            .collect();
+        assert_eq!(
+            vec![(0, "a"), (0, "b"), (0, "c"), (1, "c"), (2, "a"), (2, "c")],
+            fired
+        );
+    }
+
+    #[test]
+    fn finished_schedules() {
+        let mut mux = ScheduleMux::new(TieBreak::InsertionOrder);
+        mux.add("once", Schedule::from_str("0 0 0 1 1 * 2030").unwrap());
+        let from = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap();
+        assert_eq!(1, mux.iter_from(&from).count());
+        assert!(ScheduleMux::<String>::new(TieBreak::Label)
+            .next_after(&from)
+            .is_none());
+    }
+}
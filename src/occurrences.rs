@@ -0,0 +1,515 @@
+use crate::schedule::{Schedule, Years, HORIZON_YEARS, MAX_YEAR};
+use chrono::{DateTime, Datelike, NaiveDate, Offset, TimeZone, Utc};
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+/// Returns whether the UTC offset is the same throughout the day, so every local time of the
+/// day exists once.
+fn fixed_offset<Z>(tz: &Z, date: NaiveDate) -> bool
+where
+    Z: TimeZone,
+{
+    let offset = |date: NaiveDate| {
+        tz.from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+            .single()
+            .map(|dt| dt.offset().fix())
+    };
+    match (offset(date), date.succ_opt().and_then(offset)) {
+        (Some(start), Some(end)) => start == end,
+        _ => false,
+    }
+}
+
+/// Occurrence totals of a schedule within a range, broken down by local year and month.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct OccurrenceStats {
+    /// The total number of occurrences.
+    pub total: u64,
+    /// The number of occurrences per year.
+    pub by_year: BTreeMap<i32, u64>,
+    /// The number of occurrences per year and month, months numbered from 1.
+    pub by_month: BTreeMap<(i32, u32), u64>,
+}
+
+impl OccurrenceStats {
+    /// Returns the number of occurrences per month of the year summed across all years,
+    /// indexed from January, to show seasonal load.
+    pub fn by_month_of_year(&self) -> [u64; 12] {
+        let mut totals = [0; 12];
+        for ((_, month), count) in &self.by_month {
+            totals[*month as usize - 1] += count;
+        }
+        totals
+    }
+}
+
+impl Schedule {
+    /// Returns the zero based `page` of `per_page` occurrences after `from`.
+    ///
+    /// The pages are stable for a fixed `from` so endpoints listing upcoming runs can paginate
+    /// by passing the same `from` with each request rather than holding an iterator across
+    /// requests.
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 9 * * *").unwrap();
+    /// let from = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap();
+    ///
+    /// let page = schedule.page(&from, 1, 2);
+    /// assert_eq!(
+    ///     vec![
+    ///         Utc.with_ymd_and_hms(2022, 6, 3, 9, 0, 0).unwrap(),
+    ///         Utc.with_ymd_and_hms(2022, 6, 4, 9, 0, 0).unwrap(),
+    ///     ],
+    ///     page
+    /// );
+    /// ```
+    pub fn page<Z>(&self, from: &DateTime<Z>, page: usize, per_page: usize) -> Vec<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        self.iter_from(from)
+            .skip(page.saturating_mul(per_page))
+            .take(per_page)
+            .collect()
+    }
+
+    /// Returns the `n`th occurrence strictly after `from`, counting from zero as `Iterator::nth`
+    /// so `nth_after(&from, 0)` is the next occurrence.
+    ///
+    /// Whole days of occurrences are counted rather than searched for, so far away occurrences
+    /// of frequent schedules are found without stepping through every one of them. Days with a
+    /// UTC offset transition are still searched.
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("*/10 * * * * *").unwrap();
+    /// let from = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap();
+    /// assert_eq!(
+    ///     Some(Utc.with_ymd_and_hms(2022, 6, 11, 0, 0, 10).unwrap()),
+    ///     schedule.nth_after(&from, 86_400)
+    /// );
+    /// ```
+    pub fn nth_after<Z>(&self, from: &DateTime<Z>, n: usize) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        #[cfg(feature = "tz")]
+        if let Some(timezone) = self.timezone {
+            return self
+                .nth_after_in_zone(&from.with_timezone(&timezone), n)
+                .map(|found| found.with_timezone(&from.timezone()));
+        }
+        self.nth_after_in_zone(from, n)
+    }
+
+    fn nth_after_in_zone<Z>(&self, from: &DateTime<Z>, n: usize) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        let tz = from.timezone();
+        let times = self.times_of_day();
+        let last_time = *times.last()?;
+        let last_year = match &self.years {
+            Years::All => MAX_YEAR as i32,
+            Years::Constrained(years) => *years.iter().next_back()? as i32,
+            Years::Unbound => from.year().saturating_add(HORIZON_YEARS as i32),
+        };
+
+        let mut current = self.after(from)?;
+        let mut index = 0;
+        while index < n {
+            // an ambiguous local time may still occur again later the same day
+            let last_of_day = current.naive_local().time() == last_time
+                && tz
+                    .from_local_datetime(&current.naive_local())
+                    .latest()
+                    .as_ref()
+                    == Some(&current);
+            if last_of_day {
+                // count the occurrences of the following days without a transition
+                let mut skipped = None;
+                let mut date = current.date_naive().succ_opt()?;
+                while date.year() <= last_year {
+                    if !self.matches_date(&date) {
+                        date = date.succ_opt()?;
+                        continue;
+                    }
+                    if !fixed_offset(&tz, date) {
+                        break;
+                    }
+                    if n - index > times.len() {
+                        index += times.len();
+                        skipped = Some(date);
+                        date = date.succ_opt()?;
+                        continue;
+                    }
+                    let time = times[n - index - 1];
+                    return tz.from_local_datetime(&date.and_time(time)).single();
+                }
+                if date.year() > last_year {
+                    return None;
+                }
+                if let Some(skipped) = skipped {
+                    current = tz
+                        .from_local_datetime(&skipped.and_time(last_time))
+                        .single()?;
+                }
+            }
+            current = self.after(&current)?;
+            index += 1;
+        }
+        Some(current)
+    }
+
+    /// Returns the next `n` occurrences strictly after `from` in chronological order, fewer once
+    /// the schedule is exhausted.
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 9 * * *").unwrap();
+    /// let from = Utc.with_ymd_and_hms(2022, 6, 1, 12, 0, 0).unwrap();
+    /// assert_eq!(
+    ///     vec![
+    ///         Utc.with_ymd_and_hms(2022, 6, 2, 9, 0, 0).unwrap(),
+    ///         Utc.with_ymd_and_hms(2022, 6, 3, 9, 0, 0).unwrap(),
+    ///     ],
+    ///     schedule.upcoming_after(&from, 2)
+    /// );
+    /// assert_eq!(
+    ///     vec![Utc.with_ymd_and_hms(2022, 6, 1, 9, 0, 0).unwrap()],
+    ///     schedule.previous_before(&from, 1)
+    /// );
+    /// ```
+    pub fn upcoming_after<Z>(&self, from: &DateTime<Z>, n: usize) -> Vec<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        self.iter_after(from).take(n).collect()
+    }
+
+    /// Returns the last `n` occurrences strictly before `from` in reverse chronological order,
+    /// the mirror of `upcoming_after`.
+    pub fn previous_before<Z>(&self, from: &DateTime<Z>, n: usize) -> Vec<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        self.iter_before(from).take(n).collect()
+    }
+
+    /// Returns the next `n` occurrences after the current time in the supplied timezone in
+    /// chronological order.
+    /// ```rust
+    /// use chrono::Utc;
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 9 * * *").unwrap();
+    /// let upcoming = schedule.upcoming(3, &Utc);
+    /// assert_eq!(3, upcoming.len());
+    /// assert!(upcoming[0] > Utc::now());
+    /// ```
+    pub fn upcoming<Z>(&self, n: usize, tz: &Z) -> Vec<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        self.upcoming_after(&Utc::now().with_timezone(tz), n)
+    }
+
+    /// Returns the last `n` occurrences before the current time in the supplied timezone in
+    /// reverse chronological order, the mirror of `upcoming`, e.g. to reconcile the runs that
+    /// were expected against an execution log.
+    /// ```rust
+    /// use chrono::Utc;
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 9 * * *").unwrap();
+    /// let recent = schedule.recent(3, &Utc);
+    /// assert_eq!(3, recent.len());
+    /// assert!(recent[0] < Utc::now());
+    /// assert!(recent[1] < recent[0]);
+    /// ```
+    pub fn recent<Z>(&self, n: usize, tz: &Z) -> Vec<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        self.previous_before(&Utc::now().with_timezone(tz), n)
+    }
+
+    /// Counts the occurrences within the half open range by year and month of the range's
+    /// timezone, e.g. for capacity planning of seasonal schedules.
+    ///
+    /// Every occurrence is visited so the range should be kept proportionate to how often the
+    /// schedule fires.
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 9 * May-Aug Mon-Fri").unwrap();
+    /// let start = Utc.with_ymd_and_hms(2022, 1, 1, 0, 0, 0).unwrap();
+    /// let end = Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap();
+    ///
+    /// let stats = schedule.stats(start..end);
+    /// assert_eq!(88, stats.total);
+    /// assert_eq!(Some(&22), stats.by_month.get(&(2022, 5)));
+    /// assert_eq!(None, stats.by_month.get(&(2022, 9)));
+    /// ```
+    pub fn stats<Z>(&self, range: Range<DateTime<Z>>) -> OccurrenceStats
+    where
+        Z: TimeZone,
+    {
+        let mut stats = OccurrenceStats::default();
+        for dt in self.iter_between(&range.start, &range.end) {
+            stats.total += 1;
+            *stats.by_year.entry(dt.year()).or_insert(0) += 1;
+            *stats.by_month.entry((dt.year(), dt.month())).or_insert(0) += 1;
+        }
+        stats
+    }
+
+    /// Returns the first instant the schedule can ever fire in the supplied timezone, or `None`
+    /// when it has no years field constraining when it starts or never fires.
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 0 9 * * Mon 2030-2031").unwrap();
+    /// assert_eq!(
+    ///     Some(Utc.with_ymd_and_hms(2030, 1, 7, 9, 0, 0).unwrap()),
+    ///     schedule.earliest(&Utc)
+    /// );
+    /// assert_eq!(None, Schedule::from_str("0 9 * * Mon").unwrap().earliest(&Utc));
+    /// ```
+    pub fn earliest<Z>(&self, tz: &Z) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        let first = match &self.years {
+            Years::Constrained(years) => *years.iter().next()?,
+            Years::All | Years::Unbound => return None,
+        };
+        // a day before the year starts in any timezone
+        let before = NaiveDate::from_ymd_opt(first as i32 - 1, 12, 31)?.and_hms_opt(0, 0, 0)?;
+        self.after(&tz.from_utc_datetime(&before))
+    }
+
+    /// Returns the last instant the schedule can ever fire in the supplied timezone after which
+    /// it is permanently inactive, or `None` when it has no years field constraining when it
+    /// ends or never fires.
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 0 9 * * Mon 2030-2031").unwrap();
+    /// assert_eq!(
+    ///     Some(Utc.with_ymd_and_hms(2031, 12, 29, 9, 0, 0).unwrap()),
+    ///     schedule.latest(&Utc)
+    /// );
+    /// ```
+    pub fn latest<Z>(&self, tz: &Z) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        let last = match &self.years {
+            Years::Constrained(years) => *years.iter().next_back()?,
+            Years::All | Years::Unbound => return None,
+        };
+        // a day after the year ends in any timezone
+        let after = NaiveDate::from_ymd_opt(last as i32 + 1, 1, 2)?.and_hms_opt(0, 0, 0)?;
+        self.before(&tz.from_utc_datetime(&after))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AmbiguousTimePolicy;
+    use std::str::FromStr;
+
+    #[test]
+    fn page() {
+        let schedule = Schedule::from_str("0 0 0 1 * * 2022").unwrap();
+        let from = Utc.with_ymd_and_hms(2022, 1, 1, 0, 0, 0).unwrap();
+
+        let pages: Vec<Vec<DateTime<Utc>>> = (0..4).map(|p| schedule.page(&from, p, 5)).collect();
+        assert_eq!(5, pages[0].len());
+        assert_eq!(
+            Utc.with_ymd_and_hms(2022, 2, 1, 0, 0, 0).unwrap(),
+            pages[0][0]
+        );
+        assert_eq!(
+            Utc.with_ymd_and_hms(2022, 7, 1, 0, 0, 0).unwrap(),
+            pages[1][0]
+        );
+        // the final page is partial and the ones after it empty
+        assert_eq!(1, pages[2].len());
+        assert!(pages[3].is_empty());
+        assert!(schedule.page(&from, usize::MAX, usize::MAX).is_empty());
+    }
+
+    #[test]
+    fn upcoming_and_previous() {
+        let schedule = Schedule::from_str("0 0 0 1 * * 2022").unwrap();
+        let from = Utc.with_ymd_and_hms(2022, 11, 1, 0, 0, 0).unwrap();
+        let at = |m| Utc.with_ymd_and_hms(2022, m, 1, 0, 0, 0).unwrap();
+
+        assert_eq!(vec![at(12)], schedule.upcoming_after(&from, 3));
+        assert_eq!(
+            vec![at(10), at(9), at(8)],
+            schedule.previous_before(&from, 3)
+        );
+        assert!(schedule.upcoming_after(&from, 0).is_empty());
+    }
+
+    #[test]
+    fn nth_after() {
+        let from = Utc.with_ymd_and_hms(2022, 6, 1, 8, 40, 1).unwrap();
+        for expression in &[
+            "*/15 * * * * *",
+            "0 30 9,12,15 1,15 May-Aug Mon,Wed,Fri 2018/2",
+            "0 0 * * 1-5",
+            "*/90s * * * * *",
+            "0 0 0 29 2 * *",
+        ] {
+            let schedule = Schedule::from_str(expression).unwrap();
+            let expected: Vec<_> = schedule.iter_after(&from).take(200).collect();
+            for (n, occurrence) in expected.iter().enumerate() {
+                assert_eq!(
+                    Some(*occurrence),
+                    schedule.nth_after(&from, n),
+                    "{} {}",
+                    expression,
+                    n
+                );
+            }
+        }
+
+        let finished = Schedule::from_str("0 0 0 1 * * 2022").unwrap();
+        assert_eq!(None, finished.nth_after(&from, 6));
+        assert!(finished.nth_after(&from, 5).is_some());
+        assert_eq!(
+            None,
+            Schedule::from_str("@reboot").unwrap().nth_after(&from, 0)
+        );
+    }
+
+    #[test]
+    fn nth_after_across_transitions() {
+        let from = chrono_tz::Europe::Paris
+            .with_ymd_and_hms(2021, 3, 20, 0, 0, 0)
+            .unwrap();
+        for expression in &["0 30 2 * * *", "0 */20 * * * *", "0 30 1,2 * * *"] {
+            let schedule = Schedule::from_str(expression).unwrap();
+            let expected: Vec<_> = schedule.iter_after(&from).take(300).collect();
+            for (n, occurrence) in expected.iter().enumerate() {
+                assert_eq!(
+                    Some(*occurrence),
+                    schedule.nth_after(&from, n),
+                    "{} {}",
+                    expression,
+                    n
+                );
+            }
+        }
+
+        // both instances of a repeated local time when clocks fall back
+        let from = chrono_tz::America::New_York
+            .with_ymd_and_hms(2020, 10, 30, 0, 0, 0)
+            .unwrap();
+        let schedule = Schedule::from_str("30 1 * * *")
+            .unwrap()
+            .with_ambiguous_time_policy(AmbiguousTimePolicy::Both);
+        let expected: Vec<_> = schedule.iter_after(&from).take(20).collect();
+        assert_eq!(expected[2] + chrono::Duration::hours(1), expected[3]);
+        for (n, occurrence) in expected.iter().enumerate() {
+            assert_eq!(Some(*occurrence), schedule.nth_after(&from, n), "{}", n);
+        }
+    }
+
+    #[test]
+    fn recent() {
+        let schedule = Schedule::from_str("0 0 */6 * * *").unwrap();
+        let recent = schedule.recent(4, &chrono_tz::Asia::Tokyo);
+
+        assert_eq!(4, recent.len());
+        assert!(recent[0] < Utc::now());
+        for pair in recent.windows(2) {
+            assert_eq!(chrono::Duration::hours(6), pair[0] - pair[1]);
+        }
+
+        // a schedule that finished long ago still reports its last runs
+        let finished = Schedule::from_str("0 0 0 1 1 * 2020-2021").unwrap();
+        assert_eq!(
+            vec![
+                Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
+            ],
+            finished.recent(5, &Utc)
+        );
+    }
+
+    #[test]
+    fn stats() {
+        let schedule = Schedule::from_str("0 0 0 1 */4 * *").unwrap();
+        let start = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2022, 5, 1, 0, 0, 0).unwrap();
+
+        let stats = schedule.stats(start..end);
+        assert_eq!(4, stats.total);
+        assert_eq!(Some(&3), stats.by_year.get(&2021));
+        assert_eq!(Some(&1), stats.by_year.get(&2022));
+        assert_eq!(
+            [2, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0],
+            stats.by_month_of_year()
+        );
+
+        // local months of the range's timezone are used
+        let start = chrono_tz::America::New_York
+            .with_ymd_and_hms(2021, 1, 1, 0, 0, 0)
+            .unwrap();
+        let end = start + chrono::Duration::days(1);
+        let stats = Schedule::from_str("0 0 0 * * *").unwrap().stats(start..end);
+        assert_eq!(1, stats.total);
+        assert_eq!(Some(&1), stats.by_month.get(&(2021, 1)));
+    }
+
+    #[test]
+    fn earliest_and_latest() {
+        let schedule = Schedule::from_str("0 30 23 31 12 * 2025,2027").unwrap();
+        let tokyo = chrono_tz::Asia::Tokyo;
+        assert_eq!(
+            Some(tokyo.with_ymd_and_hms(2025, 12, 31, 23, 30, 0).unwrap()),
+            schedule.earliest(&tokyo)
+        );
+        assert_eq!(
+            Some(tokyo.with_ymd_and_hms(2027, 12, 31, 23, 30, 0).unwrap()),
+            schedule.latest(&tokyo)
+        );
+
+        let first_of_january = Schedule::from_str("0 0 0 1 1 * 2030").unwrap();
+        let honolulu = chrono_tz::Pacific::Honolulu;
+        assert_eq!(
+            first_of_january.earliest(&honolulu),
+            first_of_january.latest(&honolulu)
+        );
+        assert!(first_of_january.earliest(&honolulu).is_some());
+
+        let never = Schedule::from_str("0 0 0 30 2 * 2030").unwrap();
+        assert_eq!(None, never.earliest(&Utc));
+        assert_eq!(None, never.latest(&Utc));
+        assert_eq!(
+            None,
+            Schedule::from_str("0 0 0 1 1 *").unwrap().latest(&Utc)
+        );
+    }
+}
@@ -0,0 +1,120 @@
+use crate::schedule::Schedule;
+use chrono::{DateTime, Duration, TimeZone};
+
+/// A schedule whose occurrences are all shifted by a fixed duration, created with
+/// `Schedule::offset_by`, e.g. to run 5 minutes after each run of another team's schedule.
+///
+/// A negative offset fires before the occurrences instead.
+/// ```rust
+/// use chrono::{Duration, TimeZone, Utc};
+/// use cron_exp::Schedule;
+/// use std::str::FromStr;
+///
+/// let upstream = Schedule::from_str("0 * * * *").unwrap();
+/// let downstream = upstream.offset_by(Duration::minutes(5));
+///
+/// let from = Utc.with_ymd_and_hms(2022, 6, 1, 9, 2, 0).unwrap();
+/// let next: Vec<String> = downstream
+///     .iter_from(&from)
+///     .take(2)
+///     .map(|dt| dt.format("%H:%M").to_string())
+///     .collect();
+/// assert_eq!(vec!["09:05", "10:05"], next);
+/// assert!(downstream.includes(&Utc.with_ymd_and_hms(2022, 6, 1, 11, 5, 0).unwrap()));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Offset {
+    schedule: Schedule,
+    offset: Duration,
+}
+
+impl Schedule {
+    /// Returns the schedule with every occurrence shifted by `offset`, see `Offset`.
+    pub fn offset_by(&self, offset: Duration) -> Offset {
+        Offset {
+            schedule: self.clone(),
+            offset,
+        }
+    }
+}
+
+impl Offset {
+    /// Returns the wrapped schedule.
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    /// Returns the duration occurrences are shifted by.
+    pub fn offset(&self) -> Duration {
+        self.offset
+    }
+
+    /// Returns the next shifted occurrence strictly after the supplied datetime.
+    pub fn next_after<Z>(&self, dt: &DateTime<Z>) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        let next = self.schedule.after(&(dt.clone() - self.offset))?;
+        Some(next + self.offset)
+    }
+
+    /// Returns the previous shifted occurrence strictly before the supplied datetime.
+    pub fn prev_before<Z>(&self, dt: &DateTime<Z>) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        let prev = self.schedule.before(&(dt.clone() - self.offset))?;
+        Some(prev + self.offset)
+    }
+
+    /// Returns whether the supplied instant is a shifted occurrence, see `Schedule::includes`.
+    pub fn includes<Z>(&self, dt: &DateTime<Z>) -> bool
+    where
+        Z: TimeZone,
+    {
+        self.schedule.includes(&(dt.clone() - self.offset))
+    }
+
+    /// Iterates the shifted occurrences after the supplied datetime, or before it in reverse, as
+    /// `Schedule::iter_from` does.
+    pub fn iter_from<'a, Z>(
+        &'a self,
+        dt: &DateTime<Z>,
+    ) -> impl DoubleEndedIterator<Item = DateTime<Z>> + 'a
+    where
+        Z: TimeZone + 'a,
+    {
+        let offset = self.offset;
+        self.schedule
+            .iter_from(&(dt.clone() - offset))
+            .map(move |occurrence| occurrence + offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::str::FromStr;
+
+    #[test]
+    fn shifted_both_ways() {
+        let schedule = Schedule::from_str("0 */6 * * *").unwrap();
+        let at = |d, h, m| Utc.with_ymd_and_hms(2022, 6, d, h, m, 0).unwrap();
+
+        let later = schedule.offset_by(Duration::minutes(90));
+        let next: Vec<_> = later.iter_from(&at(1, 0, 0)).take(3).collect();
+        assert_eq!(vec![at(1, 1, 30), at(1, 7, 30), at(1, 13, 30)], next);
+        let previous: Vec<_> = later.iter_from(&at(1, 7, 0)).rev().take(2).collect();
+        let may = Utc.with_ymd_and_hms(2022, 5, 31, 19, 30, 0).unwrap();
+        assert_eq!(vec![at(1, 1, 30), may], previous);
+        assert_eq!(Some(at(1, 7, 30)), later.next_after(&at(1, 1, 30)));
+        assert_eq!(Some(at(1, 1, 30)), later.prev_before(&at(1, 7, 30)));
+        assert!(later.includes(&at(1, 13, 30)) && !later.includes(&at(1, 12, 0)));
+
+        // shifted into the previous day
+        let earlier = schedule.offset_by(Duration::minutes(-30));
+        assert_eq!(Some(at(1, 23, 30)), earlier.next_after(&at(1, 20, 0)));
+        assert!(earlier.includes(&at(1, 5, 30)) && !earlier.includes(&at(1, 6, 0)));
+    }
+}
@@ -0,0 +1,205 @@
+use crate::bitset::BitSet;
+use crate::errors::OnCalendarError;
+use crate::options::DaySemantics;
+use crate::schedule::{
+    DayOfMonthTerm, DayOfWeekTerm, Schedule, Seconds, TimeRange, Years, MAX_YEAR, MIN_YEAR,
+};
+
+const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+impl Schedule {
+    /// Returns the equivalent systemd timer `OnCalendar=` value of the schedule.
+    /// ```rust
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("*/15 9-17 * * Mon-Fri").unwrap();
+    /// assert_eq!(
+    ///     "Mon..Fri *-*-* 09..17:00/15:00",
+    ///     schedule.to_oncalendar().unwrap()
+    /// );
+    ///
+    /// // expressions with seconds stop at the supported years
+    /// let schedule = Schedule::from_str("0 */15 9-17 * * Mon-Fri").unwrap();
+    /// assert_eq!(
+    ///     "Mon..Fri 1970..2099-*-* 09..17:00/15:00",
+    ///     schedule.to_oncalendar().unwrap()
+    /// );
+    /// ```
+    pub fn to_oncalendar(&self) -> Result<String, OnCalendarError> {
+        if self.reboot {
+            // systemd expresses this with OnBootSec= rather than a calendar event
+            return Err(OnCalendarError::Inexpressible("@reboot".into()));
+        }
+        if self.effective_day_semantics() == DaySemantics::Or {
+            return Err(OnCalendarError::Inexpressible(
+                "days of month or days of week".into(),
+            ));
+        }
+        let mut s = String::new();
+
+        let week_terms: Vec<&DayOfWeekTerm> = self.day_of_week_terms.iter().collect();
+        // terms for a single day of week are the day within a week of the month
+        let (days_of_week, week_of_month) = match (&self.days_of_week, week_terms.as_slice()) {
+            (days, []) => (*days, None),
+            (TimeRange::Constrained(days), [term])
+                if days.is_empty()
+                    && self.days_of_month == TimeRange::All
+                    && self.day_of_month_terms.is_empty() =>
+            {
+                let (day_of_week, week) = match term {
+                    // the last occurrence falls within the last seven days of the month
+                    DayOfWeekTerm::Last(day_of_week) => (day_of_week, "~07/1".to_string()),
+                    DayOfWeekTerm::Nth(day_of_week, n) => (
+                        day_of_week,
+                        format!("-{:02}..{:02}", (n - 1) * 7 + 1, (n * 7).min(31)),
+                    ),
+                };
+                (
+                    TimeRange::Constrained(BitSet::from([*day_of_week])),
+                    Some(week),
+                )
+            }
+            _ => {
+                return Err(OnCalendarError::Inexpressible(
+                    "days of week relative to the month alongside other days".into(),
+                ))
+            }
+        };
+        if let TimeRange::Constrained(dow) = &days_of_week {
+            s.push_str(&format_list(dow, 1, 7, false, |v| {
+                WEEKDAYS[v as usize - 1].to_string()
+            }));
+            s.push(' ');
+        }
+
+        match &self.years {
+            // expressions with seconds stop at the supported years, systemd continues past them
+            Years::All => s.push_str(&format!("{}..{}", MIN_YEAR, MAX_YEAR)),
+            Years::Unbound => s.push('*'),
+            Years::Constrained(years) => {
+                // systemd accepts years beyond the supported range so repetitions can't be used
+                s.push_str(&format_list(years.iter().cloned(), 0, 0, false, |v| {
+                    v.to_string()
+                }))
+            }
+        }
+        s.push('-');
+        s.push_str(&format_time_range(&self.months, 1, 12));
+        match week_of_month {
+            Some(week) => s.push_str(&week),
+            None => s.push_str(&self.oncalendar_days_of_month()?),
+        }
+        s.push(' ');
+        s.push_str(&format_time_range(&self.hours, 0, 23));
+        s.push(':');
+        s.push_str(&format_time_range(&self.minutes, 0, 59));
+        s.push(':');
+        match &self.seconds {
+            Seconds::Ignore => s.push_str("00"),
+            Seconds::All => s.push('*'),
+            Seconds::Constrained(seconds) => s.push_str(&format_list(seconds, 0, 59, true, pad)),
+            Seconds::Interval(interval) => {
+                return Err(OnCalendarError::Inexpressible(format!(
+                    "an interval of {} seconds",
+                    interval
+                )))
+            }
+        }
+        if let Some(name) = self.timezone_name() {
+            s.push(' ');
+            s.push_str(name);
+        }
+        Ok(s)
+    }
+
+    /// Returns the equivalent systemd timer `OnCalendar=` value of the schedule, the same as
+    /// `Schedule::to_oncalendar`.
+    pub fn to_on_calendar(&self) -> Result<String, OnCalendarError> {
+        self.to_oncalendar()
+    }
+}
+
+impl Schedule {
+    /// Returns the day of month component including its leading separator, which is `~` for
+    /// days counted from the end of the month.
+    fn oncalendar_days_of_month(&self) -> Result<String, OnCalendarError> {
+        let terms: Vec<&DayOfMonthTerm> = self.day_of_month_terms.iter().collect();
+        match (&self.days_of_month, terms.as_slice()) {
+            (days, []) => Ok(format!("-{}", format_time_range(days, 1, 31))),
+            // systemd counts days from the end of the month after a `~`, starting at 1
+            (TimeRange::Constrained(days), [DayOfMonthTerm::Last(offset)]) if days.is_empty() => {
+                Ok(format!("~{:02}", offset + 1))
+            }
+            (_, terms)
+                if terms.iter().any(|term| {
+                    matches!(
+                        term,
+                        DayOfMonthTerm::NearestWeekday(_) | DayOfMonthTerm::LastWeekday
+                    )
+                }) =>
+            {
+                Err(OnCalendarError::Inexpressible(
+                    "the weekday nearest a day of the month".into(),
+                ))
+            }
+            _ => Err(OnCalendarError::Inexpressible(
+                "days relative to the end of the month alongside other days".into(),
+            )),
+        }
+    }
+}
+
+fn pad(v: u32) -> String {
+    format!("{:02}", v)
+}
+
+fn format_time_range(field: &TimeRange, min: u32, max: u32) -> String {
+    match field {
+        TimeRange::All => "*".to_string(),
+        TimeRange::Constrained(set) => format_list(set, min, max, true, pad),
+    }
+}
+
+/// Formats the values using systemd's `start/repetition` and `start..end` notations where
+/// possible falling back to a comma separated list.
+fn format_list<I, F>(set: I, min: u32, max: u32, allow_step: bool, f: F) -> String
+where
+    I: IntoIterator<Item = u32>,
+    F: Fn(u32) -> String,
+{
+    let values: Vec<u32> = set.into_iter().collect();
+
+    if allow_step && values.len() > 2 {
+        let step = values[1] - values[0];
+        let last = values[values.len() - 1];
+        if step > 1
+            && values.windows(2).all(|w| w[1] - w[0] == step)
+            && last + step > max
+            && values[0] >= min
+        {
+            return format!("{}/{}", f(values[0]), step);
+        }
+    }
+
+    let mut parts = Vec::new();
+    let mut i = 0;
+    while i < values.len() {
+        let start = values[i];
+        let mut end = start;
+        while i + 1 < values.len() && values[i + 1] == end + 1 {
+            i += 1;
+            end = values[i];
+        }
+        match end - start {
+            0 => parts.push(f(start)),
+            1 => {
+                parts.push(f(start));
+                parts.push(f(end));
+            }
+            _ => parts.push(format!("{}..{}", f(start), f(end))),
+        }
+        i += 1;
+    }
+    parts.join(",")
+}
@@ -0,0 +1,298 @@
+use crate::errors::ParseScheduleError;
+use crate::format::{CronFormat, DayOfWeekNumbering};
+use crate::locale::Locale;
+use crate::schedule::{parse_expression, Schedule};
+
+/// How the days of month and days of week fields combine when both are restricted, i.e.
+/// neither is written as `*`. Values covering a whole field, such as `1-31`, still restrict it.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DaySemantics {
+    /// Both fields must match, `0 0 1,15 * Mon` fires on Mondays falling on the 1st or 15th.
+    #[default]
+    And,
+    /// Either field may match as in Vixie CRON, `0 0 1,15 * Mon` fires on the 1st, the 15th
+    /// and every Monday.
+    Or,
+}
+
+/// How occurrences whose local time is skipped by a daylight saving time transition, such as
+/// 02:30 when clocks spring forward from 02:00 to 03:00, are handled.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DstGapPolicy {
+    /// The occurrence is skipped, a daily 02:30 job doesn't run on the day of the transition.
+    #[default]
+    Skip,
+    /// The occurrence fires at the first instant after the gap, 03:00 in the example.
+    FireAtGapEnd,
+    /// The occurrence fires at the last instant before the gap at the resolution of the
+    /// schedule, 01:59 in the example or 01:59:59 for schedules with a seconds field.
+    FireBeforeGap,
+}
+
+/// How occurrences whose local time is repeated by a daylight saving time transition, such as
+/// 01:30 when clocks fall back from 02:00 to 01:00, are handled.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AmbiguousTimePolicy {
+    /// The occurrence is skipped, a daily 01:30 job doesn't run on the day of the transition.
+    #[default]
+    Skip,
+    /// The occurrence fires once, at the first of the two instants.
+    Earliest,
+    /// The occurrence fires once, at the second of the two instants.
+    Latest,
+    /// The occurrence fires at both instants.
+    Both,
+}
+
+/// Controls which expressions `Schedule::parse_with` accepts.
+///
+/// The defaults match `Schedule::from_str`.
+/// ```rust
+/// use cron_exp::{ParseOptions, Schedule};
+///
+/// let options = ParseOptions::default()
+///     .allow_seconds(false)
+///     .wildcard_missing_fields(true);
+/// let schedule = Schedule::parse_with("30 9", &options).unwrap();
+/// assert_eq!(schedule, Schedule::parse_with("30 9 * * *", &options).unwrap());
+/// assert!(Schedule::parse_with("0 30 9 * * *", &options).is_err());
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct ParseOptions {
+    pub(crate) allow_seconds: bool,
+    pub(crate) lenient_steps: bool,
+    pub(crate) wildcard_missing_fields: bool,
+    pub(crate) auto_detect: bool,
+    pub(crate) format: Option<CronFormat>,
+    pub(crate) day_of_week_numbering: Option<DayOfWeekNumbering>,
+    pub(crate) locale: Locale,
+    pub(crate) day_semantics: DaySemantics,
+    pub(crate) dst_gap_policy: DstGapPolicy,
+    pub(crate) ambiguous_time_policy: AmbiguousTimePolicy,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            allow_seconds: true,
+            lenient_steps: false,
+            wildcard_missing_fields: false,
+            auto_detect: true,
+            format: None,
+            day_of_week_numbering: None,
+            locale: Locale::En,
+            day_semantics: DaySemantics::And,
+            dst_gap_policy: DstGapPolicy::Skip,
+            ambiguous_time_policy: AmbiguousTimePolicy::Skip,
+        }
+    }
+}
+
+impl ParseOptions {
+    /// Whether formats with a leading seconds field are accepted, defaults to true.
+    pub fn allow_seconds(mut self, allow: bool) -> Self {
+        self.allow_seconds = allow;
+        self
+    }
+
+    /// Whether steps larger than their field such as `*/90` minutes are accepted, matching the
+    /// first value only, rather than rejected. Defaults to false.
+    pub fn lenient_steps(mut self, lenient: bool) -> Self {
+        self.lenient_steps = lenient;
+        self
+    }
+
+    /// Whether missing trailing fields are taken to be `*`, up to the fields of the supplied
+    /// format or of a 5 field Crontab expression otherwise. Defaults to false.
+    pub fn wildcard_missing_fields(mut self, wildcard: bool) -> Self {
+        self.wildcard_missing_fields = wildcard;
+        self
+    }
+
+    /// Whether the format may be detected from the number of fields, defaults to true. Without
+    /// it a format must be supplied.
+    pub fn auto_detect(mut self, auto_detect: bool) -> Self {
+        self.auto_detect = auto_detect;
+        self
+    }
+
+    /// Parses expressions in the supplied format rather than detecting it.
+    pub fn format(mut self, format: CronFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Reads numeric days of week as numbered by the supplied convention rather than that of
+    /// the format, e.g. `MondayOne` for ISO 8601 numbers in any format.
+    pub fn day_of_week_numbering(mut self, numbering: DayOfWeekNumbering) -> Self {
+        self.day_of_week_numbering = Some(numbering);
+        self
+    }
+
+    /// Accepts month and day of week names of the supplied language, in full or by a unique
+    /// prefix such as `Lun`, `Ene` or `Mär`, in addition to English ones.
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Combines the days of month and days of week fields as supplied when both are restricted,
+    /// `DaySemantics::Or` for the behaviour of Vixie CRON. The semantics aren't written by
+    /// `Display`, see `Schedule::with_day_semantics` to restore them.
+    pub fn day_semantics(mut self, day_semantics: DaySemantics) -> Self {
+        self.day_semantics = day_semantics;
+        self
+    }
+
+    /// How occurrences falling in a daylight saving time gap are handled, defaults to
+    /// `DstGapPolicy::Skip`. The policy isn't written by `Display` either, see
+    /// `Schedule::with_dst_gap_policy`.
+    pub fn dst_gap_policy(mut self, dst_gap_policy: DstGapPolicy) -> Self {
+        self.dst_gap_policy = dst_gap_policy;
+        self
+    }
+
+    /// How occurrences at local times repeated when clocks fall back are handled, defaults to
+    /// `AmbiguousTimePolicy::Skip`. See `Schedule::with_ambiguous_time_policy` to restore it
+    /// after `Display`.
+    pub fn ambiguous_time_policy(mut self, ambiguous_time_policy: AmbiguousTimePolicy) -> Self {
+        self.ambiguous_time_policy = ambiguous_time_policy;
+        self
+    }
+}
+
+impl Schedule {
+    /// Parses the expression as configured by the options.
+    /// ```rust
+    /// use cron_exp::{CronFormat, ParseOptions, Schedule};
+    ///
+    /// let options = ParseOptions::default()
+    ///     .auto_detect(false)
+    ///     .format(CronFormat::Crontab);
+    /// assert!(Schedule::parse_with("*/15 * * * *", &options).is_ok());
+    /// assert!(Schedule::parse_with("0 */15 * * * *", &options).is_err());
+    /// ```
+    pub fn parse_with(
+        expression: &str,
+        options: &ParseOptions,
+    ) -> Result<Schedule, ParseScheduleError> {
+        Ok(parse_expression(expression, options)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::Field;
+    use std::str::FromStr;
+
+    #[test]
+    fn defaults_match_from_str() {
+        let options = ParseOptions::default();
+        for expression in ["0 30 9 * * Mon-Fri", "30 9 * * 1-5", "0 0 0 1 1 * 2030"].iter() {
+            assert_eq!(
+                Schedule::from_str(expression),
+                Schedule::parse_with(expression, &options)
+            );
+        }
+        assert_eq!(
+            Err(ParseScheduleError::InvalidStepRange {
+                field: Field::Minutes,
+                value: "*/90".into()
+            }),
+            Schedule::parse_with("*/90 * * * *", &options)
+        );
+    }
+
+    #[test]
+    fn parse_options() {
+        let no_seconds = ParseOptions::default().allow_seconds(false);
+        assert!(Schedule::parse_with("30 9 * * *", &no_seconds).is_ok());
+        assert_eq!(
+            Err(ParseScheduleError::SecondsNotAllowed),
+            Schedule::parse_with("0 30 9 * * *", &no_seconds)
+        );
+
+        let lenient = ParseOptions::default().lenient_steps(true);
+        assert_eq!(
+            Schedule::from_str("0 * * * *").unwrap(),
+            Schedule::parse_with("*/90 * * * *", &lenient).unwrap()
+        );
+        assert_eq!(
+            Err(ParseScheduleError::InvalidStepRange {
+                field: Field::Minutes,
+                value: "*/0".into()
+            }),
+            Schedule::parse_with("*/0 * * * *", &lenient)
+        );
+
+        let wildcard = ParseOptions::default().wildcard_missing_fields(true);
+        assert_eq!(
+            Schedule::from_str("30 9 * * *").unwrap(),
+            Schedule::parse_with("30 9", &wildcard).unwrap()
+        );
+        assert_eq!(
+            Schedule::from_str("0 30 9 * * *").unwrap(),
+            Schedule::parse_with("0 30 9", &wildcard.format(CronFormat::VixieWithSeconds)).unwrap()
+        );
+
+        let explicit = ParseOptions::default().auto_detect(false);
+        assert_eq!(
+            Err(ParseScheduleError::FormatRequired),
+            Schedule::parse_with("30 9 * * *", &explicit)
+        );
+        assert!(Schedule::parse_with("30 9 * * *", &explicit.format(CronFormat::Crontab)).is_ok());
+    }
+
+    #[test]
+    fn day_of_week_numbering() {
+        let iso = ParseOptions::default().day_of_week_numbering(DayOfWeekNumbering::MondayOne);
+        assert_eq!(
+            Schedule::from_str("0 9 * * Mon-Fri").unwrap(),
+            Schedule::parse_with("0 9 * * 1-5", &iso).unwrap()
+        );
+        assert_eq!(
+            Schedule::from_str("0 9 * * Sun").unwrap(),
+            Schedule::parse_with("0 9 * * 7", &iso).unwrap()
+        );
+        assert!(Schedule::parse_with("0 9 * * 0", &iso).is_err());
+
+        let sunday_one =
+            ParseOptions::default().day_of_week_numbering(DayOfWeekNumbering::SundayOne);
+        assert_eq!(
+            Schedule::from_str("0 9 * * Sat").unwrap(),
+            Schedule::parse_with("0 9 * * 7", &sunday_one).unwrap()
+        );
+        let sunday_zero =
+            ParseOptions::default().day_of_week_numbering(DayOfWeekNumbering::SundayZero);
+        assert_eq!(
+            Schedule::from_str("0 0 9 * * Sun").unwrap(),
+            Schedule::parse_with("0 0 9 * * 0", &sunday_zero).unwrap()
+        );
+
+        let schedule = Schedule::parse_with("0 9 * * 1,7", &iso).unwrap();
+        for numbering in [
+            DayOfWeekNumbering::SundayZero,
+            DayOfWeekNumbering::SundayOne,
+            DayOfWeekNumbering::MondayOne,
+        ] {
+            let days = schedule.day_of_week_numbers(numbering);
+            let options = ParseOptions::default().day_of_week_numbering(numbering);
+            let expression = format!("0 9 * * {}", format_days(&days));
+            assert_eq!(
+                schedule,
+                Schedule::parse_with(&expression, &options).unwrap()
+            );
+        }
+    }
+
+    fn format_days(days: &[u32]) -> String {
+        days.iter()
+            .map(|d| d.to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
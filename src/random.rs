@@ -0,0 +1,156 @@
+use crate::capabilities::Capabilities;
+use crate::errors::ParseScheduleError;
+use crate::fingerprint::Fnv64;
+use crate::schedule::Schedule;
+use crate::tokens::{self, Bounds, Syntax, Token};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+impl Schedule {
+    /// Parses an expression containing OpenBSD style random tokens, each replaced by a value
+    /// picked at random once while parsing.
+    ///
+    /// - `~` a value within the field
+    /// - `a~b` a value within `a-b`, either bound may be left out for the limit of the field
+    ///
+    /// Use `Schedule::from_str_seeded` for reproducible values. A `CRON_TZ=` prefix is kept.
+    /// ```rust
+    /// use chrono::{TimeZone, Timelike, Utc};
+    /// use cron_exp::Schedule;
+    ///
+    /// let schedule = Schedule::from_str_random("0~29 ~ * * *").unwrap();
+    /// let from = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap();
+    /// let next = schedule.iter_from(&from).next().unwrap();
+    /// assert!(next.minute() < 30);
+    /// ```
+    pub fn from_str_random(expression: &str) -> Result<Schedule, ParseScheduleError> {
+        let seed = RandomState::new().build_hasher().finish();
+        Schedule::from_str_seeded(expression, seed)
+    }
+
+    /// Parses an expression containing OpenBSD style random tokens as `Schedule::from_str_random`
+    /// does, picking the values from the seed so the same seed always yields the same schedule.
+    /// ```rust
+    /// use cron_exp::Schedule;
+    ///
+    /// let schedule = Schedule::from_str_seeded("~ 0~5 * * *", 42).unwrap();
+    /// assert_eq!(schedule, Schedule::from_str_seeded("~ 0~5 * * *", 42).unwrap());
+    /// ```
+    pub fn from_str_seeded(expression: &str, seed: u64) -> Result<Schedule, ParseScheduleError> {
+        let syntax = Syntax {
+            token,
+            last_day_of_month: 31,
+            capability: Capabilities::RANDOM,
+        };
+        tokens::parse_picking(expression, syntax, |field, item| pick(seed, field, item))
+    }
+}
+
+/// Returns a value derived from the seed for the list item at `item` of the field at `field`.
+fn pick(seed: u64, field: usize, item: usize) -> u64 {
+    let mut hasher = Fnv64::new();
+    for b in seed.to_le_bytes().iter() {
+        hasher.write_u8(*b);
+    }
+    hasher.write_u8(field as u8);
+    hasher.write_u8(item as u8);
+    hasher.0
+}
+
+/// Returns the random token of a list item, such as `0~29`.
+fn token(item: &str, bounds: Bounds) -> Result<Option<Token>, ParseScheduleError> {
+    let (low, high) = match item.split_once('~') {
+        Some(range) => range,
+        None => return Ok(None),
+    };
+    let bound = |bound: &str, limit: u32| match bound {
+        "" => Ok(limit),
+        bound => bounds.number(bound),
+    };
+    let (low, high) = (bound(low, bounds.min)?, bound(high, bounds.max)?);
+    bounds.token(item, low, high, None).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::Field;
+    use chrono::{Datelike, TimeZone, Timelike, Utc};
+    use std::str::FromStr;
+
+    #[test]
+    fn seeded() {
+        let from = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap();
+        for seed in 0..50 {
+            let schedule = Schedule::from_str_seeded("10~20 ~ * * 1~", seed).unwrap();
+            assert_eq!(
+                schedule,
+                Schedule::from_str_seeded("10~20 ~ * * 1~", seed).unwrap()
+            );
+            let next = schedule.iter_from(&from).next().unwrap();
+            assert!((10..=20).contains(&next.minute()), "{}", seed);
+            assert_ne!(0, next.weekday().num_days_from_sunday(), "{}", seed);
+            assert!(schedule.capabilities().contains(Capabilities::RANDOM));
+
+            let vixie = Schedule::from_str_seeded("~ 0 0 ~5 * * ~1975", seed).unwrap();
+            let next = vixie.iter_from(&from).next();
+            assert!(next.is_none_or(|dt| dt.day() <= 5), "{}", seed);
+        }
+
+        // the list items and fields are picked independently
+        let picked: Vec<Schedule> = (0..10)
+            .map(|seed| Schedule::from_str_seeded("~,~ ~ * * *", seed).unwrap())
+            .collect();
+        assert!(picked.iter().any(|s| s != &picked[0]));
+        assert_ne!(pick(7, 0, 0), pick(7, 0, 1));
+        assert_ne!(pick(7, 0, 0), pick(7, 1, 0));
+
+        assert_eq!(
+            Schedule::from_str("30 9 * * Mon").unwrap().capabilities(),
+            Schedule::from_str_random("30 9 * * Mon")
+                .unwrap()
+                .capabilities()
+        );
+        assert!(Schedule::from_str_random("~ ~ * * *").is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "tz")]
+    fn random_timezone() {
+        let schedule = Schedule::from_str_seeded("TZ=Europe/Paris ~ 9 * * *", 7).unwrap();
+        assert_eq!(Some(chrono_tz::Europe::Paris), schedule.timezone());
+        assert_eq!(
+            Schedule::from_str_seeded("~ 9 * * *", 7)
+                .unwrap()
+                .with_timezone(chrono_tz::Europe::Paris),
+            schedule
+        );
+    }
+
+    #[test]
+    fn invalid_random() {
+        let parse = |e| Schedule::from_str_seeded(e, 1);
+        assert_eq!(
+            Err(ParseScheduleError::InvalidRange {
+                field: Field::Minutes,
+                value: "50~70".into()
+            }),
+            parse("50~70 * * * *")
+        );
+        assert_eq!(
+            Err(ParseScheduleError::InvalidRange {
+                field: Field::Hours,
+                value: "9~5".into()
+            }),
+            parse("0 9~5 * * *")
+        );
+        assert_eq!(
+            Err(ParseScheduleError::InvalidNumber {
+                field: Field::DaysOfWeek,
+                value: "Mon".into()
+            }),
+            parse("0 0 * * Mon~Fri")
+        );
+        assert_eq!(Err(ParseScheduleError::ArgumentCount), parse("~ ~ ~"));
+    }
+}
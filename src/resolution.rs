@@ -0,0 +1,86 @@
+use crate::schedule::{Schedule, Seconds, TimeRange};
+use chrono::Duration;
+
+/// The finest unit of time in which a schedule's occurrences vary, ordered from finest to
+/// coarsest.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum Resolution {
+    /// Occurrences fall on different seconds of a minute.
+    Seconds,
+    /// Occurrences fall on a single second of different minutes of an hour.
+    Minutes,
+    /// Occurrences fall on a single minute and second of different hours of a day.
+    Hours,
+    /// Occurrences fall on a single time of day.
+    Days,
+}
+
+impl Resolution {
+    /// Returns the length of the unit, e.g. an appropriate polling interval or storage
+    /// precision.
+    pub fn duration(self) -> Duration {
+        match self {
+            Resolution::Seconds => Duration::seconds(1),
+            Resolution::Minutes => Duration::minutes(1),
+            Resolution::Hours => Duration::hours(1),
+            Resolution::Days => Duration::days(1),
+        }
+    }
+}
+
+impl Schedule {
+    /// Returns the effective granularity of the schedule derived from which of its time of day
+    /// fields match more than a single value.
+    /// ```rust
+    /// use cron_exp::{Resolution, Schedule};
+    /// use std::str::FromStr;
+    ///
+    /// let resolution = |e| Schedule::from_str(e).unwrap().resolution();
+    /// assert_eq!(Resolution::Seconds, resolution("*/15 * * * * *"));
+    /// assert_eq!(Resolution::Minutes, resolution("*/5 * * * *"));
+    /// assert_eq!(Resolution::Hours, resolution("0 30 9,17 * * *"));
+    /// assert_eq!(Resolution::Days, resolution("30 9 * * Mon-Fri"));
+    /// ```
+    pub fn resolution(&self) -> Resolution {
+        let varies = |field: &TimeRange| match field {
+            TimeRange::All => true,
+            TimeRange::Constrained(set) => set.len() > 1,
+        };
+        let seconds_vary = match &self.seconds {
+            Seconds::Ignore => false,
+            Seconds::All | Seconds::Interval(_) => true,
+            Seconds::Constrained(set) => set.len() > 1,
+        };
+
+        if seconds_vary {
+            Resolution::Seconds
+        } else if varies(&self.minutes) {
+            Resolution::Minutes
+        } else if varies(&self.hours) {
+            Resolution::Hours
+        } else {
+            Resolution::Days
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn resolution() {
+        let resolution = |e| Schedule::from_str(e).unwrap().resolution();
+
+        assert_eq!(Resolution::Seconds, resolution("*/90s * * * * *"));
+        assert_eq!(Resolution::Minutes, resolution("* * * * *"));
+        assert_eq!(Resolution::Minutes, resolution("0 0,30 * * * *"));
+        assert_eq!(Resolution::Hours, resolution("0 * * * *"));
+        // an interval lowered to the hours field
+        assert_eq!(Resolution::Hours, resolution("*/7200s * * * * *"));
+        assert_eq!(Resolution::Days, resolution("0 0 0 1 1 * 2030"));
+        assert!(Resolution::Seconds < Resolution::Days);
+        assert_eq!(Duration::hours(1), Resolution::Hours.duration());
+    }
+}
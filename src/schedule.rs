@@ -1,183 +1,355 @@
-use crate::errors::ParseScheduleError;
-use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike};
-use once_cell::sync::Lazy;
+use crate::bitset::BitSet;
+use crate::capabilities::Capabilities;
+use crate::errors::{ExhaustionReason, Field, ParseScheduleError, SpannedParseError};
+use crate::format::{CronFormat, DayOfWeekNumbering};
+use crate::locale::Locale;
+use crate::options::{AmbiguousTimePolicy, DaySemantics, DstGapPolicy, ParseOptions};
+use chrono::{
+    DateTime, Datelike, Duration, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+    TimeZone, Timelike, Weekday,
+};
+#[cfg(feature = "tz")]
+use chrono_tz::Tz;
+use std::borrow::Borrow;
+use std::cmp::Ordering;
 use std::collections::BTreeSet;
-use std::collections::Bound::Included;
 use std::str::FromStr;
 
-static EMPTY: Lazy<BTreeSet<u32>> = Lazy::new(BTreeSet::new);
-static MONTHS: Lazy<BTreeSet<u32>> = Lazy::new(|| (1..=12).into_iter().collect());
-static DAYS: Lazy<BTreeSet<u32>> = Lazy::new(|| (1..=31).into_iter().collect());
-static HOURS: Lazy<BTreeSet<u32>> = Lazy::new(|| (0..=23).into_iter().collect());
-static MINUTES_OR_SECONDS: Lazy<BTreeSet<u32>> = Lazy::new(|| (0..=59).into_iter().collect());
-static DAYS_OF_WEEK: Lazy<BTreeSet<u32>> = Lazy::new(|| (1..=7).into_iter().collect());
+const EMPTY: BitSet = BitSet::new();
+const MONTHS: BitSet = BitSet::span(1, 12);
+const DAYS: BitSet = BitSet::span(1, 31);
+const HOURS: BitSet = BitSet::span(0, 23);
+const MINUTES_OR_SECONDS: BitSet = BitSet::span(0, 59);
+const DAYS_OF_WEEK: BitSet = BitSet::span(1, 7);
 
-const MIN_YEAR: u32 = 1970;
-const MAX_YEAR: u32 = 2099;
+#[cfg(feature = "tracing")]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        tracing::debug!($($arg)*)
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! debug {
+    ($($arg:tt)*) => {};
+}
+
+pub(crate) const MIN_YEAR: u32 = 1970;
+pub(crate) const MAX_YEAR: u32 = 2099;
 
+/// The crontab nickname of a schedule run once at startup.
+pub(crate) const REBOOT: &str = "@reboot";
+
+pub(crate) const SECONDS_PER_DAY: u32 = 86_400;
+
+/// The Gregorian calendar repeats every 400 years so a schedule without a years field that has
+/// no occurrence within this many years never fires.
+pub(crate) const HORIZON_YEARS: u32 = 400;
+
+#[derive(Clone, Copy)]
 enum Direction {
     Forward,
     Back,
 }
 
-struct ResetHelper<'a, Z>
-where
-    Z: TimeZone,
-{
-    initial_datetime: &'a DateTime<Z>,
-    initial_seconds: bool,
-    initial_minutes: bool,
-    initial_hours: bool,
-    initial_days: bool,
-    initial_months: bool,
-    reset_seconds: u32,
-    reset_minutes: u32,
-    reset_hours: u32,
-    reset_days: u32,
-    reset_months: u32,
-}
-
-impl<'a, Z> ResetHelper<'a, Z>
-where
-    Z: TimeZone,
-{
-    fn new(dt: &'a DateTime<Z>, direction: Direction) -> Self {
-        match direction {
-            Direction::Forward => Self {
-                initial_datetime: dt,
-                initial_seconds: true,
-                initial_minutes: true,
-                initial_hours: true,
-                initial_days: true,
-                initial_months: true,
-                reset_seconds: 0,
-                reset_minutes: 0,
-                reset_hours: 0,
-                reset_days: 1,
-                reset_months: 1,
-            },
-            Direction::Back => Self {
-                initial_datetime: dt,
-                initial_seconds: true,
-                initial_minutes: true,
-                initial_hours: true,
-                initial_days: true,
-                initial_months: true,
-                reset_seconds: 59,
-                reset_minutes: 59,
-                reset_hours: 23,
-                reset_days: 31,
-                reset_months: 12,
-            },
-        }
-    }
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub(crate) enum Years {
+    All,
+    Constrained(BTreeSet<u32>),
+    Unbound,
+}
 
-    fn reset_seconds(&mut self) {
-        if self.initial_seconds {
-            self.initial_seconds = false;
-        }
-    }
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub(crate) enum Seconds {
+    Ignore,
+    All,
+    Constrained(BitSet),
+    /// Every N seconds counted from local midnight, written `*/Ns`, for intervals that can't be
+    /// expressed by steps of the individual fields.
+    Interval(u32),
+}
 
-    fn reset_minutes(&mut self) {
-        if self.initial_minutes {
-            self.initial_minutes = false;
-            self.reset_seconds();
-        }
-    }
+/// A Quartz day of month term resolved against each month.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub(crate) enum DayOfMonthTerm {
+    /// The last day of the month less an offset, written `L` or `L-3`.
+    Last(u32),
+    /// The weekday nearest to the day within the same month, written `15W`.
+    NearestWeekday(u32),
+    /// The last weekday of the month, written `LW`.
+    LastWeekday,
+}
 
-    fn reset_hours(&mut self) {
-        if self.initial_hours {
-            self.initial_hours = false;
-            self.reset_minutes();
+impl DayOfMonthTerm {
+    /// Returns the day of the month the term falls on, if any.
+    fn resolve(self, month: u32, year: u32) -> Option<u32> {
+        let days = days_in_month(month, year);
+        match self {
+            DayOfMonthTerm::Last(offset) => days.checked_sub(offset).filter(|day| *day >= 1),
+            DayOfMonthTerm::NearestWeekday(day) if day > days => None,
+            DayOfMonthTerm::NearestWeekday(day) => nearest_weekday(day, month, year),
+            DayOfMonthTerm::LastWeekday => nearest_weekday(days, month, year),
         }
     }
+}
 
-    fn reset_days(&mut self) {
-        if self.initial_days {
-            self.initial_days = false;
-            self.reset_hours();
-        }
-    }
+/// Returns the weekday nearest to the day without leaving its month, so a Saturday the 1st
+/// resolves to Monday the 3rd as in Quartz.
+fn nearest_weekday(day: u32, month: u32, year: u32) -> Option<u32> {
+    let date = NaiveDate::from_ymd_opt(year as i32, month, day)?;
+    let nearest = match date.weekday() {
+        Weekday::Sat if day == 1 => day + 2,
+        Weekday::Sat => day - 1,
+        Weekday::Sun if day == days_in_month(month, year) => day - 2,
+        Weekday::Sun => day + 1,
+        _ => day,
+    };
+    Some(nearest)
+}
 
-    fn reset_months(&mut self) {
-        if self.initial_months {
-            self.initial_months = false;
-            self.reset_days();
-        }
-    }
+/// A Quartz day of week term resolved against each month.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub(crate) enum DayOfWeekTerm {
+    /// The last occurrence of the day of week in the month, written `6L` or `FriL`.
+    Last(u32),
+    /// The nth occurrence of the day of week in the month, written `6#2` or `Fri#2`.
+    Nth(u32, u32),
+}
 
-    fn seconds(&self) -> u32 {
-        if self.initial_seconds {
-            return self.initial_datetime.second();
+impl DayOfWeekTerm {
+    /// Returns whether the date falls on the term.
+    fn matches(self, date: &NaiveDate) -> bool {
+        match self {
+            DayOfWeekTerm::Last(day_of_week) => {
+                date.weekday().number_from_sunday() == day_of_week
+                    && date.day() + 7 > days_in_month(date.month(), date.year() as u32)
+            }
+            DayOfWeekTerm::Nth(day_of_week, n) => {
+                date.weekday().number_from_sunday() == day_of_week && (date.day() - 1) / 7 + 1 == n
+            }
         }
-        self.reset_seconds
     }
 
-    fn minutes(&self) -> u32 {
-        if self.initial_minutes {
-            return self.initial_datetime.minute();
-        }
-        self.reset_minutes
+    /// Returns the day of the month the term falls on, if any, in a month of `days` days whose
+    /// first day is the `first` day of the week.
+    fn resolve(self, first: u32, days: u32) -> Option<u32> {
+        let first_day = |day_of_week: u32| 1 + (day_of_week + 7 - first) % 7;
+        let day = match self {
+            DayOfWeekTerm::Last(day_of_week) => {
+                let first_day = first_day(day_of_week);
+                first_day + (days - first_day) / 7 * 7
+            }
+            DayOfWeekTerm::Nth(day_of_week, n) => first_day(day_of_week) + (n - 1) * 7,
+        };
+        Some(day).filter(|day| *day <= days)
     }
 
-    fn hours(&self) -> u32 {
-        if self.initial_hours {
-            return self.initial_datetime.hour();
+    fn day_of_week(self) -> u32 {
+        match self {
+            DayOfWeekTerm::Last(day_of_week) | DayOfWeekTerm::Nth(day_of_week, _) => day_of_week,
         }
-        self.reset_hours
     }
+}
 
-    fn days(&self) -> u32 {
-        if self.initial_days {
-            return self.initial_datetime.day();
-        }
-        self.reset_days
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub(crate) enum TimeRange {
+    All,
+    Constrained(BitSet),
+}
+
+/// Represents a parsed CRON schedule.
+/// It is designed for space efficiency for caching and storage purposes such as in a CRON Scheduler.
+///
+/// Schedules are normalized while parsing so equality and ordering operate on their canonical
+/// form, e.g. `0-59` and `*` compare equal, allowing them to be used as `BTreeMap` keys and be
+/// sorted deterministically.
+#[derive(Debug, Clone)]
+pub struct Schedule {
+    pub(crate) seconds: Seconds,
+    pub(crate) minutes: TimeRange,
+    pub(crate) hours: TimeRange,
+    pub(crate) days_of_month: TimeRange,
+    /// Terms matching days of month in addition to `days_of_month`, e.g. the last day.
+    pub(crate) day_of_month_terms: BTreeSet<DayOfMonthTerm>,
+    /// Whether the days of month field was written as `*` or `?` rather than values covering
+    /// every day, such as `1-31`, which Vixie cron treats as restricting the field.
+    pub(crate) days_of_month_wildcard: bool,
+    pub(crate) months: TimeRange,
+    pub(crate) days_of_week: TimeRange,
+    /// Terms matching days of week in addition to `days_of_week`, e.g. the last Friday.
+    pub(crate) day_of_week_terms: BTreeSet<DayOfWeekTerm>,
+    /// Whether the days of week field was written as `*` or `?`, see `days_of_month_wildcard`.
+    pub(crate) days_of_week_wildcard: bool,
+    pub(crate) years: Years,
+    /// The syntax extensions the expression was written with, see `Schedule::capabilities`.
+    pub(crate) syntax: Capabilities,
+    /// Whether the schedule is `@reboot`, running once at startup rather than at any time.
+    pub(crate) reboot: bool,
+    /// How the days of month and days of week fields combine when both are restricted.
+    pub(crate) day_semantics: DaySemantics,
+    /// How occurrences whose local time falls in a DST gap are handled.
+    pub(crate) dst_gap_policy: DstGapPolicy,
+    /// How occurrences whose local time is repeated when clocks fall back are handled.
+    pub(crate) ambiguous_time_policy: AmbiguousTimePolicy,
+    /// The zone occurrences are computed in, written with a `CRON_TZ=` or `TZ=` prefix, instead
+    /// of the zone of the datetime iterated from.
+    #[cfg(feature = "tz")]
+    pub(crate) timezone: Option<Tz>,
+}
+
+type CanonicalKey<'a> = (
+    &'a Seconds,
+    &'a TimeRange,
+    &'a TimeRange,
+    &'a TimeRange,
+    &'a BTreeSet<DayOfMonthTerm>,
+    &'a TimeRange,
+    &'a TimeRange,
+    &'a BTreeSet<DayOfWeekTerm>,
+    &'a Years,
+    bool,
+    (DaySemantics, DstGapPolicy, AmbiguousTimePolicy),
+    Option<&'static str>,
+);
+
+impl Schedule {
+    /// The fields determining when the schedule fires, excluding how it was written.
+    fn canonical_key(&self) -> CanonicalKey<'_> {
+        (
+            &self.seconds,
+            &self.minutes,
+            &self.hours,
+            &self.days_of_month,
+            &self.day_of_month_terms,
+            &self.months,
+            &self.days_of_week,
+            &self.day_of_week_terms,
+            &self.years,
+            self.reboot,
+            (
+                self.effective_day_semantics(),
+                self.dst_gap_policy,
+                self.ambiguous_time_policy,
+            ),
+            self.timezone_name(),
+        )
     }
 
-    fn months(&self) -> u32 {
-        if self.initial_months {
-            return self.initial_datetime.month();
+    /// Returns the name of the zone named by the expression, if any.
+    pub(crate) fn timezone_name(&self) -> Option<&'static str> {
+        #[cfg(feature = "tz")]
+        if let Some(timezone) = self.timezone {
+            return Some(timezone.name());
         }
-        self.reset_months
+        None
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
-enum Years {
-    All,
-    Constrained(BTreeSet<u32>),
-    Unbound,
+impl PartialEq for Schedule {
+    fn eq(&self, other: &Self) -> bool {
+        self.canonical_key() == other.canonical_key()
+    }
 }
 
-#[derive(Debug, PartialEq, Clone)]
-enum Seconds {
-    Ignore,
-    All,
-    Constrained(BTreeSet<u32>),
-}
+impl Eq for Schedule {}
 
-#[derive(Debug, PartialEq, Clone)]
-enum TimeRange {
-    All,
-    Constrained(BTreeSet<u32>),
+impl PartialOrd for Schedule {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
-/// Represents a parsed CRON schedule.
-/// It is designed for space efficiency for caching and storage purposes such as in a CRON Scheduler.
-#[derive(Debug, PartialEq, Clone)]
-pub struct Schedule {
-    seconds: Seconds,
-    minutes: TimeRange,
-    hours: TimeRange,
-    days_of_month: TimeRange,
-    months: TimeRange,
-    days_of_week: TimeRange,
-    years: Years,
+impl Ord for Schedule {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical_key().cmp(&other.canonical_key())
+    }
 }
 
 impl Schedule {
+    /// Returns true if the schedule is `@reboot`, which runs once when the scheduler starts and
+    /// so never yields any datetimes.
+    /// ```rust
+    /// use chrono::Utc;
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("@reboot").unwrap();
+    /// assert!(schedule.is_reboot());
+    /// assert_eq!(None, schedule.iter_from(&Utc::now()).next());
+    /// ```
+    pub fn is_reboot(&self) -> bool {
+        self.reboot
+    }
+
+    /// Returns the days of week of the field numbered as supplied, every day for `*`. Quartz
+    /// terms such as `FriL` aren't included.
+    /// ```rust
+    /// use cron_exp::{DayOfWeekNumbering, Schedule};
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 9 * * Sat,Sun").unwrap();
+    /// assert_eq!(vec![0, 6], schedule.day_of_week_numbers(DayOfWeekNumbering::SundayZero));
+    /// assert_eq!(vec![1, 7], schedule.day_of_week_numbers(DayOfWeekNumbering::SundayOne));
+    /// assert_eq!(vec![6, 7], schedule.day_of_week_numbers(DayOfWeekNumbering::MondayOne));
+    /// ```
+    pub fn day_of_week_numbers(&self, numbering: DayOfWeekNumbering) -> Vec<u32> {
+        let mut numbers: Vec<u32> = self
+            .days_of_week()
+            .iter()
+            .filter_map(|day| numbering.weekday_number(day))
+            .collect();
+        numbers.sort_unstable();
+        numbers
+    }
+
+    /// Returns the zone named by a `CRON_TZ=` or `TZ=` prefix, occurrences are computed in it
+    /// and converted to the zone of the datetime iterated from.
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("CRON_TZ=Europe/Paris 0 30 9 * * *").unwrap();
+    /// assert_eq!(Some(chrono_tz::Europe::Paris), schedule.timezone());
+    ///
+    /// let from = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap();
+    /// let next = schedule.iter_from(&from).next().unwrap();
+    /// assert_eq!("2022-06-01T07:30:00+00:00", next.to_rfc3339());
+    /// ```
+    #[cfg(feature = "tz")]
+    pub fn timezone(&self) -> Option<Tz> {
+        self.timezone
+    }
+
+    /// Returns the schedule computing its occurrences in the supplied zone, as if written with a
+    /// `CRON_TZ=` prefix, whatever the zone of the datetime iterated from.
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use chrono_tz::Europe::Paris;
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 9 * * Mon-Fri").unwrap().with_timezone(Paris);
+    /// assert_eq!(Schedule::from_str("TZ=Europe/Paris 0 9 * * Mon-Fri").unwrap(), schedule);
+    ///
+    /// let from = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap();
+    /// assert_eq!(
+    ///     Some(Utc.with_ymd_and_hms(2022, 6, 1, 7, 0, 0).unwrap()),
+    ///     schedule.next_after(&from)
+    /// );
+    /// ```
+    #[cfg(feature = "tz")]
+    pub fn with_timezone(mut self, timezone: Tz) -> Schedule {
+        self.timezone = Some(timezone);
+        self
+    }
+
     /// Accepts a DateTime as a placeholder to iterate forwards or backwards for the next time the
     /// CRON expression is to run or should have ran.
+    ///
+    /// Both ends share a single cursor, so calling `next_back` after `next` steps back from the
+    /// last occurrence returned rather than from the supplied datetime, prefer `iter_after` or
+    /// `iter_before` to iterate in a single direction.
     /// ```rust
     /// use chrono::{DateTime, TimeZone, Utc};
     /// use cron_exp::Schedule;
@@ -187,7 +359,7 @@ impl Schedule {
     /// let expression = "0   30   9,12,15     1,15       May-Aug  Mon,Wed,Fri  2018/2";
     /// let schedule = Schedule::from_str(expression).unwrap();
     /// let mut last: Option<DateTime<Utc>> = None;
-    /// let from_date = Utc.ymd(2022, 6, 1).and_hms(8, 40, 1);
+    /// let from_date = Utc.with_ymd_and_hms(2022, 6, 1, 8, 40, 1).unwrap();
     ///
     /// // upcoming
     /// for datetime in schedule.iter_from(&from_date).take(10) {
@@ -200,889 +372,4033 @@ impl Schedule {
     ///     println!("prev -> {:?}", datetime);
     /// }
     /// ```
-    pub fn iter_from<'a, Z: 'a>(
+    pub fn iter_from<'a, Z>(
         &'a self,
         dt: &DateTime<Z>,
     ) -> impl DoubleEndedIterator<Item = DateTime<Z>> + 'a
     where
-        Z: TimeZone,
+        Z: TimeZone + 'a,
     {
         ScheduleIterator::new(self, dt)
     }
 
-    fn date<Z>(&self, dt: &DateTime<Z>, direction: Direction) -> DateTime<Z>
+    /// Iterates the occurrences strictly after the supplied datetime in ascending order.
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 30 9,12,15 1,15 May-Aug Mon,Wed,Fri 2018/2").unwrap();
+    /// let from = Utc.with_ymd_and_hms(2022, 6, 1, 8, 40, 1).unwrap();
+    ///
+    /// let upcoming: Vec<_> = schedule.iter_after(&from).take(2).collect();
+    /// assert_eq!(Utc.with_ymd_and_hms(2022, 6, 1, 9, 30, 0).unwrap(), upcoming[0]);
+    /// assert_eq!(Utc.with_ymd_and_hms(2022, 6, 1, 12, 30, 0).unwrap(), upcoming[1]);
+    /// ```
+    pub fn iter_after<'a, Z>(&'a self, dt: &DateTime<Z>) -> impl Iterator<Item = DateTime<Z>> + 'a
     where
-        Z: TimeZone,
+        Z: TimeZone + 'a,
     {
-        match direction {
-            Direction::Forward => {
-                dt.clone()
-                    + if let Seconds::Ignore = self.seconds {
-                        Duration::minutes(1)
-                    } else {
-                        Duration::seconds(1)
-                    }
-            }
-            Direction::Back => {
-                dt.clone()
-                    - if let Seconds::Ignore = self.seconds {
-                        Duration::minutes(1)
-                    } else {
-                        Duration::seconds(1)
-                    }
-            }
-        }
+        ScheduleIterator::new(self, dt)
+    }
+
+    /// Iterates the occurrences strictly before the supplied datetime in descending order.
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 30 9,12,15 1,15 May-Aug Mon,Wed,Fri 2018/2").unwrap();
+    /// let from = Utc.with_ymd_and_hms(2022, 6, 1, 8, 40, 1).unwrap();
+    ///
+    /// // the schedule only fires in even years
+    /// let previous = schedule.iter_before(&from).next().unwrap();
+    /// assert_eq!(Utc.with_ymd_and_hms(2020, 7, 15, 15, 30, 0).unwrap(), previous);
+    /// ```
+    pub fn iter_before<'a, Z>(&'a self, dt: &DateTime<Z>) -> impl Iterator<Item = DateTime<Z>> + 'a
+    where
+        Z: TimeZone + 'a,
+    {
+        ScheduleIterator::new(self, dt).rev()
     }
 
-    fn years<Z>(&self, dt: &DateTime<Z>, direction: Direction) -> Box<dyn Iterator<Item = u32> + '_>
+    /// Iterates as `iter_from` taking ownership of the schedule, so the iterator can be returned
+    /// from functions or moved into spawned tasks.
+    /// ```rust
+    /// use chrono::{DateTime, Utc};
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// fn upcoming(expression: &str) -> impl Iterator<Item = DateTime<Utc>> {
+    ///     Schedule::from_str(expression).unwrap().into_iter_from(&Utc::now())
+    /// }
+    ///
+    /// let handle = std::thread::spawn(|| upcoming("0 9 * * *").take(3).count());
+    /// assert_eq!(3, handle.join().unwrap());
+    /// ```
+    pub fn into_iter_from<Z>(self, dt: &DateTime<Z>) -> impl DoubleEndedIterator<Item = DateTime<Z>>
     where
         Z: TimeZone,
     {
-        let from_year = dt.year() as u32;
+        ScheduleIterator::new(self, dt)
+    }
 
-        match direction {
-            Direction::Forward => match &self.years {
-                Years::All => Box::new(from_year.max(MIN_YEAR)..=MAX_YEAR),
-                Years::Constrained(btree) => Box::new(
-                    btree
-                        .range(from_year.max(MIN_YEAR) as u32..=MAX_YEAR)
-                        .cloned(),
-                ),
-                Years::Unbound => Box::new(from_year..),
-            },
-            Direction::Back => match &self.years {
-                Years::All => Box::new((MIN_YEAR..=from_year.min(MAX_YEAR)).rev()),
-                Years::Constrained(btree) => Box::new(
-                    btree
-                        .range(MIN_YEAR..=from_year.min(MAX_YEAR))
-                        .rev()
-                        .cloned(),
-                ),
-                Years::Unbound => Box::new((u32::MIN..=from_year).rev()),
-            },
-        }
+    /// Iterates the occurrences from `start`, inclusive, to `end`, exclusive, forwards or, with
+    /// `rev`, backwards from the end without taking and skipping at each end.
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 9,17 * * *").unwrap();
+    /// let start = Utc.with_ymd_and_hms(2022, 6, 1, 9, 0, 0).unwrap();
+    /// let end = Utc.with_ymd_and_hms(2022, 6, 3, 9, 0, 0).unwrap();
+    ///
+    /// assert_eq!(4, schedule.iter_between(&start, &end).count());
+    /// assert_eq!(5, schedule.iter_between_inclusive(&start, &end).count());
+    /// assert_eq!(
+    ///     Some(Utc.with_ymd_and_hms(2022, 6, 2, 17, 0, 0).unwrap()),
+    ///     schedule.iter_between(&start, &end).next_back()
+    /// );
+    /// ```
+    pub fn iter_between<'a, Z>(
+        &'a self,
+        start: &DateTime<Z>,
+        end: &DateTime<Z>,
+    ) -> impl DoubleEndedIterator<Item = DateTime<Z>> + 'a
+    where
+        Z: TimeZone + 'a,
+    {
+        BetweenIterator::new(self, start, end, false)
     }
 
-    fn months(&self) -> &BTreeSet<u32> {
-        match &self.months {
-            TimeRange::All => &MONTHS,
-            TimeRange::Constrained(m) => m,
-        }
+    /// Iterates the occurrences from `start` to `end`, both inclusive, as `iter_between`.
+    pub fn iter_between_inclusive<'a, Z>(
+        &'a self,
+        start: &DateTime<Z>,
+        end: &DateTime<Z>,
+    ) -> impl DoubleEndedIterator<Item = DateTime<Z>> + 'a
+    where
+        Z: TimeZone + 'a,
+    {
+        BetweenIterator::new(self, start, end, true)
     }
 
-    fn days_of_month(&self) -> &BTreeSet<u32> {
-        match &self.days_of_month {
-            TimeRange::All => &DAYS,
-            TimeRange::Constrained(m) => m,
-        }
+    /// Returns the next occurrence strictly after the supplied datetime, or `None` when there is
+    /// none, without building an iterator.
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("30 9 * * Mon-Fri").unwrap();
+    /// let from = Utc.with_ymd_and_hms(2022, 6, 3, 12, 0, 0).unwrap();
+    /// assert_eq!(
+    ///     Some(Utc.with_ymd_and_hms(2022, 6, 6, 9, 30, 0).unwrap()),
+    ///     schedule.next_after(&from)
+    /// );
+    /// assert_eq!(
+    ///     Some(Utc.with_ymd_and_hms(2022, 6, 3, 9, 30, 0).unwrap()),
+    ///     schedule.prev_before(&from)
+    /// );
+    /// ```
+    pub fn next_after<Z>(&self, dt: &DateTime<Z>) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        self.after(dt)
     }
 
-    fn hours(&self) -> &BTreeSet<u32> {
-        match &self.hours {
-            TimeRange::All => &HOURS,
-            TimeRange::Constrained(m) => m,
-        }
+    /// Returns the previous occurrence strictly before the supplied datetime, or `None` when
+    /// there is none, without building an iterator.
+    pub fn prev_before<Z>(&self, dt: &DateTime<Z>) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        self.before(dt)
     }
 
-    fn minutes(&self) -> &BTreeSet<u32> {
-        match &self.minutes {
-            TimeRange::All => &MINUTES_OR_SECONDS,
-            TimeRange::Constrained(m) => m,
+    /// Returns the next occurrence after the supplied datetime distinguishing a schedule that
+    /// has finished, `Ok(None)` once all of its constrained years have passed, from one that
+    /// can't produce any further occurrences for another `ExhaustionReason`.
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use cron_exp::{ExhaustionReason, Schedule};
+    /// use std::str::FromStr;
+    ///
+    /// let from_date = Utc.with_ymd_and_hms(2022, 6, 1, 8, 40, 1).unwrap();
+    ///
+    /// let finished = Schedule::from_str("0 0 0 1 1 * 2020").unwrap();
+    /// assert_eq!(Ok(None), finished.try_next_after(&from_date));
+    ///
+    /// let broken = Schedule::from_str("0 0 30 2 *").unwrap();
+    /// assert_eq!(
+    ///     Err(ExhaustionReason::Unsatisfiable),
+    ///     broken.try_next_after(&from_date)
+    /// );
+    /// ```
+    pub fn try_next_after<Z>(
+        &self,
+        dt: &DateTime<Z>,
+    ) -> Result<Option<DateTime<Z>>, ExhaustionReason>
+    where
+        Z: TimeZone,
+    {
+        match self.after(dt) {
+            Some(next) => Ok(Some(next)),
+            None => self.exhaustion_reason(),
         }
     }
 
-    fn seconds(&self) -> &BTreeSet<u32> {
-        match &self.seconds {
-            Seconds::All => &MINUTES_OR_SECONDS,
-            Seconds::Constrained(s) => s,
-            Seconds::Ignore => &EMPTY,
+    /// Returns the previous occurrence before the supplied datetime, the mirror of
+    /// `try_next_after`.
+    pub fn try_prev_before<Z>(
+        &self,
+        dt: &DateTime<Z>,
+    ) -> Result<Option<DateTime<Z>>, ExhaustionReason>
+    where
+        Z: TimeZone,
+    {
+        match self.before(dt) {
+            Some(prev) => Ok(Some(prev)),
+            None => self.exhaustion_reason(),
         }
     }
 
-    fn days_of_week(&self) -> &BTreeSet<u32> {
-        match &self.days_of_week {
-            TimeRange::All => &DAYS_OF_WEEK,
-            TimeRange::Constrained(dow) => dow,
+    /// Returns whether the supplied instant is an occurrence of the schedule at its resolution,
+    /// any time within a matching minute for schedules without a seconds field and within a
+    /// matching second otherwise.
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("30 9 * * Mon-Fri").unwrap();
+    /// assert!(schedule.includes(&Utc.with_ymd_and_hms(2022, 6, 1, 9, 30, 45).unwrap()));
+    /// assert!(!schedule.includes(&Utc.with_ymd_and_hms(2022, 6, 4, 9, 30, 0).unwrap()));
+    ///
+    /// let with_seconds = Schedule::from_str("0 30 9 * * *").unwrap();
+    /// assert!(!with_seconds.includes(&Utc.with_ymd_and_hms(2022, 6, 1, 9, 30, 45).unwrap()));
+    /// ```
+    pub fn includes<Z>(&self, dt: &DateTime<Z>) -> bool
+    where
+        Z: TimeZone,
+    {
+        #[cfg(feature = "tz")]
+        if let Some(timezone) = self.timezone {
+            return self.fires_at(&self.truncate_to_step(&dt.with_timezone(&timezone)));
         }
+        self.fires_at(&self.truncate_to_step(dt))
     }
 
-    fn before<Z>(&self, dt: &DateTime<Z>) -> Option<DateTime<Z>>
+    /// Returns whether the schedule fires at the instant, taken at its resolution in its zone.
+    /// Its local time matches the fields and is kept by the `AmbiguousTimePolicy`, or the
+    /// `DstGapPolicy` moves matching local times skipped by a DST gap to it.
+    pub(crate) fn fires_at<Z>(&self, dt: &DateTime<Z>) -> bool
     where
         Z: TimeZone,
     {
-        let timezone = dt.timezone();
-        let dt = self.date(dt, Direction::Back);
-
-        let mut helper = ResetHelper::new(&dt, Direction::Back);
-
-        let months = self.months();
-        let days_of_month = self.days_of_month();
-        let hours = self.hours();
-        let minutes = self.minutes();
-        let seconds = self.seconds();
-        let ignore_seconds = seconds.is_empty();
-        let days_of_week = self.days_of_week();
-
-        for year in self.years(&dt, Direction::Back) {
-            let month_end = helper.months();
-            if !months.contains(&month_end) {
-                helper.reset_months();
-            }
-
-            for month in months.range(1..=month_end).rev().cloned() {
-                let days_end = helper.days();
-                if !days_of_month.contains(&days_end) {
-                    helper.reset_days();
-                }
-
-                'days_loop: for day_of_month in days_of_month
-                    .range((
-                        Included(1),
-                        Included(days_in_month(month, year).min(days_end)),
-                    ))
-                    .rev()
-                    .cloned()
-                {
-                    let hours_end = helper.hours();
-                    if !hours.contains(&hours_end) {
-                        helper.reset_hours();
-                    }
-
-                    for hour in hours.range(0..=hours_end).rev().cloned() {
-                        let minutes_end = helper.minutes();
-                        if !minutes.contains(&minutes_end) {
-                            helper.reset_minutes();
-                        }
-
-                        for minute in minutes.range(0..=minutes_end).rev().cloned() {
-                            if ignore_seconds {
-                                let candidate = if let Some(candidate) = timezone
-                                    .ymd(year as i32, month, day_of_month)
-                                    .and_hms_opt(hour, minute, 0)
-                                {
-                                    candidate
-                                } else {
-                                    continue;
-                                };
-                                if !days_of_week.contains(&candidate.weekday().number_from_sunday())
-                                {
-                                    helper.reset_days();
-                                    continue 'days_loop;
-                                }
-                                return Some(candidate);
-                            } else {
-                                let seconds_end = helper.seconds();
-                                if !seconds.contains(&seconds_end) {
-                                    helper.reset_seconds();
-                                }
-
-                                for second in seconds.range(0..=seconds_end).rev().cloned() {
-                                    let candidate = if let Some(candidate) = timezone
-                                        .ymd(year as i32, month, day_of_month)
-                                        .and_hms_opt(hour, minute, second)
-                                    {
-                                        candidate
-                                    } else {
-                                        continue;
-                                    };
-                                    if !days_of_week
-                                        .contains(&candidate.weekday().number_from_sunday())
-                                    {
-                                        helper.reset_days();
-                                        continue 'days_loop;
-                                    }
-                                    return Some(candidate);
-                                }
-                                helper.reset_seconds();
-                            }
-                        }
-                        helper.reset_minutes();
-                    }
-                    helper.reset_hours();
-                }
-                helper.reset_days();
+        let local = dt.naive_local();
+        if self.matches_local(&local) {
+            return match dt.timezone().from_local_datetime(&local) {
+                LocalResult::Ambiguous(earliest, latest) => match self.ambiguous_time_policy {
+                    AmbiguousTimePolicy::Skip => false,
+                    AmbiguousTimePolicy::Earliest => *dt == earliest,
+                    AmbiguousTimePolicy::Latest => *dt == latest,
+                    AmbiguousTimePolicy::Both => true,
+                },
+                _ => true,
+            };
+        }
+        let gap_end = match self.dst_gap_policy {
+            DstGapPolicy::Skip => return false,
+            DstGapPolicy::FireAtGapEnd => dt.clone(),
+            DstGapPolicy::FireBeforeGap => dt.clone() + self.step(),
+        };
+        // the local times skipped when clocks jump forward at the end of the gap, if any
+        let start =
+            (gap_end.clone() - Duration::nanoseconds(1)).naive_local() + Duration::nanoseconds(1);
+        let end = gap_end.naive_local();
+        let mut skipped = match self.truncate_local(start) {
+            Some(truncated) if truncated < start => truncated + self.step(),
+            Some(truncated) => truncated,
+            None => return false,
+        };
+        while skipped < end {
+            if self.matches_local(&skipped) {
+                return true;
             }
-            helper.reset_months();
+            skipped += self.step();
         }
-        None
+        false
     }
 
-    fn after<Z>(&self, dt: &DateTime<Z>) -> Option<DateTime<Z>>
+    /// Returns the local time of the instant in the zone of the schedule, truncated to its
+    /// resolution.
+    pub(crate) fn truncated_local<Z>(&self, dt: &DateTime<Z>) -> Option<NaiveDateTime>
     where
         Z: TimeZone,
     {
-        let timezone = dt.timezone();
-        let dt = self.date(dt, Direction::Forward);
-
-        let mut helper = ResetHelper::new(&dt, Direction::Forward);
-
-        let months = self.months();
-        let days_of_month = self.days_of_month();
-        let hours = self.hours();
-        let minutes = self.minutes();
-        let seconds = self.seconds();
-        let ignore_seconds = seconds.is_empty();
-        let days_of_week = self.days_of_week();
-
-        for year in self.years(&dt, Direction::Forward) {
-            let month_start = helper.months();
-            if !months.contains(&month_start) {
-                helper.reset_months();
-            }
+        #[cfg(feature = "tz")]
+        if let Some(timezone) = self.timezone {
+            return self.truncate_local(dt.with_timezone(&timezone).naive_local());
+        }
+        self.truncate_local(dt.naive_local())
+    }
 
-            for month in months.range(month_start..=12).cloned() {
-                let day_start = helper.days();
-                if !days_of_month.contains(&day_start) {
-                    helper.reset_days();
-                }
+    /// Returns how the days of month and days of week fields combine when both are restricted,
+    /// see `ParseOptions::day_semantics`.
+    pub fn day_semantics(&self) -> DaySemantics {
+        self.day_semantics
+    }
 
-                'days_loop: for day_of_month in days_of_month
-                    .range((Included(day_start), Included(days_in_month(month, year))))
-                    .cloned()
-                {
-                    let hour_start = helper.hours();
-                    if !hours.contains(&hour_start) {
-                        helper.reset_hours();
-                    }
+    /// Returns the schedule combining its days of month and days of week fields as supplied.
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use cron_exp::{DaySemantics, Schedule};
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 0 1,15 * Mon").unwrap();
+    /// let from = Utc.with_ymd_and_hms(2022, 6, 1, 12, 0, 0).unwrap();
+    /// // the 1st of August 2022 is the first Monday falling on the 1st or 15th
+    /// assert_eq!(
+    ///     Some(Utc.with_ymd_and_hms(2022, 8, 1, 0, 0, 0).unwrap()),
+    ///     schedule.next_after(&from)
+    /// );
+    /// // Monday the 6th of June 2022
+    /// assert_eq!(
+    ///     Some(Utc.with_ymd_and_hms(2022, 6, 6, 0, 0, 0).unwrap()),
+    ///     schedule.with_day_semantics(DaySemantics::Or).next_after(&from)
+    /// );
+    /// ```
+    pub fn with_day_semantics(mut self, day_semantics: DaySemantics) -> Schedule {
+        self.day_semantics = day_semantics;
+        self
+    }
 
-                    for hour in hours.range(hour_start..=23).cloned() {
-                        let minutes_start = helper.minutes();
-                        if !minutes.contains(&minutes_start) {
-                            helper.reset_minutes();
-                        }
-
-                        for minute in minutes.range(minutes_start..=59).cloned() {
-                            if ignore_seconds {
-                                let candidate = if let Some(candidate) = timezone
-                                    .ymd(year as i32, month, day_of_month)
-                                    .and_hms_opt(hour, minute, 0)
-                                {
-                                    candidate
-                                } else {
-                                    continue;
-                                };
-                                if !days_of_week.contains(&candidate.weekday().number_from_sunday())
-                                {
-                                    helper.reset_days();
-                                    continue 'days_loop;
-                                }
-                                return Some(candidate);
-                            } else {
-                                let seconds_start = helper.seconds();
-                                if !seconds.contains(&seconds_start) {
-                                    helper.reset_seconds();
-                                }
-
-                                for second in seconds.range(seconds_start..=59).cloned() {
-                                    let candidate = if let Some(candidate) = timezone
-                                        .ymd(year as i32, month, day_of_month)
-                                        .and_hms_opt(hour, minute, second)
-                                    {
-                                        candidate
-                                    } else {
-                                        continue;
-                                    };
-                                    if !days_of_week
-                                        .contains(&candidate.weekday().number_from_sunday())
-                                    {
-                                        helper.reset_days();
-                                        continue 'days_loop;
-                                    }
-                                    return Some(candidate);
-                                }
-                                helper.reset_seconds();
-                            }
-                        }
-                        helper.reset_minutes();
-                    }
-                    helper.reset_hours();
-                }
-                helper.reset_days();
-            }
-            helper.reset_months();
-        }
-        None
+    /// Returns how occurrences whose local time falls in a daylight saving time gap are
+    /// handled, see `ParseOptions::dst_gap_policy`.
+    pub fn dst_gap_policy(&self) -> DstGapPolicy {
+        self.dst_gap_policy
     }
-}
 
-fn is_leap_year(year: u32) -> bool {
-    let by_four = year % 4 == 0;
-    let by_hundred = year % 100 == 0;
-    let by_four_hundred = year % 400 == 0;
-    by_four && ((!by_hundred) || by_four_hundred)
-}
+    /// Returns the schedule handling occurrences in daylight saving time gaps as supplied, rather
+    /// than skipping them. `Schedule::includes` matches the instants they are moved to.
+    /// ```rust
+    /// use chrono::TimeZone;
+    /// use chrono_tz::Europe::Paris;
+    /// use cron_exp::{DstGapPolicy, Schedule};
+    /// use std::str::FromStr;
+    ///
+    /// // clocks spring forward from 02:00 to 03:00 on the 28th of March 2021 in Paris
+    /// let schedule = Schedule::from_str("30 2 * * *").unwrap();
+    /// let from = Paris.with_ymd_and_hms(2021, 3, 28, 0, 0, 0).unwrap();
+    /// assert_eq!(
+    ///     Some(Paris.with_ymd_and_hms(2021, 3, 29, 2, 30, 0).unwrap()),
+    ///     schedule.next_after(&from)
+    /// );
+    /// assert_eq!(
+    ///     Some(Paris.with_ymd_and_hms(2021, 3, 28, 3, 0, 0).unwrap()),
+    ///     schedule
+    ///         .with_dst_gap_policy(DstGapPolicy::FireAtGapEnd)
+    ///         .next_after(&from)
+    /// );
+    /// ```
+    pub fn with_dst_gap_policy(mut self, dst_gap_policy: DstGapPolicy) -> Schedule {
+        self.dst_gap_policy = dst_gap_policy;
+        self
+    }
 
-fn days_in_month(month: u32, year: u32) -> u32 {
-    let is_leap_year = is_leap_year(year);
-    match month {
-        9 | 4 | 6 | 11 => 30,
-        2 if is_leap_year => 29,
-        2 => 28,
-        _ => 31,
+    /// Returns how occurrences whose local time is repeated when clocks fall back are handled,
+    /// see `ParseOptions::ambiguous_time_policy`.
+    pub fn ambiguous_time_policy(&self) -> AmbiguousTimePolicy {
+        self.ambiguous_time_policy
     }
-}
 
-impl FromStr for Schedule {
-    type Err = ParseScheduleError;
+    /// Returns the schedule handling occurrences at local times repeated when clocks fall back
+    /// as supplied, rather than skipping them. `Schedule::includes` only matches the instants kept.
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use chrono_tz::Europe::London;
+    /// use cron_exp::{AmbiguousTimePolicy, Schedule};
+    /// use std::str::FromStr;
+    ///
+    /// // clocks fall back from 02:00 to 01:00 on the 31st of October 2021 in London
+    /// let schedule = Schedule::from_str("30 1 * * *").unwrap();
+    /// let from = London.with_ymd_and_hms(2021, 10, 31, 0, 0, 0).unwrap();
+    /// let fire_times = |policy| {
+    ///     schedule
+    ///         .clone()
+    ///         .with_ambiguous_time_policy(policy)
+    ///         .iter_after(&from)
+    ///         .take(2)
+    ///         .map(|dt| dt.with_timezone(&Utc).to_rfc3339())
+    ///         .collect::<Vec<_>>()
+    /// };
+    /// assert_eq!(
+    ///     vec!["2021-10-31T00:30:00+00:00", "2021-11-01T01:30:00+00:00"],
+    ///     fire_times(AmbiguousTimePolicy::Earliest)
+    /// );
+    /// assert_eq!(
+    ///     vec!["2021-10-31T01:30:00+00:00", "2021-11-01T01:30:00+00:00"],
+    ///     fire_times(AmbiguousTimePolicy::Latest)
+    /// );
+    /// assert_eq!(
+    ///     vec!["2021-10-31T00:30:00+00:00", "2021-10-31T01:30:00+00:00"],
+    ///     fire_times(AmbiguousTimePolicy::Both)
+    /// );
+    /// ```
+    pub fn with_ambiguous_time_policy(
+        mut self,
+        ambiguous_time_policy: AmbiguousTimePolicy,
+    ) -> Schedule {
+        self.ambiguous_time_policy = ambiguous_time_policy;
+        self
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let fields: Vec<&str> = s.split_whitespace().collect();
-        match fields.len() {
-            5 => Ok(Schedule {
-                seconds: Seconds::Ignore,
-                minutes: parse_field(fields[0], 0, 59, false, false, false)?,
-                hours: parse_field(fields[1], 0, 23, false, false, false)?,
-                days_of_month: parse_field(fields[2], 1, 31, false, false, false)?,
-                months: parse_field(fields[3], 1, 12, false, false, true)?,
-                days_of_week: parse_field(fields[4], 1, 7, false, true, false)?,
-                years: Years::Unbound,
-            }),
-            6 => Ok(Schedule {
-                seconds: match parse_field(fields[0], 0, 59, true, false, false)? {
-                    TimeRange::All => Seconds::All,
-                    TimeRange::Constrained(set) => Seconds::Constrained(set),
-                },
-                minutes: parse_field(fields[1], 0, 59, true, false, false)?,
-                hours: parse_field(fields[2], 0, 23, true, false, false)?,
-                days_of_month: parse_field(fields[3], 1, 31, true, false, false)?,
-                months: parse_field(fields[4], 1, 12, true, false, true)?,
-                days_of_week: parse_field(fields[5], 1, 7, true, true, false)?,
-                years: Years::All,
-            }),
-            7 => Ok(Schedule {
-                seconds: match parse_field(fields[0], 0, 59, true, false, false)? {
-                    TimeRange::All => Seconds::All,
-                    TimeRange::Constrained(set) => Seconds::Constrained(set),
-                },
-                minutes: parse_field(fields[1], 0, 59, true, false, false)?,
-                hours: parse_field(fields[2], 0, 23, true, false, false)?,
-                days_of_month: parse_field(fields[3], 1, 31, true, false, false)?,
-                months: parse_field(fields[4], 1, 12, true, false, true)?,
-                days_of_week: parse_field(fields[5], 1, 7, true, true, false)?,
-                years: match parse_field(fields[6], MIN_YEAR, MAX_YEAR, true, false, false)? {
-                    TimeRange::All => Years::All,
-                    TimeRange::Constrained(f) => Years::Constrained(f),
-                },
-            }),
-            _ => Err(ParseScheduleError::ArgumentCount),
+    /// Returns the semantics in effect, either field being written as `*` makes `Or` match like
+    /// `And`. As in Vixie cron values covering the whole field, such as `1-31`, restrict it.
+    pub(crate) fn effective_day_semantics(&self) -> DaySemantics {
+        if self.days_of_month_wildcard || self.days_of_week_wildcard {
+            DaySemantics::And
+        } else {
+            self.day_semantics
         }
     }
-}
 
-fn parse_range(
-    left_range: &str,
-    right_range: &str,
-    is_vixie: bool,
-    is_dom: bool,
-    is_dow: bool,
-) -> Result<(u32, u32), ParseScheduleError> {
-    let l = parse_time_unit(left_range, is_vixie, is_dom, is_dow)?;
-    let r = parse_time_unit(right_range, is_vixie, is_dom, is_dow)?;
-    Ok((l, r))
-}
+    /// Returns whether no date ever satisfies the schedule, such as the 30th of February or the
+    /// 13th of February being a Friday in a range of years where it never is. `@reboot`
+    /// schedules fire at startup so they aren't reported.
+    /// ```rust
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let never_fires = |e| Schedule::from_str(e).unwrap().never_fires();
+    /// assert!(never_fires("0 0 0 30 2 *"));
+    /// assert!(never_fires("0 0 0 29 2 * 2021-2023"));
+    /// assert!(never_fires("0 0 0 13 2 Fri 2016-2025"));
+    /// assert!(!never_fires("0 0 0 29 2 *"));
+    /// assert!(!never_fires("0 0 0 13 * Fri"));
+    /// ```
+    pub fn never_fires(&self) -> bool {
+        if self.reboot {
+            return false;
+        }
+        if self.dates_impossible() {
+            return true;
+        }
+        match &self.years {
+            Years::Constrained(years) => !years.iter().any(|year| self.fires_in_year(*year)),
+            Years::All => !(MIN_YEAR..=MAX_YEAR).any(|year| self.fires_in_year(year)),
+            // the calendar, including days of week, repeats every 400 years
+            Years::Unbound => !(2000..2000 + HORIZON_YEARS).any(|year| self.fires_in_year(year)),
+        }
+    }
 
-fn parse_time_unit(
-    s: &str,
-    is_vixie: bool,
-    is_dom: bool,
-    is_dow: bool,
-) -> Result<u32, ParseScheduleError> {
-    let num;
-    if is_dom {
-        num = month(s)?;
-    } else if is_dow {
-        num = day_of_week(s, is_vixie)?;
-    } else {
-        num = s.parse()?;
+    /// Returns whether none of the days of month exist in any of the months, e.g. the 30th of
+    /// February or the 29th without a leap year, checked without scanning years so searches can
+    /// fail fast.
+    fn dates_impossible(&self) -> bool {
+        if !self.day_of_month_terms.is_empty() || self.effective_day_semantics() == DaySemantics::Or
+        {
+            return false;
+        }
+        let leap_year = match &self.years {
+            Years::Constrained(years) => years.iter().any(|year| is_leap_year(*year)),
+            Years::All | Years::Unbound => true,
+        };
+        let first_day = match self.days_of_month().iter().next() {
+            Some(day) => day,
+            None => return true,
+        };
+        !self.months().iter().any(|month| {
+            let year = if leap_year { 2000 } else { 2001 };
+            first_day <= days_in_month(month, year)
+        })
     }
-    Ok(num)
-}
 
-fn parse_field(
-    value: &str,
-    min: u32,
-    max: u32,
-    is_vixie: bool,
-    is_dow: bool,
-    is_dom: bool,
-) -> Result<TimeRange, ParseScheduleError> {
-    let mut set = BTreeSet::<u32>::new();
+    /// Returns an nth day of week term that can't fall on any of the days of month in any of the
+    /// months when both fields must match, e.g. a fifth Friday on the 1st to the 7th.
+    fn impossible_nth_day_of_week(&self) -> Option<DayOfWeekTerm> {
+        if !self.day_of_month_terms.is_empty() || self.effective_day_semantics() == DaySemantics::Or
+        {
+            return None;
+        }
+        let days = self.days_of_month();
+        let months = self.months();
+        self.day_of_week_terms
+            .iter()
+            .copied()
+            .find(|term| match *term {
+                DayOfWeekTerm::Nth(_, n) => !months.iter().any(|month| {
+                    let last = (n * 7).min(days_in_month(month, 2000));
+                    ((n - 1) * 7 + 1..=last).any(|day| days.contains(day))
+                }),
+                DayOfWeekTerm::Last(_) => false,
+            })
+    }
 
-    for v in value.split(',') {
-        let mut step_iter = v.splitn(2, '/');
-        let left_step = step_iter.next().unwrap();
-        let right_step = step_iter.next();
+    fn exhaustion_reason<T>(&self) -> Result<Option<T>, ExhaustionReason> {
+        if self.reboot {
+            return Ok(None);
+        }
+        if self.never_fires() {
+            return Err(ExhaustionReason::Unsatisfiable);
+        }
+        match &self.years {
+            Years::Constrained(_) => Ok(None),
+            Years::All => Err(ExhaustionReason::YearRangeExhausted),
+            Years::Unbound => Err(ExhaustionReason::HorizonReached),
+        }
+    }
 
-        let mut dash_iter = left_step.splitn(2, '-');
-        let left_dash = dash_iter.next().unwrap();
-        let right_dash = dash_iter.next();
+    /// Returns whether any day of the year matches the day of month, month and day of week.
+    pub(crate) fn fires_in_year(&self, year: u32) -> bool {
+        self.months()
+            .iter()
+            .any(|month| !self.days_in(month, year).is_empty())
+    }
 
-        match (left_dash, right_dash, right_step) {
-            (left_range, Some(right_range), Some(step_value)) => {
-                let (l, r) = parse_range(left_range, right_range, is_vixie, is_dom, is_dow)?;
+    /// Returns whether every field matches the supplied local datetime.
+    pub(crate) fn matches_local(&self, local: &NaiveDateTime) -> bool {
+        local.nanosecond() == 0
+            && self.matches_date(&local.date())
+            && self.matches_time(&local.time())
+    }
 
-                if l < min || l > max || r < min || r > max || l > r {
-                    return Err(ParseScheduleError::InvalidRange(v.into()));
-                }
+    /// Returns whether the years, months, days of month and days of week fields match the
+    /// supplied date.
+    pub(crate) fn matches_date(&self, date: &NaiveDate) -> bool {
+        let year = date.year() as u32;
+        let years_match = match &self.years {
+            Years::All => (MIN_YEAR..=MAX_YEAR).contains(&year),
+            Years::Constrained(years) => years.contains(&year),
+            Years::Unbound => true,
+        };
+        !self.reboot
+            && years_match
+            && self.months().contains(date.month())
+            && self.matches_day(date)
+    }
 
-                for i in (l..=r).step_by(step_value.parse()?) {
-                    set.insert(i);
-                }
+    /// Returns whether the days of month and days of week fields match the supplied date.
+    fn matches_day(&self, date: &NaiveDate) -> bool {
+        let day_of_month = self
+            .days_of_month_in(date.month(), date.year() as u32)
+            .contains(date.day());
+        match self.effective_day_semantics() {
+            DaySemantics::And => day_of_month && self.matches_day_of_week(date),
+            DaySemantics::Or => day_of_month || self.matches_day_of_week(date),
+        }
+    }
+
+    /// Returns the days of the supplied month matching the days of month and days of week
+    /// fields, so the next matching day is a single lookup.
+    fn days_in(&self, month: u32, year: u32) -> BitSet {
+        let days_of_month = self.days_of_month_in(month, year);
+        let days_of_week = self.days_of_week_in(month, year);
+        let days = match self.effective_day_semantics() {
+            DaySemantics::And => days_of_month.intersection(&days_of_week),
+            DaySemantics::Or => days_of_month.union(&days_of_week),
+        };
+        days.intersection(&BitSet::span(1, days_in_month(month, year)))
+    }
+
+    /// Returns the days of the supplied month falling on the days of week field.
+    fn days_of_week_in(&self, month: u32, year: u32) -> BitSet {
+        let first = match NaiveDate::from_ymd_opt(year as i32, month, 1) {
+            Some(first) => first.weekday().number_from_sunday(),
+            None => return BitSet::new(),
+        };
+        let days = days_in_month(month, year);
+        let mut matching = BitSet::new();
+        for day_of_week in self.days_of_week() {
+            let first_day = 1 + (day_of_week + 7 - first) % 7;
+            matching.extend((first_day..=days).step_by(7));
+        }
+        matching.extend(
+            self.day_of_week_terms
+                .iter()
+                .filter_map(|term| term.resolve(first, days)),
+        );
+        matching
+    }
+
+    /// Returns whether the days of week field matches the supplied date, which depends on the
+    /// month for terms such as the last Friday.
+    fn matches_day_of_week(&self, date: &NaiveDate) -> bool {
+        self.days_of_week()
+            .contains(date.weekday().number_from_sunday())
+            || self.day_of_week_terms.iter().any(|term| term.matches(date))
+    }
+
+    /// Returns whether the seconds, minutes and hours fields match the supplied time of day.
+    pub(crate) fn matches_time(&self, time: &NaiveTime) -> bool {
+        let seconds_match = match self.seconds {
+            Seconds::Ignore => time.second() == 0,
+            Seconds::Interval(interval) => {
+                time.num_seconds_from_midnight().is_multiple_of(interval)
             }
-            (left_range, Some(right_range), None) => {
-                let (l, r) = parse_range(left_range, right_range, is_vixie, is_dom, is_dow)?;
+            _ => self.seconds().contains(time.second()),
+        };
+        seconds_match
+            && self.minutes().contains(time.minute())
+            && self.hours().contains(time.hour())
+    }
 
-                if l < min || l > max || r < min || r > max || l > r {
-                    return Err(ParseScheduleError::InvalidRange(v.into()));
-                }
+    /// Returns the times of day matching the seconds, minutes and hours fields in order.
+    pub(crate) fn times_of_day(&self) -> Vec<NaiveTime> {
+        let mut times = Vec::new();
+        for hour in self.hours() {
+            for minute in self.minutes() {
+                times.extend(
+                    self.seconds_of_minute(hour, minute)
+                        .iter()
+                        .filter_map(|second| NaiveTime::from_hms_opt(hour, minute, second)),
+                );
+            }
+        }
+        times
+    }
 
-                if l == min && r == max {
-                    return Ok(TimeRange::All);
-                }
+    fn date<Z>(&self, dt: &DateTime<Z>, direction: Direction) -> DateTime<Z>
+    where
+        Z: TimeZone,
+    {
+        match direction {
+            Direction::Forward => dt.clone() + self.step(),
+            Direction::Back => dt.clone() - self.step(),
+        }
+    }
 
-                for i in l..=r {
-                    set.insert(i);
-                }
+    /// Returns the smallest possible duration between occurrences, a minute without a seconds
+    /// field.
+    fn step(&self) -> Duration {
+        if let Seconds::Ignore = self.seconds {
+            Duration::minutes(1)
+        } else {
+            Duration::seconds(1)
+        }
+    }
+
+    /// Truncates the datetime to the resolution of the schedule, occurrences are searched
+    /// strictly after or before it.
+    pub(crate) fn truncate_to_step<Z>(&self, dt: &DateTime<Z>) -> DateTime<Z>
+    where
+        Z: TimeZone,
+    {
+        let mut truncated = dt.clone() - Duration::nanoseconds(i64::from(dt.nanosecond()));
+        if let Seconds::Ignore = self.seconds {
+            truncated -= Duration::seconds(i64::from(dt.second()));
+        }
+        truncated
+    }
+
+    /// Returns the local time the search starts from, one step from the datetime. When the step
+    /// crosses into a DST gap whose times are resolved by the `DstGapPolicy` the search starts
+    /// within the gap so those times aren't passed over.
+    fn search_start<Z>(&self, dt: &DateTime<Z>, direction: Direction) -> NaiveDateTime
+    where
+        Z: TimeZone,
+    {
+        if self.dst_gap_policy != DstGapPolicy::Skip {
+            let local = match direction {
+                Direction::Forward => dt.naive_local() + self.step(),
+                Direction::Back => dt.naive_local() - self.step(),
+            };
+            if let LocalResult::None = dt.timezone().from_local_datetime(&local) {
+                return local;
             }
-            (left_most, None, Some(step_value)) => match left_most {
-                "*" => {
-                    for i in (min..=max).step_by(step_value.parse()?) {
-                        set.insert(i);
-                    }
-                }
-                _ => {
-                    let left = parse_time_unit(left_most, is_vixie, is_dom, is_dow)?;
+        }
+        self.date(dt, direction).naive_local()
+    }
 
-                    for i in (left..=max).step_by(step_value.parse()?) {
-                        set.insert(i);
-                    }
+    /// Returns the local time to search from in addition to `search_start` when the datetime
+    /// falls in a fold, where clocks fall back and local times repeat, and the local times of
+    /// the other pass of the fold are searched before it in the direction of the search.
+    fn fold_start<Z>(&self, dt: &DateTime<Z>, direction: Direction) -> Option<NaiveDateTime>
+    where
+        Z: TimeZone,
+    {
+        let local = dt.naive_local();
+        let (earliest, latest) = match dt.timezone().from_local_datetime(&local) {
+            LocalResult::Ambiguous(earliest, latest) => (earliest, latest),
+            _ => return None,
+        };
+        let offset = dt.offset().fix().local_minus_utc();
+        let other = if earliest.offset().fix().local_minus_utc() == offset {
+            latest
+        } else {
+            earliest
+        };
+        let delta = other.offset().fix().local_minus_utc() - offset;
+        let start = local + Duration::seconds(i64::from(delta));
+        match direction {
+            Direction::Forward if delta < 0 => Some(start),
+            Direction::Back if delta > 0 => Some(start),
+            _ => None,
+        }
+    }
+
+    /// Returns the datetime of the local time in the timezone, resolving times in a DST gap by
+    /// the `DstGapPolicy` and ambiguous times by the `AmbiguousTimePolicy`. With
+    /// `AmbiguousTimePolicy::Both` the first of the two instants beyond the bound is returned.
+    fn local_datetime<Z>(
+        &self,
+        timezone: &Z,
+        local: NaiveDateTime,
+        bound: &DateTime<Z>,
+        direction: Direction,
+    ) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        match timezone.from_local_datetime(&local) {
+            LocalResult::Single(dt) => Some(dt),
+            LocalResult::Ambiguous(earliest, latest) => match self.ambiguous_time_policy {
+                AmbiguousTimePolicy::Skip => {
+                    debug!(%local, "skipping ambiguous local time");
+                    None
                 }
+                AmbiguousTimePolicy::Earliest => Some(earliest),
+                AmbiguousTimePolicy::Latest => Some(latest),
+                AmbiguousTimePolicy::Both => match direction {
+                    Direction::Forward if earliest > *bound => Some(earliest),
+                    Direction::Forward => Some(latest),
+                    Direction::Back if latest < *bound => Some(latest),
+                    Direction::Back => Some(earliest),
+                },
             },
-            (left_most, None, None) => match left_most {
-                "*" => {
-                    return Ok(TimeRange::All);
+            LocalResult::None => match self.dst_gap_policy {
+                DstGapPolicy::Skip => {
+                    debug!(%local, "skipping local time that does not exist (DST gap)");
+                    None
                 }
-                _ => {
-                    let i = parse_time_unit(left_most, is_vixie, is_dom, is_dow)?;
-                    set.insert(i);
+                DstGapPolicy::FireAtGapEnd => gap_end(timezone, local),
+                DstGapPolicy::FireBeforeGap => {
+                    gap_end(timezone, local).map(|end| end - self.step())
                 }
             },
+        }
+    }
+
+    /// Returns the first year of the years field at or after the supplied one, up to
+    /// `last_year`.
+    fn next_year(&self, year: u32, last_year: u32) -> Option<u32> {
+        let next = match &self.years {
+            Years::All => year.max(MIN_YEAR),
+            Years::Constrained(years) => *years.range(year..).next()?,
+            Years::Unbound => year,
         };
+        Some(next).filter(|next| *next <= last_year)
     }
 
-    Ok(TimeRange::Constrained(set))
-}
+    /// Returns the last year of the years field at or before the supplied one, down to
+    /// `first_year`.
+    fn prev_year(&self, year: u32, first_year: u32) -> Option<u32> {
+        let prev = match &self.years {
+            Years::All => year.min(MAX_YEAR),
+            Years::Constrained(years) => *years.range(..=year).next_back()?,
+            Years::Unbound => year,
+        };
+        Some(prev).filter(|prev| *prev >= first_year)
+    }
 
-fn month(value: &str) -> Result<u32, ParseScheduleError> {
-    match value.to_uppercase().as_ref() {
-        "JAN" | "1" => Ok(1),
-        "FEB" | "2" => Ok(2),
-        "MAR" | "3" => Ok(3),
-        "APR" | "4" => Ok(4),
-        "MAY" | "5" => Ok(5),
-        "JUN" | "6" => Ok(6),
-        "JUL" | "7" => Ok(7),
-        "AUG" | "8" => Ok(8),
-        "SEP" | "9" => Ok(9),
-        "OCT" | "10" => Ok(10),
-        "NOV" | "11" => Ok(11),
-        "DEC" | "12" => Ok(12),
-        _ => Err(ParseScheduleError::InvalidMonthIndicator(value.into())),
+    fn months(&self) -> BitSet {
+        match self.months {
+            TimeRange::All => MONTHS,
+            TimeRange::Constrained(m) => m,
+        }
     }
-}
 
-fn day_of_week(value: &str, is_vixie: bool) -> Result<u32, ParseScheduleError> {
-    if is_vixie {
-        match value.to_uppercase().as_ref() {
-            "SUN" | "1" => Ok(1),
-            "MON" | "2" => Ok(2),
-            "TUE" | "3" => Ok(3),
-            "WED" | "4" => Ok(4),
-            "THU" | "5" => Ok(5),
-            "FRI" | "6" => Ok(6),
-            "SAT" | "7" => Ok(7),
-            _ => Err(ParseScheduleError::InvalidDayOfWeekIndicator(value.into())),
+    fn days_of_month(&self) -> BitSet {
+        match self.days_of_month {
+            TimeRange::All => DAYS,
+            TimeRange::Constrained(m) => m,
         }
-    } else {
-        match value.to_uppercase().as_ref() {
-            "SUN" | "0" | "7" => Ok(1),
-            "MON" | "1" => Ok(2),
-            "TUE" | "2" => Ok(3),
-            "WED" | "3" => Ok(4),
-            "THU" | "4" => Ok(5),
-            "FRI" | "5" => Ok(6),
-            "SAT" | "6" => Ok(7),
-            _ => Err(ParseScheduleError::InvalidDayOfWeekIndicator(value.into())),
+    }
+
+    /// Returns the days matching within the supplied month, which only vary by month for
+    /// terms such as the last day.
+    fn days_of_month_in(&self, month: u32, year: u32) -> BitSet {
+        let mut days = self.days_of_month();
+        days.extend(
+            self.day_of_month_terms
+                .iter()
+                .filter_map(|term| term.resolve(month, year)),
+        );
+        days
+    }
+
+    fn hours(&self) -> BitSet {
+        match self.hours {
+            TimeRange::All => HOURS,
+            TimeRange::Constrained(m) => m,
         }
     }
-}
 
-struct ScheduleIterator<'a, Z>
-where
-    Z: TimeZone,
-{
-    is_done: bool,
-    schedule: &'a Schedule,
-    previous_datetime: DateTime<Z>,
-}
+    fn minutes(&self) -> BitSet {
+        match self.minutes {
+            TimeRange::All => MINUTES_OR_SECONDS,
+            TimeRange::Constrained(m) => m,
+        }
+    }
 
-impl<'a, Z> ScheduleIterator<'a, Z>
-where
-    Z: TimeZone,
-{
-    fn new(schedule: &'a Schedule, starting_datetime: &DateTime<Z>) -> ScheduleIterator<'a, Z> {
-        ScheduleIterator {
-            is_done: false,
-            schedule,
-            previous_datetime: starting_datetime.clone(),
+    fn seconds(&self) -> BitSet {
+        match self.seconds {
+            Seconds::All => MINUTES_OR_SECONDS,
+            Seconds::Constrained(s) => s,
+            Seconds::Ignore | Seconds::Interval(_) => EMPTY,
         }
     }
-}
 
-impl<'a, Z> Iterator for ScheduleIterator<'a, Z>
-where
-    Z: TimeZone,
-{
-    type Item = DateTime<Z>;
+    /// Returns the seconds matching within the supplied minute, which only vary by minute for
+    /// intervals and are the first second when seconds are ignored.
+    fn seconds_of_minute(&self, hour: u32, minute: u32) -> BitSet {
+        match self.seconds {
+            Seconds::Ignore => BitSet::from([0]),
+            Seconds::Interval(interval) => {
+                let start = hour * 3600 + minute * 60;
+                let first = (interval - start % interval) % interval;
+                (first..60).step_by(interval as usize).collect()
+            }
+            _ => self.seconds(),
+        }
+    }
 
-    fn next(&mut self) -> Option<DateTime<Z>> {
-        if self.is_done {
+    fn days_of_week(&self) -> BitSet {
+        match self.days_of_week {
+            TimeRange::All => DAYS_OF_WEEK,
+            TimeRange::Constrained(dow) => dow,
+        }
+    }
+
+    /// Returns the interval of schedules firing every second, minute or hour whose occurrences
+    /// can be computed arithmetically instead of searching. It is derived from the fields on
+    /// demand rather than stored to keep the schedule small.
+    fn dense_interval(&self) -> Option<Duration> {
+        // times in DST gaps resolved by the policy aren't multiples of the interval
+        if self.dst_gap_policy != DstGapPolicy::Skip
+            || self.days_of_month != TimeRange::All
+            || self.months != TimeRange::All
+            || self.days_of_week != TimeRange::All
+            || matches!(self.years, Years::Constrained(_))
+        {
             return None;
         }
-        if let Some(next_datetime) = self.schedule.after(&self.previous_datetime) {
-            self.previous_datetime = next_datetime.clone();
-            Some(next_datetime)
-        } else {
-            self.is_done = true;
-            None
+
+        let zero = |field: &TimeRange| match field {
+            TimeRange::Constrained(set) => set.len() == 1 && set.contains(0),
+            TimeRange::All => false,
+        };
+        let zero_seconds = match &self.seconds {
+            Seconds::Ignore => true,
+            Seconds::Constrained(set) => set.len() == 1 && set.contains(0),
+            Seconds::All | Seconds::Interval(_) => false,
+        };
+
+        match (&self.seconds, &self.minutes, &self.hours) {
+            (Seconds::All, TimeRange::All, TimeRange::All) => Some(Duration::seconds(1)),
+            // intervals dividing the day stay aligned to midnight across days
+            (Seconds::Interval(interval), TimeRange::All, TimeRange::All)
+                if SECONDS_PER_DAY.is_multiple_of(*interval) =>
+            {
+                Some(Duration::seconds(i64::from(*interval)))
+            }
+            (_, TimeRange::All, TimeRange::All) if zero_seconds => Some(Duration::minutes(1)),
+            (_, minutes, TimeRange::All) if zero_seconds && zero(minutes) => {
+                Some(Duration::hours(1))
+            }
+            _ => None,
         }
     }
-}
 
-impl<'a, Z> DoubleEndedIterator for ScheduleIterator<'a, Z>
-where
-    Z: TimeZone,
-{
-    fn next_back(&mut self) -> Option<DateTime<Z>> {
-        if self.is_done {
+    /// Computes the next or previous occurrence of a schedule with a `dense_interval`, returning
+    /// `None` when the generic search must be used instead.
+    fn dense<Z>(
+        &self,
+        dt: &DateTime<Z>,
+        interval: Duration,
+        direction: Direction,
+    ) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        // the other pass of a fold the datetime falls in is left to the search
+        if self.ambiguous_time_policy != AmbiguousTimePolicy::Skip
+            && self.fold_start(dt, direction).is_some()
+        {
             return None;
         }
-        if let Some(next_datetime) = self.schedule.before(&self.previous_datetime) {
-            self.previous_datetime = next_datetime.clone();
-            Some(next_datetime)
-        } else {
-            self.is_done = true;
-            None
+        let timezone = dt.timezone();
+        let forward = matches!(direction, Direction::Forward);
+        let start = self.date(dt, direction).naive_local().with_nanosecond(0)?;
+
+        let interval_seconds = interval.num_seconds();
+        let mut candidate = truncate(start, interval_seconds)?;
+        if forward {
+            // the seconds of the start are not considered when they are ignored
+            let start = match self.seconds {
+                Seconds::Ignore => truncate(start, 60)?,
+                _ => start,
+            };
+            if candidate < start {
+                candidate += interval;
+            }
+        }
+
+        loop {
+            if self.years == Years::All
+                && !(MIN_YEAR as i32..=MAX_YEAR as i32).contains(&candidate.year())
+            {
+                return None;
+            }
+            match timezone.from_local_datetime(&candidate) {
+                LocalResult::Single(dt) => return Some(dt),
+                // ambiguous times are resolved by the search
+                LocalResult::Ambiguous(_, _)
+                    if self.ambiguous_time_policy != AmbiguousTimePolicy::Skip =>
+                {
+                    return None
+                }
+                _ => {}
+            }
+            if forward {
+                candidate += interval;
+            } else {
+                candidate -= interval;
+            }
+        }
+    }
+
+    pub(crate) fn before<Z>(&self, dt: &DateTime<Z>) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        if self.reboot || self.dates_impossible() {
+            return None;
+        }
+        #[cfg(feature = "tz")]
+        if let Some(timezone) = self.timezone {
+            return self
+                .before_in_zone(&dt.with_timezone(&timezone))
+                .map(|found| found.with_timezone(&dt.timezone()));
+        }
+        self.before_in_zone(dt)
+    }
+
+    /// Searches in the zone of the supplied datetime.
+    fn before_in_zone<Z>(&self, dt: &DateTime<Z>) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        if let Some(interval) = self.dense_interval() {
+            if let Some(prev) = self.dense(dt, interval, Direction::Back) {
+                return Some(prev);
+            }
+        }
+        self.search_before(dt)
+    }
+
+    fn search_before<Z>(&self, dt: &DateTime<Z>) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        let bound = self.truncate_to_step(dt);
+        let found = self.search_before_from(&bound, self.search_start(dt, Direction::Back));
+        match self.fold_start(dt, Direction::Back) {
+            Some(start) => match (found, self.search_before_from(&bound, start)) {
+                (Some(a), Some(b)) => Some(a.max(b)),
+                (a, b) => a.or(b),
+            },
+            None => found,
+        }
+    }
+
+    pub(crate) fn after<Z>(&self, dt: &DateTime<Z>) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        if self.reboot || self.dates_impossible() {
+            return None;
+        }
+        #[cfg(feature = "tz")]
+        if let Some(timezone) = self.timezone {
+            return self
+                .after_in_zone(&dt.with_timezone(&timezone))
+                .map(|found| found.with_timezone(&dt.timezone()));
+        }
+        self.after_in_zone(dt)
+    }
+
+    /// Searches in the zone of the supplied datetime.
+    fn after_in_zone<Z>(&self, dt: &DateTime<Z>) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        if let Some(interval) = self.dense_interval() {
+            if let Some(next) = self.dense(dt, interval, Direction::Forward) {
+                return Some(next);
+            }
         }
+        self.search_after(dt)
+    }
+
+    fn search_after<Z>(&self, dt: &DateTime<Z>) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        let bound = self.truncate_to_step(dt);
+        let found = self.search_after_from(&bound, self.search_start(dt, Direction::Forward));
+        match self.fold_start(dt, Direction::Forward) {
+            Some(start) => match (found, self.search_after_from(&bound, start)) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            },
+            None => found,
+        }
+    }
+
+    /// Searches from the local time for the first occurrence strictly before the bound.
+    fn search_before_from<Z>(&self, bound: &DateTime<Z>, dt: NaiveDateTime) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        let timezone = bound.timezone();
+        let first_year = match self.years {
+            Years::Unbound => (dt.year() as u32).saturating_sub(HORIZON_YEARS),
+            _ => MIN_YEAR,
+        };
+        let mut local = self.truncate_local(dt)?;
+        loop {
+            let found = self.prev_local(local, first_year)?;
+            // times in a DST gap or fold may resolve to the other side of the bound
+            match self.local_datetime(&timezone, found, bound, Direction::Back) {
+                Some(candidate) if candidate < *bound => return Some(candidate),
+                _ => local = found.checked_sub_signed(Duration::seconds(1))?,
+            }
+        }
+    }
+
+    /// Searches from the local time for the first occurrence strictly after the bound.
+    fn search_after_from<Z>(&self, bound: &DateTime<Z>, dt: NaiveDateTime) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        let timezone = bound.timezone();
+        let last_year = match self.years {
+            Years::Unbound => (dt.year() as u32).saturating_add(HORIZON_YEARS),
+            _ => MAX_YEAR,
+        };
+        let mut local = self.truncate_local(dt)?;
+        loop {
+            let found = self.next_local(local, last_year)?;
+            // times in a DST gap or fold may resolve to the other side of the bound
+            match self.local_datetime(&timezone, found, bound, Direction::Forward) {
+                Some(candidate) if candidate > *bound => return Some(candidate),
+                _ => local = found.checked_add_signed(Duration::seconds(1))?,
+            }
+        }
+    }
+
+    /// Drops the parts of the local time finer than the fields of the schedule.
+    fn truncate_local(&self, local: NaiveDateTime) -> Option<NaiveDateTime> {
+        match self.seconds {
+            Seconds::Ignore => local.with_second(0)?.with_nanosecond(0),
+            _ => local.with_nanosecond(0),
+        }
+    }
+
+    /// Returns the first local time at or after the supplied one matching every field, up to
+    /// the end of `last_year`.
+    ///
+    /// Each field is advanced to its next matching value with a single lookup, carrying into
+    /// the next larger unit when none is left, so the cost depends on the number of carries
+    /// rather than the number of times skipped.
+    fn next_local(&self, mut local: NaiveDateTime, last_year: u32) -> Option<NaiveDateTime> {
+        let start_of = |year: u32, month: u32, day: u32| {
+            NaiveDate::from_ymd_opt(year as i32, month, day)?.and_hms_opt(0, 0, 0)
+        };
+        loop {
+            let date = local.date();
+            let (year, month, day) = (date.year() as u32, date.month(), date.day());
+            let (hour, minute, second) = (local.hour(), local.minute(), local.second());
+
+            let next_year = self.next_year(year, last_year)?;
+            if next_year != year {
+                local = start_of(next_year, 1, 1)?;
+                continue;
+            }
+            match self.months().range(month..).next() {
+                Some(next) if next == month => {}
+                Some(next) => {
+                    local = start_of(year, next, 1)?;
+                    continue;
+                }
+                None => {
+                    local = start_of(year + 1, 1, 1)?;
+                    continue;
+                }
+            }
+            match self.days_in(month, year).range(day..).next() {
+                Some(next) if next == day => {}
+                Some(next) => {
+                    local = start_of(year, month, next)?;
+                    continue;
+                }
+                None => {
+                    debug!(year, month, "no day left in the month matches");
+                    local = match month {
+                        12 => start_of(year + 1, 1, 1)?,
+                        _ => start_of(year, month + 1, 1)?,
+                    };
+                    continue;
+                }
+            }
+            match self.hours().range(hour..).next() {
+                Some(next) if next == hour => {}
+                Some(next) => {
+                    local = date.and_hms_opt(next, 0, 0)?;
+                    continue;
+                }
+                None => {
+                    local = date.succ_opt()?.and_hms_opt(0, 0, 0)?;
+                    continue;
+                }
+            }
+            match self.minutes().range(minute..).next() {
+                Some(next) if next == minute => {}
+                Some(next) => {
+                    local = date.and_hms_opt(hour, next, 0)?;
+                    continue;
+                }
+                None => {
+                    local = date.and_hms_opt(hour, 0, 0)? + Duration::hours(1);
+                    continue;
+                }
+            }
+            match self.seconds_of_minute(hour, minute).range(second..).next() {
+                Some(next) => return date.and_hms_opt(hour, minute, next),
+                None => local = date.and_hms_opt(hour, minute, 0)? + Duration::minutes(1),
+            }
+        }
+    }
+
+    /// Returns the last local time at or before the supplied one matching every field, down to
+    /// the start of `first_year`, carrying into the previous larger unit as `next_local` does.
+    fn prev_local(&self, mut local: NaiveDateTime, first_year: u32) -> Option<NaiveDateTime> {
+        let end_of = |year: u32, month: u32, day: u32| {
+            NaiveDate::from_ymd_opt(year as i32, month, day)?.and_hms_opt(23, 59, 59)
+        };
+        let before = |local: NaiveDateTime| local.checked_sub_signed(Duration::seconds(1));
+        loop {
+            let date = local.date();
+            let (year, month, day) = (date.year() as u32, date.month(), date.day());
+            let (hour, minute, second) = (local.hour(), local.minute(), local.second());
+
+            let prev_year = self.prev_year(year, first_year)?;
+            if prev_year != year {
+                local = end_of(prev_year, 12, 31)?;
+                continue;
+            }
+            match self.months().range(..=month).next_back() {
+                Some(prev) if prev == month => {}
+                Some(prev) => {
+                    local = end_of(year, prev, days_in_month(prev, year))?;
+                    continue;
+                }
+                None => {
+                    local = before(date.with_ordinal(1)?.and_hms_opt(0, 0, 0)?)?;
+                    continue;
+                }
+            }
+            match self.days_in(month, year).range(..=day).next_back() {
+                Some(prev) if prev == day => {}
+                Some(prev) => {
+                    local = end_of(year, month, prev)?;
+                    continue;
+                }
+                None => {
+                    debug!(year, month, "no earlier day in the month matches");
+                    local = before(date.with_day(1)?.and_hms_opt(0, 0, 0)?)?;
+                    continue;
+                }
+            }
+            match self.hours().range(..=hour).next_back() {
+                Some(prev) if prev == hour => {}
+                Some(prev) => {
+                    local = date.and_hms_opt(prev, 59, 59)?;
+                    continue;
+                }
+                None => {
+                    local = before(date.and_hms_opt(0, 0, 0)?)?;
+                    continue;
+                }
+            }
+            match self.minutes().range(..=minute).next_back() {
+                Some(prev) if prev == minute => {}
+                Some(prev) => {
+                    local = date.and_hms_opt(hour, prev, 59)?;
+                    continue;
+                }
+                None => {
+                    local = before(date.and_hms_opt(hour, 0, 0)?)?;
+                    continue;
+                }
+            }
+            match self
+                .seconds_of_minute(hour, minute)
+                .range(..=second)
+                .next_back()
+            {
+                Some(prev) => return date.and_hms_opt(hour, minute, prev),
+                None => local = before(date.and_hms_opt(hour, minute, 0)?)?,
+            }
+        }
+    }
+}
+
+/// Truncates the local time to a multiple of the interval within its day.
+fn truncate(naive: NaiveDateTime, interval_seconds: i64) -> Option<NaiveDateTime> {
+    let seconds = i64::from(naive.num_seconds_from_midnight());
+    let truncated = (seconds - seconds % interval_seconds) as u32;
+    naive
+        .date()
+        .and_hms_opt(truncated / 3600, truncated % 3600 / 60, truncated % 60)
+}
+
+/// Returns the first instant after the DST gap containing the local time, the first whole
+/// minute of local time that exists.
+fn gap_end<Z>(timezone: &Z, local: NaiveDateTime) -> Option<DateTime<Z>>
+where
+    Z: TimeZone,
+{
+    let local = local.with_second(0)?;
+    // gaps last an hour in most zones and never more than a day
+    (1..=24 * 60).find_map(|minutes| {
+        timezone
+            .from_local_datetime(&(local + Duration::minutes(minutes)))
+            .earliest()
+    })
+}
+
+fn is_leap_year(year: u32) -> bool {
+    let by_four = year.is_multiple_of(4);
+    let by_hundred = year.is_multiple_of(100);
+    let by_four_hundred = year.is_multiple_of(400);
+    by_four && ((!by_hundred) || by_four_hundred)
+}
+
+fn days_in_month(month: u32, year: u32) -> u32 {
+    let is_leap_year = is_leap_year(year);
+    match month {
+        9 | 4 | 6 | 11 => 30,
+        2 if is_leap_year => 29,
+        2 => 28,
+        _ => 31,
+    }
+}
+
+impl FromStr for Schedule {
+    type Err = ParseScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(parse_expression(s, &ParseOptions::default())?)
+    }
+}
+
+impl Schedule {
+    /// Parses the expression as `Schedule::from_str` does, locating errors within the expression
+    /// so they can be underlined, e.g. by an editor.
+    /// ```rust
+    /// use cron_exp::{Field, ParseScheduleError, Schedule};
+    ///
+    /// let error = Schedule::parse_spanned("0 30 9,25 * * *").unwrap_err();
+    /// assert_eq!(Some(Field::Hours), error.field());
+    /// assert_eq!(7..9, error.span());
+    /// assert_eq!(
+    ///     &ParseScheduleError::InvalidRange {
+    ///         field: Field::Hours,
+    ///         value: "25".into()
+    ///     },
+    ///     error.error()
+    /// );
+    /// ```
+    pub fn parse_spanned(expression: &str) -> Result<Schedule, SpannedParseError> {
+        parse_expression(expression, &ParseOptions::default())
+    }
+
+    /// Parses the expression as `Schedule::from_str` does, reporting the errors of every invalid
+    /// field rather than only the first, e.g. to show them all in a form at once.
+    ///
+    /// Errors about the whole expression, such as its number of fields, are reported alone.
+    /// ```rust
+    /// use cron_exp::{Field, Schedule};
+    ///
+    /// let errors = Schedule::parse_all_errors("0 75 25 * * Mon").unwrap_err();
+    /// let fields: Vec<_> = errors.iter().filter_map(|e| e.field()).collect();
+    /// assert_eq!(vec![Field::Minutes, Field::Hours], fields);
+    /// ```
+    pub fn parse_all_errors(expression: &str) -> Result<Schedule, Vec<SpannedParseError>> {
+        parse_expression_all(expression, &ParseOptions::default())
+    }
+}
+
+/// Parses an expression with an optional zone prefix, detecting the format from the number of
+/// fields unless supplied by the options.
+pub(crate) fn parse_expression(
+    s: &str,
+    options: &ParseOptions,
+) -> Result<Schedule, SpannedParseError> {
+    parse_expression_all(s, options).map_err(|mut errors| errors.remove(0))
+}
+
+/// Parses an expression as `parse_expression` does returning every field error in order.
+fn parse_expression_all(
+    s: &str,
+    options: &ParseOptions,
+) -> Result<Schedule, Vec<SpannedParseError>> {
+    let (timezone, rest) =
+        split_timezone_prefix(s).map_err(|e| vec![SpannedParseError::new(e, None, 0..s.len())])?;
+    let offset = s.len() - rest.len();
+    let schedule = parse_fields(rest, options).map_err(|errors| {
+        errors
+            .into_iter()
+            .map(|e| e.offset_by(offset))
+            .collect::<Vec<_>>()
+    })?;
+    match timezone {
+        Some(name) => schedule.in_named_timezone(name).map_err(|e| {
+            // the name is followed by the fields
+            vec![SpannedParseError::new(e, None, offset - name.len()..offset)]
+        }),
+        None => Ok(schedule),
+    }
+}
+
+impl Schedule {
+    #[cfg(feature = "tz")]
+    fn in_named_timezone(self, name: &str) -> Result<Schedule, ParseScheduleError> {
+        let timezone =
+            Tz::from_str(name).map_err(|_| ParseScheduleError::InvalidTimezone(name.into()))?;
+        Ok(self.with_timezone(timezone))
+    }
+
+    /// Zones are only known with the `tz` feature.
+    #[cfg(not(feature = "tz"))]
+    fn in_named_timezone(self, name: &str) -> Result<Schedule, ParseScheduleError> {
+        Err(ParseScheduleError::InvalidTimezone(name.into()))
+    }
+}
+
+/// The prefixes naming the zone an expression is evaluated in.
+const TZ_PREFIXES: [&str; 2] = ["CRON_TZ=", "TZ="];
+
+/// Splits a leading `CRON_TZ=` or `TZ=` prefix from the expression returning the zone name.
+pub(crate) fn split_timezone_prefix(s: &str) -> Result<(Option<&str>, &str), ParseScheduleError> {
+    let s = s.trim_start();
+    for prefix in TZ_PREFIXES.iter() {
+        if let Some(rest) = s.strip_prefix(prefix) {
+            let end = rest
+                .find(char::is_whitespace)
+                .ok_or(ParseScheduleError::ArgumentCount)?;
+            return Ok((Some(&rest[..end]), &rest[end..]));
+        }
+    }
+    Ok((None, s))
+}
+
+/// Splits the expression into its whitespace separated fields along with their byte offsets.
+fn split_fields(s: &str) -> Vec<(usize, &str)> {
+    let mut fields = Vec::new();
+    let mut start = None;
+    for (i, c) in s.char_indices() {
+        match (c.is_whitespace(), start) {
+            (true, Some(from)) => {
+                fields.push((from, &s[from..i]));
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => {}
+        }
+    }
+    if let Some(from) = start {
+        fields.push((from, &s[from..]));
+    }
+    fields
+}
+
+/// Parses the fields of an expression without a zone prefix, returning the errors of every
+/// invalid field.
+fn parse_fields(s: &str, options: &ParseOptions) -> Result<Schedule, Vec<SpannedParseError>> {
+    let mut fields = split_fields(s);
+    let whole = |e: ParseScheduleError| vec![SpannedParseError::new(e, None, 0..s.len())];
+    if fields.len() == 1 && fields[0].1 == REBOOT {
+        return Ok(Schedule {
+            seconds: Seconds::Ignore,
+            minutes: TimeRange::All,
+            hours: TimeRange::All,
+            days_of_month: TimeRange::All,
+            day_of_month_terms: BTreeSet::new(),
+            days_of_month_wildcard: true,
+            months: TimeRange::All,
+            days_of_week: TimeRange::All,
+            day_of_week_terms: BTreeSet::new(),
+            days_of_week_wildcard: true,
+            years: Years::Unbound,
+            syntax: Capabilities::NONE,
+            reboot: true,
+            day_semantics: options.day_semantics,
+            dst_gap_policy: options.dst_gap_policy,
+            ambiguous_time_policy: options.ambiguous_time_policy,
+            #[cfg(feature = "tz")]
+            timezone: None,
+        });
+    }
+    if options.wildcard_missing_fields {
+        let count = options
+            .format
+            .map_or(CronFormat::Crontab.field_count(), CronFormat::field_count);
+        while fields.len() < count {
+            fields.push((s.len(), "*"));
+        }
+    }
+    let format = match options.format {
+        Some(format) if format.field_count() == fields.len() => format,
+        Some(_) => return Err(whole(ParseScheduleError::ArgumentCount)),
+        None if !options.auto_detect => return Err(whole(ParseScheduleError::FormatRequired)),
+        None => CronFormat::detect(fields.len())
+            .ok_or_else(|| whole(ParseScheduleError::ArgumentCount))?,
+    };
+    let (seconds_field, rest) = if format.has_seconds() {
+        if !options.allow_seconds {
+            return Err(vec![SpannedParseError::in_field(
+                ParseScheduleError::SecondsNotAllowed,
+                Field::Seconds,
+                fields[0],
+            )]);
+        }
+        (Some(fields[0]), &fields[1..])
+    } else {
+        (None, &fields[..])
+    };
+    let lenient = options.lenient_steps;
+    let names = Names {
+        numbering: options
+            .day_of_week_numbering
+            .unwrap_or_else(|| format.day_of_week_numbering()),
+        locale: options.locale,
+    };
+
+    let seconds = seconds_field
+        .map(|value| parse_seconds(value.1, lenient).map_err(located(Field::Seconds, value)))
+        .transpose();
+    let minutes = parse_field(rest[0].1, Field::Minutes, names, lenient)
+        .map_err(located(Field::Minutes, rest[0]));
+    let hours = parse_field(rest[1].1, Field::Hours, names, lenient)
+        .map_err(located(Field::Hours, rest[1]));
+    let days_of_month = parse_days_of_month(rest[2].1, names, lenient)
+        .map_err(located(Field::DaysOfMonth, rest[2]));
+    let months = parse_field(rest[3].1, Field::Months, names, lenient)
+        .map_err(located(Field::Months, rest[3]));
+    let days_of_week =
+        parse_days_of_week(rest[4].1, names, lenient).map_err(located(Field::DaysOfWeek, rest[4]));
+
+    let raw: Vec<&str> = fields.iter().map(|(_, value)| *value).collect();
+    let mut syntax = Capabilities::from_fields(&raw, format.has_seconds());
+    let years = match rest.get(5) {
+        Some(&value) => {
+            syntax |= Capabilities::YEARS;
+            parse_years_field(value.1, names, lenient).map_err(located(Field::Years, value))
+        }
+        None if format.has_seconds() => Ok(Years::All),
+        None => Ok(Years::Unbound),
+    };
+
+    let (
+        seconds,
+        minutes,
+        hours,
+        (days_of_month, day_of_month_terms),
+        months,
+        (days_of_week, day_of_week_terms),
+        years,
+    ) = match (
+        seconds,
+        minutes,
+        hours,
+        days_of_month,
+        months,
+        days_of_week,
+        years,
+    ) {
+        (Ok(a), Ok(b), Ok(c), Ok(d), Ok(e), Ok(f), Ok(g)) => (a, b, c, d, e, f, g),
+        (a, b, c, d, e, f, g) => {
+            let errors = vec![
+                a.err(),
+                b.err(),
+                c.err(),
+                d.err(),
+                e.err(),
+                f.err(),
+                g.err(),
+            ];
+            return Err(errors.into_iter().flatten().collect());
+        }
+    };
+    let (seconds, minutes, hours) = match seconds {
+        Some((interval, seconds)) => lower_interval(interval, seconds, minutes, hours),
+        None => (Seconds::Ignore, minutes, hours),
+    };
+
+    let schedule = Schedule {
+        seconds,
+        minutes,
+        hours,
+        days_of_month,
+        day_of_month_terms,
+        days_of_month_wildcard: is_wildcard(rest[2].1),
+        months,
+        days_of_week,
+        day_of_week_terms,
+        days_of_week_wildcard: is_wildcard(rest[4].1),
+        years,
+        syntax,
+        reboot: false,
+        day_semantics: options.day_semantics,
+        dst_gap_policy: options.dst_gap_policy,
+        ambiguous_time_policy: options.ambiguous_time_policy,
+        #[cfg(feature = "tz")]
+        timezone: None,
+    };
+    if let Some(term) = schedule.impossible_nth_day_of_week() {
+        let item = rest[4]
+            .1
+            .split(',')
+            .find(|item| parse_day_of_week_term(item, names) == Ok(Some(term)))
+            .unwrap_or(rest[4].1);
+        return Err(vec![SpannedParseError::in_field(
+            ParseScheduleError::ImpossibleNthDayOfWeek(item.into()),
+            Field::DaysOfWeek,
+            rest[4],
+        )]);
+    }
+    Ok(schedule)
+}
+
+/// Returns a function locating errors within the field of the expression.
+fn located(
+    field: Field,
+    value: (usize, &str),
+) -> impl Fn(ParseScheduleError) -> SpannedParseError + '_ {
+    move |e| SpannedParseError::in_field(e, field, value)
+}
+
+/// Parses the seconds field returning any `*/Ns` seconds interval separately.
+fn parse_seconds(
+    value: &str,
+    lenient_steps: bool,
+) -> Result<(Option<u32>, TimeRange), ParseScheduleError> {
+    let invalid_interval = || ParseScheduleError::InvalidStepRange {
+        field: Field::Seconds,
+        value: value.into(),
+    };
+    let interval = match value.strip_prefix("*/").and_then(|v| v.strip_suffix('s')) {
+        Some(interval) => Some(interval.parse::<u32>().map_err(|_| invalid_interval())?),
+        None => None,
+    };
+    let seconds = match interval {
+        Some(interval) if interval == 0 || interval > SECONDS_PER_DAY => {
+            return Err(invalid_interval())
+        }
+        // intervals dividing a minute are plain steps of the seconds field
+        Some(interval) if 60_u32.is_multiple_of(interval) => parse_field(
+            &format!("*/{}", interval),
+            Field::Seconds,
+            Names::new(DayOfWeekNumbering::SundayOne),
+            false,
+        )?,
+        Some(_) => TimeRange::All,
+        None => parse_field(
+            value,
+            Field::Seconds,
+            Names::new(DayOfWeekNumbering::SundayOne),
+            lenient_steps,
+        )?,
+    };
+    Ok((interval, seconds))
+}
+
+/// Lowers a `*/Ns` seconds interval to steps of the individual fields whenever they can express
+/// it.
+fn lower_interval(
+    interval: Option<u32>,
+    seconds: TimeRange,
+    minutes: TimeRange,
+    hours: TimeRange,
+) -> (Seconds, TimeRange, TimeRange) {
+    let steps = |step: u32, max: u32| -> TimeRange {
+        TimeRange::Constrained((0..=max).step_by(step as usize).collect())
+    };
+    let zero = || TimeRange::Constrained(BitSet::from([0]));
+    let zero_seconds = || Seconds::Constrained(BitSet::from([0]));
+    match interval {
+        Some(interval) if interval.is_multiple_of(60) && 3600_u32.is_multiple_of(interval) => (
+            zero_seconds(),
+            intersect(&minutes, &steps(interval / 60, 59), 0, 59),
+            hours,
+        ),
+        Some(interval)
+            if interval.is_multiple_of(3600) && SECONDS_PER_DAY.is_multiple_of(interval) =>
+        {
+            (
+                zero_seconds(),
+                intersect(&minutes, &zero(), 0, 59),
+                intersect(&hours, &steps(interval / 3600, 23), 0, 23),
+            )
+        }
+        Some(interval) if !60_u32.is_multiple_of(interval) => {
+            (Seconds::Interval(interval), minutes, hours)
+        }
+        _ => {
+            let seconds = match seconds {
+                TimeRange::All => Seconds::All,
+                TimeRange::Constrained(set) => Seconds::Constrained(set),
+            };
+            (seconds, minutes, hours)
+        }
+    }
+}
+
+/// Returns the values present in both fields, normalized to `TimeRange::All` when covering the
+/// whole field.
+fn intersect(a: &TimeRange, b: &TimeRange, min: u32, max: u32) -> TimeRange {
+    let set = match (a, b) {
+        (TimeRange::All, TimeRange::All) => return TimeRange::All,
+        (TimeRange::All, TimeRange::Constrained(set))
+        | (TimeRange::Constrained(set), TimeRange::All) => *set,
+        (TimeRange::Constrained(a), TimeRange::Constrained(b)) => a.intersection(b),
+    };
+    if set.len() as u32 == max - min + 1 {
+        return TimeRange::All;
+    }
+    TimeRange::Constrained(set)
+}
+
+/// Returns whether a field leaves it unconstrained by name rather than by covering every value.
+fn is_wildcard(value: &str) -> bool {
+    value == "*" || value == "?"
+}
+
+/// Parses the day of month field separating the terms resolved against each month from the
+/// plain days.
+fn parse_days_of_month(
+    value: &str,
+    names: Names,
+    lenient_steps: bool,
+) -> Result<(TimeRange, BTreeSet<DayOfMonthTerm>), ParseScheduleError> {
+    // Quartz requires `?` in one of the day fields to leave it unconstrained
+    if value == "?" {
+        return Ok((TimeRange::All, BTreeSet::new()));
+    }
+    let mut terms = BTreeSet::new();
+    let mut days = Vec::new();
+    for v in value.split(',') {
+        match parse_day_of_month_term(v)? {
+            Some(term) => {
+                terms.insert(term);
+            }
+            None => days.push(v),
+        }
+    }
+    if terms.is_empty() {
+        return Ok((
+            parse_field(value, Field::DaysOfMonth, names, lenient_steps)?,
+            terms,
+        ));
+    }
+
+    let days = if days.is_empty() {
+        TimeRange::Constrained(BitSet::new())
+    } else {
+        parse_field(&days.join(","), Field::DaysOfMonth, names, lenient_steps)?
+    };
+    // terms can't add to a field already matching every day
+    if days == TimeRange::All {
+        terms.clear();
+    }
+    Ok((days, terms))
+}
+
+fn parse_day_of_month_term(value: &str) -> Result<Option<DayOfMonthTerm>, ParseScheduleError> {
+    if value.eq_ignore_ascii_case("L") {
+        return Ok(Some(DayOfMonthTerm::Last(0)));
+    }
+    if value.eq_ignore_ascii_case("LW") {
+        return Ok(Some(DayOfMonthTerm::LastWeekday));
+    }
+    let invalid = || ParseScheduleError::InvalidRange {
+        field: Field::DaysOfMonth,
+        value: value.into(),
+    };
+    if let Some(day) = value.strip_suffix('W').or_else(|| value.strip_suffix('w')) {
+        let day = parse_number(day, Field::DaysOfMonth)?;
+        if !(1..=31).contains(&day) {
+            return Err(invalid());
+        }
+        return Ok(Some(DayOfMonthTerm::NearestWeekday(day)));
+    }
+    match value
+        .strip_prefix("L-")
+        .or_else(|| value.strip_prefix("l-"))
+    {
+        Some(offset) => {
+            let offset = parse_number(offset, Field::DaysOfMonth)?;
+            if offset > 30 {
+                return Err(invalid());
+            }
+            Ok(Some(DayOfMonthTerm::Last(offset)))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Parses the day of week field separating the terms resolved against each month from the
+/// plain days.
+fn parse_days_of_week(
+    value: &str,
+    names: Names,
+    lenient_steps: bool,
+) -> Result<(TimeRange, BTreeSet<DayOfWeekTerm>), ParseScheduleError> {
+    // Quartz requires `?` in one of the day fields to leave it unconstrained
+    if value == "?" {
+        return Ok((TimeRange::All, BTreeSet::new()));
+    }
+    let mut terms = BTreeSet::new();
+    let mut days = Vec::new();
+    for v in value.split(',') {
+        match parse_day_of_week_term(v, names)? {
+            Some(term) => {
+                terms.insert(term);
+            }
+            None => days.push(v),
+        }
+    }
+    if terms.is_empty() {
+        return Ok((
+            parse_field(value, Field::DaysOfWeek, names, lenient_steps)?,
+            terms,
+        ));
+    }
+
+    let days = if days.is_empty() {
+        TimeRange::Constrained(BitSet::new())
+    } else {
+        parse_field(&days.join(","), Field::DaysOfWeek, names, lenient_steps)?
+    };
+    // terms can't add to days already matched every week
+    match &days {
+        TimeRange::All => terms.clear(),
+        TimeRange::Constrained(days) => terms.retain(|term| !days.contains(term.day_of_week())),
+    }
+    Ok((days, terms))
+}
+
+fn parse_day_of_week_term(
+    value: &str,
+    names: Names,
+) -> Result<Option<DayOfWeekTerm>, ParseScheduleError> {
+    if let Some((day, n)) = value.split_once('#') {
+        let n = parse_number(n, Field::DaysOfWeek)?;
+        // no day of week occurs more than five times in a month
+        if !(1..=5).contains(&n) {
+            return Err(ParseScheduleError::InvalidNthDayOfWeek(value.into()));
+        }
+        return Ok(Some(DayOfWeekTerm::Nth(day_of_week(day, names)?, n)));
+    }
+    match value.strip_suffix('L').or_else(|| value.strip_suffix('l')) {
+        Some(day) if !day.is_empty() => Ok(Some(DayOfWeekTerm::Last(day_of_week(day, names)?))),
+        _ => Ok(None),
+    }
+}
+
+/// Parses a step value rejecting steps of zero or, unless lenient, larger than the field, which
+/// would silently match the first value only.
+fn parse_step(
+    step: &str,
+    field: Field,
+    value: &str,
+    lenient: bool,
+) -> Result<usize, ParseScheduleError> {
+    let invalid = || ParseScheduleError::InvalidStepRange {
+        field,
+        value: value.into(),
+    };
+    let (min, max) = field.bounds();
+    let step: u32 = step.parse().map_err(|_| invalid())?;
+    if step == 0 || (step > max - min + 1 && !lenient) {
+        return Err(invalid());
+    }
+    Ok(step as usize)
+}
+
+fn parse_range(
+    left_range: &str,
+    right_range: &str,
+    field: Field,
+    names: Names,
+) -> Result<(u32, u32), ParseScheduleError> {
+    let l = parse_time_unit(left_range, field, names)?;
+    let r = parse_time_unit(right_range, field, names)?;
+    Ok((l, r))
+}
+
+fn parse_time_unit(s: &str, field: Field, names: Names) -> Result<u32, ParseScheduleError> {
+    match field {
+        Field::Months => month(s, names),
+        Field::DaysOfWeek => day_of_week(s, names),
+        _ => parse_number(s, field),
+    }
+}
+
+fn parse_number(s: &str, field: Field) -> Result<u32, ParseScheduleError> {
+    s.parse().map_err(|_| ParseScheduleError::InvalidNumber {
+        field,
+        value: s.into(),
+    })
+}
+
+impl Field {
+    /// Returns the smallest and largest values of the field, days of week numbered from 1.
+    pub(crate) fn bounds(self) -> (u32, u32) {
+        match self {
+            Field::Seconds | Field::Minutes => (0, 59),
+            Field::Hours => (0, 23),
+            Field::DaysOfMonth => (1, 31),
+            Field::Months => (1, 12),
+            Field::DaysOfWeek => (1, 7),
+            Field::Years => (MIN_YEAR, MAX_YEAR),
+        }
+    }
+}
+
+/// Returns the values of the range in order, wrapping around the end of the field when the left
+/// value is greater such as `22-2` hours or `Fri-Mon`.
+fn range_values(l: u32, r: u32, min: u32, max: u32) -> Vec<u32> {
+    if l <= r {
+        (l..=r).collect()
+    } else {
+        (l..=max).chain(min..=r).collect()
+    }
+}
+
+/// Returns every `step`th value from `left`, or the start of the field for `*`, to `right`, or the
+/// end of the field. Days of week are stepped through in the order of their numbering so `*/2`
+/// with Monday numbered 1 matches Monday, Wednesday, Friday and Sunday.
+fn step_values(
+    left: &str,
+    right: Option<&str>,
+    step: usize,
+    field: Field,
+    names: Names,
+    value: &str,
+) -> Result<Vec<u32>, ParseScheduleError> {
+    if field == Field::DaysOfWeek {
+        let numbering = names.numbering;
+        let (min, max) = match numbering {
+            DayOfWeekNumbering::SundayZero => (0, 7),
+            DayOfWeekNumbering::SundayOne | DayOfWeekNumbering::MondayOne => (1, 7),
+        };
+        let l = match left {
+            "*" => min,
+            _ => numbered_day_of_week(left, names)?,
+        };
+        let r = match right {
+            Some(right) => numbered_day_of_week(right, names)?,
+            None => max,
+        };
+        // a range wrapping around the end of the week goes round it once, Sunday numbered 7 and
+        // 0 counting as the same day
+        let days: Vec<u32> = match (l, r) {
+            (l, r) if l <= r => (l..=r).collect(),
+            (7, r) if min == 0 => (0..=r).collect(),
+            (l, r) => (l..=min + 6).chain(min..=r).collect(),
+        };
+        return Ok(days
+            .into_iter()
+            .step_by(step)
+            .filter_map(|n| numbering.weekday(n))
+            .map(|weekday| weekday.number_from_sunday())
+            .collect());
+    }
+
+    let (min, max) = field.bounds();
+    let l = match left {
+        "*" => min,
+        _ => parse_time_unit(left, field, names)?,
+    };
+    let r = match right {
+        Some(right) => parse_time_unit(right, field, names)?,
+        None => max,
+    };
+    if l < min || l > max || r < min || r > max {
+        return Err(ParseScheduleError::InvalidRange {
+            field,
+            value: value.into(),
+        });
+    }
+    Ok(range_values(l, r, min, max)
+        .into_iter()
+        .step_by(step)
+        .collect())
+}
+
+/// Returns the number of a day of week in the numbering of the expression, keeping 7 and 0 apart
+/// for Sunday where the numbering has both.
+fn numbered_day_of_week(value: &str, names: Names) -> Result<u32, ParseScheduleError> {
+    let day = day_of_week(value, names)?;
+    Ok(value.parse().unwrap_or_else(|_| {
+        names
+            .numbering
+            .weekday_number(day)
+            .expect("days of week are numbered 1-7")
+    }))
+}
+
+fn parse_field(
+    value: &str,
+    field: Field,
+    names: Names,
+    lenient_steps: bool,
+) -> Result<TimeRange, ParseScheduleError> {
+    let values = parse_values(value, field, names, lenient_steps)?;
+    Ok(values.map_or(TimeRange::All, TimeRange::Constrained))
+}
+
+/// Parses the years field, whose values don't fit in a `BitSet`.
+fn parse_years_field(
+    value: &str,
+    names: Names,
+    lenient_steps: bool,
+) -> Result<Years, ParseScheduleError> {
+    let values = parse_values(value, Field::Years, names, lenient_steps)?;
+    Ok(values.map_or(Years::All, Years::Constrained))
+}
+
+/// The sets the values of fields are parsed into.
+trait ValueSet: Default + Extend<u32> {
+    fn insert(&mut self, value: u32) -> bool;
+
+    fn len(&self) -> usize;
+}
+
+impl ValueSet for BitSet {
+    fn insert(&mut self, value: u32) -> bool {
+        BitSet::insert(self, value)
+    }
+
+    fn len(&self) -> usize {
+        BitSet::len(self)
+    }
+}
+
+impl ValueSet for BTreeSet<u32> {
+    fn insert(&mut self, value: u32) -> bool {
+        BTreeSet::insert(self, value)
+    }
+
+    fn len(&self) -> usize {
+        BTreeSet::len(self)
+    }
+}
+
+/// Parses the values of a field, `None` when they cover the whole field as `*` does.
+fn parse_values<S: ValueSet>(
+    value: &str,
+    field: Field,
+    names: Names,
+    lenient_steps: bool,
+) -> Result<Option<S>, ParseScheduleError> {
+    let (min, max) = field.bounds();
+    let mut set = S::default();
+
+    for v in value.split(',') {
+        let invalid = || ParseScheduleError::InvalidRange {
+            field,
+            value: v.into(),
+        };
+        let mut step_iter = v.splitn(2, '/');
+        let left_step = step_iter.next().unwrap();
+        let right_step = step_iter.next();
+
+        let mut dash_iter = left_step.splitn(2, '-');
+        let left_dash = dash_iter.next().unwrap();
+        let right_dash = dash_iter.next();
+
+        match (left_dash, right_dash, right_step) {
+            (left_range, Some(right_range), Some(step_value)) => {
+                let step = parse_step(step_value, field, v, lenient_steps)?;
+                set.extend(step_values(
+                    left_range,
+                    Some(right_range),
+                    step,
+                    field,
+                    names,
+                    v,
+                )?);
+            }
+            (left_range, Some(right_range), None) => {
+                let (l, r) = parse_range(left_range, right_range, field, names)?;
+
+                if l < min || l > max || r < min || r > max {
+                    return Err(invalid());
+                }
+
+                if l == min && r == max {
+                    return Ok(None);
+                }
+
+                set.extend(range_values(l, r, min, max));
+            }
+            (left_most, None, Some(step_value)) => {
+                let step = parse_step(step_value, field, v, lenient_steps)?;
+                set.extend(step_values(left_most, None, step, field, names, v)?);
+            }
+            (left_most, None, None) => match left_most {
+                "*" => {
+                    return Ok(None);
+                }
+                _ => {
+                    let i = parse_time_unit(left_most, field, names)?;
+                    if i < min || i > max {
+                        return Err(invalid());
+                    }
+                    set.insert(i);
+                }
+            },
+        };
+    }
+
+    // a list covering every value of the field is equivalent to `*`
+    if set.len() as u32 == max - min + 1 {
+        return Ok(None);
+    }
+
+    Ok(Some(set))
+}
+
+/// How month and day of week values are read.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Names {
+    pub(crate) numbering: DayOfWeekNumbering,
+    /// The language of names accepted in addition to English.
+    pub(crate) locale: Locale,
+}
+
+impl Names {
+    pub(crate) fn new(numbering: DayOfWeekNumbering) -> Self {
+        Names {
+            numbering,
+            locale: Locale::En,
+        }
+    }
+}
+
+fn month(value: &str, names: Names) -> Result<u32, ParseScheduleError> {
+    let localized = names.locale.phrases().months;
+    match name_number(value, &localized).or_else(|| name_number(value, &MONTH_NAMES)) {
+        Some(month) => Ok(month),
+        None => match value.parse() {
+            Ok(month) if (1..=12).contains(&month) => Ok(month),
+            _ => Err(ParseScheduleError::InvalidMonthIndicator {
+                input: value.into(),
+                suggestion: suggest_name(value, &MONTH_NAMES),
+            }),
+        },
+    }
+}
+
+fn day_of_week(value: &str, names: Names) -> Result<u32, ParseScheduleError> {
+    let localized = names.locale.phrases().weekdays;
+    match name_number(value, &localized).or_else(|| name_number(value, &DAY_OF_WEEK_NAMES)) {
+        Some(day_of_week) => Ok(day_of_week),
+        None => value
+            .parse()
+            .ok()
+            .and_then(|n| names.numbering.weekday(n))
+            .map(|weekday| weekday.number_from_sunday())
+            .ok_or_else(|| ParseScheduleError::InvalidDayOfWeekIndicator {
+                input: value.into(),
+                suggestion: suggest_name(value, &DAY_OF_WEEK_NAMES),
+            }),
+    }
+}
+
+/// Returns the number, from 1, of the name the value is a prefix of, such as `Jan`, `JANUARY` or
+/// `Thurs`, when it's at least two letters long and matches a single name. Case and accents are
+/// ignored so `mar` matches `März`.
+fn name_number(value: &str, names: &[&str]) -> Option<u32> {
+    if value.chars().count() < 2 {
+        return None;
+    }
+    let value = fold_name(value);
+    let mut matches = names
+        .iter()
+        .enumerate()
+        .filter(|(_, name)| fold_name(name).starts_with(&value));
+    match (matches.next(), matches.next()) {
+        (Some((i, _)), None) => Some(i as u32 + 1),
+        _ => None,
+    }
+}
+
+/// Returns the name in upper case without the accents of the `Locale` languages.
+fn fold_name(name: &str) -> String {
+    name.to_uppercase()
+        .chars()
+        .map(|c| match c {
+            'À' | 'Á' | 'Â' | 'Ä' => 'A',
+            'È' | 'É' | 'Ê' | 'Ë' => 'E',
+            'Í' | 'Î' | 'Ï' => 'I',
+            'Ó' | 'Ô' | 'Ö' => 'O',
+            'Ù' | 'Ú' | 'Û' | 'Ü' => 'U',
+            'Ç' => 'C',
+            c => c,
+        })
+        .collect()
+}
+
+const MONTH_NAMES: [&str; 12] = [
+    "JANUARY",
+    "FEBRUARY",
+    "MARCH",
+    "APRIL",
+    "MAY",
+    "JUNE",
+    "JULY",
+    "AUGUST",
+    "SEPTEMBER",
+    "OCTOBER",
+    "NOVEMBER",
+    "DECEMBER",
+];
+
+const DAY_OF_WEEK_NAMES: [&str; 7] = [
+    "SUNDAY",
+    "MONDAY",
+    "TUESDAY",
+    "WEDNESDAY",
+    "THURSDAY",
+    "FRIDAY",
+    "SATURDAY",
+];
+
+/// Returns the abbreviation of the name nearest a misspelled month or day of week, comparing it
+/// with both the full names and their abbreviations. Only names within an edit distance of a
+/// third of their length, at least one, are suggested.
+fn suggest_name(value: &str, names: &[&'static str]) -> Option<&'static str> {
+    let value = value.to_uppercase();
+    let mut nearest = None;
+    for &name in names {
+        let abbreviation = &name[..3];
+        for candidate in [name, abbreviation].iter() {
+            let distance = edit_distance(&value, candidate);
+            if distance <= (candidate.len() / 3).max(1)
+                && nearest.is_none_or(|(nearest, _)| distance < nearest)
+            {
+                nearest = Some((distance, abbreviation));
+            }
+        }
+    }
+    nearest.map(|(_, abbreviation)| abbreviation)
+}
+
+/// Returns the Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Iterates from a datetime over a borrowed or owned schedule.
+struct ScheduleIterator<S, Z>
+where
+    S: Borrow<Schedule>,
+    Z: TimeZone,
+{
+    is_done: bool,
+    schedule: S,
+    previous_datetime: DateTime<Z>,
+}
+
+impl<S, Z> ScheduleIterator<S, Z>
+where
+    S: Borrow<Schedule>,
+    Z: TimeZone,
+{
+    fn new(schedule: S, starting_datetime: &DateTime<Z>) -> ScheduleIterator<S, Z> {
+        ScheduleIterator {
+            is_done: false,
+            schedule,
+            previous_datetime: starting_datetime.clone(),
+        }
+    }
+}
+
+impl<S, Z> Iterator for ScheduleIterator<S, Z>
+where
+    S: Borrow<Schedule>,
+    Z: TimeZone,
+{
+    type Item = DateTime<Z>;
+
+    fn next(&mut self) -> Option<DateTime<Z>> {
+        if self.is_done {
+            return None;
+        }
+        if let Some(next_datetime) = self.schedule.borrow().after(&self.previous_datetime) {
+            self.previous_datetime = next_datetime.clone();
+            Some(next_datetime)
+        } else {
+            self.is_done = true;
+            None
+        }
+    }
+}
+
+impl<S, Z> DoubleEndedIterator for ScheduleIterator<S, Z>
+where
+    S: Borrow<Schedule>,
+    Z: TimeZone,
+{
+    fn next_back(&mut self) -> Option<DateTime<Z>> {
+        if self.is_done {
+            return None;
+        }
+        if let Some(next_datetime) = self.schedule.borrow().before(&self.previous_datetime) {
+            self.previous_datetime = next_datetime.clone();
+            Some(next_datetime)
+        } else {
+            self.is_done = true;
+            None
+        }
+    }
+}
+
+/// Iterates the occurrences within bounds from either end, stopping once both ends meet.
+struct BetweenIterator<'a, Z>
+where
+    Z: TimeZone,
+{
+    schedule: &'a Schedule,
+    front: Option<DateTime<Z>>,
+    back: Option<DateTime<Z>>,
+}
+
+impl<'a, Z> BetweenIterator<'a, Z>
+where
+    Z: TimeZone,
+{
+    fn new(
+        schedule: &'a Schedule,
+        start: &DateTime<Z>,
+        end: &DateTime<Z>,
+        inclusive: bool,
+    ) -> BetweenIterator<'a, Z> {
+        // occurrences are strictly after and before the datetimes searched from, the last
+        // occurrence is searched for from past the end as searching backwards rounds down
+        let front = schedule.after(&(start.clone() - Duration::nanoseconds(1)));
+        let mut back = schedule.before(&(end.clone() + schedule.step()));
+        while let Some(last) = back
+            .as_ref()
+            .filter(|last| *last > end || (!inclusive && *last == end))
+        {
+            back = schedule.before(last);
+        }
+        BetweenIterator {
+            schedule,
+            front,
+            back,
+        }
+    }
+
+    fn finish(&mut self) {
+        self.front = None;
+        self.back = None;
+    }
+}
+
+impl<'a, Z> Iterator for BetweenIterator<'a, Z>
+where
+    Z: TimeZone,
+{
+    type Item = DateTime<Z>;
+
+    fn next(&mut self) -> Option<DateTime<Z>> {
+        let next = self.front.take()?;
+        match self.back.as_ref() {
+            Some(back) if next < *back => self.front = self.schedule.after(&next),
+            Some(back) if next == *back => self.finish(),
+            _ => {
+                self.finish();
+                return None;
+            }
+        }
+        Some(next)
+    }
+}
+
+impl<'a, Z> DoubleEndedIterator for BetweenIterator<'a, Z>
+where
+    Z: TimeZone,
+{
+    fn next_back(&mut self) -> Option<DateTime<Z>> {
+        let prev = self.back.take()?;
+        match self.front.as_ref() {
+            Some(front) if prev > *front => self.back = self.schedule.before(&prev),
+            Some(front) if prev == *front => self.finish(),
+            _ => {
+                self.finish();
+                return None;
+            }
+        }
+        Some(prev)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    const VIXIE: Names = Names {
+        numbering: DayOfWeekNumbering::SundayOne,
+        locale: Locale::En,
+    };
+
+    /// Counts the allocations of each thread so tests running in parallel don't interfere.
+    struct CountingAllocator;
+
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    fn allocations() -> usize {
+        ALLOCATIONS.with(Cell::get)
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(
+            Err(ParseScheduleError::InvalidNumber {
+                field: Field::Minutes,
+                value: "invalid".into()
+            }),
+            parse_field("invalid", Field::Minutes, VIXIE, false)
+        );
+    }
+
+    #[test]
+    fn parse_seconds_minutes() {
+        let expected = TimeRange::Constrained((0..=58).collect());
+        assert_eq!(
+            Ok(expected),
+            parse_field("0-58", Field::Minutes, VIXIE, false)
+        );
+        assert_eq!(
+            Ok(TimeRange::All),
+            parse_field("*", Field::Minutes, VIXIE, false)
+        );
+        assert_eq!(
+            Ok(TimeRange::All),
+            parse_field("0-59", Field::Minutes, VIXIE, false)
+        );
+    }
+
+    #[test]
+    fn parse_seconds_minutes_step_2() {
+        let expected = TimeRange::Constrained((0..=59).step_by(2).collect());
+        assert_eq!(
+            Ok(expected),
+            parse_field("*/2", Field::Minutes, VIXIE, false)
+        );
+        assert_eq!(
+            Ok(expected),
+            parse_field("0-59/2", Field::Minutes, VIXIE, false)
+        );
+    }
+
+    #[test]
+    fn day_of_week_steps_follow_the_numbering() {
+        let days = |expression, numbering| {
+            let options = ParseOptions::default().day_of_week_numbering(numbering);
+            Schedule::parse_with(expression, &options)
+                .unwrap()
+                .day_of_week_numbers(DayOfWeekNumbering::MondayOne)
+        };
+        // Sunday, Tuesday, Thursday and Saturday
+        assert_eq!(
+            vec![2, 4, 6, 7],
+            days("0 0 * * */2", DayOfWeekNumbering::SundayZero)
+        );
+        // Monday, Wednesday, Friday and Sunday numbered 7
+        assert_eq!(
+            vec![1, 3, 5, 7],
+            days("0 0 * * 1/2", DayOfWeekNumbering::SundayZero)
+        );
+        assert_eq!(
+            vec![5, 7],
+            days("0 0 * * 5-0/2", DayOfWeekNumbering::SundayZero)
+        );
+        assert_eq!(
+            vec![2, 4, 6, 7],
+            days("0 0 0 * * */2", DayOfWeekNumbering::SundayOne)
+        );
+        assert_eq!(
+            vec![1, 3, 5],
+            days("0 0 0 * * 2/2", DayOfWeekNumbering::SundayOne)
+        );
+        assert_eq!(
+            vec![1, 3, 5, 7],
+            days("0 0 * * */2", DayOfWeekNumbering::MondayOne)
+        );
+        assert_eq!(
+            vec![1, 3, 5, 7],
+            days("0 0 * * 1/2", DayOfWeekNumbering::MondayOne)
+        );
+        assert_eq!(
+            vec![1, 3, 5, 7],
+            days("0 0 * * Mon/2", DayOfWeekNumbering::MondayOne)
+        );
+        assert_eq!(
+            vec![1, 3, 6],
+            days("0 0 * * Sat-3/2", DayOfWeekNumbering::MondayOne)
+        );
+    }
+
+    #[test]
+    fn parse_hours() {
+        let expected = TimeRange::Constrained((0..=22).collect());
+        assert_eq!(
+            Ok(expected),
+            parse_field("0-22", Field::Hours, VIXIE, false)
+        );
+        assert_eq!(
+            Ok(TimeRange::All),
+            parse_field("*", Field::Hours, VIXIE, false)
+        );
+        assert_eq!(
+            Ok(TimeRange::All),
+            parse_field("0-23", Field::Hours, VIXIE, false)
+        );
+    }
+
+    #[test]
+    fn parse_days_of_month() {
+        let expected = TimeRange::Constrained((1..=30).collect());
+        assert_eq!(
+            Ok(expected),
+            parse_field("1-30", Field::DaysOfMonth, VIXIE, false)
+        );
+        assert_eq!(
+            Ok(TimeRange::All),
+            parse_field("*", Field::DaysOfMonth, VIXIE, false)
+        );
+    }
+
+    #[test]
+    fn parse_months() {
+        assert_eq!(
+            Ok(TimeRange::All),
+            parse_field("*", Field::Months, VIXIE, false)
+        );
+        assert_eq!(
+            Ok(TimeRange::All),
+            parse_field("1-12", Field::Months, VIXIE, false)
+        );
+        assert_eq!(
+            Ok(TimeRange::All),
+            parse_field("JAN-DEC", Field::Months, VIXIE, false)
+        );
+        assert_eq!(
+            Ok(TimeRange::All),
+            parse_field("1-DEC", Field::Months, VIXIE, false)
+        );
+        assert_eq!(
+            Ok(TimeRange::All),
+            parse_field("JAN-12", Field::Months, VIXIE, false)
+        );
+        assert_eq!(
+            Ok(TimeRange::Constrained((2..=4).collect())),
+            parse_field("FEB-APR", Field::Months, VIXIE, false)
+        );
+        assert_eq!(
+            Ok(TimeRange::Constrained((2..=4).collect())),
+            parse_field("2-APR", Field::Months, VIXIE, false)
+        );
+        assert_eq!(
+            Ok(TimeRange::Constrained((2..=4).collect())),
+            parse_field("FEB-4", Field::Months, VIXIE, false)
+        );
+        assert_eq!(
+            Ok(TimeRange::Constrained((2..=4).collect())),
+            parse_field("2-4", Field::Months, VIXIE, false)
+        );
+        assert_eq!(
+            Ok(TimeRange::Constrained({
+                let mut b = (2..=4).step_by(2).collect::<BitSet>();
+                b.insert(11);
+                b
+            })),
+            parse_field("FEB-APR/2,NOV", Field::Months, VIXIE, false)
+        );
+        assert_eq!(
+            Ok(TimeRange::Constrained({
+                let mut b = (2..=4).step_by(2).collect::<BitSet>();
+                b.insert(11);
+                b
+            })),
+            parse_field("feb-APR/2,nOv", Field::Months, VIXIE, false)
+        );
+    }
+
+    #[test]
+    fn parse_years() {
+        let expected = Years::Constrained((1980..=2000).collect());
+        assert_eq!(Ok(expected), parse_years_field("1980-2000", VIXIE, false));
+        assert_eq!(Ok(Years::All), parse_years_field("*", VIXIE, false));
+        assert_eq!(
+            Ok(Years::Constrained(
+                (MIN_YEAR..=MAX_YEAR).step_by(2).collect()
+            )),
+            parse_years_field("*/2", VIXIE, false)
+        );
+    }
+
+    #[test]
+    fn parse_vixie() {
+        let expected = Schedule {
+            seconds: Seconds::Constrained((0..=59).step_by(5).collect()),
+            minutes: TimeRange::All,
+            hours: TimeRange::All,
+            days_of_month: TimeRange::All,
+            day_of_month_terms: BTreeSet::new(),
+            days_of_month_wildcard: true,
+            months: TimeRange::All,
+            days_of_week: TimeRange::All,
+            day_of_week_terms: BTreeSet::new(),
+            days_of_week_wildcard: true,
+            years: Years::All,
+            syntax: Capabilities::STEPS | Capabilities::YEARS,
+            reboot: false,
+            day_semantics: DaySemantics::And,
+            dst_gap_policy: DstGapPolicy::Skip,
+            ambiguous_time_policy: AmbiguousTimePolicy::Skip,
+            #[cfg(feature = "tz")]
+            timezone: None,
+        };
+        let parsed = Schedule::from_str("*/5 * * * * * *");
+        assert_eq!(Ok(expected), parsed);
+    }
+
+    #[test]
+    fn parse_vixie_optional_year() {
+        let expected = Schedule {
+            seconds: Seconds::Constrained((0..=59).step_by(5).collect()),
+            minutes: TimeRange::All,
+            hours: TimeRange::All,
+            days_of_month: TimeRange::All,
+            day_of_month_terms: BTreeSet::new(),
+            days_of_month_wildcard: true,
+            months: TimeRange::All,
+            days_of_week: TimeRange::All,
+            day_of_week_terms: BTreeSet::new(),
+            days_of_week_wildcard: true,
+            years: Years::All,
+            syntax: Capabilities::STEPS,
+            reboot: false,
+            day_semantics: DaySemantics::And,
+            dst_gap_policy: DstGapPolicy::Skip,
+            ambiguous_time_policy: AmbiguousTimePolicy::Skip,
+            #[cfg(feature = "tz")]
+            timezone: None,
+        };
+        let parsed = Schedule::from_str("*/5 * * * * *");
+        assert_eq!(Ok(expected), parsed);
+    }
+
+    #[test]
+    fn full_day_ranges_are_not_wildcards() {
+        let wildcards = |expression| {
+            let schedule = Schedule::from_str(expression).unwrap();
+            (
+                schedule.days_of_month_wildcard,
+                schedule.days_of_week_wildcard,
+            )
+        };
+        assert_eq!((true, true), wildcards("0 0 * * *"));
+        assert_eq!((true, true), wildcards("0 0 0 ? * *"));
+        assert_eq!((false, false), wildcards("0 0 1-31 * SUN-SAT"));
+        assert_eq!((false, true), wildcards("0 0 */1 * *"));
+
+        // normalized alike nonetheless
+        let full = Schedule::from_str("0 0 1-31 * *").unwrap();
+        assert_eq!(TimeRange::All, full.days_of_month);
+        assert_eq!(Schedule::from_str("0 0 * * *").unwrap(), full);
+    }
+
+    #[test]
+    fn parse_linux_crontab() {
+        let expected = Schedule {
+            seconds: Seconds::Ignore,
+            minutes: TimeRange::Constrained((0..=59).step_by(5).collect()),
+            hours: TimeRange::All,
+            days_of_month: TimeRange::All,
+            day_of_month_terms: BTreeSet::new(),
+            days_of_month_wildcard: true,
+            months: TimeRange::All,
+            days_of_week: TimeRange::All,
+            day_of_week_terms: BTreeSet::new(),
+            days_of_week_wildcard: true,
+            years: Years::Unbound,
+            syntax: Capabilities::STEPS,
+            reboot: false,
+            day_semantics: DaySemantics::And,
+            dst_gap_policy: DstGapPolicy::Skip,
+            ambiguous_time_policy: AmbiguousTimePolicy::Skip,
+            #[cfg(feature = "tz")]
+            timezone: None,
+        };
+        let parsed = Schedule::from_str("*/5 * * * *");
+        assert_eq!(Ok(expected), parsed);
+    }
+
+    #[test]
+    fn schedule_every_5_seconds() {
+        let from_date = Utc.with_ymd_and_hms(2021, 2, 1, 1, 1, 40).unwrap();
+        let parsed = Schedule::from_str("*/5 * * * * *").unwrap();
+        let mut iter = parsed.iter_from(&from_date);
+        assert_eq!(
+            iter.next().unwrap(),
+            "2021-02-01T01:01:45Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(
+            iter.next().unwrap(),
+            "2021-02-01T01:01:50Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(
+            iter.next().unwrap(),
+            "2021-02-01T01:01:55Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(
+            iter.next().unwrap(),
+            "2021-02-01T01:02:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+    }
+
+    #[test]
+    fn schedule_every_5_minutes_vixie() {
+        let from_date = Utc.with_ymd_and_hms(2021, 2, 1, 1, 1, 1).unwrap();
+        let parsed = Schedule::from_str("0 */5 * * * *").unwrap();
+        let mut iter = parsed.iter_from(&from_date);
+        assert_eq!(
+            iter.next().unwrap(),
+            "2021-02-01T01:05:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(
+            iter.next().unwrap(),
+            "2021-02-01T01:10:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+    }
+
+    #[test]
+    fn schedule_every_5_minutes_crontab() {
+        let from_date = Utc.with_ymd_and_hms(2021, 2, 1, 1, 1, 1).unwrap();
+        let parsed = Schedule::from_str("*/5 * * * *").unwrap();
+        let mut iter = parsed.iter_from(&from_date);
+        assert_eq!(
+            iter.next().unwrap(),
+            "2021-02-01T01:05:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(
+            iter.next().unwrap(),
+            "2021-02-01T01:10:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_no_panic_on_nonexistent_time_after() {
+        use chrono::offset::TimeZone;
+        use chrono_tz::Tz;
+
+        let schedule_tz: Tz = "Europe/London".parse().unwrap();
+        let dt = schedule_tz
+            .with_ymd_and_hms(2019, 10, 27, 0, 3, 29)
+            .unwrap()
+            .checked_add_signed(chrono::Duration::hours(1)) // puts it in the middle of the DST transition
+            .unwrap();
+        let schedule = Schedule::from_str("* * * * * Sat,Sun *").unwrap();
+        let next = schedule.iter_from(&dt).next().unwrap();
+        assert!(next > dt); // test is ensuring line above does not panic
+        assert_eq!(
+            next,
+            "2019-10-27T02:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_no_panic_on_nonexistent_time_before() {
+        use chrono::offset::TimeZone;
+        use chrono_tz::Tz;
+
+        let schedule_tz: Tz = "Europe/London".parse().unwrap();
+        let dt = schedule_tz
+            .with_ymd_and_hms(2019, 10, 27, 0, 3, 29)
+            .unwrap()
+            .checked_add_signed(chrono::Duration::hours(1)) // puts it in the middle of the DST transition
+            .unwrap();
+        let schedule = Schedule::from_str("* * * * * Sat,Sun *").unwrap();
+        let prev = schedule.iter_from(&dt).next_back().unwrap();
+        assert!(prev < dt); // test is ensuring line above does not panic
+        assert_eq!(
+            prev,
+            "2019-10-26T23:59:59Z".parse::<DateTime<Utc>>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_next_and_prev_from() {
+        let expression = "0 5,13,40-42 17 1 Jan *";
+        let schedule: Schedule = expression.parse().unwrap();
+
+        let from_date = Utc.with_ymd_and_hms(2021, 2, 14, 23, 49, 55).unwrap();
+
+        let next = schedule.iter_from(&from_date).next();
+        assert!(next.is_some());
+        assert_eq!(
+            next.unwrap(),
+            "2022-01-01T17:05:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+
+        let next2 = schedule.iter_from(&next.unwrap()).next();
+        assert!(next2.is_some());
+        assert_eq!(
+            next2.unwrap(),
+            "2022-01-01T17:13:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+
+        let prev = schedule.iter_from(&next2.unwrap()).next_back();
+        assert!(prev.is_some());
+        assert_eq!(
+            prev.unwrap(),
+            "2022-01-01T17:05:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(prev, next);
+    }
+
+    #[test]
+    fn into_iter_from() {
+        let schedule = Schedule::from_str("0 5,13,40-42 17 1 Jan *").unwrap();
+        let from = Utc.with_ymd_and_hms(2021, 2, 14, 23, 49, 55).unwrap();
+        let owned: Vec<_> = schedule.clone().into_iter_from(&from).take(5).collect();
+        assert_eq!(schedule.iter_from(&from).take(5).collect::<Vec<_>>(), owned);
+        let last = schedule.clone().into_iter_from(&from).next_back();
+        assert_eq!(schedule.iter_from(&from).next_back(), last);
+    }
+
+    #[test]
+    fn next_after_and_prev_before() {
+        let schedule = Schedule::from_str("0 5,13,40-42 17 1 Jan *").unwrap();
+        let from = Utc.with_ymd_and_hms(2021, 2, 14, 23, 49, 55).unwrap();
+        let next = Utc.with_ymd_and_hms(2022, 1, 1, 17, 5, 0).unwrap();
+        let next2 = Utc.with_ymd_and_hms(2022, 1, 1, 17, 13, 0).unwrap();
+
+        assert_eq!(Some(next), schedule.next_after(&from));
+        assert_eq!(Some(next2), schedule.next_after(&next));
+        assert_eq!(Some(next), schedule.prev_before(&next2));
+        // strictly after and before an occurrence
+        assert_ne!(Some(next), schedule.prev_before(&next));
+    }
+
+    #[test]
+    fn iter_after_and_before() {
+        let schedule = Schedule::from_str("0 9,17 * * *").unwrap();
+        let from = Utc.with_ymd_and_hms(2022, 6, 1, 12, 0, 0).unwrap();
+        let at = |d, h| Utc.with_ymd_and_hms(2022, 6, d, h, 0, 0).unwrap();
+
+        let after: Vec<_> = schedule.iter_after(&from).take(3).collect();
+        assert_eq!(vec![at(1, 17), at(2, 9), at(2, 17)], after);
+        let before: Vec<_> = schedule.iter_before(&from).take(3).collect();
+        let may_31 = |h| Utc.with_ymd_and_hms(2022, 5, 31, h, 0, 0).unwrap();
+        assert_eq!(vec![at(1, 9), may_31(17), may_31(9)], before);
+    }
+
+    #[test]
+    fn iter_between() {
+        let at = |d, h, m, s| Utc.with_ymd_and_hms(2022, 6, d, h, m, s).unwrap();
+        let schedule = Schedule::from_str("0 9,17 * * *").unwrap();
+
+        let all: Vec<_> = schedule
+            .iter_between(&at(1, 9, 0, 0), &at(3, 9, 0, 0))
+            .collect();
+        assert_eq!(
+            vec![
+                at(1, 9, 0, 0),
+                at(1, 17, 0, 0),
+                at(2, 9, 0, 0),
+                at(2, 17, 0, 0)
+            ],
+            all
+        );
+        let mut reversed: Vec<_> = schedule
+            .iter_between(&at(1, 9, 0, 0), &at(3, 9, 0, 0))
+            .rev()
+            .collect();
+        reversed.reverse();
+        assert_eq!(all, reversed);
+
+        // bounds between occurrences, such as within a matching minute
+        let within: Vec<_> = schedule
+            .iter_between_inclusive(&at(1, 9, 0, 30), &at(2, 17, 0, 30))
+            .collect();
+        assert_eq!(
+            vec![at(1, 17, 0, 0), at(2, 9, 0, 0), at(2, 17, 0, 0)],
+            within
+        );
+
+        // both ends meet without repeating an occurrence
+        let mut both = schedule.iter_between_inclusive(&at(1, 9, 0, 0), &at(2, 9, 0, 0));
+        assert_eq!(Some(at(1, 9, 0, 0)), both.next());
+        assert_eq!(Some(at(2, 9, 0, 0)), both.next_back());
+        assert_eq!(Some(at(1, 17, 0, 0)), both.next_back());
+        assert_eq!(None, both.next());
+        assert_eq!(None, both.next_back());
+
+        assert_eq!(
+            0,
+            schedule
+                .iter_between(&at(1, 10, 0, 0), &at(1, 17, 0, 0))
+                .count()
+        );
+        assert_eq!(
+            0,
+            schedule
+                .iter_between(&at(2, 9, 0, 0), &at(1, 9, 0, 0))
+                .count()
+        );
+        assert_eq!(
+            1,
+            schedule
+                .iter_between_inclusive(&at(1, 17, 0, 0), &at(1, 17, 0, 0))
+                .count()
+        );
+
+        let seconds = Schedule::from_str("*/20 * * * * *").unwrap();
+        assert_eq!(
+            vec![at(1, 0, 0, 20), at(1, 0, 0, 40)],
+            seconds
+                .iter_between(&at(1, 0, 0, 1), &at(1, 0, 1, 0))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn includes() {
+        let at = |h, m, s| Utc.with_ymd_and_hms(2022, 6, 1, h, m, s).unwrap();
+        let schedule = Schedule::from_str("*/15 9 * * *").unwrap();
+        assert!(schedule.includes(&at(9, 15, 0)));
+        assert!(schedule.includes(&(at(9, 15, 59) + Duration::milliseconds(999))));
+        assert!(!schedule.includes(&at(9, 16, 0)));
+        assert!(!schedule.includes(&at(10, 15, 0)));
+
+        let with_seconds = Schedule::from_str("*/10 30 9 * * *").unwrap();
+        assert!(with_seconds.includes(&(at(9, 30, 20) + Duration::milliseconds(500))));
+        assert!(!with_seconds.includes(&at(9, 30, 21)));
+
+        let interval = Schedule::from_str("*/90s * * * * *").unwrap();
+        assert!(interval.includes(&at(0, 1, 30)));
+        assert!(!interval.includes(&at(0, 1, 0)));
+
+        assert!(!Schedule::from_str("@reboot")
+            .unwrap()
+            .includes(&at(0, 0, 0)));
+        for occurrence in schedule.iter_from(&at(0, 0, 0)).take(8) {
+            assert!(schedule.includes(&occurrence));
+        }
+    }
+
+    #[cfg(feature = "tz")]
+    #[test]
+    fn includes_in_timezone() {
+        let schedule = Schedule::from_str("CRON_TZ=Europe/Paris 0 9 * * *").unwrap();
+        assert!(schedule.includes(&Utc.with_ymd_and_hms(2022, 6, 1, 7, 0, 0).unwrap()));
+        assert!(!schedule.includes(&Utc.with_ymd_and_hms(2022, 6, 1, 9, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn fingerprint_is_stable() {
+        assert_eq!(
+            0xd6cf_61d6_e8fa_5ea9,
+            Schedule::from_str("*/5 * * * *").unwrap().fingerprint()
+        );
+        assert_eq!(
+            0x002b_0682_ddcd_d228,
+            Schedule::from_str("0 30 9,12,15 1,15 May-Aug Mon,Wed,Fri 2018/2")
+                .unwrap()
+                .fingerprint()
+        );
+    }
+
+    #[test]
+    fn fingerprint_canonical() {
+        let a = Schedule::from_str("0 0-59 */1 * JAN-DEC *").unwrap();
+        let b = Schedule::from_str("0 * * * * *").unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.fingerprint(), b.fingerprint());
+
+        let crontab = Schedule::from_str("0 * * * *").unwrap();
+        let vixie = Schedule::from_str("0 0 * * * *").unwrap();
+        assert_ne!(crontab.fingerprint(), vixie.fingerprint());
+    }
+
+    #[test]
+    fn ordering() {
+        let mut schedules: Vec<Schedule> = vec!["0 5 * * * *", "0 1-5 * * * *", "* * * * * *"]
+            .into_iter()
+            .map(|e| e.parse().unwrap())
+            .collect();
+        schedules.sort();
+        assert_eq!(
+            schedules,
+            vec![
+                Schedule::from_str("* * * * * *").unwrap(),
+                Schedule::from_str("0 1-5 * * * *").unwrap(),
+                Schedule::from_str("0 5 * * * *").unwrap(),
+            ]
+        );
+
+        let mut map = std::collections::BTreeMap::new();
+        map.insert(Schedule::from_str("0 0-59 * * * *").unwrap(), 1);
+        map.insert(Schedule::from_str("0 * * * * *").unwrap(), 2);
+        assert_eq!(map.len(), 1);
+        assert_eq!(
+            Schedule::from_str("0 * * * * *")
+                .unwrap()
+                .cmp(&Schedule::from_str("0 0-59 * * * *").unwrap()),
+            std::cmp::Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn to_oncalendar() {
+        let tests = vec![
+            ("* * * * *", "*-*-* *:*:00"),
+            ("*/5 * * * * *", "1970..2099-*-* *:*:00/5"),
+            ("0 30 9 * * *", "1970..2099-*-* 09:30:00"),
+            ("0 0 0 1 1 * *", "1970..2099-01-01 00:00:00"),
+            ("0 0 12 * * Sat,Sun", "Sun,Sat 1970..2099-*-* 12:00:00"),
+            (
+                "0 30 9,12,15 1,15 May-Aug Mon,Wed,Fri 2018-2020,2025",
+                "Mon,Wed,Fri 2018..2020,2025-05..08-01,15 09,12,15:30:00",
+            ),
+            ("0 1-10/2 * * *", "*-*-* 01,03,05,07,09:00:00"),
+            ("0 0 L 2 *", "*-02~01 00:00:00"),
+            ("0 0 L-2 * *", "*-*~03 00:00:00"),
+            ("0 0 17 ? 5 6L", "Fri 1970..2099-05~07/1 17:00:00"),
+            ("0 9 * * Mon#2", "Mon *-*-08..14 09:00:00"),
+        ];
+        for (expression, expected) in tests {
+            assert_eq!(
+                expected,
+                Schedule::from_str(expression)
+                    .unwrap()
+                    .to_oncalendar()
+                    .unwrap(),
+                "{}",
+                expression
+            );
+        }
+        assert!(Schedule::from_str("0 0 1,L * *")
+            .unwrap()
+            .to_oncalendar()
+            .is_err());
+        let schedule = Schedule::from_str("*/15 9-17 * * Mon-Fri").unwrap();
+        assert_eq!(schedule.to_oncalendar(), schedule.to_on_calendar());
+    }
+
+    #[test]
+    fn to_oncalendar_inexpressible() {
+        let inexpressible = [
+            "@reboot",
+            "*/90s * * * * *",
+            "0 0 15W * *",
+            "0 0 LW * *",
+            "0 0 * * 1,5L",
+            "0 0 * * 1#2,3#4",
+            "0 0 1 * 5L",
+        ];
+        for expression in inexpressible.iter() {
+            assert!(
+                matches!(
+                    Schedule::from_str(expression).unwrap().to_oncalendar(),
+                    Err(crate::OnCalendarError::Inexpressible(_))
+                ),
+                "{}",
+                expression
+            );
+        }
+    }
+
+    #[test]
+    fn field_count_conversions() {
+        use crate::{ConversionError, DropPolicy};
+
+        let vixie = Schedule::from_str("0 30 9 * * Mon-Fri").unwrap();
+        let crontab = Schedule::from_str("30 9 * * Mon-Fri").unwrap();
+        assert_eq!(Ok(crontab.clone()), vixie.to_five_field(DropPolicy::Reject));
+        assert_eq!(Ok(vixie.clone()), crontab.to_six_field(DropPolicy::Reject));
+        assert_eq!(
+            Schedule::from_str("0 30 9 * * Mon-Fri *").unwrap(),
+            crontab.to_seven_field()
+        );
+
+        let seconds = Schedule::from_str("*/5 30 9 * * * 2030").unwrap();
+        assert_eq!(
+            Err(ConversionError::LossySeconds),
+            seconds.to_five_field(DropPolicy::Reject)
+        );
+        assert_eq!(
+            Err(ConversionError::LossyYears),
+            seconds.to_six_field(DropPolicy::Reject)
+        );
+        assert_eq!(
+            Ok(Schedule::from_str("30 9 * * *").unwrap()),
+            seconds.to_five_field(DropPolicy::Discard)
+        );
+        assert_eq!(
+            Ok(Schedule::from_str("*/5 30 9 * * *").unwrap()),
+            seconds.to_six_field(DropPolicy::Discard)
+        );
+    }
+
+    #[test]
+    fn dense_schedules_match_search() {
+        use chrono_tz::Tz;
+
+        let tz: Tz = "Europe/London".parse().unwrap();
+        let expressions = vec![
+            "* * * * * *",
+            "0 * * * * *",
+            "* * * * *",
+            "0 0 * * * *",
+            "0 * * * *",
+            "0 0 * * * * *",
+        ];
+        // includes the spring forward gap and the fall back overlap
+        let anchors = vec![
+            tz.with_ymd_and_hms(2019, 3, 31, 0, 59, 59).unwrap(),
+            tz.with_ymd_and_hms(2019, 3, 31, 2, 0, 0).unwrap(),
+            tz.with_ymd_and_hms(2019, 10, 27, 0, 59, 30).unwrap(),
+            tz.with_ymd_and_hms(2019, 10, 27, 2, 0, 0).unwrap(),
+            tz.with_ymd_and_hms(2021, 2, 14, 23, 49, 55).unwrap(),
+            tz.with_ymd_and_hms(2021, 2, 14, 23, 0, 0).unwrap(),
+        ];
+
+        for expression in expressions {
+            let schedule = Schedule::from_str(expression).unwrap();
+            assert!(schedule.dense_interval().is_some(), "{}", expression);
+            for anchor in &anchors {
+                let mut dt = *anchor;
+                for _ in 0..5 {
+                    let expected = schedule.search_after(&dt);
+                    assert_eq!(expected, schedule.after(&dt), "{} after {}", expression, dt);
+                    dt = expected.unwrap();
+                }
+                let mut dt = *anchor + Duration::milliseconds(500);
+                for _ in 0..5 {
+                    let expected = schedule.search_before(&dt);
+                    assert_eq!(
+                        expected,
+                        schedule.before(&dt),
+                        "{} before {}",
+                        expression,
+                        dt
+                    );
+                    dt = expected.unwrap();
+                }
+            }
+        }
+
+        assert!(Schedule::from_str("0 0 0 * * *")
+            .unwrap()
+            .dense_interval()
+            .is_none());
+    }
+
+    #[test]
+    fn day_semantics_or() {
+        let options = ParseOptions::default().day_semantics(DaySemantics::Or);
+        let schedule = Schedule::parse_with("0 0 1,15 * Mon", &options).unwrap();
+        let from = Utc.with_ymd_and_hms(2022, 6, 1, 12, 0, 0).unwrap();
+        let at = |m, d| Utc.with_ymd_and_hms(2022, m, d, 0, 0, 0).unwrap();
+        assert_eq!(
+            vec![
+                at(6, 6),
+                at(6, 13),
+                at(6, 15),
+                at(6, 20),
+                at(6, 27),
+                at(7, 1)
+            ],
+            schedule.iter_after(&from).take(6).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![at(6, 1), at(5, 30), at(5, 23)],
+            schedule.iter_before(&from).take(3).collect::<Vec<_>>()
+        );
+        assert!(schedule.includes(&at(6, 13)));
+        assert!(!schedule.includes(&at(6, 14)));
+        assert_ne!(Schedule::from_str("0 0 1,15 * Mon").unwrap(), schedule);
+
+        // the days of month alone may never exist
+        let fridays = Schedule::parse_with("0 0 30 2 Fri", &options).unwrap();
+        assert!(!fridays.never_fires());
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2023, 2, 3, 0, 0, 0).unwrap()),
+            fridays.next_after(&from)
+        );
+
+        // a wildcard field leaves the other field alone to match
+        let wildcard = Schedule::parse_with("0 0 * * Mon", &options).unwrap();
+        assert_eq!(Schedule::from_str("0 0 * * Mon").unwrap(), wildcard);
+        assert_eq!(Some(at(6, 6)), wildcard.next_after(&from));
+
+        // as in Vixie cron a range covering every day restricts the field, matching every day
+        let every_day = Schedule::parse_with("0 0 1-31 * Mon", &options).unwrap();
+        assert_eq!(
+            vec![at(6, 2), at(6, 3), at(6, 4)],
+            every_day.iter_after(&from).take(3).collect::<Vec<_>>()
+        );
+        assert_eq!("0 0 1-31 * 1", every_day.to_string());
+        assert_ne!(wildcard, every_day);
+        let every_weekday = Schedule::parse_with("0 0 1 * 0-6", &options).unwrap();
+        assert_eq!(Some(at(6, 2)), every_weekday.next_after(&from));
+    }
+
+    #[test]
+    fn dst_gap_policy() {
+        use chrono_tz::Europe::Paris;
+
+        // clocks spring forward from 02:00 to 03:00 on the 28th of March 2021 in Paris
+        let from = Paris.with_ymd_and_hms(2021, 3, 28, 0, 0, 0).unwrap();
+        let to = Paris.with_ymd_and_hms(2021, 3, 28, 12, 0, 0).unwrap();
+        let at = |h, m, s| Paris.with_ymd_and_hms(2021, 3, 28, h, m, s).unwrap();
+        let schedule = |expression, policy| {
+            Schedule::from_str(expression)
+                .unwrap()
+                .with_dst_gap_policy(policy)
+        };
+        let between = |schedule: &Schedule| schedule.iter_between(&from, &to).collect::<Vec<_>>();
+
+        assert!(between(&schedule("30 2 * * *", DstGapPolicy::Skip)).is_empty());
+        let gap_end = schedule("30 2 * * *", DstGapPolicy::FireAtGapEnd);
+        assert_eq!(vec![at(3, 0, 0)], between(&gap_end));
+        assert_eq!(
+            vec![at(3, 0, 0)],
+            gap_end.iter_between(&from, &to).rev().collect::<Vec<_>>()
+        );
+        let before_gap = schedule("30 2 * * *", DstGapPolicy::FireBeforeGap);
+        assert_eq!(vec![at(1, 59, 0)], between(&before_gap));
+        assert_eq!(
+            vec![at(1, 59, 59)],
+            between(&schedule("0 30 2 * * *", DstGapPolicy::FireBeforeGap))
+        );
+
+        // bounds next to the gap
+        assert_eq!(Some(at(3, 0, 0)), gap_end.next_after(&at(1, 59, 30)));
+        assert_eq!(Some(at(3, 0, 0)), gap_end.prev_before(&at(3, 1, 0)));
+        assert_eq!(Some(at(1, 59, 0)), before_gap.next_after(&at(1, 58, 30)));
+        assert_eq!(Some(at(1, 59, 0)), before_gap.prev_before(&at(3, 0, 30)));
+        assert_ne!(Some(at(1, 59, 0)), before_gap.next_after(&at(1, 59, 0)));
+
+        // the instants times in the gap are moved to are occurrences
+        assert!(gap_end.includes(&at(3, 0, 0)));
+        assert!(gap_end.includes(&at(3, 0, 30)));
+        assert!(!gap_end.includes(&at(3, 1, 0)));
+        assert!(!schedule("30 2 * * *", DstGapPolicy::Skip).includes(&at(3, 0, 0)));
+        assert!(before_gap.includes(&at(1, 59, 0)));
+        assert!(!before_gap.includes(&at(3, 0, 0)));
+        assert!(!before_gap.includes(&at(1, 58, 0)));
+
+        // times in the gap resolving to an occurrence of the schedule don't repeat it
+        let every_half_hour = schedule("0,30 * * * *", DstGapPolicy::FireAtGapEnd);
+        assert_eq!(
+            vec![at(1, 30, 0), at(3, 0, 0), at(3, 30, 0)],
+            every_half_hour
+                .iter_after(&at(1, 0, 0))
+                .take(3)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![at(3, 0, 0), at(1, 30, 0)],
+            every_half_hour
+                .iter_before(&at(3, 30, 0))
+                .take(2)
+                .collect::<Vec<_>>()
+        );
+
+        let options = ParseOptions::default().dst_gap_policy(DstGapPolicy::FireAtGapEnd);
+        assert_eq!(
+            gap_end,
+            Schedule::parse_with("30 2 * * *", &options).unwrap()
+        );
+        assert_ne!(gap_end, Schedule::from_str("30 2 * * *").unwrap());
+    }
+
+    #[test]
+    fn ambiguous_time_policy() {
+        use chrono_tz::Europe::London;
+
+        // clocks fall back from 02:00 to 01:00 on the 31st of October 2021 in London
+        let from = London.with_ymd_and_hms(2021, 10, 31, 0, 0, 0).unwrap();
+        let to = London.with_ymd_and_hms(2021, 10, 31, 12, 0, 0).unwrap();
+        let utc = |h, m| Utc.with_ymd_and_hms(2021, 10, 31, h, m, 0).unwrap();
+        let between = |policy| {
+            Schedule::from_str("15,45 1 * * *")
+                .unwrap()
+                .with_ambiguous_time_policy(policy)
+                .iter_between(&from, &to)
+                .rev()
+                .map(|dt| dt.with_timezone(&Utc))
+                .collect::<Vec<_>>()
+        };
+        assert!(between(AmbiguousTimePolicy::Skip).is_empty());
+        assert_eq!(
+            vec![utc(0, 45), utc(0, 15)],
+            between(AmbiguousTimePolicy::Earliest)
+        );
+        assert_eq!(
+            vec![utc(1, 45), utc(1, 15)],
+            between(AmbiguousTimePolicy::Latest)
+        );
+        assert_eq!(
+            vec![utc(1, 45), utc(1, 15), utc(0, 45), utc(0, 15)],
+            between(AmbiguousTimePolicy::Both)
+        );
+
+        let includes = |policy, h, m| {
+            Schedule::from_str("15,45 1 * * *")
+                .unwrap()
+                .with_ambiguous_time_policy(policy)
+                .includes(&utc(h, m).with_timezone(&London))
+        };
+        assert!(!includes(AmbiguousTimePolicy::Skip, 0, 15));
+        assert!(!includes(AmbiguousTimePolicy::Skip, 1, 15));
+        assert!(includes(AmbiguousTimePolicy::Earliest, 0, 15));
+        assert!(!includes(AmbiguousTimePolicy::Earliest, 1, 15));
+        assert!(!includes(AmbiguousTimePolicy::Latest, 0, 15));
+        assert!(includes(AmbiguousTimePolicy::Latest, 1, 15));
+        assert!(includes(AmbiguousTimePolicy::Both, 0, 15));
+        assert!(includes(AmbiguousTimePolicy::Both, 1, 15));
+
+        let options = ParseOptions::default().ambiguous_time_policy(AmbiguousTimePolicy::Both);
+        let both = Schedule::parse_with("30 1 * * *", &options).unwrap();
+        assert_eq!(AmbiguousTimePolicy::Both, both.ambiguous_time_policy());
+        assert_ne!(Schedule::from_str("30 1 * * *").unwrap(), both);
+    }
+
+    #[test]
+    fn exhaustion_reasons() {
+        let from_date = Utc.with_ymd_and_hms(2022, 6, 1, 8, 40, 1).unwrap();
+
+        let finished = Schedule::from_str("0 0 0 1 1 * 2020").unwrap();
+        assert_eq!(Ok(None), finished.try_next_after(&from_date));
+        assert_eq!(
+            Ok(Some(Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap())),
+            finished.try_prev_before(&from_date)
+        );
+
+        let range_exhausted = Schedule::from_str("0 0 0 1 1 *").unwrap();
+        let late_date = Utc.with_ymd_and_hms(2099, 6, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            Err(ExhaustionReason::YearRangeExhausted),
+            range_exhausted.try_next_after(&late_date)
+        );
+
+        let unsatisfiable = Schedule::from_str("0 0 30 2 *").unwrap();
+        assert_eq!(
+            Err(ExhaustionReason::Unsatisfiable),
+            unsatisfiable.try_next_after(&from_date)
+        );
+        assert_eq!(
+            Err(ExhaustionReason::Unsatisfiable),
+            unsatisfiable.try_prev_before(&from_date)
+        );
+
+        let leap_day = Schedule::from_str("0 0 0 29 2 * 2021-2023").unwrap();
+        assert!(leap_day.never_fires());
+        assert_eq!(None, leap_day.next_after(&from_date));
+        assert!(!Schedule::from_str("0 0 0 29 2 * 2021-2024")
+            .unwrap()
+            .never_fires());
+        assert!(!Schedule::from_str("0 0 0 L 2 *").unwrap().never_fires());
+        assert!(!Schedule::from_str("@reboot").unwrap().never_fires());
+
+        // the 13th of February 2022 is a Sunday, it's a Friday in 2015 and 2026 only
+        let unsatisfiable = Schedule::from_str("0 0 0 13 2 Fri 2016-2025").unwrap();
+        assert_eq!(
+            Err(ExhaustionReason::Unsatisfiable),
+            unsatisfiable.try_next_after(&from_date)
+        );
+        assert!(unsatisfiable.never_fires());
+    }
+
+    #[test]
+    fn interval_seconds() {
+        let parse = |e: &str| Schedule::from_str(e);
+
+        let schedule = parse("*/90s * * * * *").unwrap();
+        let from_date = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap();
+        let next: Vec<DateTime<Utc>> = schedule.iter_from(&from_date).take(2).collect();
+        assert_eq!(
+            vec![
+                Utc.with_ymd_and_hms(2022, 6, 1, 0, 1, 30).unwrap(),
+                Utc.with_ymd_and_hms(2022, 6, 1, 0, 3, 0).unwrap(),
+            ],
+            next
+        );
+        assert!(schedule.capabilities().contains(Capabilities::INTERVAL));
+
+        // intervals that don't divide the day restart at midnight
+        let start = Utc.with_ymd_and_hms(2022, 6, 1, 23, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2022, 6, 2, 1, 0, 0).unwrap();
+        for expression in &["*/90s * * * * *", "*/7s * * * * *", "*/7s 10-20 0,23 * * *"] {
+            assert_eq!(
+                Ok(()),
+                parse(expression)
+                    .unwrap()
+                    .verify_against_bruteforce(start..end),
+                "{}",
+                expression
+            );
+        }
+
+        // intervals the fields can express are lowered to them
+        assert_eq!(parse("*/30 * * * * *"), parse("*/30s * * * * *"));
+        assert_eq!(parse("0 */5 * * * *"), parse("*/300s * * * * *"));
+        assert_eq!(parse("0 0-30/5 * * * *"), parse("*/300s 0-30 * * * *"));
+        assert_eq!(parse("0 0 */2 * * *"), parse("*/7200s * * * * *"));
+
+        assert_eq!(
+            Err(ParseScheduleError::InvalidStepRange {
+                field: Field::Seconds,
+                value: "*/90".into()
+            }),
+            parse("*/90 * * * * *")
+        );
+        assert_eq!(
+            Err(ParseScheduleError::InvalidStepRange {
+                field: Field::Seconds,
+                value: "*/0s".into()
+            }),
+            parse("*/0s * * * * *")
+        );
+        assert_eq!(
+            Err(ParseScheduleError::InvalidStepRange {
+                field: Field::Seconds,
+                value: "*/86401s".into()
+            }),
+            parse("*/86401s * * * * *")
+        );
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use chrono::Utc;
 
     #[test]
-    fn parse_invalid() {
+    fn reboot() {
+        let from_date = Utc.with_ymd_and_hms(2022, 6, 1, 8, 40, 1).unwrap();
+        let reboot = Schedule::from_str(" @reboot ").unwrap();
+        assert!(reboot.is_reboot());
+        assert!(!Schedule::from_str("* * * * *").unwrap().is_reboot());
+        assert_ne!(reboot, Schedule::from_str("* * * * *").unwrap());
+
+        assert_eq!(None, reboot.iter_from(&from_date).next());
+        assert_eq!(None, reboot.iter_from(&from_date).next_back());
+        assert_eq!(Ok(None), reboot.try_next_after(&from_date));
         assert_eq!(
-            Err(ParseScheduleError::ParseIntError(
-                "invalid".parse::<u32>().err().unwrap()
-            )),
-            parse_field("invalid", 0, 59, true, false, false)
+            Err(ParseScheduleError::ArgumentCount),
+            Schedule::from_str("@reboot now")
         );
     }
 
     #[test]
-    fn parse_seconds_minutes() {
-        let expected = TimeRange::Constrained((0..=58).into_iter().collect());
-        assert_eq!(Ok(expected), parse_field("0-58", 0, 59, true, false, false));
+    fn last_day_of_month() {
+        let from_date = Utc.with_ymd_and_hms(2023, 12, 15, 0, 0, 0).unwrap();
+        let parse = |e| Schedule::from_str(e);
+        let dates = |schedule: &Schedule, n| -> Vec<String> {
+            schedule
+                .iter_from(&from_date)
+                .take(n)
+                .map(|dt| dt.format("%Y-%m-%d").to_string())
+                .collect()
+        };
+
+        let last = parse("0 0 0 L * *").unwrap();
         assert_eq!(
-            Ok(TimeRange::All),
-            parse_field("*", 0, 59, true, false, false)
+            vec![
+                "2023-12-31",
+                "2024-01-31",
+                "2024-02-29",
+                "2024-03-31",
+                "2024-04-30"
+            ],
+            dates(&last, 5)
         );
         assert_eq!(
-            Ok(TimeRange::All),
-            parse_field("0-59", 0, 59, true, false, false)
+            vec!["2023-11-30", "2023-10-31"],
+            last.iter_from(&from_date)
+                .rev()
+                .take(2)
+                .map(|dt| dt.format("%Y-%m-%d").to_string())
+                .collect::<Vec<_>>()
         );
-    }
+        assert_eq!(
+            vec!["2023-12-29", "2024-01-29", "2024-02-27"],
+            dates(&parse("0 0 L-2 * *").unwrap(), 3)
+        );
+        assert_eq!(
+            vec!["2024-01-01", "2024-01-31", "2024-02-01"],
+            dates(&parse("0 0 1,L 1,2 *").unwrap(), 3)
+        );
+        // terms add nothing to a field matching every day
+        assert_eq!(parse("0 0 *,L * *"), parse("0 0 * * *"));
 
-    #[test]
-    fn parse_seconds_minutes_step_2() {
-        let expected = TimeRange::Constrained((0..=59).into_iter().step_by(2).collect());
+        // the last day is never the 31st in February
         assert_eq!(
-            Ok(expected.clone()),
-            parse_field("*/2", 0, 59, true, false, false)
+            Err(ExhaustionReason::Unsatisfiable),
+            parse("0 0 L-30 2 *").unwrap().try_next_after(&from_date)
         );
         assert_eq!(
-            Ok(expected),
-            parse_field("0-59/2", 0, 59, true, false, false)
+            Err(ParseScheduleError::InvalidRange {
+                field: Field::DaysOfMonth,
+                value: "L-31".into()
+            }),
+            parse("0 0 L-31 * *")
         );
     }
 
     #[test]
-    fn parse_hours() {
-        let expected = TimeRange::Constrained((0..=22).into_iter().collect());
-        assert_eq!(Ok(expected), parse_field("0-22", 0, 23, true, false, false));
+    fn last_day_of_week_in_month() {
+        let from_date = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let dates = |expression: &str, n| -> Vec<String> {
+            Schedule::from_str(expression)
+                .unwrap()
+                .iter_from(&from_date)
+                .take(n)
+                .map(|dt| dt.format("%Y-%m-%d").to_string())
+                .collect()
+        };
+
+        // the last Friday of the month in Quartz and Crontab numbering
+        let last_fridays = vec!["2024-01-26", "2024-02-23", "2024-03-29"];
+        assert_eq!(last_fridays, dates("0 0 17 * * 6L", 3));
+        assert_eq!(last_fridays, dates("0 17 * * 5L", 3));
+        assert_eq!(last_fridays, dates("0 17 * * friL", 3));
         assert_eq!(
-            Ok(TimeRange::All),
-            parse_field("*", 0, 23, true, false, false)
+            vec![
+                "2024-01-01",
+                "2024-01-08",
+                "2024-01-15",
+                "2024-01-22",
+                "2024-01-26"
+            ],
+            dates("0 17 * * Mon,FriL", 5)
         );
+
+        let schedule = Schedule::from_str("0 0 0 * * 6L").unwrap();
+        let start = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
         assert_eq!(
-            Ok(TimeRange::All),
-            parse_field("0-23", 0, 23, true, false, false)
+            Ok(()),
+            schedule.verify_against_bruteforce(start..start + Duration::days(10))
+        );
+        assert_eq!(
+            Err(ParseScheduleError::InvalidDayOfWeekIndicator {
+                input: "8".into(),
+                suggestion: None
+            }),
+            Schedule::from_str("0 0 0 * * 8L")
         );
     }
 
     #[test]
-    fn parse_days_of_month() {
-        let expected = TimeRange::Constrained((1..=30).into_iter().collect());
-        assert_eq!(Ok(expected), parse_field("1-30", 1, 31, true, false, false));
+    fn nearest_weekday() {
+        let from_date = Utc.with_ymd_and_hms(2022, 12, 31, 0, 0, 0).unwrap();
+        let dates = |expression: &str, n| -> Vec<String> {
+            Schedule::from_str(expression)
+                .unwrap()
+                .iter_from(&from_date)
+                .take(n)
+                .map(|dt| dt.format("%Y-%m-%d").to_string())
+                .collect()
+        };
+
+        // the 15th of January 2023 is a Sunday, of April a Saturday
         assert_eq!(
-            Ok(TimeRange::All),
-            parse_field("*", 1, 31, true, false, false)
+            vec!["2023-01-16", "2023-02-15", "2023-03-15", "2023-04-14"],
+            dates("0 0 15W * *", 4)
+        );
+        // a Saturday the 1st moves forwards and a Sunday the 31st backwards within the month
+        assert_eq!(
+            vec!["2023-04-03", "2023-07-03"],
+            dates("0 0 1W Apr,Jul *", 2)
+        );
+        assert_eq!(vec!["2023-12-29"], dates("0 0 0 31W Dec * 2023", 1));
+        assert_eq!(vec!["2023-04-28", "2024-04-30"], dates("0 0 30W Apr *", 2));
+        // a day missing from the month never matches
+        assert_eq!(vec!["2023-03-31", "2023-05-31"], dates("0 0 31W 2-5 *", 2));
+
+        // the last day of April 2023 is a Sunday, of September a Saturday
+        assert_eq!(
+            vec!["2023-01-31", "2023-02-28", "2023-03-31", "2023-04-28"],
+            dates("0 0 LW * *", 4)
+        );
+        assert_eq!(vec!["2023-09-29"], dates("0 0 0 LW 9 * 2023", 1));
+
+        assert_eq!(
+            Err(ParseScheduleError::InvalidRange {
+                field: Field::DaysOfMonth,
+                value: "32W".into()
+            }),
+            Schedule::from_str("0 0 32W * *")
         );
     }
 
     #[test]
-    fn parse_months() {
+    fn nth_day_of_week_in_month() {
+        let from_date = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let dates = |expression: &str, n| -> Vec<String> {
+            Schedule::from_str(expression)
+                .unwrap()
+                .iter_from(&from_date)
+                .take(n)
+                .map(|dt| dt.format("%Y-%m-%d").to_string())
+                .collect()
+        };
+
+        let second_fridays = vec!["2024-01-12", "2024-02-09", "2024-03-08"];
+        assert_eq!(second_fridays, dates("0 0 9 * * FRI#2", 3));
+        assert_eq!(second_fridays, dates("0 0 9 * * 6#2", 3));
+        assert_eq!(second_fridays, dates("0 9 * * 5#2", 3));
         assert_eq!(
-            Ok(TimeRange::All),
-            parse_field("*", 1, 12, true, false, true)
+            vec!["2024-01-01", "2024-01-12", "2024-02-05"],
+            dates("0 9 * * Mon#1,Fri#2", 3)
         );
+        // months without a fifth Thursday are skipped
         assert_eq!(
-            Ok(TimeRange::All),
-            parse_field("1-12", 1, 12, true, false, true)
+            vec!["2024-02-29", "2024-05-30", "2024-08-29"],
+            dates("0 9 * * Thu#5", 3)
         );
         assert_eq!(
-            Ok(TimeRange::All),
-            parse_field("JAN-DEC", 1, 12, true, false, true)
+            Err(ExhaustionReason::Unsatisfiable),
+            Schedule::from_str("0 0 9 * Feb Mon#5 2023")
+                .unwrap()
+                .try_next_after(&from_date)
         );
+
         assert_eq!(
-            Ok(TimeRange::All),
-            parse_field("1-DEC", 1, 12, true, false, true)
+            Err(ParseScheduleError::ImpossibleNthDayOfWeek("Fri#5".into())),
+            Schedule::from_str("0 9 1-7 * Mon,Fri#5")
         );
         assert_eq!(
-            Ok(TimeRange::All),
-            parse_field("JAN-12", 1, 12, true, false, true)
+            Err(ParseScheduleError::ImpossibleNthDayOfWeek("Mon#5".into())),
+            Schedule::from_str("0 0 9 30,31 Feb Mon#5")
         );
         assert_eq!(
-            Ok(TimeRange::Constrained((2..=4).into_iter().collect())),
-            parse_field("FEB-APR", 1, 12, true, false, true)
+            Some(Field::DaysOfWeek),
+            Schedule::from_str("0 9 8-14 * Tue#1").unwrap_err().field()
+        );
+        // either field matching, or a day of month term, lets the term fall on other days
+        assert!(Schedule::parse_with(
+            "0 9 1-7 * Fri#5",
+            &ParseOptions::default().day_semantics(DaySemantics::Or)
+        )
+        .is_ok());
+        assert!(Schedule::from_str("0 9 L * Fri#1").is_ok());
+        assert!(Schedule::from_str("0 9 29 Feb Thu#5").is_ok());
+
+        assert_eq!(
+            Err(ParseScheduleError::InvalidNthDayOfWeek("Fri#6".into())),
+            Schedule::from_str("0 9 * * Fri#6")
         );
         assert_eq!(
-            Ok(TimeRange::Constrained((2..=4).into_iter().collect())),
-            parse_field("2-APR", 1, 12, true, false, true)
+            Err(ParseScheduleError::InvalidNthDayOfWeek("Fri#0".into())),
+            Schedule::from_str("0 9 * * Fri#0")
         );
+    }
+
+    #[test]
+    fn question_mark_wildcard() {
+        let parse = |e| Schedule::from_str(e);
+        assert_eq!(parse("0 0 12 * * Mon"), parse("0 0 12 ? * Mon"));
+        assert_eq!(parse("0 0 12 15 * *"), parse("0 0 12 15 * ?"));
+        assert_eq!(parse("0 12 * * *"), parse("0 12 ? * ?"));
+        assert!(matches!(
+            parse("? 0 12 * * *"),
+            Err(ParseScheduleError::InvalidNumber {
+                field: Field::Seconds,
+                ..
+            })
+        ));
+        assert!(parse("0 0 12 * ? *").is_err());
+    }
+
+    #[cfg(feature = "tz")]
+    #[test]
+    fn timezone_prefix() {
+        let paris = Schedule::from_str("CRON_TZ=Europe/Paris 0 30 9 * * *").unwrap();
         assert_eq!(
-            Ok(TimeRange::Constrained((2..=4).into_iter().collect())),
-            parse_field("FEB-4", 1, 12, true, false, true)
+            paris,
+            Schedule::from_str("TZ=Europe/Paris 0 30 9 * * *").unwrap()
+        );
+        assert_ne!(paris, Schedule::from_str("0 30 9 * * *").unwrap());
+        assert_ne!(
+            paris.fingerprint(),
+            Schedule::from_str("0 30 9 * * *").unwrap().fingerprint()
         );
+
+        // occurrences follow the zone's offset but are yielded in the caller's zone
+        let from = Utc.with_ymd_and_hms(2022, 10, 29, 12, 0, 0).unwrap();
+        let next: Vec<String> = paris
+            .iter_from(&from)
+            .take(2)
+            .map(|dt| dt.to_rfc3339())
+            .collect();
         assert_eq!(
-            Ok(TimeRange::Constrained((2..=4).into_iter().collect())),
-            parse_field("2-4", 1, 12, true, false, true)
+            vec!["2022-10-30T08:30:00+00:00", "2022-10-31T08:30:00+00:00"],
+            next
         );
         assert_eq!(
-            Ok(TimeRange::Constrained({
-                let mut b = (2..=4).into_iter().step_by(2).collect::<BTreeSet<u32>>();
-                b.insert(11);
-                b
-            })),
-            parse_field("FEB-APR/2,NOV", 1, 12, true, false, true)
+            Utc.with_ymd_and_hms(2022, 10, 29, 7, 30, 0).unwrap(),
+            paris.iter_from(&from).next_back().unwrap()
         );
         assert_eq!(
-            Ok(TimeRange::Constrained({
-                let mut b = (2..=4).into_iter().step_by(2).collect::<BTreeSet<u32>>();
-                b.insert(11);
-                b
-            })),
-            parse_field("feb-APR/2,nOv", 1, 12, true, false, true)
+            "1970..2099-*-* 09:30:00 Europe/Paris",
+            paris.to_oncalendar().unwrap()
         );
-    }
 
-    #[test]
-    fn parse_years() {
-        let expected = TimeRange::Constrained((1980..=2000).into_iter().collect());
+        // a zone attached after parsing applies whatever the zone iterated from
+        let attached = Schedule::from_str("0 30 9 * * *")
+            .unwrap()
+            .with_timezone(chrono_tz::Europe::Paris);
+        assert_eq!(paris, attached);
+        assert_eq!("CRON_TZ=Europe/Paris 0 30 9 * * *", attached.to_string());
+        let new_york = from.with_timezone(&chrono_tz::America::New_York);
         assert_eq!(
-            Ok(expected),
-            parse_field("1980-2000", MIN_YEAR, MAX_YEAR, true, false, false)
+            new_york
+                .timezone()
+                .with_ymd_and_hms(2022, 10, 30, 4, 30, 0)
+                .unwrap(),
+            attached.next_after(&new_york).unwrap()
         );
+
         assert_eq!(
-            Ok(TimeRange::All),
-            parse_field("*", MIN_YEAR, MAX_YEAR, true, false, false)
+            Err(ParseScheduleError::InvalidTimezone("Mars/Olympus".into())),
+            Schedule::from_str("TZ=Mars/Olympus 30 9 * * *")
         );
         assert_eq!(
-            Ok(TimeRange::Constrained(
-                (MIN_YEAR..=MAX_YEAR).step_by(2).into_iter().collect()
-            )),
-            parse_field("*/2", MIN_YEAR, MAX_YEAR, true, false, false)
+            Err(ParseScheduleError::ArgumentCount),
+            Schedule::from_str("TZ=Europe/Paris")
         );
     }
 
+    #[cfg(not(feature = "tz"))]
     #[test]
-    fn parse_vixie() {
-        let expected = Schedule {
-            seconds: Seconds::Constrained((0..=59).into_iter().step_by(5).collect()),
-            minutes: TimeRange::All,
-            hours: TimeRange::All,
-            days_of_month: TimeRange::All,
-            months: TimeRange::All,
-            days_of_week: TimeRange::All,
-            years: Years::All,
-        };
-        let parsed = Schedule::from_str("*/5 * * * * * *");
-        assert_eq!(Ok(expected), parsed);
+    fn timezone_prefix_without_feature() {
+        assert_eq!(
+            Err(ParseScheduleError::InvalidTimezone("Europe/Paris".into())),
+            Schedule::from_str("CRON_TZ=Europe/Paris 0 30 9 * * *")
+        );
     }
 
     #[test]
-    fn parse_vixie_optional_year() {
-        let expected = Schedule {
-            seconds: Seconds::Constrained((0..=59).into_iter().step_by(5).collect()),
-            minutes: TimeRange::All,
-            hours: TimeRange::All,
-            days_of_month: TimeRange::All,
-            months: TimeRange::All,
-            days_of_week: TimeRange::All,
-            years: Years::All,
-        };
-        let parsed = Schedule::from_str("*/5 * * * * *");
-        assert_eq!(Ok(expected), parsed);
+    fn wrap_around_ranges() {
+        let parse = |e| Schedule::from_str(e).unwrap();
+        assert_eq!(parse("0 22,23,0,1,2 * * *"), parse("0 22-2 * * *"));
+        assert_eq!(parse("0 0 * * Fri,Sat,Sun,Mon"), parse("0 0 * * Fri-Mon"));
+        assert_eq!(parse("0 0 * * 5,6,0,1"), parse("0 0 * * 5-1"));
+        assert_eq!(parse("0 0 0 * * 6,7,1,2"), parse("0 0 0 * * 6-2"));
+        assert_eq!(parse("0 0 1 11,12,1,2 *"), parse("0 0 1 Nov-Feb *"));
+        // steps continue across the wrap
+        assert_eq!(parse("0 22,0,2 * * *"), parse("0 22-2/2 * * *"));
+        // wrapping around the whole field is equivalent to `*`
+        assert_eq!(parse("0 0 * * *"), parse("0 0 * * 1-7"));
+
+        assert_eq!(
+            Err(ParseScheduleError::InvalidRange {
+                field: Field::Hours,
+                value: "22-24".into()
+            }),
+            Schedule::from_str("0 22-24 * * *")
+        );
     }
 
     #[test]
-    fn parse_linux_crontab() {
-        let expected = Schedule {
-            seconds: Seconds::Ignore,
-            minutes: TimeRange::Constrained((0..=59).into_iter().step_by(5).collect()),
-            hours: TimeRange::All,
-            days_of_month: TimeRange::All,
-            months: TimeRange::All,
-            days_of_week: TimeRange::All,
-            years: Years::Unbound,
+    fn parse_spanned() {
+        let locate = |e: &str| {
+            let error = Schedule::parse_spanned(e).unwrap_err();
+            (error.field(), error.span())
         };
-        let parsed = Schedule::from_str("*/5 * * * *");
-        assert_eq!(Ok(expected), parsed);
+        assert_eq!((Some(Field::Minutes), 0..2), locate("75 * * * *"));
+        assert_eq!((Some(Field::Hours), 7..9), locate("0 30 9,25 * * *"));
+        assert_eq!((Some(Field::Seconds), 0..4), locate("*/0s 0 0 * * *"));
+        assert_eq!((Some(Field::DaysOfMonth), 8..9), locate("  0 0 1-L * *"));
+        assert_eq!((Some(Field::Months), 6..9), locate("0 0 1 Foo *"));
+        assert_eq!((Some(Field::DaysOfWeek), 8..13), locate("0 0 * * Mon#9"));
+        assert_eq!(
+            (Some(Field::Years), 17..21),
+            locate("0 0 0 * * * 1980,2100")
+        );
+        assert_eq!((None, 0..5), locate("* * *"));
+        assert_eq!(
+            (Some(Field::Minutes), 18..20),
+            locate("TZ=Europe/Paris 0 99 9 * * *")
+        );
+        #[cfg(not(feature = "tz"))]
+        assert_eq!((None, 3..15), locate("TZ=Europe/Paris 0 0 9 * * *"));
+        #[cfg(feature = "tz")]
+        assert_eq!((None, 8..15), locate("CRON_TZ=Nowhere 0 0 9 * * *"));
+
+        let error = Schedule::parse_spanned("0 30 9,25 * * *").unwrap_err();
+        assert_eq!(
+            "Invalid Range '25' in the hours field at bytes 7..9",
+            error.to_string()
+        );
+        assert_eq!(
+            Err(ParseScheduleError::InvalidRange {
+                field: Field::Hours,
+                value: "25".into()
+            }),
+            Schedule::from_str("0 30 9,25 * * *")
+        );
     }
 
     #[test]
-    fn schedule_every_5_seconds() {
-        let from_date = Utc.ymd(2021, 2, 1).and_hms_opt(1, 1, 40).unwrap();
-        let parsed = Schedule::from_str("*/5 * * * * *").unwrap();
-        let mut iter = parsed.iter_from(&from_date);
+    fn parse_all_errors() {
+        let errors = |e: &str| -> Vec<(Option<Field>, ParseScheduleError)> {
+            Schedule::parse_all_errors(e)
+                .unwrap_err()
+                .into_iter()
+                .map(|e| (e.field(), e.error().clone()))
+                .collect()
+        };
         assert_eq!(
-            iter.next().unwrap(),
-            "2021-02-01T01:01:45Z".parse::<DateTime<Utc>>().unwrap()
+            vec![
+                (
+                    Some(Field::Seconds),
+                    ParseScheduleError::InvalidRange {
+                        field: Field::Seconds,
+                        value: "60".into()
+                    }
+                ),
+                (
+                    Some(Field::Hours),
+                    ParseScheduleError::InvalidRange {
+                        field: Field::Hours,
+                        value: "25".into()
+                    }
+                ),
+                (
+                    Some(Field::Months),
+                    ParseScheduleError::InvalidMonthIndicator {
+                        input: "Foo".into(),
+                        suggestion: None
+                    }
+                ),
+                (
+                    Some(Field::Years),
+                    ParseScheduleError::InvalidRange {
+                        field: Field::Years,
+                        value: "2100".into()
+                    }
+                ),
+            ],
+            errors("60 0 25 * Foo * 2100")
         );
         assert_eq!(
-            iter.next().unwrap(),
-            "2021-02-01T01:01:50Z".parse::<DateTime<Utc>>().unwrap()
+            vec![(None, ParseScheduleError::ArgumentCount)],
+            errors("60 0 25")
         );
         assert_eq!(
-            iter.next().unwrap(),
-            "2021-02-01T01:01:55Z".parse::<DateTime<Utc>>().unwrap()
+            Schedule::from_str("0 30 9 * * Mon-Fri"),
+            Ok(Schedule::parse_all_errors("0 30 9 * * Mon-Fri").unwrap())
         );
+        // the first error is the one reported by `from_str`
         assert_eq!(
-            iter.next().unwrap(),
-            "2021-02-01T01:02:00Z".parse::<DateTime<Utc>>().unwrap()
+            Err(ParseScheduleError::InvalidRange {
+                field: Field::Seconds,
+                value: "60".into()
+            }),
+            Schedule::from_str("60 0 25 * Foo * 2100")
         );
     }
 
     #[test]
-    fn schedule_every_5_minutes_vixie() {
-        let from_date = Utc.ymd(2021, 2, 1).and_hms_opt(1, 1, 1).unwrap();
-        let parsed = Schedule::from_str("0 */5 * * * *").unwrap();
-        let mut iter = parsed.iter_from(&from_date);
+    fn invalid_steps() {
+        let invalid = |field, v: &str| {
+            Err(ParseScheduleError::InvalidStepRange {
+                field,
+                value: v.into(),
+            })
+        };
+        // a step of zero never advances
         assert_eq!(
-            iter.next().unwrap(),
-            "2021-02-01T01:05:00Z".parse::<DateTime<Utc>>().unwrap()
+            invalid(Field::Minutes, "*/0"),
+            Schedule::from_str("*/0 * * * *")
         );
         assert_eq!(
-            iter.next().unwrap(),
-            "2021-02-01T01:10:00Z".parse::<DateTime<Utc>>().unwrap()
+            invalid(Field::Hours, "5/0"),
+            Schedule::from_str("0 5/0 * * *")
+        );
+        assert_eq!(
+            invalid(Field::DaysOfMonth, "1-10/0"),
+            Schedule::from_str("0 0 1-10/0 * *")
+        );
+        // a step beyond the field would only match its first value
+        assert_eq!(
+            invalid(Field::Hours, "*/25"),
+            Schedule::from_str("0 */25 * * *")
+        );
+        assert_eq!(
+            invalid(Field::Months, "Jan-Dec/13"),
+            Schedule::from_str("0 0 1 Jan-Dec/13 *")
+        );
+        assert_eq!(
+            invalid(Field::Minutes, "*/"),
+            Schedule::from_str("*/ * * * *")
+        );
+        assert_eq!(
+            invalid(Field::Minutes, "*/x"),
+            Schedule::from_str("*/x * * * *")
         );
+        assert!(Schedule::from_str("0 */24 * * *").is_ok());
     }
 
     #[test]
-    fn schedule_every_5_minutes_crontab() {
-        let from_date = Utc.ymd(2021, 2, 1).and_hms_opt(1, 1, 1).unwrap();
-        let parsed = Schedule::from_str("*/5 * * * *").unwrap();
-        let mut iter = parsed.iter_from(&from_date);
+    fn out_of_range_values() {
+        let invalid = |field, v: &str| {
+            Err(ParseScheduleError::InvalidRange {
+                field,
+                value: v.into(),
+            })
+        };
         assert_eq!(
-            iter.next().unwrap(),
-            "2021-02-01T01:05:00Z".parse::<DateTime<Utc>>().unwrap()
+            invalid(Field::Minutes, "75"),
+            Schedule::from_str("75 * * * *")
         );
         assert_eq!(
-            iter.next().unwrap(),
-            "2021-02-01T01:10:00Z".parse::<DateTime<Utc>>().unwrap()
+            invalid(Field::Hours, "99"),
+            Schedule::from_str("0 99 * * *")
+        );
+        assert_eq!(
+            invalid(Field::DaysOfMonth, "0"),
+            Schedule::from_str("0 0 0 * *")
+        );
+        assert_eq!(
+            invalid(Field::DaysOfMonth, "32"),
+            Schedule::from_str("0 0 1,32 * *")
+        );
+        assert_eq!(
+            Err(ParseScheduleError::InvalidMonthIndicator {
+                input: "13".into(),
+                suggestion: None
+            }),
+            Schedule::from_str("0 0 * 13 *")
+        );
+        assert_eq!(
+            Some(Field::Months),
+            Schedule::from_str("0 0 * 13 *").unwrap_err().field()
+        );
+        assert_eq!(
+            invalid(Field::Seconds, "60"),
+            Schedule::from_str("60 0 0 * * *")
         );
+        assert_eq!(
+            invalid(Field::Years, "2100"),
+            Schedule::from_str("0 0 0 * * * 2100")
+        );
+        // step anchors
+        assert_eq!(
+            invalid(Field::Minutes, "70/5"),
+            Schedule::from_str("70/5 * * * *")
+        );
+        assert_eq!(
+            invalid(Field::Years, "1969/2"),
+            Schedule::from_str("0 0 0 * * * 1969/2")
+        );
+        assert!(Schedule::from_str("59 23 31 12 *").is_ok());
     }
 
     #[test]
-    fn test_no_panic_on_nonexistent_time_after() {
-        use chrono::offset::TimeZone;
-        use chrono_tz::Tz;
+    fn full_names() {
+        let parse = |e| Schedule::from_str(e).unwrap();
+        assert_eq!(parse("0 9 1 Jan *"), parse("0 9 1 JANUARY *"));
+        assert_eq!(parse("0 9 1 Sep-Dec *"), parse("0 9 1 sept-December *"));
+        assert_eq!(parse("0 9 * * Wed"), parse("0 9 * * wednesday"));
+        assert_eq!(parse("0 9 * * Mon-Thu"), parse("0 9 * * Mo-Thurs"));
+        assert_eq!(parse("0 0 9 ? * FriL"), parse("0 0 9 ? * FridayL"));
+        assert_eq!(parse("0 0 9 ? * Tue#2"), parse("0 0 9 ? * Tuesday#2"));
+        // ambiguous prefixes and single letters are rejected
+        assert!(Schedule::from_str("0 9 1 Ju *").is_err());
+        assert!(Schedule::from_str("0 9 * * S").is_err());
+        assert!(Schedule::from_str("0 9 * * Mondays").is_err());
+    }
 
-        let schedule_tz: Tz = "Europe/London".parse().unwrap();
-        let dt = schedule_tz
-            .ymd(2019, 10, 27)
-            .and_hms(0, 3, 29)
-            .checked_add_signed(chrono::Duration::hours(1)) // puts it in the middle of the DST transition
-            .unwrap();
-        let schedule = Schedule::from_str("* * * * * Sat,Sun *").unwrap();
-        let next = schedule.iter_from(&dt).next().unwrap();
-        assert!(next > dt); // test is ensuring line above does not panic
+    #[cfg(all(feature = "locale-de", feature = "locale-fr", feature = "locale-es"))]
+    #[test]
+    fn localized_names() {
+        let parse =
+            |e, locale| Schedule::parse_with(e, &ParseOptions::default().locale(locale)).unwrap();
+        let english = |e| Schedule::from_str(e).unwrap();
+        assert_eq!(english("0 9 1 Mar *"), parse("0 9 1 Mär *", Locale::De));
+        assert_eq!(english("0 9 1 Mar *"), parse("0 9 1 marz *", Locale::De));
         assert_eq!(
-            next,
-            "2019-10-27T02:00:00Z".parse::<DateTime<Utc>>().unwrap()
+            english("0 9 * * Mon-Fri"),
+            parse("0 9 * * Mo-Fr", Locale::De)
+        );
+        assert_eq!(
+            english("0 9 * * Mon-Fri"),
+            parse("0 9 * * Lun-Ven", Locale::Fr)
+        );
+        assert_eq!(
+            english("0 9 1 Jan,Aug *"),
+            parse("0 9 1 Ene,Ago *", Locale::Es)
         );
+        assert_eq!(
+            english("0 9 * * Wed,Sat"),
+            parse("0 9 * * Mie,Sáb", Locale::Es)
+        );
+        // English names are still accepted
+        assert_eq!(english("0 9 * * Mon"), parse("0 9 * * Monday", Locale::Fr));
+        assert!(Schedule::from_str("0 9 * * Lun").is_err());
     }
 
     #[test]
-    fn test_no_panic_on_nonexistent_time_before() {
-        use chrono::offset::TimeZone;
-        use chrono_tz::Tz;
+    fn carries_into_larger_units() {
+        let at = |y, mo, d, h, mi| Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap();
+        let from = at(2022, 6, 1, 0, 0);
+        let cases = [
+            // the last Friday of the month
+            ("0 12 * * 5L", Schedule::from_str("0 12 * * 5L").unwrap()),
+            // the fifth Monday, found two months on
+            (
+                "0 0 12 ? * 2#5",
+                Schedule::from_str("0 0 12 ? * 2#5").unwrap(),
+            ),
+            // Friday the 13th
+            (
+                "0 0 13 * Fri",
+                Schedule::from_str("0 0 13 * Fri")
+                    .unwrap()
+                    .with_day_semantics(DaySemantics::And),
+            ),
+            (
+                "59 23 31 12 *",
+                Schedule::from_str("59 23 31 12 *").unwrap(),
+            ),
+        ];
+        let expected = [
+            (at(2022, 6, 24, 12, 0), at(2022, 5, 27, 12, 0)),
+            (at(2022, 8, 29, 12, 0), at(2022, 5, 30, 12, 0)),
+            (at(2023, 1, 13, 0, 0), at(2022, 5, 13, 0, 0)),
+            (at(2022, 12, 31, 23, 59), at(2021, 12, 31, 23, 59)),
+        ];
+        for ((expression, schedule), (next, prev)) in cases.iter().zip(expected.iter()) {
+            assert_eq!(Some(*next), schedule.after(&from), "{}", expression);
+            assert_eq!(Some(*prev), schedule.before(&from), "{}", expression);
+        }
 
-        let schedule_tz: Tz = "Europe/London".parse().unwrap();
-        let dt = schedule_tz
-            .ymd(2019, 10, 27)
-            .and_hms(0, 3, 29)
-            .checked_add_signed(chrono::Duration::hours(1)) // puts it in the middle of the DST transition
-            .unwrap();
-        let schedule = Schedule::from_str("* * * * * Sat,Sun *").unwrap();
-        let prev = schedule.iter_from(&dt).rev().next().unwrap();
-        assert!(prev < dt); // test is ensuring line above does not panic
+        // the end of a year carries into the next one
+        let schedule = Schedule::from_str("59 23 31 12 *").unwrap();
         assert_eq!(
-            prev,
-            "2019-10-26T23:59:59Z".parse::<DateTime<Utc>>().unwrap()
+            Some(at(2023, 12, 31, 23, 59)),
+            schedule.after(&at(2022, 12, 31, 23, 59))
+        );
+        assert_eq!(
+            Some(at(2022, 12, 31, 23, 59)),
+            schedule.before(&at(2023, 1, 1, 0, 0))
         );
     }
 
     #[test]
-    fn test_next_and_prev_from() {
-        let expression = "0 5,13,40-42 17 1 Jan *";
-        let schedule: Schedule = expression.parse().unwrap();
-
-        let from_date = Utc.ymd(2021, 2, 14).and_hms(23, 49, 55);
+    fn searching_does_not_allocate() {
+        let mut expressions = vec![
+            "0 30 9 * * Mon-Fri",
+            "0 0 12 L * ? 2030-2040",
+            "*/90s * * * * *",
+            "*/5 * * * *",
+            "0 0 29 2 *",
+        ];
+        if cfg!(feature = "tz") {
+            expressions.push("CRON_TZ=Europe/Paris 30 2 * * *");
+        }
+        let from = Utc.with_ymd_and_hms(2031, 6, 1, 8, 40, 1).unwrap();
+        for expression in expressions {
+            let schedule = Schedule::from_str(expression).unwrap();
+            let before = allocations();
+            let next = schedule.after(&from);
+            let prev = schedule.before(&from);
+            assert_eq!(before, allocations(), "{}", expression);
+            assert!(next.is_some() && prev.is_some(), "{}", expression);
+        }
+    }
 
-        let next = schedule.iter_from(&from_date).next();
-        assert!(next.is_some());
-        assert_eq!(
-            next.unwrap(),
-            "2022-01-01T17:05:00Z".parse::<DateTime<Utc>>().unwrap()
-        );
+    #[test]
+    fn name_suggestions() {
+        let suggestion = |e: &str| match Schedule::from_str(e) {
+            Err(ParseScheduleError::InvalidMonthIndicator { suggestion, .. })
+            | Err(ParseScheduleError::InvalidDayOfWeekIndicator { suggestion, .. }) => suggestion,
+            other => panic!("{} {:?}", e, other),
+        };
+        assert_eq!(Some("FEB"), suggestion("0 0 1 Febuary *"));
+        assert_eq!(Some("SEP"), suggestion("0 0 1 Setp *"));
+        assert_eq!(Some("OCT"), suggestion("0 0 1 Ocy *"));
+        assert_eq!(Some("WED"), suggestion("0 0 * * Wensday"));
+        assert_eq!(Some("THU"), suggestion("0 0 * * Mon-Thrusday"));
+        assert_eq!(Some("SAT"), suggestion("0 0 0 * * Staurday"));
+        assert_eq!(None, suggestion("0 0 1 Foo *"));
+        assert_eq!(None, suggestion("0 0 * * 9"));
 
-        let next2 = schedule.iter_from(&next.unwrap()).next();
-        assert!(next2.is_some());
         assert_eq!(
-            next2.unwrap(),
-            "2022-01-01T17:13:00Z".parse::<DateTime<Utc>>().unwrap()
+            "Invalid Day of Week 'Wensday', did you mean 'WED'?",
+            Schedule::from_str("0 0 * * Wensday")
+                .unwrap_err()
+                .to_string()
         );
-
-        let prev = schedule.iter_from(&next2.unwrap()).rev().next();
-        assert!(prev.is_some());
         assert_eq!(
-            prev.unwrap(),
-            "2022-01-01T17:05:00Z".parse::<DateTime<Utc>>().unwrap()
+            "Invalid Month '13'",
+            Schedule::from_str("0 0 * 13 *").unwrap_err().to_string()
         );
-        assert_eq!(prev, next);
     }
 }
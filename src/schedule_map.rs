@@ -0,0 +1,242 @@
+use crate::schedule::Schedule;
+use std::collections::BTreeMap;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+
+/// An immutable view of a `ScheduleMap` at a point in time.
+pub type ScheduleSnapshot = Arc<BTreeMap<String, Arc<Schedule>>>;
+
+/// The names affected by a single update of a `ScheduleMap`.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct ScheduleMapChange {
+    /// Names that weren't present before the update.
+    pub added: Vec<String>,
+    /// Names whose schedule changed.
+    pub updated: Vec<String>,
+    /// Names no longer present after the update.
+    pub removed: Vec<String>,
+}
+
+impl ScheduleMapChange {
+    /// Returns true if the update didn't change anything.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.updated.is_empty() && self.removed.is_empty()
+    }
+
+    fn between(
+        old: &BTreeMap<String, Arc<Schedule>>,
+        new: &BTreeMap<String, Arc<Schedule>>,
+    ) -> Self {
+        let mut change = ScheduleMapChange::default();
+        for (name, schedule) in new {
+            match old.get(name) {
+                None => change.added.push(name.clone()),
+                Some(previous) if previous != schedule => change.updated.push(name.clone()),
+                Some(_) => {}
+            }
+        }
+        change.removed = old
+            .keys()
+            .filter(|name| !new.contains_key(*name))
+            .cloned()
+            .collect();
+        change
+    }
+}
+
+/// A concurrent map of named schedules shared between a task reloading configuration and many
+/// threads evaluating the schedules.
+///
+/// Reads are cheap and never observe a partially applied update: every update swaps in a new
+/// snapshot of the whole map, after which subscribers are sent the `ScheduleMapChange`.
+/// ```rust
+/// use cron_exp::{Schedule, ScheduleMap};
+/// use std::str::FromStr;
+///
+/// let map = ScheduleMap::new();
+/// let changes = map.subscribe();
+///
+/// map.replace_all(vec![
+///     ("backup".to_string(), Schedule::from_str("0 3 * * *").unwrap()),
+///     ("report".to_string(), Schedule::from_str("0 9 * * Mon").unwrap()),
+/// ]);
+/// assert!(map.get("backup").is_some());
+/// assert_eq!(vec!["backup", "report"], changes.recv().unwrap().added);
+/// ```
+#[derive(Debug, Default)]
+pub struct ScheduleMap {
+    schedules: RwLock<ScheduleSnapshot>,
+    subscribers: Mutex<Vec<Sender<ScheduleMapChange>>>,
+}
+
+impl ScheduleMap {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the schedule registered under the supplied name.
+    pub fn get(&self, name: &str) -> Option<Arc<Schedule>> {
+        self.snapshot().get(name).cloned()
+    }
+
+    /// Returns a consistent view of all schedules which isn't affected by later updates.
+    pub fn snapshot(&self) -> ScheduleSnapshot {
+        self.schedules
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Returns the number of schedules.
+    pub fn len(&self) -> usize {
+        self.snapshot().len()
+    }
+
+    /// Returns true if there are no schedules.
+    pub fn is_empty(&self) -> bool {
+        self.snapshot().is_empty()
+    }
+
+    /// Inserts or updates a single schedule.
+    pub fn insert(&self, name: impl Into<String>, schedule: Schedule) -> ScheduleMapChange {
+        let name = name.into();
+        self.update(|schedules| {
+            schedules.insert(name, Arc::new(schedule));
+        })
+    }
+
+    /// Removes a single schedule.
+    pub fn remove(&self, name: &str) -> ScheduleMapChange {
+        self.update(|schedules| {
+            schedules.remove(name);
+        })
+    }
+
+    /// Atomically replaces all schedules, readers observe either the old or the new set but
+    /// never a mix of both.
+    pub fn replace_all<I>(&self, schedules: I) -> ScheduleMapChange
+    where
+        I: IntoIterator<Item = (String, Schedule)>,
+    {
+        let replacement: BTreeMap<String, Arc<Schedule>> = schedules
+            .into_iter()
+            .map(|(name, schedule)| (name, Arc::new(schedule)))
+            .collect();
+        self.update(|schedules| *schedules = replacement)
+    }
+
+    /// Returns a receiver of every non empty change made to the map from now on. Dropping the
+    /// receiver unsubscribes.
+    pub fn subscribe(&self) -> Receiver<ScheduleMapChange> {
+        let (sender, receiver) = channel();
+        self.subscribers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(sender);
+        receiver
+    }
+
+    fn update<F>(&self, f: F) -> ScheduleMapChange
+    where
+        F: FnOnce(&mut BTreeMap<String, Arc<Schedule>>),
+    {
+        // holding the subscribers lock for the whole update serializes writers so subscribers
+        // receive changes in the order they were applied
+        let mut subscribers = self
+            .subscribers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let old = self.snapshot();
+        let mut new = (*old).clone();
+        f(&mut new);
+        let change = ScheduleMapChange::between(&old, &new);
+        if change.is_empty() {
+            return change;
+        }
+        *self
+            .schedules
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = Arc::new(new);
+
+        subscribers.retain(|subscriber| subscriber.send(change.clone()).is_ok());
+        change
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn schedule(expression: &str) -> Schedule {
+        Schedule::from_str(expression).unwrap()
+    }
+
+    #[test]
+    fn updates_and_notifications() {
+        let map = ScheduleMap::new();
+        let changes = map.subscribe();
+
+        map.insert("a", schedule("0 * * * *"));
+        map.insert("b", schedule("0 1 * * *"));
+        let before = map.snapshot();
+
+        // inserting an equivalent expression doesn't count as a change
+        assert!(map.insert("a", schedule("0 */1 * * *")).is_empty());
+
+        let change = map.replace_all(vec![
+            ("a".to_string(), schedule("0 2 * * *")),
+            ("c".to_string(), schedule("0 3 * * *")),
+        ]);
+        assert_eq!(
+            ScheduleMapChange {
+                added: vec!["c".to_string()],
+                updated: vec!["a".to_string()],
+                removed: vec!["b".to_string()],
+            },
+            change
+        );
+        assert_eq!(2, before.len());
+        assert_eq!(Some(Arc::new(schedule("0 2 * * *"))), map.get("a"));
+        assert!(map.get("b").is_none());
+
+        let received: Vec<ScheduleMapChange> = changes.try_iter().collect();
+        assert_eq!(3, received.len());
+        assert_eq!(change, received[2]);
+
+        drop(changes);
+        map.remove("a");
+        assert!(map.subscribers.lock().unwrap().is_empty());
+        assert_eq!(1, map.len());
+    }
+
+    #[test]
+    fn concurrent_readers() {
+        let map = Arc::new(ScheduleMap::new());
+        map.insert("a", schedule("0 * * * *"));
+
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let map = map.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..100 {
+                        let snapshot = map.snapshot();
+                        assert!(snapshot.len() == 1 || snapshot.len() == 2);
+                    }
+                })
+            })
+            .collect();
+        for i in 0..100 {
+            let mut schedules = vec![("a".to_string(), schedule("0 * * * *"))];
+            if i % 2 == 0 {
+                schedules.push(("b".to_string(), schedule("0 1 * * *")));
+            }
+            map.replace_all(schedules);
+        }
+        for reader in readers {
+            reader.join().unwrap();
+        }
+    }
+}
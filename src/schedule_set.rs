@@ -0,0 +1,221 @@
+use crate::schedule::Schedule;
+use chrono::{DateTime, TimeZone};
+use std::iter::FromIterator;
+
+/// The union of several schedules behaving as a single schedule, for requirements a single
+/// expression can't express such as every weekday at 9 plus the 1st of the month at midnight.
+///
+/// Schedules firing at the same instant yield a single occurrence.
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use cron_exp::{Schedule, ScheduleSet};
+/// use std::str::FromStr;
+///
+/// let set: ScheduleSet = ["0 9 * * Mon-Fri", "0 0 1 * *"]
+///     .iter()
+///     .map(|expression| Schedule::from_str(expression).unwrap())
+///     .collect();
+///
+/// let from = Utc.with_ymd_and_hms(2022, 6, 29, 12, 0, 0).unwrap();
+/// let upcoming: Vec<_> = set.iter_from(&from).take(3).collect();
+/// assert_eq!(
+///     vec![
+///         Utc.with_ymd_and_hms(2022, 6, 30, 9, 0, 0).unwrap(),
+///         Utc.with_ymd_and_hms(2022, 7, 1, 0, 0, 0).unwrap(),
+///         Utc.with_ymd_and_hms(2022, 7, 1, 9, 0, 0).unwrap(),
+///     ],
+///     upcoming
+/// );
+/// assert!(set.includes(&Utc.with_ymd_and_hms(2022, 7, 1, 0, 0, 0).unwrap()));
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct ScheduleSet {
+    schedules: Vec<Schedule>,
+}
+
+impl ScheduleSet {
+    /// Creates an empty set, which never fires.
+    pub fn new() -> Self {
+        ScheduleSet::default()
+    }
+
+    /// Adds a schedule to the union.
+    pub fn add(&mut self, schedule: Schedule) {
+        self.schedules.push(schedule);
+    }
+
+    /// Returns the schedules in the order they were added.
+    pub fn schedules(&self) -> &[Schedule] {
+        &self.schedules
+    }
+
+    /// Returns the number of schedules.
+    pub fn len(&self) -> usize {
+        self.schedules.len()
+    }
+
+    /// Returns true if there are no schedules.
+    pub fn is_empty(&self) -> bool {
+        self.schedules.is_empty()
+    }
+
+    /// Returns the earliest occurrence of any schedule strictly after the supplied datetime.
+    pub fn next_after<Z>(&self, dt: &DateTime<Z>) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        self.schedules.iter().filter_map(|s| s.after(dt)).min()
+    }
+
+    /// Returns the latest occurrence of any schedule strictly before the supplied datetime.
+    pub fn prev_before<Z>(&self, dt: &DateTime<Z>) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        self.schedules.iter().filter_map(|s| s.before(dt)).max()
+    }
+
+    /// Returns whether the supplied instant is an occurrence of any schedule, see
+    /// `Schedule::includes`.
+    pub fn includes<Z>(&self, dt: &DateTime<Z>) -> bool
+    where
+        Z: TimeZone,
+    {
+        self.schedules.iter().any(|s| s.includes(dt))
+    }
+
+    /// Iterates the occurrences of the union after the supplied datetime, or before it in
+    /// reverse, as `Schedule::iter_from` does.
+    pub fn iter_from<'a, Z>(
+        &'a self,
+        dt: &DateTime<Z>,
+    ) -> impl DoubleEndedIterator<Item = DateTime<Z>> + 'a
+    where
+        Z: TimeZone + 'a,
+    {
+        ScheduleSetIterator {
+            is_done: false,
+            set: self,
+            previous_datetime: dt.clone(),
+        }
+    }
+}
+
+impl From<Vec<Schedule>> for ScheduleSet {
+    fn from(schedules: Vec<Schedule>) -> Self {
+        ScheduleSet { schedules }
+    }
+}
+
+impl FromIterator<Schedule> for ScheduleSet {
+    fn from_iter<I: IntoIterator<Item = Schedule>>(iter: I) -> Self {
+        ScheduleSet {
+            schedules: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<Schedule> for ScheduleSet {
+    fn extend<I: IntoIterator<Item = Schedule>>(&mut self, iter: I) {
+        self.schedules.extend(iter);
+    }
+}
+
+struct ScheduleSetIterator<'a, Z>
+where
+    Z: TimeZone,
+{
+    is_done: bool,
+    set: &'a ScheduleSet,
+    previous_datetime: DateTime<Z>,
+}
+
+impl<Z> Iterator for ScheduleSetIterator<'_, Z>
+where
+    Z: TimeZone,
+{
+    type Item = DateTime<Z>;
+
+    fn next(&mut self) -> Option<DateTime<Z>> {
+        if self.is_done {
+            return None;
+        }
+        match self.set.next_after(&self.previous_datetime) {
+            Some(next) => {
+                self.previous_datetime = next.clone();
+                Some(next)
+            }
+            None => {
+                self.is_done = true;
+                None
+            }
+        }
+    }
+}
+
+impl<Z> DoubleEndedIterator for ScheduleSetIterator<'_, Z>
+where
+    Z: TimeZone,
+{
+    fn next_back(&mut self) -> Option<DateTime<Z>> {
+        if self.is_done {
+            return None;
+        }
+        match self.set.prev_before(&self.previous_datetime) {
+            Some(prev) => {
+                self.previous_datetime = prev.clone();
+                Some(prev)
+            }
+            None => {
+                self.is_done = true;
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::str::FromStr;
+
+    fn set(expressions: &[&str]) -> ScheduleSet {
+        expressions
+            .iter()
+            .map(|expression| Schedule::from_str(expression).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn union_of_schedules() {
+        let set = set(&["0 */6 * * *", "0 */4 * * *"]);
+        let from = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap();
+        let at = |h| Utc.with_ymd_and_hms(2022, 6, 1, h, 0, 0).unwrap();
+
+        // midnight and noon are shared and occur once
+        let upcoming: Vec<_> = set.iter_from(&from).take(5).collect();
+        assert_eq!(vec![at(4), at(6), at(8), at(12), at(16)], upcoming);
+        let previous: Vec<_> = set.iter_from(&at(13)).rev().take(3).collect();
+        assert_eq!(vec![at(12), at(8), at(6)], previous);
+
+        assert_eq!(Some(at(4)), set.next_after(&from));
+        assert_eq!(Some(at(8)), set.prev_before(&at(12)));
+        assert!(set.includes(&at(6)) && set.includes(&at(8)));
+        assert!(!set.includes(&at(10)));
+    }
+
+    #[test]
+    fn empty_set_never_fires() {
+        let from = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap();
+        let empty = ScheduleSet::new();
+        assert!(empty.is_empty());
+        assert_eq!(None, empty.next_after(&from));
+        assert_eq!(0, empty.iter_from(&from).count());
+
+        let finished = set(&["0 0 0 1 1 * 2020", "0 0 0 1 1 * 2021"]);
+        assert_eq!(2, finished.len());
+        assert_eq!(0, finished.iter_from(&from).count());
+        assert_eq!(2, finished.iter_from(&from).rev().count());
+    }
+}
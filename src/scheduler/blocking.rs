@@ -0,0 +1,634 @@
+//! A scheduler running jobs on a pool of threads, for binaries without an async runtime.
+use super::events::{Listeners, SkipReason};
+use super::job::{
+    event, Backends, Clock, JobHandle, JobOutput, MisfirePolicy, PausePolicy, SystemClock, Trigger,
+    MISFIRE_THRESHOLD,
+};
+use super::leader::LeadershipProvider;
+use super::lock::DistributedLock;
+use super::store::{JobDefinition, JobStore};
+use super::BoxError;
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, Weak};
+use std::thread::{self, JoinHandle};
+
+/// Runs jobs at the fire times of their triggers on a fixed pool of worker threads, with a
+/// dispatcher thread waiting on a condition variable for the next fire time.
+///
+/// As with the async `Scheduler`, each job runs at most once at a time, the occurrences it
+/// misses are handled by the `MisfirePolicy` and the fire times reached while it is paused by the
+/// `PausePolicy`, replicas sharing a `DistributedLock` run each fire time once between them and
+/// a `LeadershipProvider` keeps standby replicas from running jobs. Dropping the scheduler stops
+/// dispatching and waits for the runs in progress to finish.
+/// ```rust
+/// use cron_exp::scheduler::blocking::Scheduler;
+/// use cron_exp::Schedule;
+/// use std::str::FromStr;
+///
+/// let scheduler = Scheduler::new(2);
+/// let schedule = Schedule::from_str("0 3 * * *").unwrap();
+/// let backup = scheduler.add("backup", schedule, || println!("backing up"));
+/// assert_eq!(1, scheduler.jobs().len());
+/// backup.cancel();
+/// scheduler.shutdown();
+/// ```
+#[derive(Debug)]
+pub struct Scheduler {
+    shared: Arc<Shared>,
+    misfire_policy: MisfirePolicy,
+    pause_policy: PausePolicy,
+    backends: Backends,
+    threads: Vec<JoinHandle<()>>,
+}
+
+#[derive(Debug)]
+struct Shared {
+    state: Mutex<State>,
+    /// Whether the dispatcher was woken, kept apart from the state so jobs can be cancelled or
+    /// resumed from the listeners called while the state is locked.
+    signal: Mutex<bool>,
+    /// Wakes the dispatcher when a job is added, finishes running, is cancelled or resumed, or on
+    /// shutdown.
+    dispatch: Condvar,
+    /// Wakes the workers when a run is queued or on shutdown.
+    work: Condvar,
+    listeners: Listeners,
+    clock: Arc<dyn Clock>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    jobs: Vec<Entry>,
+    /// The jobs to run and their fire times, in order.
+    queue: VecDeque<(Arc<Job>, Vec<DateTime<Utc>>)>,
+    shutdown: bool,
+}
+
+struct Entry {
+    job: Arc<Job>,
+    trigger: Box<dyn Trigger>,
+    misfire_policy: MisfirePolicy,
+    pause_policy: PausePolicy,
+    /// The last fire time handled.
+    last: DateTime<Utc>,
+    next: Option<DateTime<Utc>>,
+    /// Whether the job was paused when last dispatched.
+    paused: bool,
+    /// The fire times reached while paused, run once resumed.
+    accumulated: Vec<DateTime<Utc>>,
+}
+
+type Run = Box<dyn FnMut() -> Option<String> + Send>;
+
+struct Job {
+    handle: JobHandle,
+    run: Mutex<Run>,
+    running: AtomicBool,
+    /// When the last run finished.
+    finished: Mutex<Option<DateTime<Utc>>>,
+    backends: Backends,
+}
+
+impl fmt::Debug for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Entry")
+            .field("job", &self.job)
+            .field("misfire_policy", &self.misfire_policy)
+            .field("pause_policy", &self.pause_policy)
+            .field("last", &self.last)
+            .field("next", &self.next)
+            .field("paused", &self.paused)
+            .field("accumulated", &self.accumulated)
+            .finish()
+    }
+}
+
+impl fmt::Debug for Job {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Job")
+            .field("handle", &self.handle)
+            .field("running", &self.running)
+            .finish()
+    }
+}
+
+impl Scheduler {
+    /// Starts a scheduler running jobs on `workers` threads, at least one.
+    pub fn new(workers: usize) -> Self {
+        Scheduler::with_listeners(workers, Listeners::new())
+    }
+
+    /// Starts a scheduler running jobs on `workers` threads, at least one, notifying the
+    /// supplied listeners about every job.
+    pub fn with_listeners(workers: usize, listeners: Listeners) -> Self {
+        Scheduler::with_clock(workers, listeners, Arc::new(SystemClock))
+    }
+
+    /// Starts a scheduler like `with_listeners`, telling the time with the supplied clock.
+    pub(crate) fn with_clock(workers: usize, listeners: Listeners, clock: Arc<dyn Clock>) -> Self {
+        let shared = Arc::new(Shared {
+            state: Mutex::default(),
+            signal: Mutex::default(),
+            dispatch: Condvar::new(),
+            work: Condvar::new(),
+            listeners,
+            clock,
+        });
+        let mut threads = Vec::new();
+        let dispatcher = shared.clone();
+        threads.push(thread::spawn(move || dispatcher.dispatch()));
+        for _ in 0..workers.max(1) {
+            let worker = shared.clone();
+            threads.push(thread::spawn(move || worker.work()));
+        }
+        Scheduler {
+            shared,
+            misfire_policy: MisfirePolicy::default(),
+            pause_policy: PausePolicy::default(),
+            backends: Backends::default(),
+            threads,
+        }
+    }
+
+    /// Sets the policy for the occurrences missed by the jobs added afterwards.
+    pub fn with_misfire_policy(mut self, policy: MisfirePolicy) -> Self {
+        self.misfire_policy = policy;
+        self
+    }
+
+    /// Sets the policy for the fire times reached while the jobs added afterwards are paused.
+    pub fn with_pause_policy(mut self, policy: PausePolicy) -> Self {
+        self.pause_policy = policy;
+        self
+    }
+
+    /// Claims every fire time of the jobs added afterwards from the lock before running it,
+    /// skipping it with `SkipReason::AlreadyClaimed` when another replica claimed it.
+    pub fn with_lock(mut self, lock: Arc<dyn DistributedLock>) -> Self {
+        self.backends.lock = Some(lock);
+        self
+    }
+
+    /// Only runs the fire times of the jobs added afterwards while the provider reports this
+    /// replica as the leader, skipping them with `SkipReason::NotLeader` otherwise.
+    pub fn with_leadership(mut self, leadership: Arc<dyn LeadershipProvider>) -> Self {
+        self.backends.leadership = Some(leadership);
+        self
+    }
+
+    /// Records the fire time of every run in the store, which `add_stored` saves jobs to and
+    /// `restore` resumes them from after a restart.
+    pub fn with_store(mut self, store: Arc<dyn JobStore>) -> Self {
+        self.backends.store = Some(store);
+        self
+    }
+
+    /// Runs `job` on a worker thread at every fire time of the trigger until the trigger is
+    /// exhausted or the job is cancelled.
+    pub fn add<T, F, O>(&self, name: &str, trigger: T, job: F) -> JobHandle
+    where
+        T: Trigger + 'static,
+        F: FnMut() -> O + Send + 'static,
+        O: JobOutput,
+    {
+        self.add_from(name, trigger, job, self.shared.clock.now())
+    }
+
+    /// Saves the definition to the store and runs `job` at the occurrences of its expression
+    /// like `add`. A definition with a last run resumes from it, the occurrences passed since
+    /// are handled by the `MisfirePolicy`.
+    pub fn add_stored<F, O>(
+        &self,
+        definition: &JobDefinition,
+        job: F,
+    ) -> Result<JobHandle, BoxError>
+    where
+        F: FnMut() -> O + Send + 'static,
+        O: JobOutput,
+    {
+        let schedule = definition.schedule()?;
+        if let Some(store) = &self.backends.store {
+            store.save_job(definition)?;
+        }
+        let last = definition
+            .last_run
+            .unwrap_or_else(|| self.shared.clock.now());
+        Ok(self.add_from(&definition.id, schedule, job, last))
+    }
+
+    /// Runs every job of the store from its last run, with the job `jobs` returns for its
+    /// definition, and returns their handles ordered by id. Fails without running any job when
+    /// the expression of one of them is invalid.
+    pub fn restore<J, F, O>(&self, mut jobs: J) -> Result<Vec<JobHandle>, BoxError>
+    where
+        J: FnMut(&JobDefinition) -> F,
+        F: FnMut() -> O + Send + 'static,
+        O: JobOutput,
+    {
+        let definitions = match &self.backends.store {
+            Some(store) => store.load_jobs()?,
+            None => Vec::new(),
+        };
+        let schedules = definitions
+            .iter()
+            .map(JobDefinition::schedule)
+            .collect::<Result<Vec<_>, _>>()?;
+        let handles = definitions
+            .iter()
+            .zip(schedules)
+            .map(|(definition, schedule)| {
+                let last = definition
+                    .last_run
+                    .unwrap_or_else(|| self.shared.clock.now());
+                self.add_from(&definition.id, schedule, jobs(definition), last)
+            })
+            .collect();
+        Ok(handles)
+    }
+
+    fn add_from<T, F, O>(
+        &self,
+        name: &str,
+        trigger: T,
+        mut job: F,
+        last: DateTime<Utc>,
+    ) -> JobHandle
+    where
+        T: Trigger + 'static,
+        F: FnMut() -> O + Send + 'static,
+        O: JobOutput,
+    {
+        let shared: Weak<Shared> = Arc::downgrade(&self.shared);
+        let wake = Box::new(move || {
+            if let Some(shared) = shared.upgrade() {
+                shared.wake_dispatcher();
+            }
+        });
+        let handle = JobHandle::with_wake(name, self.shared.clock.clone(), wake);
+        let next = trigger.next_after(&last);
+        if let Some(next) = next {
+            self.shared.listeners.scheduled(&event(&handle, next));
+        }
+        let job = Job {
+            handle: handle.clone(),
+            run: Mutex::new(Box::new(move || job().into_error())),
+            running: AtomicBool::new(false),
+            finished: Mutex::default(),
+            backends: self.backends.clone(),
+        };
+        self.shared.state.lock().unwrap().jobs.push(Entry {
+            job: Arc::new(job),
+            trigger: Box::new(trigger),
+            misfire_policy: self.misfire_policy,
+            pause_policy: self.pause_policy,
+            last,
+            next,
+            paused: false,
+            accumulated: Vec::new(),
+        });
+        self.shared.wake_dispatcher();
+        handle
+    }
+
+    /// Returns the handles of the jobs that weren't cancelled.
+    pub fn jobs(&self) -> Vec<JobHandle> {
+        let state = self.shared.state.lock().unwrap();
+        state
+            .jobs
+            .iter()
+            .map(|entry| &entry.job.handle)
+            .filter(|handle| !handle.is_cancelled())
+            .cloned()
+            .collect()
+    }
+
+    /// Cancels every job and waits for the runs in progress to finish, as dropping the scheduler
+    /// does.
+    pub fn shutdown(self) {}
+}
+
+impl Drop for Scheduler {
+    fn drop(&mut self) {
+        let handles: Vec<JobHandle> = {
+            let mut state = self.shared.state.lock().unwrap();
+            state.shutdown = true;
+            state.queue.clear();
+            state
+                .jobs
+                .iter()
+                .map(|entry| entry.job.handle.clone())
+                .collect()
+        };
+        handles.iter().for_each(JobHandle::cancel);
+        self.shared.wake_dispatcher();
+        self.shared.work.notify_all();
+        for thread in self.threads.drain(..) {
+            // a panicking job already reported its panic on its own thread
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Shared {
+    fn wake_dispatcher(&self) {
+        *self.signal.lock().unwrap() = true;
+        self.dispatch.notify_one();
+    }
+
+    /// Queues the runs of the jobs as they come due, until shutdown.
+    fn dispatch(&self) {
+        loop {
+            let earliest = {
+                let mut state = self.state.lock().unwrap();
+                if state.shutdown {
+                    return;
+                }
+                self.dispatch_due(&mut state)
+            };
+
+            let signal = self.signal.lock().unwrap();
+            let mut signal = match earliest {
+                Some(earliest) => {
+                    let timeout = (earliest - self.clock.now()).to_std().unwrap_or_default();
+                    let woken = |signal: &mut bool| !*signal;
+                    self.dispatch
+                        .wait_timeout_while(signal, timeout, woken)
+                        .unwrap()
+                        .0
+                }
+                None => self.dispatch.wait_while(signal, |signal| !*signal).unwrap(),
+            };
+            *signal = false;
+        }
+    }
+
+    /// Queues the runs of the jobs that came due, returning when the next one comes due.
+    fn dispatch_due(&self, state: &mut State) -> Option<DateTime<Utc>> {
+        // runs already queued or in progress keep their jobs alive
+        state.jobs.retain(|entry| {
+            !entry.job.handle.is_cancelled()
+                && (entry.next.is_some() || entry.job.running.load(Ordering::SeqCst))
+        });
+
+        let now = self.clock.now();
+        let mut queued = Vec::new();
+        for entry in state.jobs.iter_mut() {
+            // occurrences passing while the job runs are handled once it finishes
+            if entry.job.running.load(Ordering::SeqCst) {
+                continue;
+            }
+            let handle = &entry.job.handle;
+            let mut runs = Vec::new();
+            let paused = handle.is_paused();
+            if entry.paused && !paused {
+                // resumed, the next fire time is computed from now
+                runs.append(&mut entry.accumulated);
+                entry.last = now;
+            }
+            entry.paused = paused;
+
+            let due = entry.trigger.missed_between(&entry.last, &now);
+            if let Some(last) = due.last() {
+                entry.last = *last;
+            }
+            if paused {
+                match entry.pause_policy {
+                    PausePolicy::Skip => due.iter().for_each(|fire_time| {
+                        self.listeners
+                            .skip(&event(handle, *fire_time), SkipReason::Paused)
+                    }),
+                    PausePolicy::Accumulate => entry.accumulated.extend(due),
+                }
+            } else if !due.is_empty() {
+                // passed while the job last ran or reached late, e.g. after a suspension
+                let finished = *entry.job.finished.lock().unwrap();
+                let (missed, on_time): (Vec<_>, Vec<_>) = due.into_iter().partition(|fire_time| {
+                    finished.is_some_and(|finished| *fire_time < finished)
+                        || now - *fire_time > MISFIRE_THRESHOLD
+                });
+                runs.extend(entry.misfire_policy.resolve(
+                    handle,
+                    missed,
+                    finished,
+                    &self.listeners,
+                ));
+                runs.extend(on_time);
+            }
+
+            let next = entry.trigger.next_after(&entry.last);
+            if next != entry.next {
+                entry.next = next;
+                if let Some(next) = next {
+                    self.listeners.scheduled(&event(handle, next));
+                }
+            }
+            if !runs.is_empty() {
+                entry.job.running.store(true, Ordering::SeqCst);
+                queued.push((entry.job.clone(), runs));
+            }
+        }
+        if !queued.is_empty() {
+            state.queue.extend(queued);
+            self.work.notify_all();
+        }
+
+        state
+            .jobs
+            .iter()
+            .filter(|entry| !entry.job.running.load(Ordering::SeqCst))
+            .filter_map(|entry| entry.next)
+            .min()
+    }
+
+    /// Runs the queued runs, until shutdown.
+    fn work(&self) {
+        loop {
+            let (job, runs) = {
+                let mut state = self.state.lock().unwrap();
+                loop {
+                    if state.shutdown {
+                        return;
+                    }
+                    if let Some(queued) = state.queue.pop_front() {
+                        break queued;
+                    }
+                    state = self.work.wait(state).unwrap();
+                }
+            };
+            for scheduled in runs {
+                if job.handle.is_cancelled() {
+                    break;
+                }
+                if !job.backends.admit(&job.handle, scheduled, &self.listeners) {
+                    continue;
+                }
+                self.listeners.start(&event(&job.handle, scheduled));
+                let error = (job.run.lock().unwrap())();
+                match error {
+                    None => self.listeners.finish(&event(&job.handle, scheduled)),
+                    Some(error) => self.listeners.error(&event(&job.handle, scheduled), &error),
+                }
+                job.backends.record(&job.handle, scheduled, &self.listeners);
+            }
+            *job.finished.lock().unwrap() = Some(job.handle.now());
+            job.running.store(false, Ordering::SeqCst);
+            self.wake_dispatcher();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::testing::{self, block_on, Harness, ManualClock, Setup, Work};
+
+    /// Starts schedulers on a manual clock, which it advances a millisecond at a time, waiting
+    /// for the schedulers to run the fire times reached.
+    struct Threads {
+        clock: Arc<ManualClock>,
+        schedulers: Mutex<Vec<Arc<Shared>>>,
+    }
+
+    impl Threads {
+        fn new() -> Self {
+            Threads {
+                clock: Arc::new(ManualClock::new()),
+                schedulers: Mutex::default(),
+            }
+        }
+
+        /// Waits until the fire times reached are dispatched and every job running sleeps on
+        /// the clock.
+        fn settle(&self) {
+            loop {
+                let mut busy = false;
+                let mut running = 0;
+                for shared in self.schedulers.lock().unwrap().iter() {
+                    let mut state = shared.state.lock().unwrap();
+                    if state.shutdown {
+                        continue;
+                    }
+                    // dispatching again at the same time only handles the runs that finished
+                    shared.dispatch_due(&mut state);
+                    busy |= !state.queue.is_empty();
+                    running += state
+                        .jobs
+                        .iter()
+                        .filter(|entry| entry.job.running.load(Ordering::SeqCst))
+                        .count();
+                }
+                if !busy && running == self.clock.sleeping() {
+                    return;
+                }
+                thread::yield_now();
+            }
+        }
+
+        fn job(&self, work: Work) -> impl FnMut() -> Result<(), &'static str> + Send + 'static {
+            let clock = self.clock.clone();
+            move || match work {
+                Work::Nothing => Ok(()),
+                Work::Sleep(millis) => {
+                    clock.sleep(millis);
+                    Ok(())
+                }
+                Work::Fail(error) => Err(error),
+            }
+        }
+    }
+
+    impl Harness for Threads {
+        type Scheduler = Scheduler;
+
+        fn scheduler(&self, listeners: Listeners, setup: Setup) -> Scheduler {
+            let mut scheduler = Scheduler::with_clock(2, listeners, self.clock.clone());
+            scheduler.misfire_policy = setup.misfire_policy;
+            scheduler.pause_policy = setup.pause_policy;
+            scheduler.backends = setup.backends;
+            self.schedulers
+                .lock()
+                .unwrap()
+                .push(scheduler.shared.clone());
+            scheduler
+        }
+
+        fn add<T>(&self, scheduler: &Scheduler, name: &str, trigger: T, work: Work) -> JobHandle
+        where
+            T: Trigger + 'static,
+        {
+            scheduler.add(name, trigger, self.job(work))
+        }
+
+        fn restore(&self, scheduler: &Scheduler, work: Work) -> Result<Vec<JobHandle>, BoxError> {
+            scheduler.restore(|_| self.job(work))
+        }
+
+        fn jobs(&self, scheduler: &Scheduler) -> Vec<JobHandle> {
+            scheduler.jobs()
+        }
+
+        async fn advance(&self, millis: i64) {
+            self.settle();
+            for _ in 0..millis {
+                self.clock.advance(1);
+                self.settle();
+            }
+        }
+    }
+
+    #[test]
+    fn runs_at_fire_times() {
+        block_on(testing::runs_at_fire_times(&Threads::new()));
+    }
+
+    #[test]
+    fn replicas_sharing_a_lock_run_each_fire_time_once() {
+        block_on(testing::replicas_sharing_a_lock_run_each_fire_time_once(
+            &Threads::new(),
+        ));
+    }
+
+    #[test]
+    fn followers_never_run_jobs() {
+        block_on(testing::followers_never_run_jobs(&Threads::new()));
+    }
+
+    #[test]
+    fn restored_jobs_resume_from_their_last_run() {
+        block_on(testing::restored_jobs_resume_from_their_last_run(
+            &Threads::new(),
+        ));
+    }
+
+    #[test]
+    fn invalid_stored_jobs_are_not_restored() {
+        block_on(testing::invalid_stored_jobs_are_not_restored(
+            &Threads::new(),
+        ));
+    }
+
+    #[test]
+    fn overlapping_occurrences_are_skipped() {
+        block_on(testing::overlapping_occurrences_are_skipped(&Threads::new()));
+    }
+
+    #[test]
+    fn missed_occurrences_follow_the_policy() {
+        block_on(testing::missed_occurrences_follow_the_policy(
+            &Threads::new(),
+        ));
+    }
+
+    #[test]
+    fn paused_fire_times_follow_the_policy() {
+        block_on(testing::paused_fire_times_follow_the_policy(&Threads::new()));
+    }
+
+    #[test]
+    fn cancelled_jobs_stop_firing() {
+        block_on(testing::cancelled_jobs_stop_firing(&Threads::new()));
+    }
+}
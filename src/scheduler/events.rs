@@ -0,0 +1,142 @@
+use chrono::{DateTime, Utc};
+use std::fmt;
+use std::sync::Arc;
+
+/// Describes a single scheduling decision about an occurrence of a job.
+#[derive(Debug, PartialEq, Clone)]
+pub struct JobEvent {
+    /// The id of the job.
+    pub job: String,
+    /// The time the occurrence was scheduled to fire at.
+    pub scheduled_time: DateTime<Utc>,
+    /// The time the decision was made.
+    pub actual_time: DateTime<Utc>,
+}
+
+/// Why an occurrence of a job was not run.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SkipReason {
+    /// This replica is not the elected leader.
+    NotLeader,
+    /// Another replica already claimed the occurrence.
+    AlreadyClaimed,
+    /// The previous run of the job was still in progress.
+    Overlap,
+    /// The job was paused.
+    Paused,
+}
+
+/// Observes the lifecycle of scheduled jobs, every method defaults to doing nothing.
+pub trait JobListener: Send + Sync {
+    /// The next occurrence of the job was computed.
+    fn on_scheduled(&self, _event: &JobEvent) {}
+
+    /// The job started running.
+    fn on_start(&self, _event: &JobEvent) {}
+
+    /// The job finished running successfully.
+    fn on_finish(&self, _event: &JobEvent) {}
+
+    /// The occurrence was deliberately not run.
+    fn on_skip(&self, _event: &JobEvent, _reason: SkipReason) {}
+
+    /// The occurrence was not run on time, e.g. because the process was suspended.
+    fn on_miss(&self, _event: &JobEvent) {}
+
+    /// The job failed.
+    fn on_error(&self, _event: &JobEvent, _error: &str) {}
+}
+
+/// The registered `JobListener`s of a scheduler, notified in registration order.
+#[derive(Default, Clone)]
+pub struct Listeners {
+    listeners: Vec<Arc<dyn JobListener>>,
+}
+
+impl fmt::Debug for Listeners {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Listeners")
+            .field("len", &self.listeners.len())
+            .finish()
+    }
+}
+
+impl Listeners {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a listener.
+    pub fn register(&mut self, listener: Arc<dyn JobListener>) {
+        self.listeners.push(listener);
+    }
+
+    pub fn scheduled(&self, event: &JobEvent) {
+        self.listeners.iter().for_each(|l| l.on_scheduled(event));
+    }
+
+    pub fn start(&self, event: &JobEvent) {
+        self.listeners.iter().for_each(|l| l.on_start(event));
+    }
+
+    pub fn finish(&self, event: &JobEvent) {
+        self.listeners.iter().for_each(|l| l.on_finish(event));
+    }
+
+    pub fn skip(&self, event: &JobEvent, reason: SkipReason) {
+        self.listeners.iter().for_each(|l| l.on_skip(event, reason));
+    }
+
+    pub fn miss(&self, event: &JobEvent) {
+        self.listeners.iter().for_each(|l| l.on_miss(event));
+    }
+
+    pub fn error(&self, event: &JobEvent, error: &str) {
+        self.listeners.iter().for_each(|l| l.on_error(event, error));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<String>>);
+
+    impl JobListener for Recorder {
+        fn on_start(&self, event: &JobEvent) {
+            self.0.lock().unwrap().push(format!("start {}", event.job));
+        }
+
+        fn on_skip(&self, event: &JobEvent, reason: SkipReason) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("skip {} {:?}", event.job, reason));
+        }
+    }
+
+    #[test]
+    fn dispatch() {
+        let recorder = Arc::new(Recorder::default());
+        let mut listeners = Listeners::new();
+        listeners.register(recorder.clone());
+
+        let time = Utc.with_ymd_and_hms(2021, 2, 1, 0, 0, 0).unwrap();
+        let event = JobEvent {
+            job: "backup".to_string(),
+            scheduled_time: time,
+            actual_time: time,
+        };
+        listeners.scheduled(&event);
+        listeners.start(&event);
+        listeners.skip(&event, SkipReason::Overlap);
+
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            vec!["start backup", "skip backup Overlap"]
+        );
+    }
+}
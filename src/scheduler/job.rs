@@ -0,0 +1,376 @@
+use super::events::{JobEvent, Listeners, SkipReason};
+use super::leader::LeadershipProvider;
+use super::lock::DistributedLock;
+use super::store::JobStore;
+use crate::{Bounded, Excluding, Jittered, Offset, Schedule, ScheduleSet};
+use chrono::{DateTime, Duration, Utc};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::Notify;
+
+/// Computes the fire times of a job run by a scheduler.
+///
+/// Implemented for `Schedule` and its adaptors so any of them can drive a job.
+pub trait Trigger: Send + Sync {
+    /// Returns the next fire time strictly after the supplied datetime, or `None` when the job
+    /// won't fire again.
+    fn next_after(&self, dt: &DateTime<Utc>) -> Option<DateTime<Utc>>;
+
+    /// Returns the fire times strictly after `last_run` up to `now` inclusive, the occurrences
+    /// missed when a job overran or the process was suspended.
+    fn missed_between(&self, last_run: &DateTime<Utc>, now: &DateTime<Utc>) -> Vec<DateTime<Utc>> {
+        let mut missed = Vec::new();
+        let mut cursor = *last_run;
+        while let Some(next) = self.next_after(&cursor).filter(|next| next <= now) {
+            missed.push(next);
+            cursor = next;
+        }
+        missed
+    }
+}
+
+impl Trigger for Schedule {
+    fn next_after(&self, dt: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+        Schedule::next_after(self, dt)
+    }
+
+    fn missed_between(&self, last_run: &DateTime<Utc>, now: &DateTime<Utc>) -> Vec<DateTime<Utc>> {
+        self.iter_between_inclusive(last_run, now)
+            .filter(|fire_time| fire_time > last_run)
+            .collect()
+    }
+}
+
+macro_rules! impl_trigger {
+    ($($ty:ty),*) => {
+        $(
+            impl Trigger for $ty {
+                fn next_after(&self, dt: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+                    <$ty>::next_after(self, dt)
+                }
+            }
+        )*
+    };
+}
+
+impl_trigger!(ScheduleSet, Excluding, Bounded, Offset, Jittered);
+
+/// How long after its fire time an occurrence may be reached and still run on time, beyond which
+/// it is handled by the `MisfirePolicy`.
+pub(crate) const MISFIRE_THRESHOLD: Duration = Duration::seconds(1);
+
+/// Determines what happens to the occurrences of a job missed because the job overran past them
+/// or the process was suspended.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum MisfirePolicy {
+    /// Don't run the missed occurrences, reporting them as skipped for an overlap when the job
+    /// overran and as missed otherwise.
+    #[default]
+    Skip,
+    /// Run the job once right away for the last missed occurrence, reporting the others as
+    /// skipped or missed as `Skip` does.
+    FireOnceNow,
+    /// Run the job for every missed occurrence, back to back.
+    FireAllMissed,
+}
+
+impl MisfirePolicy {
+    /// Returns the missed fire times to run, reporting the others to the listeners as skipped
+    /// for an overlap when they passed before the last run finished at `finished`, and as missed
+    /// otherwise.
+    pub(crate) fn resolve(
+        self,
+        handle: &JobHandle,
+        mut missed: Vec<DateTime<Utc>>,
+        finished: Option<DateTime<Utc>>,
+        listeners: &Listeners,
+    ) -> Vec<DateTime<Utc>> {
+        let kept = match self {
+            MisfirePolicy::Skip => 0,
+            MisfirePolicy::FireOnceNow => missed.len().min(1),
+            MisfirePolicy::FireAllMissed => missed.len(),
+        };
+        let runs = missed.split_off(missed.len() - kept);
+        for fire_time in missed {
+            if finished.is_some_and(|finished| fire_time < finished) {
+                listeners.skip(&event(handle, fire_time), SkipReason::Overlap);
+            } else {
+                listeners.miss(&event(handle, fire_time));
+            }
+        }
+        runs
+    }
+}
+
+pub(crate) fn event(handle: &JobHandle, scheduled_time: DateTime<Utc>) -> JobEvent {
+    JobEvent {
+        job: handle.name().to_string(),
+        scheduled_time,
+        actual_time: handle.now(),
+    }
+}
+
+/// Tells the time to a scheduler, the system time except in tests.
+pub(crate) trait Clock: fmt::Debug + Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// The `Clock` of the system.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// The pluggable building blocks a scheduler consults around each run of a job.
+#[derive(Clone, Default)]
+pub(crate) struct Backends {
+    pub(crate) lock: Option<Arc<dyn DistributedLock>>,
+    pub(crate) leadership: Option<Arc<dyn LeadershipProvider>>,
+    pub(crate) store: Option<Arc<dyn JobStore>>,
+}
+
+impl fmt::Debug for Backends {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Backends")
+            .field("lock", &self.lock.is_some())
+            .field("leadership", &self.leadership.is_some())
+            .field("store", &self.store.is_some())
+            .finish()
+    }
+}
+
+impl Backends {
+    /// Returns whether this replica runs the fire time, reporting to the listeners why not.
+    ///
+    /// A backend failing is reported as an error of the job and the fire time isn't run, as
+    /// another replica may run it.
+    pub(crate) fn admit(
+        &self,
+        handle: &JobHandle,
+        scheduled: DateTime<Utc>,
+        listeners: &Listeners,
+    ) -> bool {
+        if let Some(leadership) = &self.leadership {
+            match leadership.is_leader() {
+                Ok(true) => {}
+                Ok(false) => {
+                    listeners.skip(&event(handle, scheduled), SkipReason::NotLeader);
+                    return false;
+                }
+                Err(error) => {
+                    listeners.error(&event(handle, scheduled), &error.to_string());
+                    return false;
+                }
+            }
+        }
+        if let Some(lock) = &self.lock {
+            match lock.try_acquire(handle.name(), &scheduled) {
+                Ok(true) => {}
+                Ok(false) => {
+                    listeners.skip(&event(handle, scheduled), SkipReason::AlreadyClaimed);
+                    return false;
+                }
+                Err(error) => {
+                    listeners.error(&event(handle, scheduled), &error.to_string());
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Records the fire time as the last run of the job in the store, reporting a failure as
+    /// an error of the job.
+    pub(crate) fn record(
+        &self,
+        handle: &JobHandle,
+        scheduled: DateTime<Utc>,
+        listeners: &Listeners,
+    ) {
+        if let Some(store) = &self.store {
+            if let Err(error) = store.set_last_run(handle.name(), &scheduled) {
+                listeners.error(&event(handle, scheduled), &error.to_string());
+            }
+        }
+    }
+}
+
+/// The value returned by a job, reported to the `JobListener`s as a finish or an error.
+pub trait JobOutput {
+    /// Returns the message of the error the job failed with, if any.
+    fn into_error(self) -> Option<String>;
+}
+
+impl JobOutput for () {
+    fn into_error(self) -> Option<String> {
+        None
+    }
+}
+
+impl<E: fmt::Display> JobOutput for Result<(), E> {
+    fn into_error(self) -> Option<String> {
+        self.err().map(|error| error.to_string())
+    }
+}
+
+/// Determines what happens to the fire times of a paused job.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum PausePolicy {
+    /// Don't run them, reporting them to the listeners as skipped with `SkipReason::Paused`.
+    #[default]
+    Skip,
+    /// Run the job for each of them, back to back, once the job is resumed.
+    Accumulate,
+}
+
+/// Wakes the scheduler waiting for the next fire time of a job.
+pub(crate) type Wake = Box<dyn Fn() + Send + Sync>;
+
+/// The state of a job shared between its handles and the scheduler running it.
+pub(crate) struct Control {
+    cancelled: AtomicBool,
+    paused: AtomicBool,
+    /// Wakes the tokio task of the job.
+    pub(crate) notify: Notify,
+    /// Wakes the thread dispatching the job, if any.
+    wake: Option<Wake>,
+    /// The clock of the scheduler running the job.
+    clock: Arc<dyn Clock>,
+}
+
+impl fmt::Debug for Control {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Control")
+            .field("cancelled", &self.cancelled)
+            .field("paused", &self.paused)
+            .finish()
+    }
+}
+
+impl Control {
+    fn wake(&self) {
+        // the permit is kept when the job isn't waiting yet
+        self.notify.notify_one();
+        if let Some(wake) = &self.wake {
+            wake();
+        }
+    }
+}
+
+/// A handle to a job added to a scheduler, used to cancel, pause or resume it.
+///
+/// Cancelling or pausing leaves a run in progress to finish.
+#[derive(Debug, Clone)]
+pub struct JobHandle {
+    name: Arc<str>,
+    pub(crate) control: Arc<Control>,
+}
+
+impl JobHandle {
+    pub(crate) fn new(name: &str, clock: Arc<dyn Clock>) -> Self {
+        JobHandle::with_control(name, clock, None)
+    }
+
+    /// Creates a handle calling `wake` whenever the job is cancelled or resumed.
+    pub(crate) fn with_wake(name: &str, clock: Arc<dyn Clock>, wake: Wake) -> Self {
+        JobHandle::with_control(name, clock, Some(wake))
+    }
+
+    fn with_control(name: &str, clock: Arc<dyn Clock>, wake: Option<Wake>) -> Self {
+        JobHandle {
+            name: name.into(),
+            control: Arc::new(Control {
+                cancelled: AtomicBool::default(),
+                paused: AtomicBool::default(),
+                notify: Notify::new(),
+                wake,
+                clock,
+            }),
+        }
+    }
+
+    /// Returns the name the job was added with.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Stops the job from firing again.
+    pub fn cancel(&self) {
+        self.control.cancelled.store(true, Ordering::SeqCst);
+        self.control.wake();
+    }
+
+    /// Returns whether the job was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.control.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Stops the job from running at its fire times until resumed, which are skipped or
+    /// accumulated according to the `PausePolicy` of the scheduler.
+    pub fn pause(&self) {
+        self.control.paused.store(true, Ordering::SeqCst);
+    }
+
+    /// Resumes a paused job, running the accumulated fire times right away and computing the
+    /// next fire time from now.
+    pub fn resume(&self) {
+        if self.control.paused.swap(false, Ordering::SeqCst) {
+            self.control.wake();
+        }
+    }
+
+    /// Returns whether the job is paused.
+    pub fn is_paused(&self) -> bool {
+        self.control.paused.load(Ordering::SeqCst)
+    }
+
+    /// Returns the time of the clock of the scheduler running the job.
+    pub(crate) fn now(&self) -> DateTime<Utc> {
+        self.control.clock.now()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::testing::recorded;
+    use chrono::TimeZone;
+    use std::str::FromStr;
+
+    #[test]
+    fn missed_between() {
+        let schedule = Schedule::from_str("0 */15 * * * *").unwrap();
+        let set = ScheduleSet::from(vec![schedule.clone()]);
+        let last_run = Utc.with_ymd_and_hms(2022, 6, 1, 9, 0, 0).unwrap();
+        let now = Utc.with_ymd_and_hms(2022, 6, 1, 10, 0, 0).unwrap();
+        let missed = Trigger::missed_between(&schedule, &last_run, &now);
+        assert_eq!(4, missed.len());
+        assert_eq!(Some(&now), missed.last());
+        assert_eq!(missed, Trigger::missed_between(&set, &last_run, &now));
+    }
+
+    #[test]
+    fn resolve_missed() {
+        let (listeners, recorder) = recorded();
+        let handle = JobHandle::new("job", Arc::new(SystemClock));
+        let at = |m| Utc.with_ymd_and_hms(2022, 6, 1, 9, m, 0).unwrap();
+        let missed = vec![at(0), at(1), at(2)];
+
+        let runs = MisfirePolicy::Skip.resolve(&handle, missed.clone(), Some(at(1)), &listeners);
+        assert!(runs.is_empty());
+        // only the fire times before the last run finished passed while it ran
+        assert_eq!(
+            vec!["skip job Overlap", "miss job", "miss job"],
+            recorder.events()
+        );
+        let runs = MisfirePolicy::FireOnceNow.resolve(&handle, missed.clone(), None, &listeners);
+        assert_eq!(vec![at(2)], runs);
+        let runs = MisfirePolicy::FireAllMissed.resolve(&handle, missed.clone(), None, &listeners);
+        assert_eq!(missed, runs);
+    }
+}
@@ -0,0 +1,61 @@
+use super::BoxError;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Decides whether this replica of a highly available scheduler may dispatch jobs.
+///
+/// The scheduler consults the provider before every dispatch. Standbys keep computing the next
+/// run of every job without firing it, so a replica taking over leadership resumes immediately.
+pub trait LeadershipProvider: Send + Sync {
+    /// Returns `true` when this replica currently holds leadership.
+    fn is_leader(&self) -> Result<bool, BoxError>;
+}
+
+/// A `LeadershipProvider` for schedulers running as a single replica, always the leader.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AlwaysLeader;
+
+impl LeadershipProvider for AlwaysLeader {
+    fn is_leader(&self) -> Result<bool, BoxError> {
+        Ok(true)
+    }
+}
+
+/// A `LeadershipProvider` whose leadership is toggled explicitly, e.g. by an external election
+/// callback or in tests.
+#[derive(Debug, Default)]
+pub struct ManualLeadership {
+    leader: AtomicBool,
+}
+
+impl ManualLeadership {
+    pub fn new(leader: bool) -> Self {
+        ManualLeadership {
+            leader: AtomicBool::new(leader),
+        }
+    }
+
+    /// Grants or revokes leadership.
+    pub fn set_leader(&self, leader: bool) {
+        self.leader.store(leader, Ordering::SeqCst);
+    }
+}
+
+impl LeadershipProvider for ManualLeadership {
+    fn is_leader(&self) -> Result<bool, BoxError> {
+        Ok(self.leader.load(Ordering::SeqCst))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manual_leadership() {
+        let provider = ManualLeadership::new(false);
+        assert!(!provider.is_leader().unwrap());
+        provider.set_leader(true);
+        assert!(provider.is_leader().unwrap());
+        assert!(AlwaysLeader.is_leader().unwrap());
+    }
+}
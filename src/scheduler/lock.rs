@@ -0,0 +1,63 @@
+use super::BoxError;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Coordinates replicas running the same set of jobs so each occurrence fires exactly once
+/// across the cluster.
+///
+/// The scheduler calls `try_acquire` right before firing a job and only runs it when the lock
+/// was acquired. Locks are never explicitly released, an occurrence is identified by the job and
+/// its scheduled fire time so it must stay claimed once fired.
+pub trait DistributedLock: Send + Sync {
+    /// Attempts to claim the occurrence of `job` scheduled at `fire_time` returning `true` when
+    /// this replica should fire it.
+    fn try_acquire(&self, job: &str, fire_time: &DateTime<Utc>) -> Result<bool, BoxError>;
+}
+
+/// A `DistributedLock` for a single process, useful for tests and as a default when running a
+/// single replica.
+#[derive(Debug, Default)]
+pub struct InMemoryLock {
+    last_acquired: Mutex<HashMap<String, DateTime<Utc>>>,
+}
+
+impl InMemoryLock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl DistributedLock for InMemoryLock {
+    fn try_acquire(&self, job: &str, fire_time: &DateTime<Utc>) -> Result<bool, BoxError> {
+        let mut last_acquired = self.last_acquired.lock().unwrap();
+        match last_acquired.get(job) {
+            // occurrences of a job are fired in chronological order so anything up to the last
+            // acquired fire time has already been claimed
+            Some(last) if last >= fire_time => Ok(false),
+            _ => {
+                last_acquired.insert(job.to_string(), *fire_time);
+                Ok(true)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn acquire_once() {
+        let lock = InMemoryLock::new();
+        let first = Utc.with_ymd_and_hms(2021, 2, 1, 1, 0, 0).unwrap();
+        let second = Utc.with_ymd_and_hms(2021, 2, 1, 2, 0, 0).unwrap();
+
+        assert!(lock.try_acquire("backup", &first).unwrap());
+        assert!(!lock.try_acquire("backup", &first).unwrap());
+        assert!(lock.try_acquire("report", &first).unwrap());
+        assert!(lock.try_acquire("backup", &second).unwrap());
+        assert!(!lock.try_acquire("backup", &first).unwrap());
+    }
+}
@@ -0,0 +1,29 @@
+//! Running jobs according to a `Schedule` on tokio with `Scheduler` or on threads with
+//! `blocking::Scheduler`, and pluggable building blocks for schedulers.
+pub mod blocking;
+mod events;
+mod job;
+mod leader;
+mod lock;
+#[cfg(feature = "redis")]
+mod redis_lock;
+#[cfg(feature = "sqlite")]
+mod sqlite_store;
+mod store;
+#[cfg(test)]
+pub(crate) mod testing;
+mod tokio_scheduler;
+
+pub use events::{JobEvent, JobListener, Listeners, SkipReason};
+pub use job::{JobHandle, JobOutput, MisfirePolicy, PausePolicy, Trigger};
+pub use leader::{AlwaysLeader, LeadershipProvider, ManualLeadership};
+pub use lock::{DistributedLock, InMemoryLock};
+#[cfg(feature = "redis")]
+pub use redis_lock::RedisLock;
+#[cfg(feature = "sqlite")]
+pub use sqlite_store::SqliteJobStore;
+pub use store::{InMemoryJobStore, JobDefinition, JobStore};
+pub use tokio_scheduler::Scheduler;
+
+/// Error returned by the pluggable scheduler backends.
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
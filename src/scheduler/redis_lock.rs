@@ -0,0 +1,71 @@
+use super::{BoxError, DistributedLock};
+use chrono::{DateTime, Duration, Utc};
+
+/// A `DistributedLock` backed by Redis.
+///
+/// Each occurrence is claimed with `SET key value NX PX ttl`, the key expiring after the TTL
+/// which must comfortably exceed the clock skew between replicas.
+#[derive(Debug, Clone)]
+pub struct RedisLock {
+    client: ::redis::Client,
+    prefix: String,
+    owner: String,
+    ttl: Duration,
+}
+
+impl RedisLock {
+    /// Creates a lock with keys of the form `cron-exp:lock:<job>:<fire time millis>` identifying
+    /// the replica holding it by `owner`.
+    pub fn new(client: ::redis::Client, owner: impl Into<String>) -> Self {
+        RedisLock {
+            client,
+            prefix: "cron-exp:lock:".to_string(),
+            owner: owner.into(),
+            ttl: Duration::hours(1),
+        }
+    }
+
+    /// Sets the prefix of all keys created by the lock.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Sets how long a claimed occurrence is remembered.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    fn key(&self, job: &str, fire_time: &DateTime<Utc>) -> String {
+        format!("{}{}:{}", self.prefix, job, fire_time.timestamp_millis())
+    }
+}
+
+impl DistributedLock for RedisLock {
+    fn try_acquire(&self, job: &str, fire_time: &DateTime<Utc>) -> Result<bool, BoxError> {
+        let mut conn = self.client.get_connection()?;
+        let result: Option<String> = ::redis::cmd("SET")
+            .arg(self.key(job, fire_time))
+            .arg(&self.owner)
+            .arg("NX")
+            .arg("PX")
+            .arg(self.ttl.num_milliseconds())
+            .query(&mut conn)?;
+        Ok(result.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn key() {
+        let lock = RedisLock::new(::redis::Client::open("redis://127.0.0.1/").unwrap(), "a")
+            .with_prefix("jobs:");
+        let fire_time = Utc.with_ymd_and_hms(2021, 2, 1, 1, 0, 0).unwrap();
+        assert_eq!(lock.key("backup", &fire_time), "jobs:backup:1612141200000");
+    }
+}
@@ -0,0 +1,171 @@
+use super::{BoxError, JobDefinition, JobStore};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+use std::sync::Mutex;
+
+const CREATE_TABLE: &str = "CREATE TABLE IF NOT EXISTS cron_exp_jobs (
+    id TEXT PRIMARY KEY NOT NULL,
+    expression TEXT NOT NULL,
+    last_run_ms INTEGER
+)";
+
+/// A `JobStore` persisting jobs in the `cron_exp_jobs` table of a SQLite database.
+#[derive(Debug)]
+pub struct SqliteJobStore {
+    conn: Mutex<Connection>,
+}
+
+impl SqliteJobStore {
+    /// Opens or creates the database at the supplied path.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, BoxError> {
+        Self::from_connection(Connection::open(path)?)
+    }
+
+    /// Uses an existing connection, creating the jobs table when missing.
+    pub fn from_connection(conn: Connection) -> Result<Self, BoxError> {
+        conn.execute(CREATE_TABLE, [])?;
+        Ok(SqliteJobStore {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Returns the stored job with the supplied id.
+    pub fn job(&self, id: &str) -> Result<Option<JobDefinition>, BoxError> {
+        let conn = self.conn.lock().unwrap();
+        let row = conn
+            .query_row(
+                "SELECT expression, last_run_ms FROM cron_exp_jobs WHERE id = ?1",
+                params![id],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<i64>>(1)?)),
+            )
+            .optional()?;
+        row.map(|(expression, last_run_ms)| {
+            Ok(JobDefinition {
+                id: id.to_string(),
+                expression,
+                last_run: last_run(id, last_run_ms)?,
+            })
+        })
+        .transpose()
+    }
+}
+
+/// Converts the stored last run of a job, failing on a timestamp out of range.
+fn last_run(id: &str, last_run_ms: Option<i64>) -> Result<Option<DateTime<Utc>>, BoxError> {
+    match last_run_ms {
+        Some(ms) => match DateTime::<Utc>::from_timestamp_millis(ms) {
+            Some(last_run) => Ok(Some(last_run)),
+            None => Err(format!("invalid last run of job {}: {}", id, ms).into()),
+        },
+        None => Ok(None),
+    }
+}
+
+impl JobStore for SqliteJobStore {
+    fn save_job(&self, job: &JobDefinition) -> Result<(), BoxError> {
+        self.conn.lock().unwrap().execute(
+            "INSERT INTO cron_exp_jobs (id, expression, last_run_ms) VALUES (?1, ?2, ?3)
+             ON CONFLICT(id) DO UPDATE SET expression = excluded.expression, last_run_ms = excluded.last_run_ms",
+            params![
+                job.id,
+                job.expression,
+                job.last_run.map(|dt| dt.timestamp_millis())
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn remove_job(&self, id: &str) -> Result<(), BoxError> {
+        self.conn
+            .lock()
+            .unwrap()
+            .execute("DELETE FROM cron_exp_jobs WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    fn load_jobs(&self) -> Result<Vec<JobDefinition>, BoxError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt =
+            conn.prepare("SELECT id, expression, last_run_ms FROM cron_exp_jobs ORDER BY id")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<i64>>(2)?,
+            ))
+        })?;
+
+        let mut jobs = Vec::new();
+        for row in rows {
+            let (id, expression, last_run_ms) = row?;
+            let last_run = last_run(&id, last_run_ms)?;
+            jobs.push(JobDefinition {
+                id,
+                expression,
+                last_run,
+            });
+        }
+        Ok(jobs)
+    }
+
+    fn set_last_run(&self, id: &str, fire_time: &DateTime<Utc>) -> Result<(), BoxError> {
+        self.conn.lock().unwrap().execute(
+            "UPDATE cron_exp_jobs SET last_run_ms = ?2 WHERE id = ?1",
+            params![id, fire_time.timestamp_millis()],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn round_trip() {
+        let store = SqliteJobStore::from_connection(Connection::open_in_memory().unwrap()).unwrap();
+        store
+            .save_job(&JobDefinition::new("report", "0 0 9 * * Mon"))
+            .unwrap();
+        store
+            .save_job(&JobDefinition::new("backup", "0 0 * * *"))
+            .unwrap();
+
+        let fire_time = Utc.with_ymd_and_hms(2021, 2, 1, 0, 0, 0).unwrap();
+        store.set_last_run("backup", &fire_time).unwrap();
+
+        let jobs = store.load_jobs().unwrap();
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0].id, "backup");
+        assert_eq!(jobs[0].last_run, Some(fire_time));
+        assert_eq!(jobs[1].last_run, None);
+        assert!(jobs[1].schedule().is_ok());
+
+        store.remove_job("backup").unwrap();
+        assert_eq!(store.job("backup").unwrap(), None);
+        assert_eq!(store.load_jobs().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn invalid_last_run() {
+        let store = SqliteJobStore::from_connection(Connection::open_in_memory().unwrap()).unwrap();
+        store
+            .save_job(&JobDefinition::new("backup", "0 0 * * *"))
+            .unwrap();
+        store
+            .conn
+            .lock()
+            .unwrap()
+            .execute(
+                "UPDATE cron_exp_jobs SET last_run_ms = ?1",
+                params![i64::MAX],
+            )
+            .unwrap();
+
+        let error = "invalid last run of job backup: 9223372036854775807";
+        assert_eq!(store.job("backup").unwrap_err().to_string(), error);
+        assert_eq!(store.load_jobs().unwrap_err().to_string(), error);
+    }
+}
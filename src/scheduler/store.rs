@@ -0,0 +1,83 @@
+use super::BoxError;
+use crate::{ParseScheduleError, Schedule};
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+/// A persisted job definition along with the watermark of its last run.
+#[derive(Debug, PartialEq, Clone)]
+pub struct JobDefinition {
+    pub id: String,
+    pub expression: String,
+    pub last_run: Option<DateTime<Utc>>,
+}
+
+impl JobDefinition {
+    pub fn new(id: impl Into<String>, expression: impl Into<String>) -> Self {
+        JobDefinition {
+            id: id.into(),
+            expression: expression.into(),
+            last_run: None,
+        }
+    }
+
+    /// Parses the expression of the job.
+    pub fn schedule(&self) -> Result<Schedule, ParseScheduleError> {
+        Schedule::from_str(&self.expression)
+    }
+}
+
+/// Persists job definitions and last-run watermarks so a scheduler can resume after a restart
+/// without losing or repeating runs.
+pub trait JobStore: Send + Sync {
+    /// Inserts the job or replaces the existing job with the same id.
+    fn save_job(&self, job: &JobDefinition) -> Result<(), BoxError>;
+
+    /// Removes the job with the supplied id, if any.
+    fn remove_job(&self, id: &str) -> Result<(), BoxError>;
+
+    /// Returns all stored jobs ordered by id.
+    fn load_jobs(&self) -> Result<Vec<JobDefinition>, BoxError>;
+
+    /// Records the fire time of the last run of the job.
+    fn set_last_run(&self, id: &str, fire_time: &DateTime<Utc>) -> Result<(), BoxError>;
+}
+
+/// A non persistent `JobStore`, useful for tests.
+#[derive(Debug, Default)]
+pub struct InMemoryJobStore {
+    jobs: Mutex<BTreeMap<String, JobDefinition>>,
+}
+
+impl InMemoryJobStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl JobStore for InMemoryJobStore {
+    fn save_job(&self, job: &JobDefinition) -> Result<(), BoxError> {
+        self.jobs
+            .lock()
+            .unwrap()
+            .insert(job.id.clone(), job.clone());
+        Ok(())
+    }
+
+    fn remove_job(&self, id: &str) -> Result<(), BoxError> {
+        self.jobs.lock().unwrap().remove(id);
+        Ok(())
+    }
+
+    fn load_jobs(&self) -> Result<Vec<JobDefinition>, BoxError> {
+        Ok(self.jobs.lock().unwrap().values().cloned().collect())
+    }
+
+    fn set_last_run(&self, id: &str, fire_time: &DateTime<Utc>) -> Result<(), BoxError> {
+        if let Some(job) = self.jobs.lock().unwrap().get_mut(id) {
+            job.last_run = Some(*fire_time);
+        }
+        Ok(())
+    }
+}
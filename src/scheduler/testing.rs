@@ -0,0 +1,396 @@
+//! Clocks, triggers and listeners shared by the tests of the schedulers, and the scenarios both
+//! schedulers are tested with.
+use super::events::{JobEvent, JobListener, Listeners, SkipReason};
+use super::job::{Backends, Clock, JobHandle, MisfirePolicy, PausePolicy, Trigger};
+use super::leader::ManualLeadership;
+use super::lock::InMemoryLock;
+use super::store::{InMemoryJobStore, JobDefinition, JobStore};
+use super::BoxError;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use std::future::Future;
+use std::pin::pin;
+use std::sync::{Arc, Condvar, Mutex};
+use std::task::{Context, Poll, Waker};
+
+/// The time the test clocks start at.
+pub(crate) fn start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2022, 6, 1, 9, 0, 0).unwrap()
+}
+
+/// A clock starting at `start()` and only moving when advanced, on which jobs can sleep.
+#[derive(Debug)]
+pub(crate) struct ManualClock {
+    time: Mutex<Time>,
+    moved: Condvar,
+}
+
+#[derive(Debug)]
+struct Time {
+    now: DateTime<Utc>,
+    /// When the sleeping jobs wake up.
+    deadlines: Vec<DateTime<Utc>>,
+}
+
+impl ManualClock {
+    pub(crate) fn new() -> Self {
+        ManualClock {
+            time: Mutex::new(Time {
+                now: start(),
+                deadlines: Vec::new(),
+            }),
+            moved: Condvar::new(),
+        }
+    }
+
+    /// Moves the time forward, waking the jobs whose sleep is over.
+    pub(crate) fn advance(&self, millis: i64) {
+        self.time.lock().unwrap().now += Duration::milliseconds(millis);
+        self.moved.notify_all();
+    }
+
+    /// Blocks until the time moved `millis` forward.
+    pub(crate) fn sleep(&self, millis: i64) {
+        let mut time = self.time.lock().unwrap();
+        let deadline = time.now + Duration::milliseconds(millis);
+        time.deadlines.push(deadline);
+        let mut time = self
+            .moved
+            .wait_while(time, |time| time.now < deadline)
+            .unwrap();
+        let index = time.deadlines.iter().position(|d| *d == deadline).unwrap();
+        time.deadlines.swap_remove(index);
+    }
+
+    /// Returns how many jobs are sleeping until a later time.
+    pub(crate) fn sleeping(&self) -> usize {
+        let time = self.time.lock().unwrap();
+        time.deadlines.iter().filter(|d| **d > time.now).count()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.time.lock().unwrap().now
+    }
+}
+
+/// A clock starting at `start()` and following the time of the tokio runtime, which the tests
+/// pause so it only moves once every task waits.
+#[derive(Debug)]
+pub(crate) struct TokioClock {
+    started: tokio::time::Instant,
+}
+
+impl TokioClock {
+    pub(crate) fn new() -> Self {
+        TokioClock {
+            started: tokio::time::Instant::now(),
+        }
+    }
+}
+
+impl Clock for TokioClock {
+    fn now(&self) -> DateTime<Utc> {
+        start() + Duration::from_std(self.started.elapsed()).unwrap()
+    }
+}
+
+/// Fires every `interval` milliseconds from `start()`, `count` times.
+#[derive(Clone)]
+pub(crate) struct Every {
+    start: DateTime<Utc>,
+    interval: i64,
+    count: i32,
+}
+
+impl Trigger for Every {
+    fn next_after(&self, dt: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+        (1..=self.count)
+            .map(|i| self.start + Duration::milliseconds(self.interval * i as i64))
+            .find(|fire| fire > dt)
+    }
+}
+
+pub(crate) fn every(interval: i64, count: i32) -> Every {
+    Every {
+        start: start(),
+        interval,
+        count,
+    }
+}
+
+/// Records the finish, skip, miss and error events.
+#[derive(Default)]
+pub(crate) struct Recorder(Mutex<Vec<String>>);
+
+impl Recorder {
+    pub(crate) fn events(&self) -> Vec<String> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+impl JobListener for Recorder {
+    fn on_finish(&self, event: &JobEvent) {
+        self.0.lock().unwrap().push(format!("finish {}", event.job));
+    }
+
+    fn on_skip(&self, event: &JobEvent, reason: SkipReason) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("skip {} {:?}", event.job, reason));
+    }
+
+    fn on_miss(&self, event: &JobEvent) {
+        self.0.lock().unwrap().push(format!("miss {}", event.job));
+    }
+
+    fn on_error(&self, event: &JobEvent, error: &str) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("error {} {}", event.job, error));
+    }
+}
+
+/// Returns listeners notifying a new recorder.
+pub(crate) fn recorded() -> (Listeners, Arc<Recorder>) {
+    let recorder = Arc::new(Recorder::default());
+    let mut listeners = Listeners::new();
+    listeners.register(recorder.clone());
+    (listeners, recorder)
+}
+
+/// What a job of a scenario does when run.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Work {
+    /// Returns right away.
+    Nothing,
+    /// Sleeps for the milliseconds.
+    Sleep(i64),
+    /// Fails with the error.
+    Fail(&'static str),
+}
+
+/// The policies and backends of a scheduler started by a scenario.
+#[derive(Default)]
+pub(crate) struct Setup {
+    pub(crate) misfire_policy: MisfirePolicy,
+    pub(crate) pause_policy: PausePolicy,
+    pub(crate) backends: Backends,
+}
+
+/// Starts schedulers of one kind on a clock only moving when a scenario advances it.
+pub(crate) trait Harness {
+    type Scheduler;
+
+    fn scheduler(&self, listeners: Listeners, setup: Setup) -> Self::Scheduler;
+
+    fn add<T>(&self, scheduler: &Self::Scheduler, name: &str, trigger: T, work: Work) -> JobHandle
+    where
+        T: Trigger + 'static;
+
+    /// Restores the jobs of the store of the scheduler, all doing the same work.
+    fn restore(&self, scheduler: &Self::Scheduler, work: Work) -> Result<Vec<JobHandle>, BoxError>;
+
+    fn jobs(&self, scheduler: &Self::Scheduler) -> Vec<JobHandle>;
+
+    /// Lets the milliseconds pass, the schedulers running the fire times reached.
+    async fn advance(&self, millis: i64);
+}
+
+/// Plays a scenario whose futures never wait, as those of the blocking scheduler.
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => unreachable!("the blocking harness never waits"),
+    }
+}
+
+pub(crate) async fn runs_at_fire_times<H: Harness>(harness: &H) {
+    let (listeners, recorder) = recorded();
+    let scheduler = harness.scheduler(listeners, Setup::default());
+    harness.add(&scheduler, "ok", every(40, 3), Work::Nothing);
+    harness.add(&scheduler, "failing", every(60, 1), Work::Fail("boom"));
+    harness.advance(200).await;
+    assert_eq!(
+        vec!["finish ok", "error failing boom", "finish ok", "finish ok"],
+        recorder.events()
+    );
+}
+
+pub(crate) async fn replicas_sharing_a_lock_run_each_fire_time_once<H: Harness>(harness: &H) {
+    let (listeners, recorder) = recorded();
+    let lock = Arc::new(InMemoryLock::new());
+    let replica = || Setup {
+        backends: Backends {
+            lock: Some(lock.clone()),
+            ..Backends::default()
+        },
+        ..Setup::default()
+    };
+    let first = harness.scheduler(listeners.clone(), replica());
+    let second = harness.scheduler(listeners, replica());
+    harness.add(&first, "job", every(40, 3), Work::Nothing);
+    harness.add(&second, "job", every(40, 3), Work::Nothing);
+    harness.advance(200).await;
+    let mut events = recorder.events();
+    events.sort();
+    assert_eq!(
+        vec![
+            "finish job",
+            "finish job",
+            "finish job",
+            "skip job AlreadyClaimed",
+            "skip job AlreadyClaimed",
+            "skip job AlreadyClaimed"
+        ],
+        events
+    );
+}
+
+pub(crate) async fn followers_never_run_jobs<H: Harness>(harness: &H) {
+    let (listeners, recorder) = recorded();
+    let leadership = Arc::new(ManualLeadership::new(false));
+    let setup = Setup {
+        backends: Backends {
+            leadership: Some(leadership.clone()),
+            ..Backends::default()
+        },
+        ..Setup::default()
+    };
+    let follower = harness.scheduler(listeners, setup);
+    harness.add(&follower, "job", every(40, 3), Work::Nothing);
+    harness.advance(60).await;
+    assert_eq!(vec!["skip job NotLeader"], recorder.events());
+    leadership.set_leader(true);
+    harness.advance(100).await;
+    assert_eq!(
+        vec!["skip job NotLeader", "finish job", "finish job"],
+        recorder.events()
+    );
+}
+
+pub(crate) async fn restored_jobs_resume_from_their_last_run<H: Harness>(harness: &H) {
+    // stored by a previous process, stopped 5 seconds ago
+    let store = Arc::new(InMemoryJobStore::new());
+    let mut definition = JobDefinition::new("job", "* * * * * *");
+    definition.last_run = Some(start() - Duration::seconds(5));
+    store.save_job(&definition).unwrap();
+
+    let (listeners, recorder) = recorded();
+    let setup = Setup {
+        misfire_policy: MisfirePolicy::FireAllMissed,
+        backends: Backends {
+            store: Some(store.clone()),
+            ..Backends::default()
+        },
+        ..Setup::default()
+    };
+    let scheduler = harness.scheduler(listeners, setup);
+    let jobs = harness.restore(&scheduler, Work::Nothing).unwrap();
+    assert_eq!("job", jobs[0].name());
+    harness.advance(100).await;
+    assert_eq!(vec!["finish job"; 5], recorder.events());
+    assert_eq!(Some(start()), store.load_jobs().unwrap()[0].last_run);
+}
+
+pub(crate) async fn invalid_stored_jobs_are_not_restored<H: Harness>(harness: &H) {
+    let store = Arc::new(InMemoryJobStore::new());
+    store
+        .save_job(&JobDefinition::new("valid", "* * * * * *"))
+        .unwrap();
+    store
+        .save_job(&JobDefinition::new("wrong", "* * * * * * * *"))
+        .unwrap();
+
+    let (listeners, recorder) = recorded();
+    let setup = Setup {
+        backends: Backends {
+            store: Some(store),
+            ..Backends::default()
+        },
+        ..Setup::default()
+    };
+    let scheduler = harness.scheduler(listeners, setup);
+    assert!(harness.restore(&scheduler, Work::Nothing).is_err());
+    assert!(harness.jobs(&scheduler).is_empty());
+    harness.advance(1000).await;
+    assert!(recorder.events().is_empty());
+}
+
+pub(crate) async fn overlapping_occurrences_are_skipped<H: Harness>(harness: &H) {
+    let (listeners, recorder) = recorded();
+    let scheduler = harness.scheduler(listeners, Setup::default());
+    harness.add(&scheduler, "slow", every(40, 3), Work::Sleep(130));
+    harness.advance(250).await;
+    assert_eq!(
+        vec!["finish slow", "skip slow Overlap", "skip slow Overlap"],
+        recorder.events()
+    );
+}
+
+pub(crate) async fn missed_occurrences_follow_the_policy<H: Harness>(harness: &H) {
+    let policy = |misfire_policy| Setup {
+        misfire_policy,
+        ..Setup::default()
+    };
+    let (listeners, once_recorder) = recorded();
+    let once = harness.scheduler(listeners, policy(MisfirePolicy::FireOnceNow));
+    harness.add(&once, "once", every(20, 3), Work::Sleep(50));
+    let (listeners, all_recorder) = recorded();
+    let all = harness.scheduler(listeners, policy(MisfirePolicy::FireAllMissed));
+    harness.add(&all, "all", every(20, 3), Work::Sleep(50));
+    harness.advance(250).await;
+    // the first runs finish at 70ms, after the fire times at 40ms and 60ms
+    assert_eq!(
+        vec!["finish once", "skip once Overlap", "finish once"],
+        once_recorder.events()
+    );
+    assert_eq!(vec!["finish all"; 3], all_recorder.events());
+}
+
+pub(crate) async fn paused_fire_times_follow_the_policy<H: Harness>(harness: &H) {
+    let (listeners, skip_recorder) = recorded();
+    let skip = harness.scheduler(listeners, Setup::default());
+    let skipped = harness.add(&skip, "skip", every(30, 4), Work::Nothing);
+    let (listeners, accumulate_recorder) = recorded();
+    let setup = Setup {
+        pause_policy: PausePolicy::Accumulate,
+        ..Setup::default()
+    };
+    let accumulate = harness.scheduler(listeners, setup);
+    let accumulated = harness.add(&accumulate, "accumulate", every(30, 4), Work::Nothing);
+    skipped.pause();
+    accumulated.pause();
+    harness.advance(75).await;
+    assert!(skipped.is_paused());
+    assert_eq!(vec!["skip skip Paused"; 2], skip_recorder.events());
+    assert!(accumulate_recorder.events().is_empty());
+    // the next fire times are computed from 75ms
+    skipped.resume();
+    accumulated.resume();
+    harness.advance(100).await;
+    assert_eq!(
+        vec![
+            "skip skip Paused",
+            "skip skip Paused",
+            "finish skip",
+            "finish skip"
+        ],
+        skip_recorder.events()
+    );
+    assert_eq!(vec!["finish accumulate"; 4], accumulate_recorder.events());
+}
+
+pub(crate) async fn cancelled_jobs_stop_firing<H: Harness>(harness: &H) {
+    let (listeners, recorder) = recorded();
+    let scheduler = harness.scheduler(listeners, Setup::default());
+    let handle = harness.add(&scheduler, "cancelled", every(20, 3), Work::Nothing);
+    harness.add(&scheduler, "kept", every(20, 1), Work::Nothing);
+    assert_eq!(2, harness.jobs(&scheduler).len());
+    handle.cancel();
+    assert_eq!(1, harness.jobs(&scheduler).len());
+    harness.advance(100).await;
+    assert_eq!(vec!["finish kept"], recorder.events());
+}
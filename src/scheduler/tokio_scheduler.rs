@@ -0,0 +1,524 @@
+use super::events::{Listeners, SkipReason};
+use super::job::{
+    event, Backends, Clock, JobHandle, JobOutput, MisfirePolicy, PausePolicy, SystemClock, Trigger,
+    MISFIRE_THRESHOLD,
+};
+use super::leader::LeadershipProvider;
+use super::lock::DistributedLock;
+use super::store::{JobDefinition, JobStore};
+use super::BoxError;
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+/// Runs jobs at the fire times of their triggers on the tokio runtime, one task per job.
+///
+/// Each job runs at most once at a time. Occurrences passing while it runs, or reached more than
+/// a second late such as after the process was suspended, are handled by the `MisfirePolicy`,
+/// skipping them by default. Fire times reached while a job is paused with `JobHandle::pause`
+/// are handled by the `PausePolicy`. Replicas sharing a `DistributedLock` run each fire time
+/// once between them, and a `LeadershipProvider` keeps standby replicas from running jobs. These
+/// and the `JobStore` are called on the blocking thread pool of tokio, as they may wait on the
+/// network or the disk.
+/// ```rust
+/// use cron_exp::scheduler::Scheduler;
+/// use cron_exp::Schedule;
+/// use std::str::FromStr;
+///
+/// let runtime = tokio::runtime::Builder::new_current_thread()
+///     .enable_all()
+///     .build()
+///     .unwrap();
+/// runtime.block_on(async {
+///     let scheduler = Scheduler::new();
+///     let schedule = Schedule::from_str("0 3 * * *").unwrap();
+///     let backup = scheduler.add("backup", schedule, || async {
+///         println!("backing up");
+///     });
+///     assert_eq!("backup", backup.name());
+///     scheduler.shutdown();
+///     assert!(backup.is_cancelled());
+/// });
+/// ```
+#[derive(Debug)]
+pub struct Scheduler {
+    listeners: Listeners,
+    misfire_policy: MisfirePolicy,
+    pause_policy: PausePolicy,
+    backends: Backends,
+    clock: Arc<dyn Clock>,
+    jobs: Mutex<Vec<JobHandle>>,
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Scheduler {
+            listeners: Listeners::default(),
+            misfire_policy: MisfirePolicy::default(),
+            pause_policy: PausePolicy::default(),
+            backends: Backends::default(),
+            clock: Arc::new(SystemClock),
+            jobs: Mutex::default(),
+        }
+    }
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a scheduler notifying the supplied listeners about every job.
+    pub fn with_listeners(listeners: Listeners) -> Self {
+        Scheduler {
+            listeners,
+            ..Scheduler::default()
+        }
+    }
+
+    /// Sets the policy for the occurrences missed by the jobs added afterwards.
+    pub fn with_misfire_policy(mut self, policy: MisfirePolicy) -> Self {
+        self.misfire_policy = policy;
+        self
+    }
+
+    /// Sets the policy for the fire times reached while the jobs added afterwards are paused.
+    pub fn with_pause_policy(mut self, policy: PausePolicy) -> Self {
+        self.pause_policy = policy;
+        self
+    }
+
+    /// Claims every fire time of the jobs added afterwards from the lock before running it,
+    /// skipping it with `SkipReason::AlreadyClaimed` when another replica claimed it.
+    pub fn with_lock(mut self, lock: Arc<dyn DistributedLock>) -> Self {
+        self.backends.lock = Some(lock);
+        self
+    }
+
+    /// Only runs the fire times of the jobs added afterwards while the provider reports this
+    /// replica as the leader, skipping them with `SkipReason::NotLeader` otherwise.
+    pub fn with_leadership(mut self, leadership: Arc<dyn LeadershipProvider>) -> Self {
+        self.backends.leadership = Some(leadership);
+        self
+    }
+
+    /// Records the fire time of every run in the store, which `add_stored` saves jobs to and
+    /// `restore` resumes them from after a restart.
+    pub fn with_store(mut self, store: Arc<dyn JobStore>) -> Self {
+        self.backends.store = Some(store);
+        self
+    }
+
+    /// Tells the time of the jobs added afterwards with the supplied clock.
+    #[cfg(test)]
+    pub(crate) fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Runs the future returned by `job` at every fire time of the trigger until the trigger is
+    /// exhausted or the job is cancelled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a tokio runtime.
+    pub fn add<T, F, Fut>(&self, name: &str, trigger: T, job: F) -> JobHandle
+    where
+        T: Trigger + 'static,
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future + Send + 'static,
+        Fut::Output: JobOutput,
+    {
+        self.add_from(name, trigger, job, self.clock.now())
+    }
+
+    /// Saves the definition to the store and runs `job` at the occurrences of its expression
+    /// like `add`. A definition with a last run resumes from it, the occurrences passed since
+    /// are handled by the `MisfirePolicy`.
+    pub fn add_stored<F, Fut>(
+        &self,
+        definition: &JobDefinition,
+        job: F,
+    ) -> Result<JobHandle, BoxError>
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future + Send + 'static,
+        Fut::Output: JobOutput,
+    {
+        let schedule = definition.schedule()?;
+        if let Some(store) = &self.backends.store {
+            store.save_job(definition)?;
+        }
+        let last = definition.last_run.unwrap_or_else(|| self.clock.now());
+        Ok(self.add_from(&definition.id, schedule, job, last))
+    }
+
+    /// Runs every job of the store from its last run, with the job `jobs` returns for its
+    /// definition, and returns their handles ordered by id. Fails without running any job when
+    /// the expression of one of them is invalid.
+    /// ```rust
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), cron_exp::scheduler::BoxError> {
+    /// use cron_exp::scheduler::{InMemoryJobStore, JobDefinition, JobStore, Scheduler};
+    /// use std::sync::Arc;
+    ///
+    /// let store = Arc::new(InMemoryJobStore::new());
+    /// store.save_job(&JobDefinition::new("backup", "0 3 * * *"))?;
+    ///
+    /// let scheduler = Scheduler::new().with_store(store);
+    /// let jobs = scheduler.restore(|definition| {
+    ///     let id = definition.id.clone();
+    ///     move || {
+    ///         let id = id.clone();
+    ///         async move { println!("running {}", id) }
+    ///     }
+    /// })?;
+    /// assert_eq!("backup", jobs[0].name());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn restore<J, F, Fut>(&self, mut jobs: J) -> Result<Vec<JobHandle>, BoxError>
+    where
+        J: FnMut(&JobDefinition) -> F,
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future + Send + 'static,
+        Fut::Output: JobOutput,
+    {
+        let definitions = match &self.backends.store {
+            Some(store) => store.load_jobs()?,
+            None => Vec::new(),
+        };
+        let schedules = definitions
+            .iter()
+            .map(JobDefinition::schedule)
+            .collect::<Result<Vec<_>, _>>()?;
+        let handles = definitions
+            .iter()
+            .zip(schedules)
+            .map(|(definition, schedule)| {
+                let last = definition.last_run.unwrap_or_else(|| self.clock.now());
+                self.add_from(&definition.id, schedule, jobs(definition), last)
+            })
+            .collect();
+        Ok(handles)
+    }
+
+    fn add_from<T, F, Fut>(&self, name: &str, trigger: T, job: F, last: DateTime<Utc>) -> JobHandle
+    where
+        T: Trigger + 'static,
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future + Send + 'static,
+        Fut::Output: JobOutput,
+    {
+        let handle = JobHandle::new(name, self.clock.clone());
+        tokio::spawn(run(
+            handle.clone(),
+            (trigger, last),
+            job,
+            (self.misfire_policy, self.pause_policy),
+            self.backends.clone(),
+            self.listeners.clone(),
+        ));
+        let mut jobs = self.jobs.lock().unwrap();
+        jobs.retain(|job| !job.is_cancelled());
+        jobs.push(handle.clone());
+        handle
+    }
+
+    /// Returns the handles of the jobs that weren't cancelled.
+    pub fn jobs(&self) -> Vec<JobHandle> {
+        let jobs = self.jobs.lock().unwrap();
+        jobs.iter()
+            .filter(|job| !job.is_cancelled())
+            .cloned()
+            .collect()
+    }
+
+    /// Cancels every job, runs in progress are left to finish.
+    pub fn shutdown(&self) {
+        for job in self.jobs.lock().unwrap().drain(..) {
+            job.cancel();
+        }
+    }
+}
+
+async fn run<T, F, Fut>(
+    handle: JobHandle,
+    (trigger, mut last): (T, DateTime<Utc>),
+    mut job: F,
+    (misfire_policy, pause_policy): (MisfirePolicy, PausePolicy),
+    backends: Backends,
+    listeners: Listeners,
+) where
+    T: Trigger,
+    F: FnMut() -> Fut,
+    Fut: Future,
+    Fut::Output: JobOutput,
+{
+    // `last` is the last fire time handled, then the fire times left to run and those reached
+    // while paused
+    let mut runs = VecDeque::new();
+    let mut accumulated = Vec::new();
+    loop {
+        if runs.is_empty() {
+            let scheduled = match trigger.next_after(&last) {
+                Some(scheduled) => scheduled,
+                None => return,
+            };
+            listeners.scheduled(&event(&handle, scheduled));
+            let delay = (scheduled - handle.now()).to_std().unwrap_or_default();
+            let woken = tokio::select! {
+                _ = tokio::time::sleep(delay) => false,
+                _ = handle.control.notify.notified() => true,
+            };
+            if handle.is_cancelled() {
+                return;
+            }
+            if woken {
+                // resumed, the next fire time is computed from now
+                if !handle.is_paused() {
+                    runs.extend(accumulated.drain(..));
+                    last = handle.now();
+                }
+                continue;
+            }
+
+            let now = handle.now();
+            let late = now - scheduled > MISFIRE_THRESHOLD;
+            let mut reached = vec![scheduled];
+            if late {
+                // e.g. after the process was suspended
+                reached.extend(trigger.missed_between(&scheduled, &now));
+            }
+            last = *reached.last().unwrap();
+            if handle.is_paused() {
+                match pause_policy {
+                    PausePolicy::Skip => reached.iter().for_each(|fire_time| {
+                        listeners.skip(&event(&handle, *fire_time), SkipReason::Paused)
+                    }),
+                    PausePolicy::Accumulate => accumulated.extend(reached),
+                }
+                continue;
+            }
+            if late {
+                runs.extend(misfire_policy.resolve(&handle, reached, None, &listeners));
+                continue;
+            }
+            runs.push_back(scheduled);
+        }
+        if handle.is_cancelled() {
+            return;
+        }
+
+        let scheduled = runs.pop_front().unwrap();
+        let admitted = {
+            let (backends, handle, listeners) =
+                (backends.clone(), handle.clone(), listeners.clone());
+            blocking(move || backends.admit(&handle, scheduled, &listeners)).await
+        };
+        if !admitted {
+            continue;
+        }
+        listeners.start(&event(&handle, scheduled));
+        match job().await.into_error() {
+            None => listeners.finish(&event(&handle, scheduled)),
+            Some(error) => listeners.error(&event(&handle, scheduled), &error),
+        }
+        {
+            let (backends, handle, listeners) =
+                (backends.clone(), handle.clone(), listeners.clone());
+            blocking(move || backends.record(&handle, scheduled, &listeners)).await;
+        }
+
+        if runs.is_empty() {
+            let finished = handle.now();
+            let missed = trigger.missed_between(&last, &finished);
+            if let Some(latest) = missed.last() {
+                last = *latest;
+            }
+            runs.extend(misfire_policy.resolve(&handle, missed, Some(finished), &listeners));
+        }
+    }
+}
+
+/// Runs the call on the blocking thread pool, resuming its panic if any.
+async fn blocking<F, R>(call: F) -> R
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    match tokio::task::spawn_blocking(call).await {
+        Ok(result) => result,
+        Err(error) => std::panic::resume_unwind(error.into_panic()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::testing::{self, every, Harness, Setup, TokioClock, Work};
+    use chrono::{DateTime, Utc};
+    use std::pin::Pin;
+    use std::thread::{self, ThreadId};
+    use std::time::Duration;
+
+    /// Starts schedulers on the clock of the tokio runtime, which the tests pause.
+    struct Tasks {
+        clock: Arc<TokioClock>,
+    }
+
+    impl Tasks {
+        fn new() -> Self {
+            Tasks {
+                clock: Arc::new(TokioClock::new()),
+            }
+        }
+    }
+
+    type Run = Pin<Box<dyn Future<Output = Result<(), &'static str>> + Send>>;
+
+    fn job(work: Work) -> impl FnMut() -> Run + Send + 'static {
+        move || {
+            Box::pin(async move {
+                match work {
+                    Work::Nothing => Ok(()),
+                    Work::Sleep(millis) => {
+                        tokio::time::sleep(Duration::from_millis(millis as u64)).await;
+                        Ok(())
+                    }
+                    Work::Fail(error) => Err(error),
+                }
+            })
+        }
+    }
+
+    impl Harness for Tasks {
+        type Scheduler = Scheduler;
+
+        fn scheduler(&self, listeners: Listeners, setup: Setup) -> Scheduler {
+            Scheduler {
+                listeners,
+                misfire_policy: setup.misfire_policy,
+                pause_policy: setup.pause_policy,
+                backends: setup.backends,
+                ..Scheduler::default()
+            }
+            .with_clock(self.clock.clone())
+        }
+
+        fn add<T>(&self, scheduler: &Scheduler, name: &str, trigger: T, work: Work) -> JobHandle
+        where
+            T: Trigger + 'static,
+        {
+            scheduler.add(name, trigger, job(work))
+        }
+
+        fn restore(&self, scheduler: &Scheduler, work: Work) -> Result<Vec<JobHandle>, BoxError> {
+            scheduler.restore(|_| job(work))
+        }
+
+        fn jobs(&self, scheduler: &Scheduler) -> Vec<JobHandle> {
+            scheduler.jobs()
+        }
+
+        async fn advance(&self, millis: i64) {
+            tokio::time::sleep(Duration::from_millis(millis as u64)).await;
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn runs_at_fire_times() {
+        testing::runs_at_fire_times(&Tasks::new()).await;
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn replicas_sharing_a_lock_run_each_fire_time_once() {
+        testing::replicas_sharing_a_lock_run_each_fire_time_once(&Tasks::new()).await;
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn followers_never_run_jobs() {
+        testing::followers_never_run_jobs(&Tasks::new()).await;
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn restored_jobs_resume_from_their_last_run() {
+        testing::restored_jobs_resume_from_their_last_run(&Tasks::new()).await;
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn invalid_stored_jobs_are_not_restored() {
+        testing::invalid_stored_jobs_are_not_restored(&Tasks::new()).await;
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn overlapping_occurrences_are_skipped() {
+        testing::overlapping_occurrences_are_skipped(&Tasks::new()).await;
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn missed_occurrences_follow_the_policy() {
+        testing::missed_occurrences_follow_the_policy(&Tasks::new()).await;
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn paused_fire_times_follow_the_policy() {
+        testing::paused_fire_times_follow_the_policy(&Tasks::new()).await;
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn cancelled_jobs_stop_firing() {
+        testing::cancelled_jobs_stop_firing(&Tasks::new()).await;
+    }
+
+    /// Records the threads it is called on.
+    #[derive(Default)]
+    struct Threads(Mutex<Vec<ThreadId>>);
+
+    impl DistributedLock for Threads {
+        fn try_acquire(&self, _job: &str, _fire_time: &DateTime<Utc>) -> Result<bool, BoxError> {
+            self.0.lock().unwrap().push(thread::current().id());
+            Ok(true)
+        }
+    }
+
+    impl JobStore for Threads {
+        fn save_job(&self, _job: &JobDefinition) -> Result<(), BoxError> {
+            Ok(())
+        }
+
+        fn remove_job(&self, _id: &str) -> Result<(), BoxError> {
+            Ok(())
+        }
+
+        fn load_jobs(&self) -> Result<Vec<JobDefinition>, BoxError> {
+            Ok(Vec::new())
+        }
+
+        fn set_last_run(&self, _id: &str, _fire_time: &DateTime<Utc>) -> Result<(), BoxError> {
+            self.0.lock().unwrap().push(thread::current().id());
+            Ok(())
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn backends_are_called_off_the_runtime() {
+        let tasks = Tasks::new();
+        let backend = Arc::new(Threads::default());
+        let setup = Setup {
+            backends: Backends {
+                lock: Some(backend.clone()),
+                store: Some(backend.clone()),
+                ..Backends::default()
+            },
+            ..Setup::default()
+        };
+        let scheduler = tasks.scheduler(Listeners::new(), setup);
+        tasks.add(&scheduler, "job", every(40, 1), Work::Nothing);
+        tasks.advance(50).await;
+        let threads = backend.0.lock().unwrap();
+        assert_eq!(2, threads.len());
+        assert!(!threads.contains(&thread::current().id()));
+    }
+}
@@ -0,0 +1,362 @@
+use crate::errors::{Field, ParseScheduleError};
+use crate::format::{CronFormat, DayOfWeekNumbering};
+use crate::options::{AmbiguousTimePolicy, DaySemantics, DstGapPolicy};
+use crate::schedule::{DayOfMonthTerm, DayOfWeekTerm, Schedule, Seconds, TimeRange, Years, REBOOT};
+use std::convert::TryFrom;
+use std::ops::RangeInclusive;
+
+/// The fields of a schedule as plain data, to build, inspect or serialize schedules without
+/// going through expressions.
+///
+/// An empty field matches every value as `*` does, except for the days of month and days of
+/// week when their Quartz terms are set. The days of week are numbered 0-6 from Sunday, with 7
+/// also Sunday, as in Crontab. Converting into a `Schedule` validates the values as when parsing.
+/// ```rust
+/// use cron_exp::{Schedule, ScheduleSpec};
+/// use std::convert::TryFrom;
+/// use std::str::FromStr;
+///
+/// let spec = ScheduleSpec {
+///     minutes: vec![30],
+///     hours: vec![9],
+///     days_of_week: vec![1, 2, 3, 4, 5],
+///     last_days_of_month: vec![0],
+///     ..ScheduleSpec::default()
+/// };
+/// let schedule = Schedule::try_from(spec.clone()).unwrap();
+/// assert_eq!(Schedule::from_str("30 9 L * Mon-Fri").unwrap(), schedule);
+/// assert_eq!(spec, ScheduleSpec::from(&schedule));
+///
+/// let invalid = ScheduleSpec {
+///     hours: vec![24],
+///     ..ScheduleSpec::default()
+/// };
+/// assert!(Schedule::try_from(invalid).is_err());
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ScheduleSpec {
+    /// The seconds, 0-59, only used with `has_seconds`.
+    pub seconds: Vec<u32>,
+    /// Fires every N seconds counted from local midnight in place of `seconds`, as written
+    /// `*/Ns`, implying `has_seconds`.
+    pub seconds_interval: Option<u32>,
+    /// The minutes, 0-59.
+    pub minutes: Vec<u32>,
+    /// The hours, 0-23.
+    pub hours: Vec<u32>,
+    /// The days of month, 1-31.
+    pub days_of_month: Vec<u32>,
+    /// The offsets from the last day of the month of the days to match, as written `L` for 0
+    /// and `L-3` for 3.
+    pub last_days_of_month: Vec<u32>,
+    /// The days of month whose nearest weekday within the month matches, as written `15W`.
+    pub nearest_weekdays: Vec<u32>,
+    /// Whether the last weekday of the month matches, as written `LW`.
+    pub last_weekday_of_month: bool,
+    /// The months, 1-12.
+    pub months: Vec<u32>,
+    /// The days of week, 0-6 from Sunday.
+    pub days_of_week: Vec<u32>,
+    /// The days of week matching on their last occurrence in the month, as written `5L`.
+    pub last_days_of_week: Vec<u32>,
+    /// The days of week and `n`, 1-5, matching on their nth occurrence in the month, as
+    /// written `5#2`.
+    pub nth_days_of_week: Vec<[u32; 2]>,
+    /// The years, 1970-2099, only used with `has_years`.
+    pub years: Vec<u32>,
+    /// Whether the schedule has a seconds field, firing at the start of the minute without.
+    pub has_seconds: bool,
+    /// Whether the schedule has a years field, firing in every year without.
+    pub has_years: bool,
+    /// Whether the schedule is `@reboot`, ignoring every field.
+    pub reboot: bool,
+    /// The name of the zone occurrences are computed in, as written with a `CRON_TZ=` prefix.
+    pub timezone: Option<String>,
+    /// How the days of month and days of week combine when both are restricted.
+    pub day_semantics: DaySemantics,
+    /// How occurrences in daylight saving time gaps are handled.
+    pub dst_gap_policy: DstGapPolicy,
+    /// How occurrences at local times repeated when clocks fall back are handled.
+    pub ambiguous_time_policy: AmbiguousTimePolicy,
+}
+
+impl TryFrom<ScheduleSpec> for Schedule {
+    type Error = ParseScheduleError;
+
+    /// Builds the schedule failing on values outside their field, as when parsing.
+    fn try_from(spec: ScheduleSpec) -> Result<Schedule, ParseScheduleError> {
+        let has_seconds = spec.has_seconds || spec.seconds_interval.is_some();
+        let format = match (has_seconds, spec.has_years) {
+            (true, true) => CronFormat::VixieWithYear,
+            (true, false) => CronFormat::VixieWithSeconds,
+            (false, true) => CronFormat::CrontabWithYear,
+            (false, false) => CronFormat::Crontab,
+        };
+
+        let mut fields = Vec::new();
+        if let Some(name) = &spec.timezone {
+            fields.push(format!("CRON_TZ={}", name));
+        }
+        if spec.reboot {
+            fields.push(REBOOT.to_string());
+        } else {
+            match spec.seconds_interval {
+                Some(interval) => fields.push(format!("*/{}s", interval)),
+                None if has_seconds => fields.push(format_values(&spec.seconds)),
+                None => {}
+            }
+            fields.push(format_values(&spec.minutes));
+            fields.push(format_values(&spec.hours));
+            fields.push(spec.format_days_of_month());
+            fields.push(format_values(&spec.months));
+            fields.push(spec.format_days_of_week(format.day_of_week_numbering())?);
+            if spec.has_years {
+                fields.push(format_values(&spec.years));
+            }
+        }
+
+        Ok(Schedule::parse_with_format(&fields.join(" "), format)?
+            .with_day_semantics(spec.day_semantics)
+            .with_dst_gap_policy(spec.dst_gap_policy)
+            .with_ambiguous_time_policy(spec.ambiguous_time_policy))
+    }
+}
+
+impl ScheduleSpec {
+    fn format_days_of_month(&self) -> String {
+        let terms = self
+            .last_days_of_month
+            .iter()
+            .map(|offset| match offset {
+                0 => "L".to_string(),
+                offset => format!("L-{}", offset),
+            })
+            .chain(self.nearest_weekdays.iter().map(|day| format!("{}W", day)))
+            .chain(self.last_weekday_of_month.then(|| "LW".to_string()));
+        format_with_terms(&self.days_of_month, terms)
+    }
+
+    /// Formats the days of week in the numbering of the format parsed with.
+    fn format_days_of_week(
+        &self,
+        numbering: DayOfWeekNumbering,
+    ) -> Result<String, ParseScheduleError> {
+        let renumber = |day: u32| {
+            DayOfWeekNumbering::SundayZero
+                .weekday(day)
+                .map(|weekday| numbering.number(weekday))
+                .ok_or_else(|| ParseScheduleError::InvalidRange {
+                    field: Field::DaysOfWeek,
+                    value: day.to_string(),
+                })
+        };
+        let days = self
+            .days_of_week
+            .iter()
+            .map(|day| renumber(*day))
+            .collect::<Result<Vec<u32>, _>>()?;
+        let mut terms = Vec::new();
+        for day in &self.last_days_of_week {
+            terms.push(format!("{}L", renumber(*day)?));
+        }
+        for [day, n] in &self.nth_days_of_week {
+            terms.push(format!("{}#{}", renumber(*day)?, n));
+        }
+        Ok(format_with_terms(&days, terms))
+    }
+}
+
+fn format_values(values: &[u32]) -> String {
+    format_with_terms(values, std::iter::empty())
+}
+
+/// Formats the values followed by the terms as a list, or `*` when there are neither.
+fn format_with_terms<I>(values: &[u32], terms: I) -> String
+where
+    I: IntoIterator<Item = String>,
+{
+    let items: Vec<String> = values
+        .iter()
+        .map(|value| value.to_string())
+        .chain(terms)
+        .collect();
+    if items.is_empty() {
+        return "*".to_string();
+    }
+    items.join(",")
+}
+
+impl From<&Schedule> for ScheduleSpec {
+    fn from(schedule: &Schedule) -> ScheduleSpec {
+        let mut spec = ScheduleSpec {
+            minutes: time_range_values(&schedule.minutes),
+            hours: time_range_values(&schedule.hours),
+            days_of_month: day_values(schedule, &schedule.days_of_month, 1..=31),
+            months: time_range_values(&schedule.months),
+            // numbered 1-7 from Sunday within the schedule
+            days_of_week: day_values(schedule, &schedule.days_of_week, 1..=7)
+                .into_iter()
+                .map(|day| day - 1)
+                .collect(),
+            has_seconds: schedule.seconds != Seconds::Ignore,
+            has_years: schedule.years != Years::Unbound,
+            reboot: schedule.reboot,
+            timezone: schedule.timezone_name().map(str::to_string),
+            day_semantics: schedule.day_semantics,
+            dst_gap_policy: schedule.dst_gap_policy,
+            ambiguous_time_policy: schedule.ambiguous_time_policy,
+            ..ScheduleSpec::default()
+        };
+        match &schedule.seconds {
+            Seconds::Constrained(seconds) => spec.seconds = seconds.iter().collect(),
+            Seconds::Interval(interval) => spec.seconds_interval = Some(*interval),
+            Seconds::Ignore | Seconds::All => {}
+        }
+        if let Years::Constrained(years) = &schedule.years {
+            spec.years = years.iter().cloned().collect();
+        }
+        for term in &schedule.day_of_month_terms {
+            match term {
+                DayOfMonthTerm::Last(offset) => spec.last_days_of_month.push(*offset),
+                DayOfMonthTerm::NearestWeekday(day) => spec.nearest_weekdays.push(*day),
+                DayOfMonthTerm::LastWeekday => spec.last_weekday_of_month = true,
+            }
+        }
+        for term in &schedule.day_of_week_terms {
+            match term {
+                DayOfWeekTerm::Last(day) => spec.last_days_of_week.push(day - 1),
+                DayOfWeekTerm::Nth(day, n) => spec.nth_days_of_week.push([day - 1, *n]),
+            }
+        }
+        spec
+    }
+}
+
+/// Lists every day of a day field covering the whole field when either field matching a day
+/// matches it, as it restricts the field unlike the empty list read as `*`.
+fn day_values(schedule: &Schedule, field: &TimeRange, all: RangeInclusive<u32>) -> Vec<u32> {
+    match field {
+        TimeRange::All if schedule.effective_day_semantics() == DaySemantics::Or => all.collect(),
+        field => time_range_values(field),
+    }
+}
+
+fn time_range_values(field: &TimeRange) -> Vec<u32> {
+    match field {
+        TimeRange::All => Vec::new(),
+        TimeRange::Constrained(set) => set.iter().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn round_trips() {
+        let expressions = [
+            "* * * * *",
+            "*/5 1,2,3,7 */2 * Sun",
+            "0 30 9 * * Mon-Fri",
+            "15 */7 0-23/5 L-3,20W * ?",
+            "*/45s * 9-17 * * *",
+            "0 0 0 LW * 2,3,4,6L 1970,1980",
+            "0 0 9 ? * Fri#2 *",
+            "@reboot",
+        ];
+        for expression in expressions.iter() {
+            let schedule = Schedule::from_str(expression).unwrap();
+            let spec = ScheduleSpec::from(&schedule);
+            assert_eq!(
+                schedule,
+                Schedule::try_from(spec.clone()).unwrap(),
+                "{} {:?}",
+                expression,
+                spec
+            );
+        }
+        let with_year = Schedule::parse_with_format("0 12 * * 1 2030", CronFormat::CrontabWithYear);
+        let spec = ScheduleSpec::from(with_year.as_ref().unwrap());
+        assert!(!spec.has_seconds && spec.has_years);
+        assert_eq!(with_year, Schedule::try_from(spec));
+        let or = Schedule::from_str("0 0 1 * Mon")
+            .unwrap()
+            .with_day_semantics(DaySemantics::Or);
+        assert_eq!(or, Schedule::try_from(ScheduleSpec::from(&or)).unwrap());
+        let every_day = Schedule::from_str("0 0 1-31 * Mon")
+            .unwrap()
+            .with_day_semantics(DaySemantics::Or);
+        let spec = ScheduleSpec::from(&every_day);
+        assert_eq!(31, spec.days_of_month.len());
+        assert_eq!(every_day, Schedule::try_from(spec).unwrap());
+        #[cfg(feature = "tz")]
+        {
+            let paris = Schedule::from_str("CRON_TZ=Europe/Paris 0 9 * * *").unwrap();
+            let spec = ScheduleSpec::from(&paris);
+            assert_eq!(Some("Europe/Paris"), spec.timezone.as_deref());
+            assert_eq!(paris, Schedule::try_from(spec).unwrap());
+        }
+    }
+
+    #[test]
+    fn fields() {
+        let schedule = Schedule::from_str("0 0 9 L-2,15W * Fri#2,6L 2030").unwrap();
+        let spec = ScheduleSpec::from(&schedule);
+        assert_eq!(vec![0], spec.seconds);
+        assert!(spec.minutes == vec![0] && spec.hours == vec![9] && spec.months.is_empty());
+        assert!(spec.days_of_month.is_empty() && spec.days_of_week.is_empty());
+        assert_eq!(vec![2], spec.last_days_of_month);
+        assert_eq!(vec![15], spec.nearest_weekdays);
+        assert_eq!(vec![5], spec.last_days_of_week);
+        assert_eq!(vec![[5, 2]], spec.nth_days_of_week);
+        assert_eq!(vec![2030], spec.years);
+        assert!(spec.has_seconds && spec.has_years);
+    }
+
+    #[test]
+    fn validation() {
+        let spec = |days_of_week| ScheduleSpec {
+            days_of_week,
+            ..ScheduleSpec::default()
+        };
+        assert_eq!(
+            Schedule::from_str("* * * * Sun").unwrap(),
+            Schedule::try_from(spec(vec![7])).unwrap()
+        );
+        assert_eq!(
+            Err(ParseScheduleError::InvalidRange {
+                field: Field::DaysOfWeek,
+                value: "8".into()
+            }),
+            Schedule::try_from(spec(vec![8]))
+        );
+        let seconds = ScheduleSpec {
+            seconds: vec![60],
+            has_seconds: true,
+            ..ScheduleSpec::default()
+        };
+        assert_eq!(
+            Some(Field::Seconds),
+            Schedule::try_from(seconds).unwrap_err().field()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let spec = ScheduleSpec::from(&Schedule::from_str("30 9 * * Mon-Fri").unwrap());
+        let json = serde_json::to_string(&spec).unwrap();
+        assert!(json.contains(r#""days_of_week":[1,2,3,4,5]"#), "{}", json);
+        assert_eq!(spec, serde_json::from_str::<ScheduleSpec>(&json).unwrap());
+
+        let partial: ScheduleSpec =
+            serde_json::from_str(r#"{"minutes": [0], "hours": [9]}"#).unwrap();
+        assert_eq!(
+            Schedule::from_str("0 9 * * *").unwrap(),
+            Schedule::try_from(partial).unwrap()
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use crate::schedule::Schedule;
+use chrono::{DateTime, TimeZone, Utc};
+use futures_core::Stream;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::time::Sleep;
+
+impl Schedule {
+    /// Returns a stream yielding each occurrence after the supplied datetime once it is reached,
+    /// sleeping on the tokio timer in between.
+    ///
+    /// Occurrences already passed when the stream is polled are yielded immediately.
+    /// ```rust
+    /// use chrono::Utc;
+    /// use cron_exp::Schedule;
+    /// use futures_util::StreamExt;
+    /// use std::str::FromStr;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread()
+    /// #     .enable_all()
+    /// #     .start_paused(true)
+    /// #     .build()
+    /// #     .unwrap()
+    /// #     .block_on(async {
+    /// let schedule = Schedule::from_str("*/10 * * * * *").unwrap();
+    /// let mut stream = schedule.stream_from(&Utc::now()).take(2);
+    /// while let Some(fire_time) = stream.next().await {
+    ///     println!("running at {}", fire_time);
+    /// }
+    /// # });
+    /// ```
+    pub fn stream_from<Z>(&self, dt: &DateTime<Z>) -> ScheduleStream<'_, Z>
+    where
+        Z: TimeZone,
+    {
+        ScheduleStream {
+            schedule: self,
+            previous: Some(dt.clone()),
+            pending: None,
+        }
+    }
+}
+
+/// A stream of the occurrences of a schedule as they are reached, see `Schedule::stream_from`.
+pub struct ScheduleStream<'a, Z>
+where
+    Z: TimeZone,
+{
+    schedule: &'a Schedule,
+    /// The last occurrence yielded or the starting datetime, `None` once exhausted.
+    previous: Option<DateTime<Z>>,
+    /// The next occurrence and the timer firing when it is reached.
+    pending: Option<(DateTime<Z>, Pin<Box<Sleep>>)>,
+}
+
+// the timer is pinned on the heap so the stream never needs pinning itself
+impl<Z> Unpin for ScheduleStream<'_, Z> where Z: TimeZone {}
+
+impl<Z> Stream for ScheduleStream<'_, Z>
+where
+    Z: TimeZone,
+{
+    type Item = DateTime<Z>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<DateTime<Z>>> {
+        let this = self.get_mut();
+        if this.pending.is_none() {
+            let next = match this
+                .previous
+                .as_ref()
+                .and_then(|dt| this.schedule.after(dt))
+            {
+                Some(next) => next,
+                None => {
+                    this.previous = None;
+                    return Poll::Ready(None);
+                }
+            };
+            let delay = next.with_timezone(&Utc) - Utc::now();
+            let sleep = tokio::time::sleep(delay.to_std().unwrap_or_default());
+            this.pending = Some((next, Box::pin(sleep)));
+        }
+        let (_, sleep) = this.pending.as_mut().unwrap();
+        if sleep.as_mut().poll(cx).is_pending() {
+            return Poll::Pending;
+        }
+        let (next, _) = this.pending.take().unwrap();
+        this.previous = Some(next.clone());
+        Poll::Ready(Some(next))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+    use std::str::FromStr;
+    use tokio::time::Instant;
+
+    #[tokio::test(start_paused = true)]
+    async fn sleeps_until_each_occurrence() {
+        let schedule = Schedule::from_str("0 * * * *").unwrap();
+        let now = Utc::now();
+        let expected: Vec<_> = schedule.iter_from(&now).take(2).collect();
+
+        let started = Instant::now();
+        let mut stream = schedule.stream_from(&now);
+        assert_eq!(Some(expected[0]), stream.next().await);
+        let waited = started.elapsed();
+        assert!(
+            waited + std::time::Duration::from_secs(1) >= (expected[0] - now).to_std().unwrap()
+        );
+        assert_eq!(Some(expected[1]), stream.next().await);
+        assert!(started.elapsed() >= waited + std::time::Duration::from_secs(3599));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn past_occurrences_are_immediate() {
+        let schedule = Schedule::from_str("0 0 0 1 1 * 2020-2021").unwrap();
+        let from = Utc.with_ymd_and_hms(2019, 6, 1, 0, 0, 0).unwrap();
+        let started = Instant::now();
+        let fired: Vec<_> = schedule.stream_from(&from).collect().await;
+        assert_eq!(schedule.iter_from(&from).collect::<Vec<_>>(), fired);
+        assert_eq!(2, fired.len());
+        assert!(started.elapsed() < std::time::Duration::from_secs(1));
+    }
+}
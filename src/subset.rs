@@ -0,0 +1,150 @@
+use crate::options::DaySemantics;
+use crate::schedule::{
+    Schedule, Seconds, TimeRange, Years, HORIZON_YEARS, MAX_YEAR, MIN_YEAR, SECONDS_PER_DAY,
+};
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime};
+
+/// Years enumerated for schedules without a years field, the Gregorian calendar repeats every
+/// 400 years so any pattern of months, days of month and days of week is covered.
+const CYCLE_START: u32 = 2000;
+
+impl Schedule {
+    /// Returns true if every fire time of this schedule is also a fire time of `other`,
+    /// comparing local datetimes, schedules in different zones only when this one never fires.
+    ///
+    /// Fields are compared individually where that's conclusive, otherwise the days and times
+    /// of day this schedule fires on are enumerated and checked against `other`. A schedule
+    /// that never fires, such as `@reboot`, is a subset of every schedule.
+    /// ```rust
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let permitted = Schedule::from_str("*/15 9-17 * * Mon-Fri").unwrap();
+    /// let custom = Schedule::from_str("30 12 * * Wed").unwrap();
+    /// assert!(custom.is_subset_of(&permitted));
+    /// assert!(!permitted.is_subset_of(&custom));
+    /// ```
+    pub fn is_subset_of(&self, other: &Schedule) -> bool {
+        if self.reboot {
+            return true;
+        }
+        // the same fields in different zones fire at different instants
+        if self.timezone_name() != other.timezone_name() {
+            return self.never_fires();
+        }
+        let day_semantics = (
+            self.effective_day_semantics(),
+            other.effective_day_semantics(),
+        );
+        if !other.reboot
+            && day_semantics == (DaySemantics::And, DaySemantics::And)
+            && self.fields_subset_of(other)
+        {
+            return true;
+        }
+
+        let years: Box<dyn Iterator<Item = u32>> = match (&self.years, &other.years) {
+            (Years::Unbound, Years::Unbound) => Box::new(CYCLE_START..CYCLE_START + HORIZON_YEARS),
+            // this schedule fires beyond the years of the other unless it never fires
+            (Years::Unbound, _) => return self.never_fires(),
+            (Years::All, _) => Box::new(MIN_YEAR..=MAX_YEAR),
+            (Years::Constrained(years), _) => Box::new(years.clone().into_iter()),
+        };
+
+        let mut fires = false;
+        for year in years {
+            let mut date = NaiveDate::from_ymd_opt(year as i32, 1, 1).unwrap();
+            while date.year() as u32 == year {
+                if self.matches_date(&date) {
+                    if !other.matches_date(&date) {
+                        return false;
+                    }
+                    fires = true;
+                }
+                date += Duration::days(1);
+            }
+        }
+        !fires
+            || (0..SECONDS_PER_DAY).all(|second| {
+                let time = NaiveTime::from_num_seconds_from_midnight_opt(second, 0).unwrap();
+                !self.matches_time(&time) || other.matches_time(&time)
+            })
+    }
+
+    /// Returns true if each field of this schedule is contained by the corresponding field of
+    /// `other`, sufficient but not necessary for the schedule to be a subset.
+    fn fields_subset_of(&self, other: &Schedule) -> bool {
+        let seconds = match (&self.seconds, &other.seconds) {
+            (_, Seconds::All) => true,
+            (Seconds::Ignore, Seconds::Ignore) => true,
+            (Seconds::Ignore, Seconds::Constrained(b)) => b.contains(0),
+            (Seconds::Constrained(a), Seconds::Ignore) => a.iter().all(|s| s == 0),
+            (Seconds::Constrained(a), Seconds::Constrained(b)) => a.is_subset(b),
+            _ => false,
+        };
+        let years = match (&self.years, &other.years) {
+            (_, Years::Unbound)
+            | (Years::All, Years::All)
+            | (Years::Constrained(_), Years::All) => true,
+            (Years::Constrained(a), Years::Constrained(b)) => a.is_subset(b),
+            _ => false,
+        };
+        seconds
+            && years
+            && range_subset(&self.minutes, &other.minutes)
+            && range_subset(&self.hours, &other.hours)
+            && (other.days_of_month == TimeRange::All
+                || (range_subset(&self.days_of_month, &other.days_of_month)
+                    && self.day_of_month_terms.is_subset(&other.day_of_month_terms)))
+            && range_subset(&self.months, &other.months)
+            && (other.days_of_week == TimeRange::All
+                || (range_subset(&self.days_of_week, &other.days_of_week)
+                    && self.day_of_week_terms.is_subset(&other.day_of_week_terms)))
+    }
+}
+
+fn range_subset(a: &TimeRange, b: &TimeRange) -> bool {
+    match (a, b) {
+        (_, TimeRange::All) => true,
+        (TimeRange::All, TimeRange::Constrained(_)) => false,
+        (TimeRange::Constrained(a), TimeRange::Constrained(b)) => a.is_subset(b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn subset(a: &str, b: &str) -> bool {
+        Schedule::from_str(a)
+            .unwrap()
+            .is_subset_of(&Schedule::from_str(b).unwrap())
+    }
+
+    #[test]
+    fn field_wise() {
+        assert!(subset("0 9 * * Mon", "0 9-17 * * Mon-Fri"));
+        assert!(subset("0 0 9 * * *", "0 9 * * *"));
+        assert!(subset("0 0 9 * * * 2030", "0 0 9 * * *"));
+        assert!(!subset("30 0 9 * * *", "0 9 * * *"));
+        assert!(!subset("0 9 * * *", "0 0 9 * * * 2030"));
+        assert!(!subset("0 9 * * Sat", "0 9-17 * * Mon-Fri"));
+    }
+
+    #[test]
+    fn enumerated() {
+        // february never has more than 29 days
+        assert!(subset("0 0 * 2 *", "0 0 1-29 2 *"));
+        assert!(!subset("0 0 * 2 *", "0 0 1-28 2 *"));
+        // the first of the month is a monday in both 2024 months
+        assert!(subset("0 0 0 1 Jan,Apr * 2024", "0 0 0 * * Mon 2024"));
+        // without a years field occurrences continue beyond 2099
+        assert!(!subset("0 0 1 1 *", "0 0 0 1 1 * *"));
+        assert!(subset("0 0 31 2 *", "0 0 0 1 1 * 2030"));
+        // intervals are compared second by second
+        assert!(subset("*/7200s * * * * *", "0 0 */2 * * *"));
+        assert!(!subset("*/90s * * * * *", "0 * * * * *"));
+        assert!(subset("0 */3 * * * *", "*/60s * * * * *"));
+    }
+}
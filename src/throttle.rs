@@ -0,0 +1,123 @@
+use crate::schedule::Schedule;
+use chrono::{DateTime, Duration, TimeZone};
+
+/// Determines what happens to occurrences arriving before the minimum spacing has elapsed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ThrottlePolicy {
+    /// Skip the occurrence.
+    Drop,
+    /// Delay the occurrence until the minimum spacing has elapsed, collapsing any further
+    /// occurrences up to that point into it.
+    Defer,
+}
+
+/// A schedule decorator enforcing a minimum spacing between the occurrences it yields, e.g.
+/// to keep user provided expressions within a platform's rate limit.
+/// ```rust
+/// use chrono::{Duration, TimeZone, Utc};
+/// use cron_exp::{Schedule, ThrottlePolicy, Throttled};
+/// use std::str::FromStr;
+///
+/// let schedule = Schedule::from_str("* * * * *").unwrap();
+/// let throttled = Throttled::new(schedule, Duration::minutes(15), ThrottlePolicy::Drop);
+///
+/// let from = Utc.with_ymd_and_hms(2022, 6, 1, 9, 0, 0).unwrap();
+/// let next: Vec<String> = throttled
+///     .iter_from(&from)
+///     .take(3)
+///     .map(|dt| dt.format("%H:%M").to_string())
+///     .collect();
+/// assert_eq!(vec!["09:01", "09:16", "09:31"], next);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Throttled {
+    schedule: Schedule,
+    min_spacing: Duration,
+    policy: ThrottlePolicy,
+}
+
+impl Throttled {
+    /// Wraps the schedule enforcing `min_spacing` between occurrences.
+    pub fn new(schedule: Schedule, min_spacing: Duration, policy: ThrottlePolicy) -> Self {
+        Throttled {
+            schedule,
+            min_spacing,
+            policy,
+        }
+    }
+
+    /// Returns the wrapped schedule.
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    /// Iterates the throttled occurrences after the supplied datetime.
+    ///
+    /// The spacing is enforced between yielded occurrences, so the first occurrence is never
+    /// throttled.
+    pub fn iter_from<'a, Z>(&'a self, dt: &DateTime<Z>) -> impl Iterator<Item = DateTime<Z>> + 'a
+    where
+        Z: TimeZone + 'a,
+    {
+        let mut last: Option<DateTime<Z>> = None;
+        let mut cursor = dt.clone();
+        std::iter::from_fn(move || loop {
+            let next = self.schedule.after(&cursor)?;
+            let earliest = match &last {
+                Some(last) => last.clone() + self.min_spacing,
+                None => next.clone(),
+            };
+            if next >= earliest {
+                cursor = next.clone();
+                last = Some(next.clone());
+                return Some(next);
+            }
+            match self.policy {
+                ThrottlePolicy::Drop => cursor = next,
+                ThrottlePolicy::Defer => {
+                    // occurrences up to the deferred time are collapsed into it
+                    cursor = earliest.clone();
+                    last = Some(earliest.clone());
+                    return Some(earliest);
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::str::FromStr;
+
+    fn minutes(throttled: &Throttled, from: &DateTime<Utc>, n: usize) -> Vec<String> {
+        throttled
+            .iter_from(from)
+            .take(n)
+            .map(|dt| dt.format("%H:%M").to_string())
+            .collect()
+    }
+
+    #[test]
+    fn throttled() {
+        let schedule = Schedule::from_str("0,5,7,20 * * * *").unwrap();
+        let from = Utc.with_ymd_and_hms(2022, 6, 1, 9, 0, 0).unwrap();
+
+        let dropped = Throttled::new(
+            schedule.clone(),
+            Duration::minutes(10),
+            ThrottlePolicy::Drop,
+        );
+        assert_eq!(
+            vec!["09:05", "09:20", "10:00", "10:20"],
+            minutes(&dropped, &from, 4)
+        );
+
+        let deferred = Throttled::new(schedule, Duration::minutes(10), ThrottlePolicy::Defer);
+        assert_eq!(
+            vec!["09:05", "09:15", "09:25", "10:00", "10:10", "10:20"],
+            minutes(&deferred, &from, 6)
+        );
+    }
+}
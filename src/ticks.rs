@@ -0,0 +1,110 @@
+use crate::schedule::Schedule;
+use chrono::{DateTime, Duration, TimeZone};
+use std::convert::TryFrom;
+
+impl Schedule {
+    /// Returns the time from `now` until the next occurrence, or `None` when there is no next
+    /// occurrence, e.g. to show that a job runs in 42 minutes.
+    /// ```rust
+    /// use chrono::{Duration, TimeZone, Utc};
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 9 * * *").unwrap();
+    /// let now = Utc.with_ymd_and_hms(2022, 6, 1, 8, 18, 0).unwrap();
+    /// assert_eq!(Some(Duration::minutes(42)), schedule.time_until_next(&now));
+    /// assert_eq!(Some(Duration::hours(23) + Duration::minutes(18)), schedule.time_since_last(&now));
+    /// ```
+    pub fn time_until_next<Z>(&self, now: &DateTime<Z>) -> Option<Duration>
+    where
+        Z: TimeZone,
+    {
+        let next = self.after(now)?;
+        Some(next.signed_duration_since(now.clone()))
+    }
+
+    /// Returns the time since the previous occurrence before `now`, or `None` when there is no
+    /// previous occurrence.
+    pub fn time_since_last<Z>(&self, now: &DateTime<Z>) -> Option<Duration>
+    where
+        Z: TimeZone,
+    {
+        let last = self.before(now)?;
+        Some(now.clone().signed_duration_since(last))
+    }
+
+    /// Returns the number of milliseconds from `now` until the next occurrence, or `None` when
+    /// there is no next occurrence.
+    ///
+    /// Intended for targets that can't hold a `DateTime` between wake ups, the value can be
+    /// loaded straight into a timer.
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 * * * * *").unwrap();
+    /// let now = Utc.with_ymd_and_hms(2022, 6, 1, 8, 40, 30).unwrap();
+    /// assert_eq!(Some(30_000), schedule.millis_until_next(&now));
+    /// ```
+    pub fn millis_until_next<Z>(&self, now: &DateTime<Z>) -> Option<u64>
+    where
+        Z: TimeZone,
+    {
+        let millis = self.time_until_next(now)?.num_milliseconds();
+        Some(millis.max(0) as u64)
+    }
+
+    /// Returns the number of timer ticks of `ticks_per_second` from `now` until the next
+    /// occurrence, rounded up so the timer never fires early and saturating at `u32::MAX` when
+    /// the occurrence is further away than the timer can count.
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let now = Utc.with_ymd_and_hms(2022, 6, 1, 8, 40, 30).unwrap();
+    ///
+    /// let schedule = Schedule::from_str("0 * * * * *").unwrap();
+    /// assert_eq!(Some(30 * 32_768), schedule.ticks_until_next(&now, 32_768));
+    ///
+    /// let yearly = Schedule::from_str("0 0 1 1 *").unwrap();
+    /// assert_eq!(Some(u32::MAX), yearly.ticks_until_next(&now, 32_768));
+    /// ```
+    pub fn ticks_until_next<Z>(&self, now: &DateTime<Z>, ticks_per_second: u32) -> Option<u32>
+    where
+        Z: TimeZone,
+    {
+        let millis = u128::from(self.millis_until_next(now)?);
+        let ticks = (millis * u128::from(ticks_per_second)).div_ceil(1000);
+        Some(u32::try_from(ticks).unwrap_or(u32::MAX))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::str::FromStr;
+
+    #[test]
+    fn ticks_until_next() {
+        let now = Utc.with_ymd_and_hms(2022, 6, 1, 8, 40, 30).unwrap()
+            + chrono::Duration::milliseconds(1);
+        let schedule = Schedule::from_str("0 * * * * *").unwrap();
+
+        assert_eq!(Some(29_999), schedule.millis_until_next(&now));
+        // rounds up to the next whole tick
+        assert_eq!(Some(30), schedule.ticks_until_next(&now, 1));
+        assert_eq!(Some(29_999), schedule.ticks_until_next(&now, 1000));
+
+        let finished = Schedule::from_str("0 0 0 1 1 * 2020").unwrap();
+        assert_eq!(None, finished.time_until_next(&now));
+        assert_eq!(
+            Some(now - Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap()),
+            finished.time_since_last(&now)
+        );
+        assert_eq!(None, finished.millis_until_next(&now));
+        assert_eq!(None, finished.ticks_until_next(&now, 1000));
+    }
+}
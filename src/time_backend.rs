@@ -0,0 +1,165 @@
+//! Occurrences as `time` crate datetimes, with the `time` feature.
+use crate::backend::CalendarBackend;
+use crate::schedule::Schedule;
+use chrono::{DateTime, FixedOffset, Utc};
+use time::{OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+impl CalendarBackend for OffsetDateTime {
+    type Zone = FixedOffset;
+
+    fn to_chrono(&self) -> Option<DateTime<FixedOffset>> {
+        let offset = FixedOffset::east_opt(self.offset().whole_seconds())?;
+        let utc = DateTime::from_timestamp(self.unix_timestamp(), self.nanosecond())?;
+        Some(utc.with_timezone(&offset))
+    }
+
+    fn from_chrono(dt: &DateTime<FixedOffset>, _from: &Self) -> Option<Self> {
+        let offset = UtcOffset::from_whole_seconds(dt.offset().local_minus_utc()).ok()?;
+        let utc = OffsetDateTime::from_unix_timestamp(dt.timestamp())
+            .ok()?
+            .replace_nanosecond(dt.timestamp_subsec_nanos())
+            .ok()?;
+        Some(utc.to_offset(offset))
+    }
+}
+
+/// Primitive datetimes are taken to be in UTC.
+impl CalendarBackend for PrimitiveDateTime {
+    type Zone = Utc;
+
+    fn to_chrono(&self) -> Option<DateTime<Utc>> {
+        self.assume_utc()
+            .to_chrono()
+            .map(|dt| dt.with_timezone(&Utc))
+    }
+
+    fn from_chrono(dt: &DateTime<Utc>, _from: &Self) -> Option<Self> {
+        let utc = OffsetDateTime::from_unix_timestamp(dt.timestamp())
+            .ok()?
+            .replace_nanosecond(dt.timestamp_subsec_nanos())
+            .ok()?;
+        Some(PrimitiveDateTime::new(utc.date(), utc.time()))
+    }
+}
+
+impl Schedule {
+    /// Returns the next occurrence after the supplied `time::OffsetDateTime`, in its offset.
+    ///
+    /// Occurrences are computed in the fixed offset of the datetime, attach a zone with
+    /// `Schedule::with_timezone` or a `CRON_TZ=` prefix to follow daylight saving time.
+    /// ```rust
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    /// use time::{Date, Month, PrimitiveDateTime, Time, UtcOffset};
+    ///
+    /// let at = |h, m| {
+    ///     let date = Date::from_calendar_date(2022, Month::June, 1).unwrap();
+    ///     PrimitiveDateTime::new(date, Time::from_hms(h, m, 0).unwrap())
+    ///         .assume_offset(UtcOffset::from_hms(2, 0, 0).unwrap())
+    /// };
+    /// let schedule = Schedule::from_str("30 9 * * *").unwrap();
+    /// assert_eq!(Some(at(9, 30)), schedule.next_after_offset(&at(8, 0)));
+    /// ```
+    pub fn next_after_offset(&self, dt: &OffsetDateTime) -> Option<OffsetDateTime> {
+        self.next_after_backend(dt)
+    }
+
+    /// Returns the previous occurrence before the supplied `time::OffsetDateTime`, in its offset.
+    pub fn prev_before_offset(&self, dt: &OffsetDateTime) -> Option<OffsetDateTime> {
+        self.prev_before_backend(dt)
+    }
+
+    /// Iterates the occurrences after the supplied `time::OffsetDateTime`, in its offset.
+    pub fn iter_after_offset(
+        &self,
+        dt: &OffsetDateTime,
+    ) -> impl Iterator<Item = OffsetDateTime> + '_ {
+        self.iter_after_backend(dt)
+    }
+
+    /// Iterates the occurrences before the supplied `time::OffsetDateTime` latest first, in its
+    /// offset.
+    pub fn iter_before_offset(
+        &self,
+        dt: &OffsetDateTime,
+    ) -> impl Iterator<Item = OffsetDateTime> + '_ {
+        self.iter_before_backend(dt)
+    }
+
+    /// Returns the next occurrence after the supplied `time::PrimitiveDateTime`, which is taken
+    /// to be in UTC.
+    pub fn next_after_primitive(&self, dt: &PrimitiveDateTime) -> Option<PrimitiveDateTime> {
+        self.next_after_backend(dt)
+    }
+
+    /// Returns the previous occurrence before the supplied `time::PrimitiveDateTime`, which is
+    /// taken to be in UTC.
+    pub fn prev_before_primitive(&self, dt: &PrimitiveDateTime) -> Option<PrimitiveDateTime> {
+        self.prev_before_backend(dt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use time::{Date, Month, Time};
+
+    fn at(day: u8, hour: u8, minute: u8) -> PrimitiveDateTime {
+        let date = Date::from_calendar_date(2022, Month::June, day).unwrap();
+        PrimitiveDateTime::new(date, Time::from_hms(hour, minute, 0).unwrap())
+    }
+
+    #[test]
+    fn offset_datetimes() {
+        let offset = UtcOffset::from_hms(-4, 0, 0).unwrap();
+        let schedule = Schedule::from_str("30 9 * * Mon-Fri").unwrap();
+        let from = at(3, 12, 0).assume_offset(offset);
+        assert_eq!(
+            vec![
+                at(6, 9, 30).assume_offset(offset),
+                at(7, 9, 30).assume_offset(offset)
+            ],
+            schedule
+                .iter_after_offset(&from)
+                .take(2)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![
+                at(3, 9, 30).assume_offset(offset),
+                at(2, 9, 30).assume_offset(offset)
+            ],
+            schedule
+                .iter_before_offset(&from)
+                .take(2)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(at(3, 9, 30).assume_offset(offset)),
+            schedule.prev_before_offset(&from)
+        );
+
+        // sub second precision is kept when converting
+        let precise = from.replace_millisecond(250).unwrap();
+        assert_eq!(
+            Some(precise),
+            precise
+                .to_chrono()
+                .and_then(|dt| OffsetDateTime::from_chrono(&dt, &precise))
+        );
+    }
+
+    #[test]
+    fn primitive_datetimes_are_utc() {
+        let schedule = Schedule::from_str("0 0 * * *").unwrap();
+        assert_eq!(
+            Some(at(2, 0, 0)),
+            schedule.next_after_primitive(&at(1, 12, 0))
+        );
+        assert_eq!(
+            Some(at(1, 0, 0)),
+            schedule.prev_before_primitive(&at(1, 12, 0))
+        );
+    }
+}
@@ -0,0 +1,138 @@
+//! Expansion of the tokens picking values of a field, shared by the hashed and random
+//! expressions.
+use crate::capabilities::Capabilities;
+use crate::errors::{Field, ParseScheduleError};
+use crate::schedule::{split_timezone_prefix, Schedule, MAX_YEAR, MIN_YEAR};
+use std::str::FromStr;
+
+/// A field and the values its tokens pick from.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Bounds {
+    pub(crate) field: Field,
+    pub(crate) min: u32,
+    pub(crate) max: u32,
+}
+
+impl Bounds {
+    const fn new(field: Field, min: u32, max: u32) -> Bounds {
+        Bounds { field, min, max }
+    }
+
+    /// Parses a number written in a token.
+    pub(crate) fn number(&self, value: &str) -> Result<u32, ParseScheduleError> {
+        value
+            .parse::<u32>()
+            .map_err(|_| ParseScheduleError::InvalidNumber {
+                field: self.field,
+                value: value.into(),
+            })
+    }
+
+    /// Returns the token of `item` picking from `low-high`, failing unless it is a non empty
+    /// range within the field.
+    pub(crate) fn token(
+        &self,
+        item: &str,
+        low: u32,
+        high: u32,
+        step: Option<u32>,
+    ) -> Result<Token, ParseScheduleError> {
+        if low < self.min || high > self.max || low > high {
+            return Err(ParseScheduleError::InvalidRange {
+                field: self.field,
+                value: item.into(),
+            });
+        }
+        Ok(Token { low, high, step })
+    }
+}
+
+/// The values a token picks from, a single one or the start of a step.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Token {
+    low: u32,
+    high: u32,
+    step: Option<u32>,
+}
+
+/// The syntax of the tokens of an expression and the capability they add.
+pub(crate) struct Syntax<T> {
+    /// Returns the token of a list item, `None` for plain items.
+    pub(crate) token: T,
+    /// The last day of month picked, as not every month has 31 days.
+    pub(crate) last_day_of_month: u32,
+    pub(crate) capability: Capabilities,
+}
+
+/// Parses an expression with an optional zone prefix, replacing the tokens in the list items of
+/// its fields with the values `pick` derives from the index of the field and the item.
+pub(crate) fn parse_picking<T, P>(
+    expression: &str,
+    syntax: Syntax<T>,
+    mut pick: P,
+) -> Result<Schedule, ParseScheduleError>
+where
+    T: Fn(&str, Bounds) -> Result<Option<Token>, ParseScheduleError>,
+    P: FnMut(usize, usize) -> u64,
+{
+    let (timezone, rest) = split_timezone_prefix(expression)?;
+    let fields: Vec<&str> = rest.split_whitespace().collect();
+    let days_of_month = Bounds::new(Field::DaysOfMonth, 1, syntax.last_day_of_month);
+    let bounds = match fields.len() {
+        5 => vec![
+            Bounds::new(Field::Minutes, 0, 59),
+            Bounds::new(Field::Hours, 0, 23),
+            days_of_month,
+            Bounds::new(Field::Months, 1, 12),
+            Bounds::new(Field::DaysOfWeek, 0, 6),
+        ],
+        6 | 7 => vec![
+            Bounds::new(Field::Seconds, 0, 59),
+            Bounds::new(Field::Minutes, 0, 59),
+            Bounds::new(Field::Hours, 0, 23),
+            days_of_month,
+            Bounds::new(Field::Months, 1, 12),
+            Bounds::new(Field::DaysOfWeek, 1, 7),
+            Bounds::new(Field::Years, MIN_YEAR, MAX_YEAR),
+        ],
+        _ => return Err(ParseScheduleError::ArgumentCount),
+    };
+
+    let mut picked = false;
+    let mut expanded = Vec::with_capacity(fields.len());
+    for (i, (value, bounds)) in fields.iter().zip(bounds).enumerate() {
+        let mut items = Vec::new();
+        for (j, item) in value.split(',').enumerate() {
+            match (syntax.token)(item, bounds)? {
+                Some(token) => {
+                    picked = true;
+                    items.push(expand(token, pick(i, j)));
+                }
+                None => items.push(item.to_string()),
+            }
+        }
+        expanded.push(items.join(","));
+    }
+
+    let expanded = expanded.join(" ");
+    let mut schedule = match timezone {
+        Some(zone) => Schedule::from_str(&format!("CRON_TZ={} {}", zone, expanded))?,
+        None => Schedule::from_str(&expanded)?,
+    };
+    if picked {
+        schedule.syntax |= syntax.capability;
+    }
+    Ok(schedule)
+}
+
+/// Writes the value picked by a token, a step starts at an offset within the step.
+fn expand(token: Token, picked: u64) -> String {
+    let span = u64::from(token.high - token.low + 1);
+    match token.step {
+        None => (token.low + (picked % span) as u32).to_string(),
+        Some(step) => {
+            let offset = picked % u64::from(step).min(span);
+            format!("{}-{}/{}", token.low + offset as u32, token.high, step)
+        }
+    }
+}
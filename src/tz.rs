@@ -0,0 +1,350 @@
+//! Time zones whose offsets are supplied by the caller.
+//!
+//! Schedules can be iterated in any `chrono::TimeZone` so the IANA database is only compiled
+//! into this crate by the `tz` feature. Where even `chrono-tz` is too heavy, such as WASM bundles that can ask the
+//! browser's `Intl` API instead, implement `TimeZoneProvider` and iterate in a `ProvidedTz`.
+use chrono::{Duration, FixedOffset, LocalResult, NaiveDate, NaiveDateTime, Offset, TimeZone};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// Supplies the UTC offsets of a time zone.
+pub trait TimeZoneProvider: fmt::Debug + Send + Sync {
+    /// Returns the offset in effect at the supplied UTC datetime.
+    fn offset_from_utc(&self, utc: &NaiveDateTime) -> FixedOffset;
+
+    /// Returns the offset(s) the supplied local datetime maps to, `LocalResult::None` for local
+    /// times skipped by a transition and `LocalResult::Ambiguous` for repeated ones.
+    fn offset_from_local(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset>;
+}
+
+/// A `chrono::TimeZone` backed by a `TimeZoneProvider`.
+/// ```rust
+/// use chrono::{FixedOffset, LocalResult, NaiveDateTime, TimeZone};
+/// use cron_exp::{ProvidedTz, Schedule, TimeZoneProvider};
+/// use std::str::FromStr;
+///
+/// // offsets would typically be looked up from the host, e.g. the browser's Intl data
+/// #[derive(Debug)]
+/// struct HostZone;
+///
+/// impl TimeZoneProvider for HostZone {
+///     fn offset_from_utc(&self, _utc: &NaiveDateTime) -> FixedOffset {
+///         FixedOffset::east_opt(5 * 3600 + 1800).unwrap()
+///     }
+///
+///     fn offset_from_local(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset> {
+///         LocalResult::Single(self.offset_from_utc(local))
+///     }
+/// }
+///
+/// let tz = ProvidedTz::new(HostZone);
+/// let from_date = tz.with_ymd_and_hms(2022, 6, 1, 8, 40, 0).unwrap();
+/// let schedule = Schedule::from_str("0 9 * * *").unwrap();
+/// let next = schedule.iter_from(&from_date).next().unwrap();
+/// assert_eq!("2022-06-01 09:00:00 +05:30", next.to_string());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProvidedTz {
+    provider: Arc<dyn TimeZoneProvider>,
+}
+
+impl ProvidedTz {
+    /// Creates a time zone from the supplied provider.
+    pub fn new<P>(provider: P) -> Self
+    where
+        P: TimeZoneProvider + 'static,
+    {
+        ProvidedTz {
+            provider: Arc::new(provider),
+        }
+    }
+
+    /// Creates a time zone from an already shared provider.
+    pub fn from_arc(provider: Arc<dyn TimeZoneProvider>) -> Self {
+        ProvidedTz { provider }
+    }
+
+    fn offset(&self, offset: FixedOffset) -> ProvidedOffset {
+        ProvidedOffset {
+            provider: self.provider.clone(),
+            offset,
+        }
+    }
+}
+
+/// The offset of a `ProvidedTz`, which keeps hold of its provider as `chrono` must be able to
+/// recover the time zone from an offset.
+#[derive(Debug, Clone)]
+pub struct ProvidedOffset {
+    provider: Arc<dyn TimeZoneProvider>,
+    offset: FixedOffset,
+}
+
+impl Offset for ProvidedOffset {
+    fn fix(&self) -> FixedOffset {
+        self.offset
+    }
+}
+
+impl fmt::Display for ProvidedOffset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.offset, f)
+    }
+}
+
+impl TimeZone for ProvidedTz {
+    type Offset = ProvidedOffset;
+
+    fn from_offset(offset: &ProvidedOffset) -> Self {
+        ProvidedTz {
+            provider: offset.provider.clone(),
+        }
+    }
+
+    fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<ProvidedOffset> {
+        match local.and_hms_opt(0, 0, 0) {
+            Some(local) => self.offset_from_local_datetime(&local),
+            None => LocalResult::None,
+        }
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<ProvidedOffset> {
+        self.provider
+            .offset_from_local(local)
+            .map(|offset| self.offset(offset))
+    }
+
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> ProvidedOffset {
+        self.offset_from_utc_datetime(&utc.and_time(chrono::NaiveTime::MIN))
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> ProvidedOffset {
+        self.offset(self.provider.offset_from_utc(utc))
+    }
+}
+
+/// A `TimeZoneProvider` defined by a base offset and a list of transitions to new offsets,
+/// e.g. a site that ignores DST or a test zone with synthetic transitions to simulate DST edge
+/// cases.
+/// ```rust
+/// use chrono::{FixedOffset, NaiveDate, TimeZone};
+/// use cron_exp::{ProvidedTz, TransitionZone};
+///
+/// let at = NaiveDate::from_ymd_opt(2021, 3, 28).unwrap().and_hms_opt(1, 0, 0).unwrap();
+/// let zone = TransitionZone::fixed(FixedOffset::east_opt(3600).unwrap())
+///     .with_transition(at, FixedOffset::east_opt(7200).unwrap());
+///
+/// let tz = ProvidedTz::new(zone);
+/// assert!(tz.with_ymd_and_hms(2021, 3, 28, 2, 30, 0).single().is_none());
+/// ```
+#[derive(Debug, Clone)]
+pub struct TransitionZone {
+    base: FixedOffset,
+    transitions: Vec<(NaiveDateTime, FixedOffset)>,
+}
+
+impl TransitionZone {
+    /// Creates a zone that is always at the supplied offset.
+    pub fn fixed(offset: FixedOffset) -> Self {
+        TransitionZone {
+            base: offset,
+            transitions: Vec::new(),
+        }
+    }
+
+    /// Switches to `offset` from the supplied UTC datetime onwards.
+    pub fn with_transition(mut self, at: NaiveDateTime, offset: FixedOffset) -> Self {
+        let index = self.transitions.partition_point(|(t, _)| *t <= at);
+        self.transitions.insert(index, (at, offset));
+        self
+    }
+
+    /// The index of the period the UTC datetime falls in, 0 being the base offset.
+    fn period(&self, utc: &NaiveDateTime) -> usize {
+        self.transitions.partition_point(|(t, _)| t <= utc)
+    }
+
+    fn period_offset(&self, period: usize) -> FixedOffset {
+        match period {
+            0 => self.base,
+            _ => self.transitions[period - 1].1,
+        }
+    }
+}
+
+impl TimeZoneProvider for TransitionZone {
+    fn offset_from_utc(&self, utc: &NaiveDateTime) -> FixedOffset {
+        self.period_offset(self.period(utc))
+    }
+
+    fn offset_from_local(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset> {
+        // a local datetime is valid in a period when converting it with that period's offset
+        // lands inside the period
+        let mut matches: Vec<(NaiveDateTime, FixedOffset)> = (0..=self.transitions.len())
+            .filter_map(|period| {
+                let offset = self.period_offset(period);
+                let utc = *local - Duration::seconds(i64::from(offset.local_minus_utc()));
+                if self.period(&utc) == period {
+                    Some((utc, offset))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        matches.sort_by_key(|(utc, _)| *utc);
+        match matches.as_slice() {
+            [] => LocalResult::None,
+            [(_, offset)] => LocalResult::Single(*offset),
+            [(_, earliest), .., (_, latest)] => LocalResult::Ambiguous(*earliest, *latest),
+        }
+    }
+}
+
+/// Named `TimeZoneProvider`s registered by the caller, used to look up custom zones or rules
+/// overriding standard ones by name, e.g. from configuration.
+/// ```rust
+/// use chrono::FixedOffset;
+/// use cron_exp::{TransitionZone, ZoneRegistry};
+///
+/// let mut registry = ZoneRegistry::new();
+/// registry.register("Site/NoDst", TransitionZone::fixed(FixedOffset::east_opt(3600).unwrap()));
+/// assert!(registry.get("Site/NoDst").is_some());
+/// assert!(registry.get("Europe/Paris").is_none());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ZoneRegistry {
+    zones: BTreeMap<String, ProvidedTz>,
+}
+
+impl ZoneRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a zone under the supplied name, replacing any zone previously registered with
+    /// it.
+    pub fn register<P>(&mut self, name: impl Into<String>, provider: P)
+    where
+        P: TimeZoneProvider + 'static,
+    {
+        self.zones.insert(name.into(), ProvidedTz::new(provider));
+    }
+
+    /// Returns the zone registered under the supplied name.
+    pub fn get(&self, name: &str) -> Option<ProvidedTz> {
+        self.zones.get(name).cloned()
+    }
+
+    /// Returns the names of all registered zones in order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.zones.keys().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Schedule;
+    use chrono::Utc;
+    use std::str::FromStr;
+
+    /// UTC+1 that springs forward to UTC+2 at 2021-03-28T01:00:00Z.
+    #[derive(Debug)]
+    struct SpringForward;
+
+    impl SpringForward {
+        fn transition() -> NaiveDateTime {
+            NaiveDate::from_ymd_opt(2021, 3, 28)
+                .unwrap()
+                .and_hms_opt(1, 0, 0)
+                .unwrap()
+        }
+    }
+
+    impl TimeZoneProvider for SpringForward {
+        fn offset_from_utc(&self, utc: &NaiveDateTime) -> FixedOffset {
+            let hours = if *utc < Self::transition() { 1 } else { 2 };
+            FixedOffset::east_opt(hours * 3600).unwrap()
+        }
+
+        fn offset_from_local(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset> {
+            let before = FixedOffset::east_opt(3600).unwrap();
+            let after = FixedOffset::east_opt(7200).unwrap();
+            let gap_start = Self::transition() + Duration::hours(1);
+            if *local < gap_start {
+                LocalResult::Single(before)
+            } else if *local < gap_start + Duration::hours(1) {
+                LocalResult::None
+            } else {
+                LocalResult::Single(after)
+            }
+        }
+    }
+
+    #[test]
+    fn provided_tz_skips_gap() {
+        let tz = ProvidedTz::new(SpringForward);
+        let from_date = tz.with_ymd_and_hms(2021, 3, 28, 0, 0, 0).unwrap();
+        let schedule = Schedule::from_str("30 * * * *").unwrap();
+        let occurrences: Vec<String> = schedule
+            .iter_from(&from_date)
+            .take(3)
+            .map(|dt| dt.with_timezone(&Utc).to_rfc3339())
+            .collect();
+        assert_eq!(
+            vec![
+                "2021-03-27T23:30:00+00:00",
+                "2021-03-28T00:30:00+00:00",
+                "2021-03-28T01:30:00+00:00",
+            ],
+            occurrences
+        );
+    }
+
+    #[test]
+    fn transition_zone_fall_back() {
+        let transition = NaiveDate::from_ymd_opt(2021, 10, 31)
+            .unwrap()
+            .and_hms_opt(1, 0, 0)
+            .unwrap();
+        let zone = TransitionZone::fixed(FixedOffset::east_opt(7200).unwrap())
+            .with_transition(transition, FixedOffset::east_opt(3600).unwrap());
+        let local = |h| {
+            NaiveDate::from_ymd_opt(2021, 10, 31)
+                .unwrap()
+                .and_hms_opt(h, 30, 0)
+                .unwrap()
+        };
+
+        assert_eq!(
+            LocalResult::Single(FixedOffset::east_opt(7200).unwrap()),
+            zone.offset_from_local(&local(1))
+        );
+        assert_eq!(
+            LocalResult::Ambiguous(
+                FixedOffset::east_opt(7200).unwrap(),
+                FixedOffset::east_opt(3600).unwrap()
+            ),
+            zone.offset_from_local(&local(2))
+        );
+        assert_eq!(
+            LocalResult::Single(FixedOffset::east_opt(3600).unwrap()),
+            zone.offset_from_local(&local(3))
+        );
+
+        // ambiguous local times are skipped while iterating
+        let mut registry = ZoneRegistry::new();
+        registry.register("Test/FallBack", zone);
+        let tz = registry.get("Test/FallBack").unwrap();
+        let from_date = tz.with_ymd_and_hms(2021, 10, 31, 0, 0, 0).unwrap();
+        let schedule = Schedule::from_str("30 * * * *").unwrap();
+        let hours: Vec<u32> = schedule
+            .iter_from(&from_date)
+            .take(3)
+            .map(|dt| chrono::Timelike::hour(&dt))
+            .collect();
+        assert_eq!(vec![0, 1, 3], hours);
+    }
+}
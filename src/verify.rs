@@ -0,0 +1,269 @@
+use crate::schedule::Schedule;
+use chrono::{DateTime, Duration, TimeZone, Timelike};
+use std::fmt;
+use std::ops::Range;
+
+/// The search whose result diverged from the brute force matcher.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SearchDirection {
+    /// Searching forwards for the next occurrence.
+    Next,
+    /// Searching backwards for the previous occurrence.
+    Prev,
+}
+
+/// The first point at which the optimized search disagreed with the brute force matcher.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Divergence<Z>
+where
+    Z: TimeZone,
+{
+    /// The direction of the diverging search.
+    pub direction: SearchDirection,
+    /// The datetime the search started from.
+    pub from: DateTime<Z>,
+    /// The occurrence found by the brute force matcher, `None` if there isn't one in range.
+    pub expected: Option<DateTime<Z>>,
+    /// The occurrence returned by the optimized search.
+    pub actual: Option<DateTime<Z>>,
+}
+
+impl<Z> fmt::Display for Divergence<Z>
+where
+    Z: TimeZone,
+    Z::Offset: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let direction = match self.direction {
+            SearchDirection::Next => "next",
+            SearchDirection::Prev => "previous",
+        };
+        let format = |dt: &Option<DateTime<Z>>| match dt {
+            Some(dt) => dt.to_string(),
+            None => "none".to_string(),
+        };
+        write!(
+            f,
+            "{} occurrence from {} expected {} but was {}",
+            direction,
+            self.from,
+            format(&self.expected),
+            format(&self.actual)
+        )
+    }
+}
+
+impl Schedule {
+    /// Compares every next and previous occurrence within the half open range against a simple
+    /// matcher that checks each second of the range, returning the first divergence.
+    ///
+    /// This is slow, checking a single year takes 31 million steps, and is intended for
+    /// downstream test suites and fuzzers validating extensions and upgrades of the optimized
+    /// search. Ambiguous local times are resolved by the `AmbiguousTimePolicy` and times in DST
+    /// gaps by the `DstGapPolicy` as while iterating.
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 */10 9-17 * * Mon-Fri").unwrap();
+    /// let start = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap();
+    /// let end = Utc.with_ymd_and_hms(2022, 6, 8, 0, 0, 0).unwrap();
+    /// assert_eq!(Ok(()), schedule.verify_against_bruteforce(start..end));
+    /// ```
+    pub fn verify_against_bruteforce<Z>(
+        &self,
+        range: Range<DateTime<Z>>,
+    ) -> Result<(), Divergence<Z>>
+    where
+        Z: TimeZone,
+    {
+        let mut cursor = range.start.clone();
+        loop {
+            let expected = self.bruteforce_next(&cursor, &range.end);
+            let actual = self.after(&cursor);
+            let agrees = match (&expected, &actual) {
+                (Some(expected), Some(actual)) => expected == actual,
+                (None, Some(actual)) => *actual >= range.end,
+                (Some(_), None) => false,
+                (None, None) => true,
+            };
+            if !agrees {
+                return Err(Divergence {
+                    direction: SearchDirection::Next,
+                    from: cursor,
+                    expected,
+                    actual,
+                });
+            }
+            match expected {
+                Some(next) => cursor = next,
+                None => break,
+            }
+        }
+
+        let mut cursor = range.end.clone();
+        loop {
+            let expected = self.bruteforce_prev(&cursor, &range.start);
+            let actual = self.before(&cursor);
+            let agrees = match (&expected, &actual) {
+                (Some(expected), Some(actual)) => expected == actual,
+                (None, Some(actual)) => *actual < range.start,
+                (Some(_), None) => false,
+                (None, None) => true,
+            };
+            if !agrees {
+                return Err(Divergence {
+                    direction: SearchDirection::Prev,
+                    from: cursor,
+                    expected,
+                    actual,
+                });
+            }
+            match expected {
+                Some(prev) => cursor = prev,
+                None => return Ok(()),
+            }
+        }
+    }
+
+    /// The first matching second after `from` and before `end`.
+    fn bruteforce_next<Z>(&self, from: &DateTime<Z>, end: &DateTime<Z>) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        let mut candidate = from.clone() - Duration::nanoseconds(i64::from(from.nanosecond()))
+            + Duration::seconds(1);
+        while candidate < *end {
+            if self.bruteforce_matches(&candidate) {
+                return Some(candidate);
+            }
+            candidate += Duration::seconds(1);
+        }
+        None
+    }
+
+    /// The last matching second before `from`, whose seconds are dropped when the schedule
+    /// ignores them as the optimized search does, and not before `start`.
+    fn bruteforce_prev<Z>(&self, from: &DateTime<Z>, start: &DateTime<Z>) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        let mut candidate = self.truncate_to_step(from) - Duration::seconds(1);
+        while candidate >= *start {
+            if self.bruteforce_matches(&candidate) {
+                return Some(candidate);
+            }
+            candidate -= Duration::seconds(1);
+        }
+        None
+    }
+
+    fn bruteforce_matches<Z>(&self, dt: &DateTime<Z>) -> bool
+    where
+        Z: TimeZone,
+    {
+        #[cfg(feature = "tz")]
+        if let Some(timezone) = self.timezone {
+            return self.fires_at(&dt.with_timezone(&timezone));
+        }
+        self.fires_at(dt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{AmbiguousTimePolicy, DstGapPolicy};
+    use chrono::Utc;
+    use chrono_tz::Europe::Paris;
+    use std::str::FromStr;
+
+    #[test]
+    fn verify_against_bruteforce() {
+        let start = Paris.with_ymd_and_hms(2021, 3, 27, 0, 0, 0).unwrap();
+        let end = Paris.with_ymd_and_hms(2021, 3, 29, 0, 0, 0).unwrap();
+        for expression in &[
+            "30 2 * * *",
+            "*/7 * * * *",
+            "15 */2 * * * *",
+            "0 0 * * * *",
+            "0 0 0 29 2 *",
+        ] {
+            let schedule = Schedule::from_str(expression).unwrap();
+            assert_eq!(
+                Ok(()),
+                schedule.verify_against_bruteforce(start..end),
+                "{}",
+                expression
+            );
+        }
+
+        // a range ending before the year range starts expects no occurrences
+        let schedule = Schedule::from_str("0 0 0 * * * 2022").unwrap();
+        let start = Utc.with_ymd_and_hms(2021, 12, 31, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2022, 1, 2, 0, 0, 0).unwrap();
+        assert_eq!(Ok(()), schedule.verify_against_bruteforce(start..end));
+    }
+
+    #[test]
+    fn verify_ambiguous_times_against_bruteforce() {
+        // clocks fall back from 03:00 to 02:00 on the 31st of October 2021 in Paris
+        let start = Paris.with_ymd_and_hms(2021, 10, 30, 12, 0, 0).unwrap();
+        let end = Paris.with_ymd_and_hms(2021, 11, 1, 0, 0, 0).unwrap();
+        for policy in &[
+            AmbiguousTimePolicy::Earliest,
+            AmbiguousTimePolicy::Latest,
+            AmbiguousTimePolicy::Both,
+        ] {
+            for expression in &["30 2 * * *", "*/7 * * * *", "15 */2 * * * *", "0 0 * * * *"] {
+                let schedule = Schedule::from_str(expression)
+                    .unwrap()
+                    .with_ambiguous_time_policy(*policy);
+                assert_eq!(
+                    Ok(()),
+                    schedule.verify_against_bruteforce(start..end),
+                    "{} {:?}",
+                    expression,
+                    policy
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn verify_dst_gaps_against_bruteforce() {
+        // clocks spring forward from 02:00 to 03:00 on the 28th of March 2021 in Paris
+        let start = Paris.with_ymd_and_hms(2021, 3, 27, 12, 0, 0).unwrap();
+        let end = Paris.with_ymd_and_hms(2021, 3, 28, 12, 0, 0).unwrap();
+        for policy in &[DstGapPolicy::FireAtGapEnd, DstGapPolicy::FireBeforeGap] {
+            for expression in &["30 2 * * *", "*/7 * * * *", "15 */2 * * * *", "0 0 * * * *"] {
+                let schedule = Schedule::from_str(expression)
+                    .unwrap()
+                    .with_dst_gap_policy(*policy);
+                assert_eq!(
+                    Ok(()),
+                    schedule.verify_against_bruteforce(start..end),
+                    "{} {:?}",
+                    expression,
+                    policy
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn divergence_display() {
+        let from = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap();
+        let divergence = Divergence {
+            direction: SearchDirection::Next,
+            from,
+            expected: Some(from + Duration::minutes(1)),
+            actual: None,
+        };
+        assert_eq!(
+            "next occurrence from 2022-06-01 00:00:00 UTC expected 2022-06-01 00:01:00 UTC but was none",
+            divergence.to_string()
+        );
+    }
+}
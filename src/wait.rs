@@ -0,0 +1,83 @@
+use crate::schedule::Schedule;
+use chrono::{DateTime, Utc};
+
+impl Schedule {
+    /// Blocks the current thread until the next occurrence after now, returning it, or returns
+    /// `None` immediately when the schedule won't fire again.
+    ///
+    /// The wall clock is checked again after sleeping, so an occurrence is never returned early
+    /// when the system clock was set back.
+    /// ```rust,no_run
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 */5 * * * *").unwrap();
+    /// while let Some(fire_time) = schedule.wait_until_next() {
+    ///     println!("running at {}", fire_time);
+    /// }
+    /// ```
+    pub fn wait_until_next(&self) -> Option<DateTime<Utc>> {
+        let next = self.after(&Utc::now())?;
+        while let Ok(delay) = (next - Utc::now()).to_std() {
+            if delay.is_zero() {
+                break;
+            }
+            std::thread::sleep(delay);
+        }
+        Some(next)
+    }
+
+    /// Sleeps on the tokio timer until the next occurrence after now, returning it, or returns
+    /// `None` immediately when the schedule won't fire again.
+    /// ```rust,no_run
+    /// use cron_exp::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// # async fn run() {
+    /// let schedule = Schedule::from_str("0 */5 * * * *").unwrap();
+    /// while let Some(fire_time) = schedule.sleep_until_next().await {
+    ///     println!("running at {}", fire_time);
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub async fn sleep_until_next(&self) -> Option<DateTime<Utc>> {
+        let next = self.after(&Utc::now())?;
+        while let Ok(delay) = (next - Utc::now()).to_std() {
+            if delay.is_zero() {
+                break;
+            }
+            tokio::time::sleep(delay).await;
+        }
+        Some(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn wait_until_next() {
+        let schedule = Schedule::from_str("* * * * * *").unwrap();
+        let before = Utc::now();
+        let next = schedule.wait_until_next().unwrap();
+        assert!(next > before && Utc::now() >= next);
+
+        let finished = Schedule::from_str("0 0 0 1 1 * 2020").unwrap();
+        assert_eq!(None, finished.wait_until_next());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn sleep_until_next() {
+        let schedule = Schedule::from_str("* * * * * *").unwrap();
+        let before = Utc::now();
+        let next = schedule.sleep_until_next().await.unwrap();
+        assert!(next > before && Utc::now() >= next);
+
+        let finished = Schedule::from_str("0 0 0 1 1 * 2020").unwrap();
+        assert_eq!(None, finished.sleep_until_next().await);
+    }
+}
@@ -0,0 +1,59 @@
+//! `wasm-bindgen` exports for web UIs validating expressions and previewing fire times with the
+//! same engine as the backend.
+//!
+//! Datetimes are exchanged as milliseconds since the Unix epoch, as returned by JavaScript's
+//! `Date.getTime()`, and occurrences are computed in UTC unless the expression has a
+//! `CRON_TZ=` prefix. Errors are the parser's messages.
+use crate::Schedule;
+use chrono::DateTime;
+use std::str::FromStr;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+fn schedule(expression: &str) -> Result<Schedule, String> {
+    Schedule::from_str(expression).map_err(|e| e.to_string())
+}
+
+/// Validates the expression, returning it in its normal form.
+#[wasm_bindgen]
+pub fn parse(expression: &str) -> Result<String, String> {
+    schedule(expression).map(|schedule| schedule.to_string())
+}
+
+/// Returns up to `count` occurrences after the epoch milliseconds `from` as epoch milliseconds.
+#[wasm_bindgen]
+pub fn next_n(expression: &str, from: f64, count: u32) -> Result<Vec<f64>, String> {
+    let schedule = schedule(expression)?;
+    let from = DateTime::from_timestamp_millis(from as i64)
+        .ok_or_else(|| format!("Invalid timestamp {}", from))?;
+    Ok(schedule
+        .iter_after(&from)
+        .take(count as usize)
+        .map(|next| next.timestamp_millis() as f64)
+        .collect())
+}
+
+/// Describes the expression in English.
+#[wasm_bindgen]
+pub fn describe(expression: &str) -> Result<String, String> {
+    schedule(expression).map(|schedule| schedule.describe())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exports() {
+        assert_eq!(Ok("30 9 * * 1-5".to_string()), parse("30 9 * * Mon-Fri"));
+        assert!(parse("30 9 * *").is_err());
+
+        // 2022-06-01T00:00:00Z
+        let from = 1_654_041_600_000.0;
+        let hour = 3_600_000.0;
+        assert_eq!(
+            Ok(vec![from + 9.5 * hour, from + 33.5 * hour]),
+            next_n("30 9 * * *", from, 2)
+        );
+        assert_eq!(Ok("At 09:30".to_string()), describe("30 9 * * *"));
+    }
+}
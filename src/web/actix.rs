@@ -0,0 +1,58 @@
+use super::{ScheduleRejection, ScheduleRequest, ValidatedSchedule};
+use actix_web::dev::Payload;
+use actix_web::http::StatusCode;
+use actix_web::{web, FromRequest, HttpRequest, HttpResponse, ResponseError};
+use std::future::Future;
+use std::pin::Pin;
+
+impl FromRequest for ValidatedSchedule {
+    type Error = ScheduleRejection;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let json = web::Json::<ScheduleRequest>::from_request(req, payload);
+        Box::pin(async move {
+            let request = json.await.map_err(|e| ScheduleRejection::InvalidBody {
+                status: e.as_response_error().status_code().as_u16(),
+                message: e.to_string(),
+            })?;
+            ValidatedSchedule::from_body(request.into_inner())
+        })
+    }
+}
+
+impl ResponseError for ScheduleRejection {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::from_u16(self.status()).unwrap()
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code()).json(self.body())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    fn extract(body: &'static str) -> Result<ValidatedSchedule, ScheduleRejection> {
+        let (req, mut payload) = TestRequest::post()
+            .insert_header(("content-type", "application/json"))
+            .set_payload(body)
+            .to_http_parts();
+        actix_web::rt::System::new().block_on(ValidatedSchedule::from_request(&req, &mut payload))
+    }
+
+    #[test]
+    fn extract_valid_and_invalid() {
+        let schedule = extract(r#"{"expression":"0 30 9 * * Mon-Fri"}"#).unwrap();
+        assert_eq!(schedule.expression(), "0 30 9 * * Mon-Fri");
+
+        let rejection = extract(r#"{"expression":"0 30 9 * * Funday"}"#).unwrap_err();
+        assert_eq!(
+            rejection.error_response().status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+    }
+}
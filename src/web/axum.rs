@@ -0,0 +1,92 @@
+use super::{ScheduleRejection, ScheduleRequest, ValidatedSchedule};
+use ::axum::extract::{FromRequest, Request};
+use ::axum::http::StatusCode;
+use ::axum::response::{IntoResponse, Response};
+use ::axum::Json;
+
+impl<S> FromRequest<S> for ValidatedSchedule
+where
+    S: Send + Sync,
+{
+    type Rejection = ScheduleRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(request) = Json::<ScheduleRequest>::from_request(req, state)
+            .await
+            .map_err(|e| ScheduleRejection::InvalidBody {
+                status: e.status().as_u16(),
+                message: e.body_text(),
+            })?;
+        ValidatedSchedule::from_body(request)
+    }
+}
+
+impl IntoResponse for ScheduleRejection {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status()).unwrap();
+        (status, Json(self.body())).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::axum::body::{to_bytes, Body};
+    use ::axum::http::header::CONTENT_TYPE;
+
+    fn request(body: &'static str) -> Request {
+        Request::builder()
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn extract_valid() {
+        let schedule =
+            ValidatedSchedule::from_request(request(r#"{"expression":"*/5 * * * *"}"#), &())
+                .await
+                .unwrap();
+        assert_eq!(schedule.expression(), "*/5 * * * *");
+    }
+
+    #[tokio::test]
+    async fn extract_invalid() {
+        let rejection = ValidatedSchedule::from_request(request(r#"{"expression":"* * *"}"#), &())
+            .await
+            .unwrap_err();
+        let response = rejection.into_response();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["error"], "invalid_schedule");
+        assert_eq!(json["expression"], "* * *");
+
+        let rejection =
+            ValidatedSchedule::from_request(request(r#"{"expression":"0 30 25 * * *"}"#), &())
+                .await
+                .unwrap_err();
+        let body = to_bytes(rejection.into_response().into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["field"], "hours");
+        assert_eq!(json["span"], serde_json::json!({"start": 5, "end": 7}));
+
+        let rejection = ValidatedSchedule::from_request(request("{"), &())
+            .await
+            .unwrap_err();
+        assert_eq!(rejection.into_response().status(), StatusCode::BAD_REQUEST);
+
+        let without_content_type = Request::builder()
+            .body(Body::from(r#"{"expression":"*/5 * * * *"}"#))
+            .unwrap();
+        let rejection = ValidatedSchedule::from_request(without_content_type, &())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            rejection.into_response().status(),
+            StatusCode::UNSUPPORTED_MEDIA_TYPE
+        );
+    }
+}
@@ -0,0 +1,117 @@
+//! Extractors validating CRON expressions received by web frameworks.
+//!
+//! Both the `axum` and `actix` integrations read a JSON body of the form
+//! `{"expression": "0 30 9 * * Mon-Fri"}` and reject invalid expressions with a structured
+//! `422 Unprocessable Entity` response carrying the parser diagnostics.
+use crate::{Schedule, SpannedParseError};
+use serde::{Deserialize, Serialize};
+use std::ops::{Deref, Range};
+use thiserror::Error;
+
+#[cfg(feature = "actix")]
+mod actix;
+#[cfg(feature = "axum")]
+mod axum;
+
+#[derive(Debug, Deserialize)]
+struct ScheduleRequest {
+    expression: String,
+}
+
+/// A parsed and validated CRON expression extracted from a request body.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ValidatedSchedule {
+    expression: String,
+    schedule: Schedule,
+}
+
+impl ValidatedSchedule {
+    /// Returns the CRON expression as it was received.
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    /// Consumes the wrapper returning the parsed `Schedule`.
+    pub fn into_inner(self) -> Schedule {
+        self.schedule
+    }
+
+    fn from_body(request: ScheduleRequest) -> Result<Self, ScheduleRejection> {
+        match Schedule::parse_spanned(&request.expression) {
+            Ok(schedule) => Ok(ValidatedSchedule {
+                expression: request.expression,
+                schedule,
+            }),
+            Err(source) => Err(ScheduleRejection::InvalidSchedule {
+                expression: request.expression,
+                source,
+            }),
+        }
+    }
+}
+
+impl Deref for ValidatedSchedule {
+    type Target = Schedule;
+
+    fn deref(&self) -> &Self::Target {
+        &self.schedule
+    }
+}
+
+/// Rejection returned when a request does not carry a valid CRON expression.
+#[derive(Error, Debug, PartialEq)]
+pub enum ScheduleRejection {
+    #[error("Invalid request body: {message}")]
+    InvalidBody { status: u16, message: String },
+
+    #[error("Invalid CRON expression '{expression}': {source}")]
+    InvalidSchedule {
+        expression: String,
+        source: SpannedParseError,
+    },
+}
+
+impl ScheduleRejection {
+    /// Returns the HTTP status code of the rejection, that of the framework's JSON extractor for
+    /// a malformed body, such as `400` or `415` without a JSON content type, and `422` for an
+    /// invalid expression.
+    pub fn status(&self) -> u16 {
+        match self {
+            ScheduleRejection::InvalidBody { status, .. } => *status,
+            ScheduleRejection::InvalidSchedule { .. } => 422,
+        }
+    }
+
+    fn body(&self) -> ErrorBody<'_> {
+        match self {
+            ScheduleRejection::InvalidBody { message, .. } => ErrorBody {
+                error: "invalid_body",
+                message: message.clone(),
+                expression: None,
+                field: None,
+                span: None,
+            },
+            ScheduleRejection::InvalidSchedule { expression, source } => ErrorBody {
+                error: "invalid_schedule",
+                message: source.to_string(),
+                expression: Some(expression),
+                field: source.field().map(|field| field.to_string()),
+                span: Some(source.span()),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorBody<'a> {
+    error: &'static str,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expression: Option<&'a str>,
+    /// The field of the expression the error was found in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    field: Option<String>,
+    /// The byte range of the offending token within the expression.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    span: Option<Range<usize>>,
+}
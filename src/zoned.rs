@@ -0,0 +1,225 @@
+//! Schedules bound to an IANA time zone.
+use crate::errors::ParseScheduleError;
+use crate::schedule::{split_timezone_prefix, Schedule};
+use chrono::{DateTime, TimeZone};
+use chrono_tz::Tz;
+use std::fmt;
+use std::str::FromStr;
+
+/// A `Schedule` evaluated in an IANA time zone, written `TZ=America/New_York 0 30 9 * * Mon-Fri`.
+///
+/// Expressions without a `TZ=` or `CRON_TZ=` prefix are evaluated in UTC, and a second prefix
+/// is rejected rather than one zone being reported while the other is used.
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use cron_exp::ZonedSchedule;
+/// use std::str::FromStr;
+///
+/// let schedule = ZonedSchedule::from_str("TZ=America/New_York 0 30 9 * * Mon-Fri").unwrap();
+/// let from = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap();
+/// let next = schedule.iter_from(&from).next().unwrap();
+/// assert_eq!("2022-06-01T09:30:00-04:00", next.to_rfc3339());
+/// assert_eq!("TZ=America/New_York 0 30 9 * * Mon-Fri", schedule.to_string());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZonedSchedule {
+    expression: String,
+    schedule: Schedule,
+    timezone: Tz,
+}
+
+impl ZonedSchedule {
+    /// Parses an expression without a time zone prefix to be evaluated in the supplied zone,
+    /// which `Schedule::timezone` of the schedule returns.
+    pub fn new(expression: &str, timezone: Tz) -> Result<Self, ParseScheduleError> {
+        let expression = expression.trim();
+        if let (Some(name), _) = split_timezone_prefix(expression)? {
+            return Err(ParseScheduleError::UnexpectedTimezone(name.into()));
+        }
+        Ok(ZonedSchedule {
+            schedule: Schedule::from_str(expression)?.with_timezone(timezone),
+            expression: expression.to_string(),
+            timezone,
+        })
+    }
+
+    /// Returns the expression without its time zone prefix.
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    /// Returns the schedule.
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    /// Returns the time zone the schedule is evaluated in.
+    pub fn timezone(&self) -> Tz {
+        self.timezone
+    }
+
+    /// Iterates forwards or backwards from the supplied datetime, in any zone, yielding
+    /// occurrences in the schedule's zone.
+    pub fn iter_from<'a, Z>(
+        &'a self,
+        dt: &DateTime<Z>,
+    ) -> impl DoubleEndedIterator<Item = DateTime<Tz>> + 'a
+    where
+        Z: TimeZone,
+    {
+        self.schedule.iter_from(&dt.with_timezone(&self.timezone))
+    }
+}
+
+impl FromStr for ZonedSchedule {
+    type Err = ParseScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match split_timezone_prefix(s)? {
+            (Some(name), expression) => {
+                let timezone = Tz::from_str(name)
+                    .map_err(|_| ParseScheduleError::InvalidTimezone(name.into()))?;
+                ZonedSchedule::new(expression, timezone)
+            }
+            (None, expression) => ZonedSchedule::new(expression, Tz::UTC),
+        }
+    }
+}
+
+impl fmt::Display for ZonedSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TZ={} {}", self.timezone.name(), self.expression)
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use super::*;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// The accepted forms, the prefixed expression or a structured one naming the zone
+    /// separately.
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Repr {
+        Prefixed(String),
+        Structured {
+            expression: String,
+            timezone: Option<String>,
+        },
+    }
+
+    impl Serialize for ZonedSchedule {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.collect_str(self)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ZonedSchedule {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            match Repr::deserialize(deserializer)? {
+                Repr::Prefixed(s) => ZonedSchedule::from_str(&s).map_err(D::Error::custom),
+                Repr::Structured {
+                    expression,
+                    timezone,
+                } => {
+                    let timezone = match timezone {
+                        Some(name) => Tz::from_str(&name).map_err(|_| {
+                            D::Error::custom(ParseScheduleError::InvalidTimezone(name))
+                        })?,
+                        None => Tz::UTC,
+                    };
+                    ZonedSchedule::new(&expression, timezone).map_err(D::Error::custom)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use chrono_tz::America::New_York;
+
+    #[test]
+    fn parse_prefixes() {
+        let paris = ZonedSchedule::from_str("CRON_TZ=Europe/Paris  0 30 9 * * *").unwrap();
+        assert_eq!(chrono_tz::Europe::Paris, paris.timezone());
+        assert_eq!("0 30 9 * * *", paris.expression());
+        assert_eq!("TZ=Europe/Paris 0 30 9 * * *", paris.to_string());
+        assert_eq!(paris, ZonedSchedule::from_str(&paris.to_string()).unwrap());
+
+        assert_eq!(
+            Tz::UTC,
+            ZonedSchedule::from_str("30 9 * * *").unwrap().timezone()
+        );
+        assert_eq!(
+            Err(ParseScheduleError::InvalidTimezone("Mars/Olympus".into())),
+            ZonedSchedule::from_str("TZ=Mars/Olympus 30 9 * * *")
+        );
+        assert_eq!(
+            Err(ParseScheduleError::ArgumentCount),
+            ZonedSchedule::from_str("TZ=Europe/Paris")
+        );
+    }
+
+    #[test]
+    fn schedule_in_the_zone() {
+        let new_york = ZonedSchedule::new("0 30 9 * * *", New_York).unwrap();
+        assert_eq!(Some(New_York), new_york.schedule().timezone());
+        let from = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2022, 6, 1, 13, 30, 0).unwrap()),
+            new_york.schedule().next_after(&from)
+        );
+        assert_eq!(
+            Some(Tz::UTC),
+            ZonedSchedule::from_str("30 9 * * *")
+                .unwrap()
+                .schedule()
+                .timezone()
+        );
+
+        // a second zone would be reported but not used, or the other way round
+        assert_eq!(
+            Err(ParseScheduleError::UnexpectedTimezone(
+                "Europe/Paris".into()
+            )),
+            ZonedSchedule::from_str("TZ=America/New_York CRON_TZ=Europe/Paris 0 30 9 * * *")
+        );
+        assert_eq!(
+            Err(ParseScheduleError::UnexpectedTimezone(
+                "Europe/Paris".into()
+            )),
+            ZonedSchedule::new("CRON_TZ=Europe/Paris 0 30 9 * * *", New_York)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let schedule = ZonedSchedule::from_str("TZ=America/New_York 0 30 9 * * Mon-Fri").unwrap();
+        let json = serde_json::to_string(&schedule).unwrap();
+        assert_eq!(r#""TZ=America/New_York 0 30 9 * * Mon-Fri""#, json);
+        assert_eq!(
+            schedule,
+            serde_json::from_str::<ZonedSchedule>(&json).unwrap()
+        );
+
+        let structured: ZonedSchedule = serde_json::from_str(
+            r#"{"expression": "0 30 9 * * Mon-Fri", "timezone": "America/New_York"}"#,
+        )
+        .unwrap();
+        assert_eq!(schedule, structured);
+
+        assert!(serde_json::from_str::<ZonedSchedule>(r#""TZ=Nowhere 0 * * * *""#).is_err());
+    }
+}